@@ -1,6 +1,7 @@
 use {
     crate::{
         decoration::{Decoration, DecorationType},
+        inlays::InlineInlay,
         layout::{BlockElement, WrappedElement},
         selection::Affinity,
         session::{SelectionMode, Session},
@@ -8,6 +9,7 @@ use {
         str::StrExt,
         text::Position,
         token::TokenKind,
+        widgets::InlineWidget,
         Line, Selection, Token,
     },
     makepad_widgets::*,
@@ -37,6 +39,7 @@ live_design! {
         delimiter_highlight: #f,
         error_decoration: #f00,
         warning_decoration: #0f0,
+        composition_decoration: #D4D4D4,
     }
 
     DrawIndentGuide = {{DrawIndentGuide}} {
@@ -51,7 +54,7 @@ live_design! {
 
     DrawDecoration = {{DrawDecoration}} {
         fn pixel(self) -> vec4 {
-            let transformed_pos = vec2(self.pos.x, self.pos.y + 0.03 * sin(self.pos.x * self.rect_size.x));
+            let transformed_pos = vec2(self.pos.x, self.pos.y + self.wave * 0.03 * sin(self.pos.x * self.rect_size.x));
             let cx = Sdf2d::viewport(transformed_pos * self.rect_size);
             cx.move_to(0.0, self.rect_size.y - 1.0);
             cx.line_to(self.rect_size.x, self.rect_size.y - 1.0);
@@ -459,6 +462,7 @@ impl CodeEditor {
         } else {
             None
         });
+        session.set_available_width(self.viewport_rect.size.x / self.cell_size.x);
 
         let scroll_pos = self.scroll_bars.get_scroll_pos();
 
@@ -474,6 +478,8 @@ impl CodeEditor {
         self.draw_gutter(cx, session);
         self.draw_selection_layer(cx, session);
         self.draw_text_layer(cx, session);
+        self.draw_overlay_layer(cx, session);
+        self.draw_ime_composition(cx, session);
         self.draw_indent_guide_layer(cx, session);
         self.draw_decoration_layer(cx, session);
         self.draw_selection_layer(cx, session);
@@ -572,9 +578,15 @@ impl CodeEditor {
         match event.hits(cx, self.scroll_bars.area()) {
             Hit::KeyFocusLost(_) => {
                 self.animator_play(cx, id!(focus.off));
+                cx.set_key_repeat(None);
             }
             Hit::KeyFocus(_) => {
                 self.animator_play(cx, id!(focus.on));
+                let settings = session.settings();
+                cx.set_key_repeat(Some(KeyRepeatSettings {
+                    initial_delay_ms: settings.key_repeat_initial_delay_ms,
+                    rate_ms: settings.key_repeat_rate_ms,
+                }));
             }
             Hit::KeyDown(KeyEvent {
                 key_code: KeyCode::Escape,
@@ -649,17 +661,14 @@ impl CodeEditor {
             }
             Hit::KeyDown(KeyEvent {
                 key_code: KeyCode::ArrowLeft,
-                modifiers:
-                    KeyModifiers {
-                        shift,
-                        control,
-                        logo,
-                        ..
-                    },
+                modifiers,
                 ..
             }) => {
-                if control || logo {
-                    //session.move_to_start_of_line(!shift);
+                let shift = modifiers.shift;
+                if is_word_modifier(cx.os_type(), modifiers) {
+                    session.move_word_left(!shift);
+                } else if is_line_modifier(cx.os_type(), modifiers) {
+                    session.home(!shift, true);
                 } else {
                     session.move_left(!shift);
                 }
@@ -668,17 +677,14 @@ impl CodeEditor {
             }
             Hit::KeyDown(KeyEvent {
                 key_code: KeyCode::ArrowRight,
-                modifiers:
-                    KeyModifiers {
-                        shift,
-                        control,
-                        logo,
-                        ..
-                    },
+                modifiers,
                 ..
             }) => {
-                if control || logo {
-                    //session.move_to_end_of_line(!shift);
+                let shift = modifiers.shift;
+                if is_word_modifier(cx.os_type(), modifiers) {
+                    session.move_word_right(!shift);
+                } else if is_line_modifier(cx.os_type(), modifiers) {
+                    session.end(!shift, true);
                 } else {
                     session.move_right(!shift);
                 }
@@ -706,19 +712,39 @@ impl CodeEditor {
             }
             Hit::KeyDown(KeyEvent {
                 key_code: KeyCode::Home,
-                modifiers: KeyModifiers { shift, .. },
+                modifiers:
+                    KeyModifiers {
+                        shift,
+                        control,
+                        logo,
+                        ..
+                    },
                 ..
             }) => {
-                session.home(!shift);
+                if control || logo {
+                    session.move_to_document_start(!shift);
+                } else {
+                    session.home(!shift, true);
+                }
                 keyboard_moved_cursor = true;
                 self.redraw(cx);
             }
             Hit::KeyDown(KeyEvent {
                 key_code: KeyCode::End,
-                modifiers: KeyModifiers { shift, .. },
+                modifiers:
+                    KeyModifiers {
+                        shift,
+                        control,
+                        logo,
+                        ..
+                    },
                 ..
             }) => {
-                session.end(!shift);
+                if control || logo {
+                    session.move_to_document_end(!shift);
+                } else {
+                    session.end(!shift, true);
+                }
                 keyboard_moved_cursor = true;
                 self.redraw(cx);
             }
@@ -764,6 +790,17 @@ impl CodeEditor {
                 keyboard_moved_cursor = true;
                 actions.push(CodeEditorAction::TextDidChange);
             }
+            Hit::ImeCompositionUpdate(ImeCompositionEvent { ref text, .. }) => {
+                session.update_ime_composition(text);
+                self.redraw(cx);
+                keyboard_moved_cursor = true;
+            }
+            Hit::ImeCompositionEnd(ImeCompositionEndEvent { ref text }) => {
+                session.end_ime_composition(text);
+                self.redraw(cx);
+                keyboard_moved_cursor = true;
+                actions.push(CodeEditorAction::TextDidChange);
+            }
             Hit::KeyDown(KeyEvent {
                 key_code: KeyCode::ReturnKey,
                 ..
@@ -795,18 +832,28 @@ impl CodeEditor {
             }
             Hit::KeyDown(KeyEvent {
                 key_code: KeyCode::Delete,
+                modifiers,
                 ..
             }) => {
-                session.delete();
+                if is_word_modifier(cx.os_type(), modifiers) {
+                    session.delete_word_right();
+                } else {
+                    session.delete();
+                }
                 self.redraw(cx);
                 keyboard_moved_cursor = true;
                 actions.push(CodeEditorAction::TextDidChange);
             }
             Hit::KeyDown(KeyEvent {
                 key_code: KeyCode::Backspace,
+                modifiers,
                 ..
             }) => {
-                session.backspace();
+                if is_word_modifier(cx.os_type(), modifiers) {
+                    session.delete_word_left();
+                } else {
+                    session.backspace();
+                }
                 self.redraw(cx);
                 keyboard_moved_cursor = true;
                 actions.push(CodeEditorAction::TextDidChange);
@@ -866,24 +913,31 @@ impl CodeEditor {
             }) => {
                 self.animator_play(cx, id!(focus.on));
                 cx.set_key_focus(self.scroll_bars.area());
-                let ((cursor, affinity), is_in_gutter) = self.pick(session, abs);
-                session.set_selection(
-                    cursor,
-                    affinity,
-                    if is_in_gutter {
-                        SelectionMode::Line
-                    } else {
-                        match tap_count {
-                            1 => SelectionMode::Simple,
-                            2 => SelectionMode::Word,
-                            3 => SelectionMode::Line,
-                            _ => SelectionMode::All,
-                        }
-                    },
-                );
-                self.reset_cursor_blinker(cx);
-                self.keep_cursor_in_view = KeepCursorInView::Always(abs, cx.new_next_frame());
-                self.redraw(cx);
+                if let Some((widget, local)) = self.pick_inline_widget(session, abs) {
+                    actions.push(CodeEditorAction::InlineWidgetClicked {
+                        id: widget.id,
+                        local,
+                    });
+                } else {
+                    let ((cursor, affinity), is_in_gutter) = self.pick(session, abs);
+                    session.set_selection(
+                        cursor,
+                        affinity,
+                        if is_in_gutter {
+                            SelectionMode::Line
+                        } else {
+                            match tap_count {
+                                1 => SelectionMode::Simple,
+                                2 => SelectionMode::Word,
+                                3 => SelectionMode::Line,
+                                _ => SelectionMode::All,
+                            }
+                        },
+                    );
+                    self.reset_cursor_blinker(cx);
+                    self.keep_cursor_in_view = KeepCursorInView::Always(abs, cx.new_next_frame());
+                    self.redraw(cx);
+                }
             }
             Hit::FingerDown(FingerDownEvent {
                 abs,
@@ -917,6 +971,24 @@ impl CodeEditor {
                 self.keep_cursor_in_view = KeepCursorInView::Always(abs, cx.new_next_frame());
                 self.redraw(cx);
             }
+            Hit::FingerDown(FingerDownEvent {
+                abs,
+                modifiers:
+                    KeyModifiers {
+                        alt: true,
+                        shift: true,
+                        ..
+                    },
+                ..
+            }) => {
+                self.animator_play(cx, id!(focus.on));
+                cx.set_key_focus(self.scroll_bars.area());
+                let ((cursor, affinity), _) = self.pick(session, abs);
+                session.set_column_selection(cursor, affinity);
+                self.reset_cursor_blinker(cx);
+                self.keep_cursor_in_view = KeepCursorInView::Always(abs, cx.new_next_frame());
+                self.redraw(cx);
+            }
             Hit::FingerUp(_) => {
                 self.reset_cursor_blinker(cx);
                 self.keep_cursor_in_view = KeepCursorInView::Off;
@@ -1117,6 +1189,83 @@ impl CodeEditor {
         }
     }
 
+    fn draw_overlay_layer(&mut self, cx: &mut Cx2d, session: &Session) {
+        let mut overlays = Vec::new();
+        let mut origin_y = session.layout().line(self.line_start).y();
+        for element in session
+            .layout()
+            .block_elements(self.line_start, self.line_end)
+        {
+            match element {
+                BlockElement::Line { line, .. } => {
+                    for (byte_index, inlay) in line.overlay_inlays() {
+                        if let InlineInlay::Text { text, z_offset } = inlay {
+                            let (row_index, column_index) =
+                                line.logical_to_grid_position(byte_index, Affinity::After);
+                            let (x, y) = line.grid_to_normalized_position(row_index, column_index);
+                            overlays.push((
+                                *z_offset,
+                                line.scale(),
+                                DVec2 { x, y: origin_y + y },
+                                text.clone(),
+                            ));
+                        }
+                    }
+                    origin_y += line.height();
+                }
+                BlockElement::Widget(widget) => {
+                    origin_y += widget.height;
+                }
+            }
+        }
+        // Lower overlays first, so that higher ones end up on top of them.
+        overlays.sort_by_key(|(z_offset, ..)| *z_offset);
+        for (_, scale, position, text) in overlays {
+            self.draw_text.font_scale = scale;
+            self.draw_text.draw_abs(
+                cx,
+                position * self.cell_size + self.viewport_rect.pos,
+                &text,
+            );
+        }
+    }
+
+    fn draw_ime_composition(&mut self, cx: &mut Cx2d, session: &Session) {
+        let Some(composition) = session.ime_composition() else {
+            return;
+        };
+        let (range, text) = &*composition;
+        let line_index = range.start().line_index;
+        if text.is_empty() || line_index < self.line_start || line_index >= self.line_end {
+            return;
+        }
+        let scale = session.layout().line(line_index).scale();
+        let (x, y) = session
+            .layout()
+            .logical_to_normalized_position(range.start(), Affinity::After);
+        let rect = Rect {
+            pos: DVec2 { x, y } * self.cell_size + self.viewport_rect.pos,
+            size: DVec2 {
+                x: text.column_count() as f64 * scale,
+                y: scale,
+            } * self.cell_size,
+        };
+        // The tentative text is not in the document, so it is drawn on top of the text it
+        // replaces, covering it up with the background.
+        let bg_depth = mem::replace(&mut self.draw_bg.draw_depth, 2.0);
+        self.draw_bg.draw_abs(cx, rect);
+        self.draw_bg.draw_depth = bg_depth;
+        let text_depth = mem::replace(&mut self.draw_text.draw_depth, 3.0);
+        self.draw_text.font_scale = scale;
+        self.draw_text.color = self.token_colors.identifier;
+        self.draw_text.outline = 0.0;
+        self.draw_text.draw_abs(cx, rect.pos, text);
+        self.draw_text.draw_depth = text_depth;
+        self.draw_decoration.wave = 0.0;
+        self.draw_decoration.color = self.token_colors.composition_decoration;
+        self.draw_decoration.draw_abs(cx, rect);
+    }
+
     fn draw_indent_guide_layer(&mut self, cx: &mut Cx2d<'_>, session: &Session) {
         let mut origin_y = session.layout().line(self.line_start).y();
         for element in session
@@ -1414,14 +1563,98 @@ impl CodeEditor {
         }
         panic!()
     }
+
+    /// The inline widget at `position`, if any, with `position` relative to its top left corner.
+    fn pick_inline_widget(
+        &self,
+        session: &Session,
+        position: DVec2,
+    ) -> Option<(InlineWidget, DVec2)> {
+        let position = (position - self.viewport_rect.pos) / self.cell_size;
+        let layout = session.layout();
+        if position.y < 0.0 || position.y > layout.height() {
+            return None;
+        }
+        let line_index = layout.find_first_line_ending_after_y(position.y);
+        let mut origin_y = layout.line(line_index).y();
+        for block in layout.block_elements(line_index, line_index + 1) {
+            match block {
+                BlockElement::Line {
+                    is_inlay: false,
+                    line,
+                } => {
+                    let mut row_index = 0;
+                    let mut column_index = 0;
+                    for element in line.wrapped_elements() {
+                        match element {
+                            WrappedElement::Text { text, .. } => {
+                                column_index += text.column_count();
+                            }
+                            WrappedElement::Widget(widget) => {
+                                let (start_x, y) =
+                                    line.grid_to_normalized_position(row_index, column_index);
+                                let start_y = origin_y + y;
+                                let (end_x, _) = line.grid_to_normalized_position(
+                                    row_index,
+                                    column_index + widget.column_count,
+                                );
+                                let end_y = start_y + line.scale();
+                                if (start_y..=end_y).contains(&position.y)
+                                    && (start_x..=end_x).contains(&position.x)
+                                {
+                                    let local = dvec2(position.x - start_x, position.y - start_y);
+                                    return Some((widget, local * self.cell_size));
+                                }
+                                column_index += widget.column_count;
+                            }
+                            WrappedElement::Wrap => {
+                                column_index = line.wrap_indent_column_count();
+                                row_index += 1;
+                            }
+                        }
+                    }
+                    origin_y += line.height();
+                }
+                BlockElement::Line {
+                    is_inlay: true,
+                    line,
+                } => {
+                    origin_y += line.height();
+                }
+                BlockElement::Widget(widget) => {
+                    origin_y += widget.height;
+                }
+            }
+        }
+        None
+    }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, DefaultNone)]
+#[derive(Clone, Copy, Debug, PartialEq, DefaultNone)]
 pub enum CodeEditorAction {
     TextDidChange,
+    /// An inline widget was clicked at `local`, relative to its top left corner.
+    InlineWidgetClicked { id: usize, local: DVec2 },
     None
 }
 
+/// Whether `modifiers` move or delete by word: Alt on macOS, Ctrl elsewhere.
+fn is_word_modifier(os_type: &OsType, modifiers: KeyModifiers) -> bool {
+    match os_type {
+        OsType::Macos => modifiers.alt,
+        _ => modifiers.control,
+    }
+}
+
+/// Whether `modifiers` move to the start or end of the line: Cmd on macOS. Elsewhere only Home
+/// and End do that.
+fn is_line_modifier(os_type: &OsType, modifiers: KeyModifiers) -> bool {
+    match os_type {
+        OsType::Macos => modifiers.logo,
+        _ => false,
+    }
+}
+
 struct DrawDecorationLayer<'a> {
     code_editor: &'a mut CodeEditor,
     active_decoration: Option<ActiveDecoration>,
@@ -1588,6 +1821,7 @@ impl<'a> DrawDecorationLayer<'a> {
     ) {
         let start_x = mem::take(&mut self.active_decoration.as_mut().unwrap().start_x);
         let (x, y) = line.grid_to_normalized_position(row_index, column_index);
+        self.code_editor.draw_decoration.wave = 1.0;
         self.code_editor.draw_decoration.color =
             match self.active_decoration.as_mut().unwrap().decoration.ty {
                 DecorationType::Warning => self.code_editor.token_colors.warning_decoration,
@@ -1902,6 +2136,8 @@ struct TokenColors {
     error_decoration: Vec4,
     #[live]
     warning_decoration: Vec4,
+    #[live]
+    composition_decoration: Vec4,
 }
 
 #[derive(Live, LiveHook, LiveRegister)]
@@ -1914,11 +2150,14 @@ pub struct DrawIndentGuide {
 }
 
 #[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
 struct DrawDecoration {
     #[deref]
     draw_super: DrawQuad,
     #[live]
     color: Vec4,
+    #[live(1.0)]
+    wave: f32,
 }
 
 #[derive(Live, LiveHook, LiveRegister)]
@@ -174,6 +174,228 @@ impl Document {
         self.update_after_edit(origin_id, None, &edits);
     }
 
+    /// Applies the edits made by `f` as a single undoable edit, for changes that are not tied
+    /// to the selections or the lines they cover.
+    pub fn edit(
+        &self,
+        origin_id: SessionId,
+        kind: EditKind,
+        selections: &SelectionSet,
+        f: impl FnOnce(Editor),
+    ) {
+        let mut history = self.0.history.borrow_mut();
+        history.push_or_extend_group(origin_id, kind, selections);
+        let mut edits = Vec::new();
+        f(Editor {
+            history: &mut *history,
+            edits: &mut edits,
+        });
+        drop(history);
+        self.update_after_edit(origin_id, None, &edits);
+    }
+
+    /// Replaces the whole text as a single undoable edit. Only the part between the common
+    /// prefix and suffix of the old and new text is changed, so selections outside of it stay.
+    pub fn set_text(&self, origin_id: SessionId, selections: &SelectionSet, text: Text) {
+        let mut history = self.0.history.borrow_mut();
+        let old_string = history.as_text().to_string();
+        let new_string = text.to_string();
+        let prefix_len = old_string
+            .char_indices()
+            .zip(new_string.chars())
+            .find(|((_, old_char), new_char)| old_char != new_char)
+            .map_or(old_string.len().min(new_string.len()), |((index, _), _)| {
+                index
+            });
+        let suffix_len = old_string[prefix_len..]
+            .char_indices()
+            .rev()
+            .zip(new_string[prefix_len..].chars().rev())
+            .find(|((_, old_char), new_char)| old_char != new_char)
+            .map_or(
+                (old_string.len() - prefix_len).min(new_string.len() - prefix_len),
+                |((index, old_char), _)| {
+                    old_string.len() - prefix_len - index - old_char.len_utf8()
+                },
+            );
+        let start = Position::from_byte_index(&old_string, prefix_len);
+        let end = Position::from_byte_index(&old_string, old_string.len() - suffix_len);
+        let inserted = &new_string[prefix_len..new_string.len() - suffix_len];
+        if start == end && inserted.is_empty() {
+            return;
+        }
+        history.push_or_extend_group(origin_id, EditKind::Other, selections);
+        let mut edits = Vec::new();
+        let mut editor = Editor {
+            history: &mut *history,
+            edits: &mut edits,
+        };
+        editor.apply_edit(Edit {
+            change: Change::Delete(start, end - start),
+            drift: Drift::Before,
+        });
+        editor.apply_edit(Edit {
+            change: Change::Insert(start, Text::from(inserted)),
+            drift: Drift::After,
+        });
+        drop(history);
+        self.update_after_edit(origin_id, None, &edits);
+    }
+
+    /// Applies edits made elsewhere, for instance by a collaborator. They move the selections of
+    /// all sessions like any other edit, but cannot be undone.
+    pub fn apply_external_edits(&self, origin_id: SessionId, edits: Vec<Edit>) {
+        let mut history = self.0.history.borrow_mut();
+        for edit in &edits {
+            history.apply_external_edit(edit.clone());
+        }
+        drop(history);
+        self.update_after_edit(origin_id, None, &edits);
+    }
+
+    pub fn block_inlay_count(&self) -> usize {
+        self.0.layout.borrow().block_inlays.len()
+    }
+
+    /// The block inlays shown in front of the line at `line_index`, with that line index.
+    pub fn block_inlays_at_line(&self, line_index: usize) -> Ref<'_, [(usize, BlockInlay)]> {
+        Ref::map(self.0.layout.borrow(), |layout| {
+            let range = block_inlay_range(&layout.block_inlays, line_index);
+            &layout.block_inlays[range]
+        })
+    }
+
+    /// Adds a block inlay in front of the line at `line_index`, after the ones already there.
+    pub fn insert_block_inlay(&self, line_index: usize, inlay: BlockInlay) {
+        debug_assert!(line_index <= self.as_text().as_lines().len());
+        let mut layout = self.0.layout.borrow_mut();
+        let index = block_inlay_range(&layout.block_inlays, line_index).end;
+        layout.block_inlays.insert(index, (line_index, inlay));
+        drop(layout);
+        self.send_update(DocumentUpdate::Inlays(line_index..line_index + 1));
+    }
+
+    /// Removes the block inlay at `inlay_index` of those in front of the line at `line_index`.
+    pub fn remove_block_inlay(&self, line_index: usize, inlay_index: usize) -> BlockInlay {
+        debug_assert!(line_index <= self.as_text().as_lines().len());
+        let mut layout = self.0.layout.borrow_mut();
+        let range = block_inlay_range(&layout.block_inlays, line_index);
+        assert!(
+            inlay_index < range.len(),
+            "line {} has {} block inlays, cannot remove inlay {}",
+            line_index,
+            range.len(),
+            inlay_index
+        );
+        let (_, inlay) = layout.block_inlays.remove(range.start + inlay_index);
+        drop(layout);
+        self.send_update(DocumentUpdate::Inlays(line_index..line_index + 1));
+        inlay
+    }
+
+    /// The inline inlays of the line at `line_index` with their byte indices, in order.
+    pub fn inline_inlays_at_line(&self, line_index: usize) -> Ref<'_, [(usize, InlineInlay)]> {
+        Ref::map(self.0.layout.borrow(), |layout| {
+            layout.inline_inlays[line_index].as_slice()
+        })
+    }
+
+    /// Adds an inline inlay at `byte_index` of the line at `line_index`, after the ones already
+    /// there.
+    pub fn insert_inline_inlay(&self, line_index: usize, byte_index: usize, inlay: InlineInlay) {
+        debug_assert!(byte_index <= self.as_text().as_lines()[line_index].len());
+        let mut layout = self.0.layout.borrow_mut();
+        let inline_inlays = &mut layout.inline_inlays[line_index];
+        let index = inline_inlays.partition_point(|&(index, _)| index <= byte_index);
+        inline_inlays.insert(index, (byte_index, inlay));
+        debug_assert!(inline_inlays
+            .windows(2)
+            .all(|inlays| inlays[0].0 <= inlays[1].0));
+        drop(layout);
+        self.send_update(DocumentUpdate::Inlays(line_index..line_index + 1));
+    }
+
+    /// Removes the inline inlay at `inlay_index` of those at `byte_index` of the line at
+    /// `line_index`.
+    pub fn remove_inline_inlay(
+        &self,
+        line_index: usize,
+        byte_index: usize,
+        inlay_index: usize,
+    ) -> InlineInlay {
+        let mut layout = self.0.layout.borrow_mut();
+        let inline_inlays = &mut layout.inline_inlays[line_index];
+        let start = inline_inlays.partition_point(|&(index, _)| index < byte_index);
+        let end = inline_inlays.partition_point(|&(index, _)| index <= byte_index);
+        assert!(
+            inlay_index < end - start,
+            "line {} has {} inline inlays at byte {}, cannot remove inlay {}",
+            line_index,
+            end - start,
+            byte_index,
+            inlay_index
+        );
+        let (_, inlay) = inline_inlays.remove(start + inlay_index);
+        drop(layout);
+        self.send_update(DocumentUpdate::Inlays(line_index..line_index + 1));
+        inlay
+    }
+
+    /// Removes all inline and block inlays, for instance when they came from a language
+    /// server that went away.
+    pub fn clear_all_inlays(&self) {
+        let mut layout = self.0.layout.borrow_mut();
+        for inline_inlays in &mut layout.inline_inlays {
+            inline_inlays.clear();
+        }
+        layout.block_inlays.clear();
+        let line_count = layout.inline_inlays.len();
+        drop(layout);
+        self.send_update(DocumentUpdate::Inlays(0..line_count + 1));
+    }
+
+    pub fn clear_inline_inlays_for_line(&self, line_index: usize) {
+        self.0.layout.borrow_mut().inline_inlays[line_index].clear();
+        self.send_update(DocumentUpdate::Inlays(line_index..line_index + 1));
+    }
+
+    /// Removes the block inlays in front of the line at `line_index`.
+    pub fn clear_block_inlays_for_line(&self, line_index: usize) {
+        debug_assert!(line_index <= self.as_text().as_lines().len());
+        let mut layout = self.0.layout.borrow_mut();
+        let range = block_inlay_range(&layout.block_inlays, line_index);
+        layout.block_inlays.drain(range);
+        drop(layout);
+        self.send_update(DocumentUpdate::Inlays(line_index..line_index + 1));
+    }
+
+    /// Collapses or expands the block inlay group with the given id, does nothing if there is
+    /// no such group.
+    pub fn set_block_group_collapsed(&self, id: u64, is_collapsed: bool) {
+        let mut layout = self.0.layout.borrow_mut();
+        let Some(line_index) = layout
+            .block_inlays
+            .iter_mut()
+            .find_map(|(line_index, inlay)| {
+                inlay
+                    .set_group_collapsed(id, is_collapsed)
+                    .then_some(*line_index)
+            })
+        else {
+            return;
+        };
+        drop(layout);
+        self.send_update(DocumentUpdate::Inlays(line_index..line_index + 1));
+    }
+
+    pub fn block_groups(&self) -> Vec<(u64, bool)> {
+        let mut groups = Vec::new();
+        for (_, inlay) in &self.0.layout.borrow().block_inlays {
+            inlay.collect_groups(&mut groups);
+        }
+        groups
+    }
+
     pub fn add_decoration(&mut self, decoration: Decoration) {
         self.0.decorations.borrow_mut().add_decoration(decoration);
     }
@@ -182,11 +404,7 @@ impl Document {
         self.0.decorations.borrow_mut().clear()
     }
 
-    pub fn add_session(
-        &mut self,
-        session_id: SessionId,
-        edit_sender: Sender<(Option<SelectionSet>, Vec<Edit>)>,
-    ) {
+    pub fn add_session(&mut self, session_id: SessionId, edit_sender: Sender<DocumentUpdate>) {
         self.0
             .edit_senders
             .borrow_mut()
@@ -197,6 +415,12 @@ impl Document {
         self.0.edit_senders.borrow_mut().remove(&session_id);
     }
 
+    fn send_update(&self, update: DocumentUpdate) {
+        for edit_sender in self.0.edit_senders.borrow().values() {
+            edit_sender.send(update.clone()).unwrap();
+        }
+    }
+
     fn autoindent(
         &self,
         line_ranges: &[Range<usize>],
@@ -389,11 +613,11 @@ impl Document {
         for (&session_id, edit_sender) in &*self.0.edit_senders.borrow() {
             if session_id == origin_id {
                 edit_sender
-                    .send((selections.clone(), edits.to_vec()))
+                    .send(DocumentUpdate::Edits(selections.clone(), edits.to_vec()))
                     .unwrap();
             } else {
                 edit_sender
-                    .send((
+                    .send(DocumentUpdate::Edits(
                         None,
                         edits
                             .iter()
@@ -655,6 +879,15 @@ pub struct DocumentLayout {
     pub block_inlays: Vec<(usize, BlockInlay)>,
 }
 
+/// What a document tells its sessions after it changed.
+#[derive(Clone, Debug)]
+pub enum DocumentUpdate {
+    /// The text was edited, with the selections to restore for an undo or redo by the session.
+    Edits(Option<SelectionSet>, Vec<Edit>),
+    /// The inlays of these lines changed.
+    Inlays(Range<usize>),
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum IndentState {
     Empty(usize),
@@ -684,7 +917,13 @@ struct DocumentInner {
     layout: RefCell<DocumentLayout>,
     tokenizer: RefCell<Tokenizer>,
     decorations: RefCell<DecorationSet>,
-    edit_senders: RefCell<HashMap<SessionId, Sender<(Option<SelectionSet>, Vec<Edit>)>>>,
+    edit_senders: RefCell<HashMap<SessionId, Sender<DocumentUpdate>>>,
+}
+
+fn block_inlay_range(block_inlays: &[(usize, BlockInlay)], line_index: usize) -> Range<usize> {
+    let start = block_inlays.partition_point(|&(index, _)| index < line_index);
+    let end = block_inlays.partition_point(|&(index, _)| index <= line_index);
+    start..end
 }
 
 fn tokenize(text: &str) -> impl Iterator<Item = Token> + '_ {
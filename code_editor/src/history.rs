@@ -1,7 +1,7 @@
 use crate::{
     selection::SelectionSet,
     session::SessionId,
-    text::{Edit, Text},
+    text::{Change, Drift, Edit, Length, Position, Text},
 };
 
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
@@ -51,6 +51,16 @@ impl History {
         self.redo_stack.clear();
     }
 
+    /// Applies an edit that did not originate here, such as one from a collaborator. The edit
+    /// itself cannot be undone, but the edits on the undo and redo stacks are rebased over it, so
+    /// that undoing them leaves the external change in place.
+    pub fn apply_external_edit(&mut self, edit: Edit) {
+        self.undo_stack.rebase(&edit.change);
+        self.redo_stack.rebase(&edit.change);
+        self.text.apply_change(edit.change);
+        self.current_desc = None;
+    }
+
     pub fn undo(
         &mut self,
         selections: &SelectionSet,
@@ -110,6 +120,7 @@ pub enum EditKind {
     Delete,
     Indent,
     Outdent,
+    Compose,
     Other,
 }
 
@@ -166,6 +177,41 @@ impl Stack {
         self.groups.clear();
         self.edits.clear();
     }
+
+    /// Rebases the stack over `change`, which applies to the text that the edits of the top
+    /// group apply to. Each edit is transformed so that it applies after `change`, while `change`
+    /// is carried down the stack by transforming it over the edit in turn.
+    fn rebase(&mut self, change: &Change) {
+        let mut changes = vec![change.clone()];
+        let mut groups = Vec::with_capacity(self.groups.len());
+        let mut edit_end = self.edits.len();
+        while let Some(mut group) = self.groups.pop() {
+            let mut edits = Vec::new();
+            for edit in self.edits.drain(group.edit_start..edit_end).rev() {
+                let (rebased_changes, next_changes) = transform(&[edit.change], &changes);
+                edits.extend(rebased_changes.into_iter().map(|change| Edit {
+                    change,
+                    drift: edit.drift,
+                }));
+                changes = next_changes;
+            }
+            edit_end = group.edit_start;
+            for change in &changes {
+                group.selections.apply_edit(
+                    &Edit {
+                        change: change.clone(),
+                        drift: Drift::Before,
+                    },
+                    None,
+                );
+            }
+            groups.push((group.selections, edits));
+        }
+        for (selections, edits) in groups.into_iter().rev() {
+            self.push_group(selections);
+            self.edits.extend(edits.into_iter().rev());
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -173,3 +219,108 @@ struct Group {
     selections: SelectionSet,
     edit_start: usize,
 }
+
+/// Transforms two sequences of changes that apply to the same text, returning the first sequence
+/// as it applies after the second, and the second as it applies after the first. Both orders end
+/// up with the same text. A change can be split in two if the other one lands inside it.
+fn transform(changes_a: &[Change], changes_b: &[Change]) -> (Vec<Change>, Vec<Change>) {
+    match (changes_a, changes_b) {
+        ([], _) | (_, []) => (changes_a.to_vec(), changes_b.to_vec()),
+        ([change_a], [change_b]) => transform_change(change_a, change_b),
+        ([change_a, rest_a @ ..], _) if !rest_a.is_empty() => {
+            let (mut new_changes_a, changes_b) = transform(&[change_a.clone()], changes_b);
+            let (new_rest_a, changes_b) = transform(rest_a, &changes_b);
+            new_changes_a.extend(new_rest_a);
+            (new_changes_a, changes_b)
+        }
+        (_, [change_b, rest_b @ ..]) => {
+            let (changes_a, mut new_changes_b) = transform(changes_a, &[change_b.clone()]);
+            let (changes_a, new_rest_b) = transform(&changes_a, rest_b);
+            new_changes_b.extend(new_rest_b);
+            (changes_a, new_changes_b)
+        }
+    }
+}
+
+fn transform_change(change_a: &Change, change_b: &Change) -> (Vec<Change>, Vec<Change>) {
+    match (change_a, change_b) {
+        (Change::Insert(position_a, text_a), Change::Insert(position_b, text_b)) => {
+            // When both insert at the same position, the text of `change_b` comes first.
+            let new_position_a = map_position(*position_a, change_b, Drift::Before);
+            let new_position_b = map_position(*position_b, change_a, Drift::After);
+            (
+                vec![Change::Insert(new_position_a, text_a.clone())],
+                vec![Change::Insert(new_position_b, text_b.clone())],
+            )
+        }
+        (Change::Insert(_, _), Change::Delete(_, _)) => {
+            let (changes_b, changes_a) = transform_change(change_b, change_a);
+            (changes_a, changes_b)
+        }
+        (Change::Delete(start, length), Change::Insert(position, text)) => {
+            let end = *start + *length;
+            if *position <= *start || *position >= end {
+                (
+                    vec![Change::Delete(
+                        map_position(*start, change_b, Drift::Before),
+                        *length,
+                    )],
+                    vec![Change::Insert(
+                        map_position(*position, change_a, Drift::After),
+                        text.clone(),
+                    )],
+                )
+            } else {
+                // The insertion lands inside the deletion, so the deletion is split around it.
+                // The part after the inserted text is deleted first, so that the start of the
+                // other part stays put.
+                (
+                    vec![
+                        Change::Delete(*position + text.length(), end - *position),
+                        Change::Delete(*start, *position - *start),
+                    ],
+                    vec![Change::Insert(*start, text.clone())],
+                )
+            }
+        }
+        (Change::Delete(start_a, length_a), Change::Delete(start_b, length_b)) => (
+            subtract_delete(*start_a, *length_a, *start_b, *length_b),
+            subtract_delete(*start_b, *length_b, *start_a, *length_a),
+        ),
+    }
+}
+
+/// The deletion of the range at `start` with `length`, as it applies after the range at
+/// `other_start` with `other_length` has been deleted. What is left of the range is contiguous.
+fn subtract_delete(
+    start: Position,
+    length: Length,
+    other_start: Position,
+    other_length: Length,
+) -> Vec<Change> {
+    let end = start + length;
+    let other_end = other_start + other_length;
+    let mut new_length = Length::zero();
+    if start < other_start {
+        new_length += end.min(other_start) - start;
+    }
+    if end > other_end {
+        new_length += end - start.max(other_end);
+    }
+    if new_length == Length::zero() {
+        return Vec::new();
+    }
+    let new_start = map_position(
+        start,
+        &Change::Delete(other_start, other_length),
+        Drift::Before,
+    );
+    vec![Change::Delete(new_start, new_length)]
+}
+
+fn map_position(position: Position, change: &Change, drift: Drift) -> Position {
+    position.apply_edit(&Edit {
+        change: change.clone(),
+        drift,
+    })
+}
@@ -2,11 +2,134 @@ use crate::widgets::{BlockWidget, InlineWidget};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum InlineInlay {
-    Text(String),
+    Text {
+        text: String,
+        /// Inlays above 0 are drawn on top of the text instead of in between it, so they take
+        /// up no room and do not move the text around them.
+        z_offset: i8,
+    },
     Widget(InlineWidget),
 }
 
+impl InlineInlay {
+    pub fn z_offset(&self) -> i8 {
+        match *self {
+            Self::Text { z_offset, .. } => z_offset,
+            Self::Widget(widget) => widget.z_offset,
+        }
+    }
+
+    pub fn is_overlay(&self) -> bool {
+        self.z_offset() > 0
+    }
+}
+
+/// Collects the inline inlays of a line in the order `Document` and `Layout` expect them in,
+/// sorted by byte index.
+#[derive(Clone, Debug, Default)]
+pub struct InlineInlaysBuilder {
+    inlays: Vec<(usize, InlineInlay)>,
+}
+
+impl InlineInlaysBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `inlay` at `byte_index`, after the inlays added so far, none of which may be at a
+    /// later byte index.
+    pub fn add_inline_inlay(mut self, byte_index: usize, inlay: InlineInlay) -> Self {
+        debug_assert!(
+            self.inlays
+                .last()
+                .map_or(true, |&(last_byte_index, _)| last_byte_index <= byte_index),
+            "inline inlay at byte {} added after one at a later byte",
+            byte_index
+        );
+        self.inlays.push((byte_index, inlay));
+        self
+    }
+
+    pub fn build(self) -> Vec<(usize, InlineInlay)> {
+        self.inlays
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum BlockInlay {
     Widget(BlockWidget),
+    /// Inlays that are shown or hidden together, such as an image with its caption.
+    Group {
+        id: u64,
+        is_collapsed: bool,
+        items: Vec<BlockInlay>,
+    },
+}
+
+impl BlockInlay {
+    /// The widgets in the inlay in order, each with whether it is shown, which it is not when
+    /// it is inside a collapsed group.
+    pub fn widgets(&self) -> Vec<(BlockWidget, bool)> {
+        let mut widgets = Vec::new();
+        self.collect_widgets(true, &mut widgets);
+        widgets
+    }
+
+    fn collect_widgets(&self, is_shown: bool, widgets: &mut Vec<(BlockWidget, bool)>) {
+        match *self {
+            Self::Widget(widget) => widgets.push((widget, is_shown)),
+            Self::Group {
+                is_collapsed,
+                ref items,
+                ..
+            } => {
+                for item in items {
+                    item.collect_widgets(is_shown && !is_collapsed, widgets);
+                }
+            }
+        }
+    }
+
+    pub fn widget_count(&self) -> usize {
+        match self {
+            Self::Widget(_) => 1,
+            Self::Group { items, .. } => items.iter().map(|item| item.widget_count()).sum(),
+        }
+    }
+
+    /// Appends the id and state of every group in the inlay to `groups`, outer groups first.
+    pub fn collect_groups(&self, groups: &mut Vec<(u64, bool)>) {
+        if let Self::Group {
+            id,
+            is_collapsed,
+            ref items,
+        } = *self
+        {
+            groups.push((id, is_collapsed));
+            for item in items {
+                item.collect_groups(groups);
+            }
+        }
+    }
+
+    /// Collapses or expands the group with the given id, returns `false` if there is no such
+    /// group in the inlay.
+    pub fn set_group_collapsed(&mut self, group_id: u64, collapsed: bool) -> bool {
+        match self {
+            Self::Widget(_) => false,
+            Self::Group {
+                id,
+                is_collapsed,
+                items,
+            } => {
+                if *id == group_id {
+                    *is_collapsed = collapsed;
+                    return true;
+                }
+                items
+                    .iter_mut()
+                    .any(|item| item.set_group_collapsed(group_id, collapsed))
+            }
+        }
+    }
 }
@@ -10,7 +10,7 @@ use {
         wrap::WrapData,
         Token,
     },
-    std::{cell::Ref, slice::Iter},
+    std::{cell::Ref, collections::HashMap, slice::Iter},
 };
 
 #[derive(Debug)]
@@ -99,19 +99,40 @@ impl<'a> Layout<'a> {
 
     pub fn block_elements(&self, line_start: usize, line_end: usize) -> BlockElements<'_> {
         let mut block_inlays = self.document_layout.block_inlays.iter();
-        while block_inlays
+        let mut block_widget_index = 0;
+        while let Some((_, block_inlay)) = block_inlays
             .as_slice()
             .first()
-            .map_or(false, |&(position, _)| position < line_start)
+            .filter(|&&(position, _)| position < line_start)
         {
             block_inlays.next();
+            block_widget_index += block_inlay.widget_count();
         }
         BlockElements {
             lines: self.lines(line_start, line_end),
             block_inlays,
+            block_widget_index,
+            block_widget_heights: &self.session_layout.block_widget_heights,
+            pending_widgets: Vec::new(),
             position: line_start,
         }
     }
+
+    /// Like `block_elements`, but leaves out the lines that lie entirely above `y_start` or
+    /// below `y_end`, so that scrolling through a large document only lays out what is visible.
+    pub fn block_elements_in_viewport(
+        &self,
+        line_start: usize,
+        line_end: usize,
+        y_start: f64,
+        y_end: f64,
+    ) -> BlockElements<'_> {
+        let line_end = line_end.min(self.find_first_line_starting_after_y(y_end));
+        let line_start = line_start
+            .max(self.find_first_line_ending_after_y(y_start))
+            .min(line_end);
+        self.block_elements(line_start, line_end)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -168,6 +189,11 @@ impl<'a> Line<'a> {
         self.wrap_data.unwrap().wraps.len() + 1
     }
 
+    /// Returns `true` if the text of the line is empty or only whitespace.
+    pub fn is_blank(&self) -> bool {
+        self.text.is_blank()
+    }
+
     pub fn column_count(&self) -> usize {
         self.column_count.unwrap()
     }
@@ -333,6 +359,15 @@ impl<'a> Line<'a> {
         }
     }
 
+    /// The inlays with a positive `z_offset`, which `inline_elements` leaves out, with the byte
+    /// index they are attached to.
+    pub fn overlay_inlays(&self) -> impl Iterator<Item = (usize, &'a InlineInlay)> {
+        self.inlays
+            .iter()
+            .filter(|(_, inlay)| inlay.is_overlay())
+            .map(|(byte_index, inlay)| (*byte_index, inlay))
+    }
+
     pub fn wrapped_elements(&self) -> WrappedElements<'a> {
         let mut elements = self.inline_elements();
         WrappedElements {
@@ -355,15 +390,18 @@ impl<'a> Iterator for InlineElements<'a> {
     type Item = InlineElement<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self
+        while self
             .inlays
             .as_slice()
             .first()
             .map_or(false, |&(position, _)| position == self.position)
         {
             let (_, inline_inlay) = self.inlays.next().unwrap();
+            if inline_inlay.is_overlay() {
+                continue;
+            }
             return Some(match *inline_inlay {
-                InlineInlay::Text(ref text) => InlineElement::Text {
+                InlineInlay::Text { ref text, .. } => InlineElement::Text {
                     is_inlay: true,
                     text,
                 },
@@ -453,6 +491,10 @@ pub enum WrappedElement<'a> {
 pub struct BlockElements<'a> {
     lines: Lines<'a>,
     block_inlays: Iter<'a, (usize, BlockInlay)>,
+    block_widget_index: usize,
+    block_widget_heights: &'a HashMap<usize, f64>,
+    /// The shown widgets of the block inlay being iterated over, last one first.
+    pending_widgets: Vec<BlockWidget>,
     position: usize,
 }
 
@@ -460,16 +502,32 @@ impl<'a> Iterator for BlockElements<'a> {
     type Item = BlockElement<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self
-            .block_inlays
-            .as_slice()
-            .first()
-            .map_or(false, |&(line, _)| line == self.position)
+        while self.pending_widgets.is_empty()
+            && self
+                .block_inlays
+                .as_slice()
+                .first()
+                .map_or(false, |&(line, _)| line == self.position)
         {
             let (_, block_inlay) = self.block_inlays.next().unwrap();
-            return Some(match *block_inlay {
-                BlockInlay::Widget(widget) => BlockElement::Widget(widget),
-            });
+            for (widget, is_shown) in block_inlay.widgets() {
+                let index = self.block_widget_index;
+                self.block_widget_index += 1;
+                if is_shown {
+                    self.pending_widgets.push(BlockWidget {
+                        height: self
+                            .block_widget_heights
+                            .get(&index)
+                            .copied()
+                            .unwrap_or(widget.height),
+                        ..widget
+                    });
+                }
+            }
+            self.pending_widgets.reverse();
+        }
+        if let Some(widget) = self.pending_widgets.pop() {
+            return Some(BlockElement::Widget(widget));
         }
         let line = self.lines.next()?;
         self.position += 1;
@@ -2,15 +2,16 @@ use {
     crate::{
         layout::Layout,
         str::StrExt,
-        text::{Edit, Length, Position},
+        text::{Edit, Length, Position, Range},
     },
-    std::{ops, ops::Deref, slice::Iter},
+    std::{cell::Ref, ops, ops::Deref, slice::Iter},
 };
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Hash, Eq)]
 pub struct Selection {
     pub cursor: Cursor,
     pub anchor: Position,
+    pub kind: SelectionKind,
 }
 
 impl Selection {
@@ -26,6 +27,22 @@ impl Selection {
         }
     }
 
+    /// Whether the two selections share at least one character. Unlike `overlaps_with`, which
+    /// decides whether selections get merged, touching selections do not overlap.
+    pub fn overlaps(self, other: Self) -> bool {
+        self.start() < other.end() && other.start() < self.end()
+    }
+
+    /// Whether `position` lies in `start()..end()`, the way selections are drawn.
+    pub fn contains_position(self, position: Position) -> bool {
+        self.start() <= position && position < self.end()
+    }
+
+    /// Whether `position` lies in `start()..=end()`.
+    pub fn contains_position_inclusive(self, position: Position) -> bool {
+        self.start() <= position && position <= self.end()
+    }
+
     pub fn start(self) -> Position {
         self.cursor.position.min(self.anchor)
     }
@@ -80,24 +97,108 @@ impl Selection {
         }
     }
 
-    pub fn merge_with(self, other: Self) -> Option<Self> {
-        if self.overlaps_with(other) {
-            Some(if self.anchor <= self.cursor.position {
+    /// Splits the selection into `start()..position` and `position..end()`, both pointing the
+    /// same way as this one, or returns `None` if `position` does not lie strictly inside it.
+    pub fn split_at(self, position: Position) -> Option<(Self, Self)> {
+        if position <= self.start() || position >= self.end() {
+            return None;
+        }
+        Some(if self.anchor <= self.cursor.position {
+            (
                 Selection {
                     anchor: self.anchor,
-                    cursor: other.cursor,
-                }
-            } else {
+                    cursor: Cursor {
+                        position,
+                        affinity: Affinity::Before,
+                        preferred_column_index: None,
+                    },
+                    kind: self.kind,
+                },
                 Selection {
-                    anchor: other.anchor,
-                    cursor: self.cursor,
-                }
-            })
+                    anchor: position,
+                    ..self
+                },
+            )
+        } else {
+            (
+                Selection {
+                    anchor: position,
+                    ..self
+                },
+                Selection {
+                    anchor: self.anchor,
+                    cursor: Cursor {
+                        position,
+                        affinity: Affinity::After,
+                        preferred_column_index: None,
+                    },
+                    kind: self.kind,
+                },
+            )
+        })
+    }
+
+    pub fn merge_with(self, other: Self) -> Option<Self> {
+        if self.overlaps_with(other) {
+            Some(self.hull(other))
         } else {
             None
         }
     }
 
+    /// The selection covering both, pointing the same way as this one.
+    fn hull(self, other: Self) -> Self {
+        let start = self.start().min(other.start());
+        let end = self.end().max(other.end());
+        let (anchor, cursor_position) = if self.anchor <= self.cursor.position {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        let cursor = [self.cursor, other.cursor]
+            .into_iter()
+            .find(|cursor| cursor.position == cursor_position)
+            .unwrap_or_else(|| Cursor::from(cursor_position));
+        Selection {
+            anchor,
+            cursor,
+            kind: self.kind,
+        }
+    }
+
+    /// Merges the selections that overlap or touch, leaving them sorted by `start()` with a gap
+    /// between any two of them. They must already be sorted by `start()`.
+    pub fn merge_sorted(selections: &mut Vec<Selection>) {
+        Self::merge_sorted_and_track(selections, None);
+    }
+
+    /// Like `merge_sorted`, but also moves `last_index` to the selection that the selection at
+    /// `last_index` was merged into.
+    pub fn merge_sorted_with_last(selections: &mut Vec<Selection>, last_index: &mut usize) {
+        Self::merge_sorted_and_track(selections, Some(last_index));
+    }
+
+    fn merge_sorted_and_track(selections: &mut Vec<Selection>, mut last_index: Option<&mut usize>) {
+        let mut current_index = 0;
+        while current_index + 1 < selections.len() {
+            let next_index = current_index + 1;
+            let current_selection = selections[current_index];
+            let next_selection = selections[next_index];
+            assert!(current_selection.start() <= next_selection.start());
+            if current_selection.end() >= next_selection.start() {
+                selections[current_index] = current_selection.hull(next_selection);
+                selections.remove(next_index);
+                if let Some(last_index) = last_index.as_deref_mut() {
+                    if next_index <= *last_index {
+                        *last_index -= 1;
+                    }
+                }
+            } else {
+                current_index += 1;
+            }
+        }
+    }
+
     pub fn apply_edit(self, edit: &Edit) -> Self {
         Self {
             anchor: self.anchor.apply_edit(edit),
@@ -112,10 +213,23 @@ impl From<Cursor> for Selection {
         Self {
             cursor,
             anchor: cursor.position,
+            kind: SelectionKind::Normal,
         }
     }
 }
 
+/// A column selection is made of one selection per line, each of which knows the columns of
+/// the whole rectangle.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum SelectionKind {
+    #[default]
+    Normal,
+    Column {
+        anchor_col: usize,
+        cursor_col: usize,
+    },
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct SelectionSet {
     selections: Vec<Selection>,
@@ -130,6 +244,18 @@ impl SelectionSet {
         &self.selections
     }
 
+    /// The selections that touch `range`, found by binary search since the selections are
+    /// sorted and do not overlap.
+    pub fn in_range(&self, range: Range) -> &[Selection] {
+        let start = self
+            .selections
+            .partition_point(|selection| selection.end() < range.start());
+        let end = self
+            .selections
+            .partition_point(|selection| selection.start() <= range.end());
+        &self.selections[start..end.max(start)]
+    }
+
     pub fn update_selection(
         &mut self,
         index: usize,
@@ -176,6 +302,12 @@ impl SelectionSet {
         self.selections.push(selection);
     }
 
+    /// Replaces all selections, which must be sorted and must not overlap.
+    pub fn set_selections(&mut self, selections: Vec<Selection>) {
+        assert!(!selections.is_empty());
+        self.selections = selections;
+    }
+
     fn normalize_selection(&mut self, index: usize) -> usize {
         let mut index = index;
         while index > 0 {
@@ -197,26 +329,16 @@ impl SelectionSet {
     }
 
     fn normalize_all_selections(&mut self, retained_index: Option<usize>) -> Option<usize> {
-        let mut retained_index = retained_index;
-        let mut current_index = 0;
-        while current_index + 1 < self.selections.len() {
-            let next_index = current_index + 1;
-            let current_selection = self.selections[current_index];
-            let next_selection = self.selections[next_index];
-            assert!(current_selection.start() <= next_selection.start());
-            if let Some(merged_selection) = current_selection.merge_with(next_selection) {
-                self.selections[current_index] = merged_selection;
-                self.selections.remove(next_index);
-                if let Some(retained_index) = &mut retained_index {
-                    if next_index <= *retained_index {
-                        *retained_index -= 1;
-                    }
-                }
-            } else {
-                current_index += 1;
+        match retained_index {
+            Some(mut retained_index) => {
+                Selection::merge_sorted_with_last(&mut self.selections, &mut retained_index);
+                Some(retained_index)
+            }
+            None => {
+                Selection::merge_sorted(&mut self.selections);
+                None
             }
         }
-        retained_index
     }
 }
 
@@ -245,6 +367,37 @@ impl<'a> IntoIterator for &'a SelectionSet {
     }
 }
 
+/// The selections of `Session::iter_selections_in_range`.
+#[derive(Debug)]
+pub struct SelectionsInRange<'a> {
+    selections: Ref<'a, [Selection]>,
+    index: usize,
+}
+
+impl<'a> SelectionsInRange<'a> {
+    pub fn new(selections: Ref<'a, [Selection]>) -> Self {
+        Self {
+            selections,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for SelectionsInRange<'a> {
+    type Item = Selection;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let selection = *self.selections.get(self.index)?;
+        self.index += 1;
+        Some(selection)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.selections.len() - self.index;
+        (len, Some(len))
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Cursor {
     pub position: Position,
@@ -302,6 +455,52 @@ impl Cursor {
         self
     }
 
+    /// Moves over the whitespace in front of the cursor, and then over either a word or a single
+    /// word separator.
+    pub fn move_word_left(self, lines: &[String], word_separators: &[char]) -> Self {
+        if self.is_at_start_of_line() {
+            return self.move_left(lines);
+        }
+        let line = &lines[self.position.line_index][..self.position.byte_index];
+        let boundaries = line.word_boundaries(word_separators).collect::<Vec<_>>();
+        let mut byte_index = boundaries[boundaries.len() - 2];
+        if line[byte_index..].is_blank() && boundaries.len() > 2 {
+            byte_index = boundaries[boundaries.len() - 3];
+        }
+        Self {
+            position: Position {
+                line_index: self.position.line_index,
+                byte_index,
+            },
+            affinity: Affinity::After,
+            preferred_column_index: None,
+        }
+    }
+
+    /// Moves over the whitespace after the cursor, and then over either a word or a single word
+    /// separator.
+    pub fn move_word_right(self, lines: &[String], word_separators: &[char]) -> Self {
+        if self.is_at_end_of_line(lines) {
+            return self.move_right(lines);
+        }
+        let line = &lines[self.position.line_index][self.position.byte_index..];
+        let mut boundaries = line.word_boundaries(word_separators).skip(1);
+        let mut byte_index = boundaries.next().unwrap();
+        if line[..byte_index].is_blank() {
+            if let Some(next_byte_index) = boundaries.next() {
+                byte_index = next_byte_index;
+            }
+        }
+        Self {
+            position: Position {
+                line_index: self.position.line_index,
+                byte_index: self.position.byte_index + byte_index,
+            },
+            affinity: Affinity::Before,
+            preferred_column_index: None,
+        }
+    }
+
     pub fn move_up(self, layout: &Layout<'_>) -> Self {
         if !self.is_at_first_row_of_line(layout) {
             return self.move_to_prev_row_of_line(layout);
@@ -322,7 +521,12 @@ impl Cursor {
         self.move_to_end_of_line(layout.as_text().as_lines())
     }
 
-    pub fn home(self, lines: &[String]) -> Self {
+    /// Moves to the start of the line. A `smart` move goes to the first non-whitespace character
+    /// first, and only to the start of the line when the cursor is at or before it already.
+    pub fn home(self, lines: &[String], smart: bool) -> Self {
+        if !smart {
+            return self.move_to_start_of_line();
+        }
         if !self.is_at_start_of_line() {
             let indent_len = lines[self.position.line_index].indent().unwrap_or("").len();
             if self.position.byte_index <= indent_len {
@@ -341,7 +545,12 @@ impl Cursor {
         self
     }
 
-    pub fn end(self, lines: &[String]) -> Self {
+    /// Moves to the end of the line. A `smart` move from inside the indentation goes to the end
+    /// of the indentation first.
+    pub fn end(self, lines: &[String], smart: bool) -> Self {
+        if !smart {
+            return self.move_to_end_of_line(lines);
+        }
         if !self.is_at_end_of_line(lines) {
             let indent_len = lines[self.position.line_index].indent().unwrap_or("").len();
             if self.position.byte_index >= indent_len {
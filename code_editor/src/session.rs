@@ -1,21 +1,24 @@
 use {
     crate::{
         char::CharExt,
-        document::Document,
+        document::{Document, DocumentUpdate},
         history::EditKind,
+        inlays::{BlockInlay, InlineInlay},
         layout::{BlockElement, Layout, WrappedElement},
-        selection::{Affinity, Cursor, SelectionSet},
+        selection::{Affinity, Cursor, SelectionKind, SelectionSet, SelectionsInRange},
         str::StrExt,
-        text::{Change, Drift, Edit, Length, Position, Text},
+        text::{Change, Drift, Edit, Length, LineEnding, Position, Range, Text},
+        widgets::BlockWidgetSizing,
         wrap,
         wrap::WrapData,
         Selection, Settings,
     },
+    makepad_widgets::{dvec2, Rect},
     std::{
         cell::{Cell, Ref, RefCell},
-        collections::HashSet,
+        collections::{HashMap, HashSet},
         fmt::Write,
-        iter, mem,
+        iter, mem, ops,
         rc::Rc,
         sync::{atomic, atomic::AtomicUsize, mpsc, mpsc::Receiver},
     },
@@ -24,24 +27,40 @@ use {
 #[derive(Debug)]
 pub struct Session {
     id: SessionId,
+    /// The settings the session was created with, possibly shared with other sessions.
+    shared_settings: Rc<Settings>,
+    /// The settings in effect, the shared ones unless this session overrides them.
     settings: Rc<Settings>,
     document: Document,
     layout: RefCell<SessionLayout>,
     selection_state: RefCell<SelectionState>,
     wrap_column: Cell<Option<usize>>,
     fold_state: RefCell<FoldState>,
-    edit_receiver: Receiver<(Option<SelectionSet>, Vec<Edit>)>,
+    /// The range an input method composition in progress replaces, and its tentative text. The
+    /// tentative text stays out of the document until the composition ends.
+    ime_composition: RefCell<Option<(Range, String)>>,
+    block_widget_sizing: RefCell<Option<Rc<dyn BlockWidgetSizing>>>,
+    available_width: Cell<Option<f64>>,
+    selection_rect_cache: RefCell<HashMap<usize, Vec<Rect>>>,
+    edit_receiver: Receiver<DocumentUpdate>,
 }
 
 impl Session {
     pub fn new(document: Document) -> Self {
+        Self::with_settings(document, Rc::new(Settings::default()))
+    }
+
+    /// A session using `settings`, which can be shared with other sessions. Override them for
+    /// just this session with `set_session_setting`.
+    pub fn with_settings(document: Document, settings: Rc<Settings>) -> Self {
         static ID: AtomicUsize = AtomicUsize::new(0);
 
         let (edit_sender, edit_receiver) = mpsc::channel();
         let line_count = document.as_text().as_lines().len();
         let mut session = Self {
             id: SessionId(ID.fetch_add(1, atomic::Ordering::AcqRel)),
-            settings: Rc::new(Settings::default()),
+            shared_settings: settings.clone(),
+            settings,
             document,
             layout: RefCell::new(SessionLayout {
                 y: Vec::new(),
@@ -49,9 +68,12 @@ impl Session {
                 fold_column: (0..line_count).map(|_| 0).collect(),
                 scale: (0..line_count).map(|_| 1.0).collect(),
                 wrap_data: (0..line_count).map(|_| None).collect(),
+                block_widget_heights: HashMap::new(),
             }),
             selection_state: RefCell::new(SelectionState {
                 mode: SelectionMode::Simple,
+                column_mode: false,
+                column_anchor: None,
                 selections: SelectionSet::new(),
                 last_added_selection_index: Some(0),
                 injected_char_stack: Vec::new(),
@@ -63,6 +85,10 @@ impl Session {
                 folded_lines: HashSet::new(),
                 unfolding_lines: HashSet::new(),
             }),
+            ime_composition: RefCell::new(None),
+            block_widget_sizing: RefCell::new(None),
+            available_width: Cell::new(None),
+            selection_rect_cache: RefCell::new(HashMap::new()),
             edit_receiver,
         };
         for line in 0..line_count {
@@ -70,6 +96,13 @@ impl Session {
         }
         session.update_y();
         session.document.add_session(session.id, edit_sender);
+        if session.settings.normalize_line_endings
+            && session.document.as_text().detect_line_ending() != LineEnding::Lf
+        {
+            let edits = session.document.as_text().line_ending_edits();
+            session.document.apply_external_edits(session.id, edits);
+            session.handle_changes();
+        }
         session
     }
 
@@ -77,10 +110,24 @@ impl Session {
         self.id
     }
 
+    /// The settings in effect, the override of this session if it has one.
     pub fn settings(&self) -> &Rc<Settings> {
         &self.settings
     }
 
+    /// Changes a setting for this session only, starting from the settings in effect.
+    pub fn set_session_setting<T>(&mut self, setter: impl FnOnce(&mut Settings) -> T) -> T {
+        let mut settings = (*self.settings).clone();
+        let result = setter(&mut settings);
+        self.settings = Rc::new(settings);
+        result
+    }
+
+    /// Goes back to the settings the session was created with.
+    pub fn clear_session_settings(&mut self) {
+        self.settings = self.shared_settings.clone();
+    }
+
     pub fn document(&self) -> &Document {
         &self.document
     }
@@ -103,10 +150,101 @@ impl Session {
         })
     }
 
+    /// The selections that touch `range`, without going over all of them.
+    pub fn iter_selections_in_range(&self, range: Range) -> SelectionsInRange<'_> {
+        SelectionsInRange::new(Ref::map(self.selection_state.borrow(), |selection_state| {
+            selection_state.selections.in_range(range)
+        }))
+    }
+
     pub fn last_added_selection_index(&self) -> Option<usize> {
         self.selection_state.borrow().last_added_selection_index
     }
 
+    /// The rectangles the selections cover on the line at `line_index`, one for each row of
+    /// each selection, in columns across and lines down from the top of the document. These
+    /// are the rectangles the editor fills in to show the selections.
+    pub fn selection_rects(&self, line_index: usize) -> Vec<Rect> {
+        if let Some(rects) = self.selection_rect_cache.borrow().get(&line_index) {
+            return rects.clone();
+        }
+        let layout = self.layout();
+        let line = layout.line(line_index);
+        let mut row_end_columns = Vec::new();
+        let mut column_index = 0;
+        for element in line.wrapped_elements() {
+            match element {
+                WrappedElement::Text { text, .. } => {
+                    column_index += text.column_count();
+                }
+                WrappedElement::Widget(widget) => {
+                    column_index += widget.column_count;
+                }
+                WrappedElement::Wrap => {
+                    row_end_columns.push(column_index);
+                    column_index = line.wrap_indent_column_count();
+                }
+            }
+        }
+        // A selection that goes on to the next line also covers the line break.
+        row_end_columns.push(column_index + 1);
+        let mut rects = Vec::new();
+        for selection in self.iter_selections_in_range(Range::from_start_and_length(
+            Position {
+                line_index,
+                byte_index: 0,
+            },
+            Length {
+                line_count: 0,
+                byte_count: line.text.len(),
+            },
+        )) {
+            if selection.is_empty() {
+                continue;
+            }
+            let (start, end) = (selection.start(), selection.end());
+            let (start_row_index, start_column_index) = if start.line_index < line_index {
+                (0, 0)
+            } else {
+                line.logical_to_grid_position(start.byte_index, selection.start_affinity())
+            };
+            let (end_row_index, end_column_index) = if end.line_index > line_index {
+                (row_end_columns.len() - 1, *row_end_columns.last().unwrap())
+            } else {
+                line.logical_to_grid_position(end.byte_index, selection.end_affinity())
+            };
+            for row_index in start_row_index..=end_row_index {
+                let (start_x, y) = line.grid_to_normalized_position(
+                    row_index,
+                    if row_index == start_row_index {
+                        start_column_index
+                    } else {
+                        0
+                    },
+                );
+                let (end_x, _) = line.grid_to_normalized_position(
+                    row_index,
+                    if row_index == end_row_index {
+                        end_column_index
+                    } else {
+                        row_end_columns[row_index]
+                    },
+                );
+                if start_x < end_x {
+                    rects.push(Rect {
+                        pos: dvec2(start_x, line.y() + y),
+                        size: dvec2(end_x - start_x, line.scale()),
+                    });
+                }
+            }
+        }
+        drop(layout);
+        self.selection_rect_cache
+            .borrow_mut()
+            .insert(line_index, rects.clone());
+        rects
+    }
+
     pub fn highlighted_delimiter_positions(&self) -> Ref<'_, HashSet<Position>> {
         Ref::map(self.selection_state.borrow(), |selection_state| {
             &selection_state.highlighted_delimiter_positions
@@ -125,6 +263,22 @@ impl Session {
         self.update_y();
     }
 
+    /// Sets what decides the heights of block widgets, `None` leaves them at their own height.
+    pub fn set_block_widget_sizing(&self, sizing: Option<Rc<dyn BlockWidgetSizing>>) {
+        *self.block_widget_sizing.borrow_mut() = sizing;
+        self.update_block_widget_heights();
+    }
+
+    /// Sets the width in columns that block widgets have, the editor does so whenever it is
+    /// resized.
+    pub fn set_available_width(&self, available_width: f64) {
+        if self.available_width.get() == Some(available_width) {
+            return;
+        }
+        self.available_width.set(Some(available_width));
+        self.update_block_widget_heights();
+    }
+
     pub fn fold(&self) {
         let mut fold_state = self.fold_state.borrow_mut();
         let line_count = self.document().as_text().as_lines().len();
@@ -191,7 +345,20 @@ impl Session {
         true
     }
 
+    /// Makes `set_selection` start column selections, as `set_column_selection` does.
+    pub fn set_column_selection_mode(&self, enabled: bool) {
+        self.selection_state.borrow_mut().column_mode = enabled;
+    }
+
+    pub fn is_column_selection_mode(&self) -> bool {
+        self.selection_state.borrow().column_mode
+    }
+
     pub fn set_selection(&self, position: Position, affinity: Affinity, mode: SelectionMode) {
+        if mode == SelectionMode::Simple && self.is_column_selection_mode() {
+            self.set_column_selection(position, affinity);
+            return;
+        }
         let selection = grow_selection(
             Selection::from(Cursor {
                 position,
@@ -204,6 +371,7 @@ impl Session {
         );
         let mut selection_state = self.selection_state.borrow_mut();
         selection_state.mode = mode;
+        selection_state.column_anchor = None;
         selection_state.selections.set_selection(selection);
         selection_state.last_added_selection_index = Some(0);
         selection_state.injected_char_stack.clear();
@@ -225,6 +393,7 @@ impl Session {
         );
         let mut selection_state = self.selection_state.borrow_mut();
         selection_state.mode = mode;
+        selection_state.column_anchor = None;
         selection_state.last_added_selection_index =
             Some(selection_state.selections.add_selection(selection));
         selection_state.injected_char_stack.clear();
@@ -233,7 +402,39 @@ impl Session {
         self.document().force_new_group();
     }
 
+    /// Starts a column selection, which `move_to` then grows into a rectangle with one
+    /// selection per line.
+    pub fn set_column_selection(&self, position: Position, affinity: Affinity) {
+        let column_index = self.document.as_text().as_lines()[position.line_index]
+            [..position.byte_index]
+            .column_count();
+        let mut selection_state = self.selection_state.borrow_mut();
+        selection_state.mode = SelectionMode::Simple;
+        selection_state.column_anchor = Some((position.line_index, column_index));
+        selection_state.selections.set_selection(Selection {
+            kind: SelectionKind::Column {
+                anchor_col: column_index,
+                cursor_col: column_index,
+            },
+            ..Selection::from(Cursor {
+                position,
+                affinity,
+                preferred_column_index: None,
+            })
+        });
+        selection_state.last_added_selection_index = Some(0);
+        selection_state.injected_char_stack.clear();
+        drop(selection_state);
+        self.update_highlighted_delimiter_positions();
+        self.document().force_new_group();
+    }
+
     pub fn move_to(&self, position: Position, affinity: Affinity) {
+        let column_anchor = self.selection_state.borrow().column_anchor;
+        if let Some((anchor_line_index, anchor_col)) = column_anchor {
+            self.move_column_selection_to(anchor_line_index, anchor_col, position);
+            return;
+        }
         let mut selection_state = self.selection_state.borrow_mut();
         let last_added_selection_index = selection_state.last_added_selection_index.unwrap();
         let mode = selection_state.mode;
@@ -259,6 +460,56 @@ impl Session {
         self.document().force_new_group();
     }
 
+    fn move_column_selection_to(
+        &self,
+        anchor_line_index: usize,
+        anchor_col: usize,
+        position: Position,
+    ) {
+        let text = self.document.as_text();
+        let lines = text.as_lines();
+        let cursor_col = lines[position.line_index][..position.byte_index].column_count();
+        let kind = SelectionKind::Column {
+            anchor_col,
+            cursor_col,
+        };
+        let start_line_index = anchor_line_index.min(position.line_index);
+        let end_line_index = anchor_line_index.max(position.line_index);
+        let selections: Vec<_> = (start_line_index..=end_line_index)
+            .map(|line_index| {
+                let line = &lines[line_index];
+                Selection {
+                    cursor: Cursor {
+                        position: Position {
+                            line_index,
+                            byte_index: byte_index_at_column(line, cursor_col),
+                        },
+                        affinity: Affinity::Before,
+                        preferred_column_index: None,
+                    },
+                    anchor: Position {
+                        line_index,
+                        byte_index: byte_index_at_column(line, anchor_col),
+                    },
+                    kind,
+                }
+            })
+            .collect();
+        drop(text);
+        let mut selection_state = self.selection_state.borrow_mut();
+        selection_state.last_added_selection_index =
+            Some(if position.line_index < anchor_line_index {
+                0
+            } else {
+                selections.len() - 1
+            });
+        selection_state.selections.set_selections(selections);
+        selection_state.injected_char_stack.clear();
+        drop(selection_state);
+        self.update_highlighted_delimiter_positions();
+        self.document().force_new_group();
+    }
+
     pub fn move_left(&self, reset_anchor: bool) {
         self.modify_selections(reset_anchor, |selection, layout| {
             selection.update_cursor(|cursor| cursor.move_left(layout.as_text().as_lines()))
@@ -271,6 +522,34 @@ impl Session {
         });
     }
 
+    pub fn move_word_left(&self, reset_anchor: bool) {
+        self.modify_selections(reset_anchor, |selection, layout| {
+            selection.update_cursor(|cursor| {
+                cursor.move_word_left(layout.as_text().as_lines(), &self.settings.word_separators)
+            })
+        });
+    }
+
+    pub fn move_word_right(&self, reset_anchor: bool) {
+        self.modify_selections(reset_anchor, |selection, layout| {
+            selection.update_cursor(|cursor| {
+                cursor.move_word_right(layout.as_text().as_lines(), &self.settings.word_separators)
+            })
+        });
+    }
+
+    pub fn move_to_document_start(&self, reset_anchor: bool) {
+        self.modify_selections(reset_anchor, |selection, _| {
+            selection.update_cursor(|cursor| cursor.move_to_file_start())
+        });
+    }
+
+    pub fn move_to_document_end(&self, reset_anchor: bool) {
+        self.modify_selections(reset_anchor, |selection, layout| {
+            selection.update_cursor(|cursor| cursor.move_to_file_end(layout.as_text().as_lines()))
+        });
+    }
+
     pub fn move_up(&self, reset_anchor: bool) {
         self.modify_selections(reset_anchor, |selection, layout| {
             selection.update_cursor(|cursor| cursor.move_up(layout))
@@ -283,18 +562,72 @@ impl Session {
         });
     }
 
-    pub fn home(&self, reset_anchor: bool) {
+    pub fn home(&self, reset_anchor: bool, smart: bool) {
         self.modify_selections(reset_anchor, |selection, layout| {
-            selection.update_cursor(|cursor| cursor.home(layout.as_text().as_lines()))
+            selection.update_cursor(|cursor| cursor.home(layout.as_text().as_lines(), smart))
         });
     }
 
-    pub fn end(&self, reset_anchor: bool) {
+    pub fn end(&self, reset_anchor: bool, smart: bool) {
         self.modify_selections(reset_anchor, |selection, layout| {
-            selection.update_cursor(|cursor| cursor.end(layout.as_text().as_lines()))
+            selection.update_cursor(|cursor| cursor.end(layout.as_text().as_lines(), smart))
         });
     }
 
+    /// Selects the next occurrence of `pattern` after every cursor, starting over at the top of
+    /// the document if `wrap` is set. Cursors without a match stay where they are.
+    pub fn move_cursors_to_next_match(&self, pattern: &str, wrap: bool) {
+        let text = self.document.as_text();
+        let mut selection_state = self.selection_state.borrow_mut();
+        let primary_index = selection_state.last_added_selection_index.unwrap_or(0);
+        let mut selections: Vec<_> = selection_state
+            .selections
+            .iter()
+            .map(|&selection| {
+                let range = text
+                    .find_from(pattern, selection.end())
+                    .next()
+                    .or_else(|| {
+                        if wrap {
+                            text.find(pattern).next()
+                        } else {
+                            None
+                        }
+                    })
+                    .map(|start| Range::from_start_and_length(start, Text::from(pattern).length()));
+                match range {
+                    Some(range) => Selection {
+                        cursor: Cursor {
+                            position: range.end(),
+                            affinity: Affinity::Before,
+                            preferred_column_index: None,
+                        },
+                        anchor: range.start(),
+                        kind: SelectionKind::Normal,
+                    },
+                    None => selection,
+                }
+            })
+            .collect();
+        drop(text);
+        let primary_selection = selections[primary_index];
+        // Wrapping around can move cursors in front of the others.
+        selections.sort_by_key(|selection| selection.start());
+        selections.dedup();
+        let primary_index = selections
+            .iter()
+            .position(|&selection| selection == primary_selection);
+        selection_state.column_anchor = None;
+        selection_state.selections.set_selections(selections);
+        selection_state.last_added_selection_index = selection_state
+            .selections
+            .update_all_selections(primary_index, |selection| selection);
+        selection_state.injected_char_stack.clear();
+        drop(selection_state);
+        self.update_highlighted_delimiter_positions();
+        self.document().force_new_group();
+    }
+
     pub fn insert(&self, text: Text) {
 
         let mut edit_kind = EditKind::Insert;
@@ -399,11 +732,118 @@ impl Session {
         );
     }
 
+    /// Replaces the whole text of the document, this can be undone like any other edit.
+    pub fn set_text(&self, text: impl Into<Text>) {
+        self.document.set_text(
+            self.id,
+            &self.selection_state.borrow().selections,
+            text.into(),
+        );
+    }
+
+    /// Turns `\r\n` and lone `\r` line breaks into `\n`, as an edit that can be undone.
+    pub fn normalize_line_endings(&self) {
+        let edits = self.document.as_text().line_ending_edits();
+        if edits.is_empty() {
+            return;
+        }
+        self.document.edit(
+            self.id,
+            EditKind::Other,
+            &self.selection_state.borrow().selections,
+            |mut editor| {
+                for edit in edits {
+                    editor.apply_edit(edit);
+                }
+            },
+        );
+    }
+
+    /// Applies edits that come from outside this editor, such as those of a remote peer.
+    pub fn apply_external_edits(&self, edits: Vec<Edit>) {
+        self.document.apply_external_edits(self.id, edits);
+    }
+
+    pub fn block_inlay_count(&self) -> usize {
+        self.document.block_inlay_count()
+    }
+
+    pub fn block_inlays_at_line(&self, line_index: usize) -> Ref<'_, [(usize, BlockInlay)]> {
+        self.document.block_inlays_at_line(line_index)
+    }
+
+    /// Adds a block inlay in front of the line at `line_index`. The sessions of the document
+    /// lay it out in `handle_changes`.
+    pub fn insert_block_inlay(&self, line_index: usize, inlay: BlockInlay) {
+        self.document.insert_block_inlay(line_index, inlay);
+    }
+
+    pub fn remove_block_inlay(&self, line_index: usize, inlay_index: usize) -> BlockInlay {
+        self.document.remove_block_inlay(line_index, inlay_index)
+    }
+
+    pub fn inline_inlays_at_line(&self, line_index: usize) -> Ref<'_, [(usize, InlineInlay)]> {
+        self.document.inline_inlays_at_line(line_index)
+    }
+
+    /// Adds an inline inlay at `byte_index` of the line at `line_index`. The sessions of the
+    /// document lay it out in `handle_changes`.
+    pub fn insert_inline_inlay(&self, line_index: usize, byte_index: usize, inlay: InlineInlay) {
+        self.document
+            .insert_inline_inlay(line_index, byte_index, inlay);
+    }
+
+    pub fn remove_inline_inlay(
+        &self,
+        line_index: usize,
+        byte_index: usize,
+        inlay_index: usize,
+    ) -> InlineInlay {
+        self.document
+            .remove_inline_inlay(line_index, byte_index, inlay_index)
+    }
+
+    pub fn clear_all_inlays(&self) {
+        self.document.clear_all_inlays();
+    }
+
+    pub fn clear_inline_inlays_for_line(&self, line_index: usize) {
+        self.document.clear_inline_inlays_for_line(line_index);
+    }
+
+    pub fn clear_block_inlays_for_line(&self, line_index: usize) {
+        self.document.clear_block_inlays_for_line(line_index);
+    }
+
+    /// Hides the inlays in the block inlay group with the given id.
+    pub fn collapse_block_group(&self, id: u64) {
+        self.document.set_block_group_collapsed(id, true);
+    }
+
+    /// Shows the inlays in the block inlay group with the given id again.
+    pub fn expand_block_group(&self, id: u64) {
+        self.document.set_block_group_collapsed(id, false);
+    }
+
+    /// The id of every block inlay group with whether it is collapsed, in document order.
+    pub fn block_groups(&self) -> Vec<(u64, bool)> {
+        self.document.block_groups()
+    }
+
     pub fn paste(&self, text: Text) {
+        let selection_state = self.selection_state.borrow();
+        // Pasting as many lines as a column selection has goes line by line.
+        let is_per_line = text.as_lines().len() > 1
+            && text.as_lines().len() == selection_state.selections.len()
+            && selection_state
+                .selections
+                .iter()
+                .all(|selection| matches!(selection.kind, SelectionKind::Column { .. }));
+        let mut lines = text.as_lines().iter();
         self.document.edit_selections(
             self.id,
             EditKind::Other,
-            &self.selection_state.borrow().selections,
+            &selection_state.selections,
             &self.settings,
             |mut editor, position, length| {
                 editor.apply_edit(Edit {
@@ -411,11 +851,79 @@ impl Session {
                     drift: Drift::Before,
                 });
                 editor.apply_edit(Edit {
-                    change: Change::Insert(position, text.clone()),
+                    change: Change::Insert(
+                        position,
+                        if is_per_line {
+                            Text::from(lines.next().unwrap())
+                        } else {
+                            text.clone()
+                        },
+                    ),
                     drift: Drift::Before,
                 });
             },
         );
+        drop(selection_state);
+    }
+
+    /// The range the input method composition in progress replaces and its tentative text, if
+    /// any.
+    pub fn ime_composition(&self) -> Option<Ref<'_, (Range, String)>> {
+        Ref::filter_map(self.ime_composition.borrow(), |composition| {
+            composition.as_ref()
+        })
+        .ok()
+    }
+
+    /// Shows `text` as the tentative text of a composition. A new composition replaces the last
+    /// added selection. The document is left alone, so other sessions and the undo history never
+    /// see the tentative text.
+    pub fn update_ime_composition(&self, text: &str) {
+        let mut composition = self.ime_composition.borrow_mut();
+        match &mut *composition {
+            Some((_, tentative_text)) => *tentative_text = text.to_string(),
+            None => *composition = Some((self.last_added_selection_range(), text.to_string())),
+        }
+    }
+
+    /// Ends the composition, replacing its range with `text` as a single edit. An empty text
+    /// cancels the composition.
+    pub fn end_ime_composition(&self, text: &str) {
+        let range = match self.ime_composition.borrow_mut().take() {
+            Some((range, _)) => range,
+            None => self.last_added_selection_range(),
+        };
+        if text.is_empty() {
+            return;
+        }
+        self.selection_state
+            .borrow_mut()
+            .injected_char_stack
+            .clear();
+        let text = Text::from(text);
+        self.document.edit(
+            self.id,
+            EditKind::Compose,
+            &self.selection_state.borrow().selections,
+            |mut editor| {
+                editor.apply_edit(Edit {
+                    change: Change::Delete(range.start(), range.length()),
+                    drift: Drift::Before,
+                });
+                editor.apply_edit(Edit {
+                    change: Change::Insert(range.start(), text),
+                    drift: Drift::Before,
+                });
+            },
+        );
+        self.document.force_new_group();
+    }
+
+    fn last_added_selection_range(&self) -> Range {
+        let selection_state = self.selection_state.borrow();
+        let selection =
+            selection_state.selections[selection_state.last_added_selection_index.unwrap_or(0)];
+        Range::new(selection.start(), selection.end()).unwrap()
     }
 
     pub fn enter(&self) {
@@ -688,6 +1196,78 @@ impl Session {
         );
     }
 
+    pub fn delete_word_left(&self) {
+        self.selection_state
+            .borrow_mut()
+            .injected_char_stack
+            .clear();
+        self.document.edit_selections(
+            self.id,
+            EditKind::Delete,
+            &self.selection_state.borrow().selections,
+            &self.settings,
+            |mut editor, position, length| {
+                if length == Length::zero() {
+                    // The selection is empty, so delete backwards until the position the cursor
+                    // would move to with `move_word_left`.
+                    let start = Cursor {
+                        position,
+                        affinity: Affinity::Before,
+                        preferred_column_index: None,
+                    }
+                    .move_word_left(editor.as_text().as_lines(), &self.settings.word_separators)
+                    .position;
+                    editor.apply_edit(Edit {
+                        change: Change::Delete(start, position - start),
+                        drift: Drift::Before,
+                    });
+                } else {
+                    // The selection is non-empty, so delete it.
+                    editor.apply_edit(Edit {
+                        change: Change::Delete(position, length),
+                        drift: Drift::Before,
+                    });
+                }
+            },
+        );
+    }
+
+    pub fn delete_word_right(&self) {
+        self.selection_state
+            .borrow_mut()
+            .injected_char_stack
+            .clear();
+        self.document.edit_selections(
+            self.id,
+            EditKind::Delete,
+            &self.selection_state.borrow().selections,
+            &self.settings,
+            |mut editor, position, length| {
+                if length == Length::zero() {
+                    // The selection is empty, so delete forward until the position the cursor
+                    // would move to with `move_word_right`.
+                    let end = Cursor {
+                        position,
+                        affinity: Affinity::After,
+                        preferred_column_index: None,
+                    }
+                    .move_word_right(editor.as_text().as_lines(), &self.settings.word_separators)
+                    .position;
+                    editor.apply_edit(Edit {
+                        change: Change::Delete(position, end - position),
+                        drift: Drift::Before,
+                    });
+                } else {
+                    // The selection is non-empty, so delete it.
+                    editor.apply_edit(Edit {
+                        change: Change::Delete(position, length),
+                        drift: Drift::Before,
+                    });
+                }
+            },
+        );
+    }
+
     pub fn indent(&self) {
         self.document.edit_linewise(
             self.id,
@@ -746,15 +1326,106 @@ impl Session {
         );
     }
 
+    /// Deletes every line that is empty or only whitespace, together with its line break, as a
+    /// single undoable edit. A document that is one blank line is left as it is.
+    pub fn delete_blank_lines(&self) {
+        let has_blank_lines = {
+            let text = self.document.as_text();
+            let lines = text.as_lines();
+            lines.len() > 1 && lines.iter().any(|line| line.is_blank())
+        };
+        if !has_blank_lines {
+            return;
+        }
+        self.document.edit(
+            self.id,
+            EditKind::Other,
+            &self.selection_state.borrow().selections,
+            |mut editor| {
+                // Going from the bottom up keeps the line indices of the lines still to do valid.
+                for line_index in (0..editor.as_text().as_lines().len()).rev() {
+                    let lines = editor.as_text().as_lines();
+                    if lines.len() == 1 || !lines[line_index].is_blank() {
+                        continue;
+                    }
+                    let change = if line_index + 1 < lines.len() {
+                        Change::Delete(
+                            Position {
+                                line_index,
+                                byte_index: 0,
+                            },
+                            Length {
+                                line_count: 1,
+                                byte_count: 0,
+                            },
+                        )
+                    } else {
+                        // The last line has no line break of its own, so take the one before it.
+                        Change::Delete(
+                            Position {
+                                line_index: line_index - 1,
+                                byte_index: lines[line_index - 1].len(),
+                            },
+                            Length {
+                                line_count: 1,
+                                byte_count: lines[line_index].len(),
+                            },
+                        )
+                    };
+                    editor.apply_edit(Edit {
+                        change,
+                        drift: Drift::Before,
+                    });
+                }
+            },
+        );
+    }
+
+    /// Replaces the text of each non-empty selection with the result of calling `f` on it, for
+    /// instance to change its case. This is undone as a single edit, and each selection ends up
+    /// over its new text.
+    pub fn replace_selections_with_fn(&self, f: impl Fn(&str) -> Text) {
+        self.selection_state
+            .borrow_mut()
+            .injected_char_stack
+            .clear();
+        self.document.edit_selections(
+            self.id,
+            EditKind::Other,
+            &self.selection_state.borrow().selections,
+            &self.settings,
+            |mut editor, position, length| {
+                if length == Length::zero() {
+                    return;
+                }
+                let text = f(&editor
+                    .as_text()
+                    .slice(Range::from_start_and_length(position, length))
+                    .to_string());
+                // Inserting the new text after the old one before deleting the old one keeps the
+                // selection over the new text.
+                editor.apply_edit(Edit {
+                    change: Change::Insert(position + length, text),
+                    drift: Drift::Before,
+                });
+                editor.apply_edit(Edit {
+                    change: Change::Delete(position, length),
+                    drift: Drift::Before,
+                });
+            },
+        );
+    }
+
     pub fn copy(&self) -> String {
         let mut string = String::new();
         for selection in &self.selection_state.borrow().selections {
             write!(
                 &mut string,
                 "{}",
-                self.document
-                    .as_text()
-                    .slice(selection.start(), selection.length())
+                self.document.as_text().slice(Range::from_start_and_length(
+                    selection.start(),
+                    selection.length()
+                ))
             )
             .unwrap();
         }
@@ -779,9 +1450,63 @@ impl Session {
             .redo(self.id, &self.selection_state.borrow().selections)
     }
 
+    pub fn savepoint(&self) -> Savepoint {
+        let selection_state = self.selection_state.borrow();
+        let layout = self.layout.borrow();
+        Savepoint {
+            text: self.document.as_text().clone(),
+            selections: selection_state.selections.clone(),
+            last_added_selection_index: selection_state.last_added_selection_index,
+            fold_state: self.fold_state.borrow().clone(),
+            fold_column: layout.fold_column.clone(),
+            scale: layout.scale.clone(),
+        }
+    }
+
+    /// Returns to the state of `savepoint`. Restoring the text is an edit of its own, so it can
+    /// be undone like any other.
+    pub fn rollback(&mut self, savepoint: Savepoint) {
+        self.set_text(savepoint.text);
+        self.handle_changes();
+        let mut selection_state = self.selection_state.borrow_mut();
+        selection_state.column_anchor = None;
+        selection_state.selections = savepoint.selections;
+        selection_state.last_added_selection_index = savepoint.last_added_selection_index;
+        selection_state.injected_char_stack.clear();
+        drop(selection_state);
+        *self.fold_state.borrow_mut() = savepoint.fold_state;
+        let mut layout = self.layout.borrow_mut();
+        layout.fold_column = savepoint.fold_column;
+        layout.scale = savepoint.scale;
+        drop(layout);
+        let line_count = self.document.as_text().as_lines().len();
+        for line in 0..line_count {
+            self.update_wrap_data(line);
+        }
+        self.update_y();
+        self.update_highlighted_delimiter_positions();
+        self.document.force_new_group();
+    }
+
+    /// Calls `f` and rolls back everything it did if it fails, for speculative edits such as
+    /// running a formatter whose output may turn out to be invalid.
+    pub fn transaction<T, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, E>) -> Result<T, E> {
+        let savepoint = self.savepoint();
+        let result = f(self);
+        if result.is_err() {
+            self.rollback(savepoint);
+        }
+        result
+    }
+
     pub fn handle_changes(&mut self) {
-        while let Ok((selections, edits)) = self.edit_receiver.try_recv() {
-            self.update_after_edit(selections, &edits);
+        while let Ok(update) = self.edit_receiver.try_recv() {
+            match update {
+                DocumentUpdate::Edits(selections, edits) => {
+                    self.update_after_edit(selections, &edits)
+                }
+                DocumentUpdate::Inlays(line_range) => self.update_after_inlays(line_range),
+            }
         }
     }
 
@@ -796,6 +1521,7 @@ impl Session {
             session_layout: self.layout.borrow(),
         };
         let mut selection_state = self.selection_state.borrow_mut();
+        selection_state.column_anchor = None;
         let last_added_selection_index = selection_state.last_added_selection_index;
         selection_state.last_added_selection_index = selection_state
             .selections
@@ -885,10 +1611,51 @@ impl Session {
             }
         }
         drop(selection_state);
+        if let Some((range, _)) = &mut *self.ime_composition.borrow_mut() {
+            for edit in edits {
+                *range = Range::new(range.start().apply_edit(edit), range.end().apply_edit(edit))
+                    .unwrap();
+            }
+        }
         self.update_highlighted_delimiter_positions();
     }
 
+    fn update_after_inlays(&self, line_range: ops::Range<usize>) {
+        self.update_block_widget_heights();
+        let line_count = self.document.as_text().as_lines().len();
+        for line_index in line_range.start..line_range.end.min(line_count) {
+            self.update_wrap_data(line_index);
+        }
+        self.layout.borrow_mut().y.truncate(line_range.start);
+        self.update_y();
+    }
+
+    fn update_block_widget_heights(&self) {
+        let mut heights = HashMap::new();
+        if let (Some(sizing), Some(available_width)) = (
+            &*self.block_widget_sizing.borrow(),
+            self.available_width.get(),
+        ) {
+            let document_layout = self.document.layout();
+            let widgets = document_layout
+                .block_inlays
+                .iter()
+                .flat_map(|(_, inlay)| inlay.widgets());
+            for (index, (widget, _)) in widgets.enumerate() {
+                heights.insert(index, sizing.preferred_height(&widget, available_width));
+            }
+        }
+        let mut layout = self.layout.borrow_mut();
+        if layout.block_widget_heights != heights {
+            layout.block_widget_heights = heights;
+            layout.y.clear();
+            drop(layout);
+            self.update_y();
+        }
+    }
+
     fn update_y(&self) {
+        self.selection_rect_cache.borrow_mut().clear();
         let start = self.layout.borrow().y.len();
         let end = self.document.as_text().as_lines().len();
         if start == end + 1 {
@@ -957,6 +1724,8 @@ impl Session {
     }
 
     fn update_highlighted_delimiter_positions(&self) {
+        // This runs whenever the selections change, so the cached rects are stale now.
+        self.selection_rect_cache.borrow_mut().clear();
         let mut selection_state = self.selection_state.borrow_mut();
         let mut highlighted_delimiter_positions =
             mem::take(&mut selection_state.highlighted_delimiter_positions);
@@ -995,6 +1764,20 @@ pub struct SessionLayout {
     pub fold_column: Vec<usize>,
     pub scale: Vec<f64>,
     pub wrap_data: Vec<Option<WrapData>>,
+    /// The heights `BlockWidgetSizing` picked, by index into the block widgets of the document,
+    /// counting those inside groups whether they are collapsed or not.
+    pub block_widget_heights: HashMap<usize, f64>,
+}
+
+/// The text, selections and folds of a session at some point, see `Session::savepoint`.
+#[derive(Clone, Debug)]
+pub struct Savepoint {
+    text: Text,
+    selections: SelectionSet,
+    last_added_selection_index: Option<usize>,
+    fold_state: FoldState,
+    fold_column: Vec<usize>,
+    scale: Vec<f64>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -1008,13 +1791,16 @@ pub enum SelectionMode {
 #[derive(Debug)]
 struct SelectionState {
     mode: SelectionMode,
+    column_mode: bool,
+    /// The line and column where the current column selection started.
+    column_anchor: Option<(usize, usize)>,
     selections: SelectionSet,
     last_added_selection_index: Option<usize>,
     injected_char_stack: Vec<char>,
     highlighted_delimiter_positions: HashSet<Position>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct FoldState {
     folding_lines: HashSet<usize>,
     folded_lines: HashSet<usize>,
@@ -1044,10 +1830,16 @@ fn grow_selection(
         SelectionMode::Simple => selection,
         SelectionMode::Word => {
             let position = selection.cursor.position;
-            let start_byte_index = lines[position.line_index]
-                .find_prev_word_boundary(position.byte_index, word_separators);
-            let end_byte_index = lines[position.line_index]
-                .find_next_word_boundary(position.byte_index, word_separators);
+            // The word, whitespace or separator under the cursor, or the one it ends if the
+            // cursor is at the end of the line.
+            let boundaries = lines[position.line_index]
+                .word_boundaries(word_separators)
+                .collect::<Vec<_>>();
+            let end_index = boundaries
+                .partition_point(|&byte_index| byte_index <= position.byte_index)
+                .min(boundaries.len() - 1);
+            let start_byte_index = boundaries[end_index.saturating_sub(1)];
+            let end_byte_index = boundaries[end_index];
             if selection.anchor < selection.cursor.position {
                 Selection {
                     cursor: Cursor {
@@ -1059,6 +1851,7 @@ fn grow_selection(
                         preferred_column_index: None,
                     },
                     anchor: selection.anchor,
+                    kind: selection.kind,
                 }
             } else if selection.anchor > selection.cursor.position {
                 Selection {
@@ -1071,6 +1864,7 @@ fn grow_selection(
                         preferred_column_index: None,
                     },
                     anchor: selection.anchor,
+                    kind: selection.kind,
                 }
             } else {
                 Selection {
@@ -1086,6 +1880,7 @@ fn grow_selection(
                         line_index: position.line_index,
                         byte_index: start_byte_index,
                     },
+                    kind: selection.kind,
                 }
             }
         }
@@ -1105,6 +1900,7 @@ fn grow_selection(
                         line_index: selection.anchor.line_index,
                         byte_index: 0,
                     },
+                    kind: selection.kind,
                 }
             } else if selection.anchor > selection.cursor.position {
                 Selection {
@@ -1120,6 +1916,7 @@ fn grow_selection(
                         line_index: selection.anchor.line_index,
                         byte_index: lines[selection.anchor.line_index].len(),
                     },
+                    kind: selection.kind,
                 }
             } else {
                 Selection {
@@ -1135,6 +1932,7 @@ fn grow_selection(
                         line_index: position.line_index,
                         byte_index: 0,
                     },
+                    kind: selection.kind,
                 }
             }
         }
@@ -1151,10 +1949,22 @@ fn grow_selection(
                 line_index: 0,
                 byte_index: 0,
             },
+            kind: selection.kind,
         },
     }
 }
 
+fn byte_index_at_column(line: &str, column_index: usize) -> usize {
+    let mut current_column_index = 0;
+    for (byte_index, grapheme) in line.grapheme_indices() {
+        if current_column_index >= column_index {
+            return byte_index;
+        }
+        current_column_index += grapheme.column_count();
+    }
+    line.len()
+}
+
 fn new_indentation(column_count: usize) -> String {
     iter::repeat(' ').take(column_count).collect()
 }
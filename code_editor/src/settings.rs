@@ -3,6 +3,52 @@ pub struct Settings {
     pub tab_column_count: usize,
     pub fold_level: usize,
     pub word_separators: Vec<char>,
+    pub key_repeat_initial_delay_ms: u64,
+    pub key_repeat_rate_ms: u64,
+    /// Whether new sessions turn `\r\n` line breaks into `\n` without recording it in the history,
+    /// see `Text::detect_line_ending`.
+    pub normalize_line_endings: bool,
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tab_column_count(mut self, tab_column_count: usize) -> Self {
+        debug_assert!(tab_column_count > 0, "tab_column_count must be positive");
+        self.tab_column_count = tab_column_count;
+        self
+    }
+
+    pub fn fold_level(mut self, fold_level: usize) -> Self {
+        self.fold_level = fold_level;
+        self
+    }
+
+    pub fn word_separators(mut self, word_separators: Vec<char>) -> Self {
+        self.word_separators = word_separators;
+        self
+    }
+
+    pub fn key_repeat_initial_delay_ms(mut self, key_repeat_initial_delay_ms: u64) -> Self {
+        self.key_repeat_initial_delay_ms = key_repeat_initial_delay_ms;
+        self
+    }
+
+    pub fn key_repeat_rate_ms(mut self, key_repeat_rate_ms: u64) -> Self {
+        debug_assert!(
+            key_repeat_rate_ms > 0,
+            "key_repeat_rate_ms must be positive"
+        );
+        self.key_repeat_rate_ms = key_repeat_rate_ms;
+        self
+    }
+
+    pub fn normalize_line_endings(mut self, normalize_line_endings: bool) -> Self {
+        self.normalize_line_endings = normalize_line_endings;
+        self
+    }
 }
 
 impl Default for Settings {
@@ -14,6 +60,9 @@ impl Default for Settings {
                 ' ', '`', '~', '!', '@', '#', '$', '%', '^', '&', '*', '(', ')', '-', '=', '+',
                 '[', '{', ']', '}', '\\', '|', ';', ':', '\'', '"', '.', '<', '>', '/', '?', ',',
             ],
+            key_repeat_initial_delay_ms: 500,
+            key_repeat_rate_ms: 30,
+            normalize_line_endings: false,
         }
     }
 }
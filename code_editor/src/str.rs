@@ -1,17 +1,17 @@
-use crate::char::CharExt;
+use {crate::char::CharExt, std::iter::Peekable};
 
 pub trait StrExt {
     fn column_count(&self) -> usize;
     fn indent_level(&self, indent_column_count: usize) -> usize;
     fn next_indent_level(&self, indent_column_count: usize) -> usize;
     fn prev_indent_level(&self, indent_column_count: usize) -> usize;
-    fn find_next_word_boundary(&self, index: usize, word_separators: &[char]) -> usize;
-    fn find_prev_word_boundary(&self, index: usize, word_separators: &[char]) -> usize;
     fn indent(&self) -> Option<&str>;
+    fn is_blank(&self) -> bool;
     fn longest_common_prefix(&self, other: &str) -> &str;
     fn graphemes(&self) -> Graphemes<'_>;
     fn grapheme_indices(&self) -> GraphemeIndices<'_>;
     fn split_whitespace_boundaries(&self) -> SplitWhitespaceBoundaries<'_>;
+    fn word_boundaries<'a>(&'a self, word_separators: &'a [char]) -> WordBoundaries<'a>;
 }
 
 impl StrExt for str {
@@ -31,35 +31,16 @@ impl StrExt for str {
         self.indent().unwrap_or("").column_count().saturating_sub(1) / indent_column_count
     }
 
-    fn find_next_word_boundary(&self, index: usize, word_separators: &[char]) -> usize {
-        if index == 0 {
-            return index;
-        }
-        let start = index;
-        self[index..]
-            .char_indices()
-            .find(|&(_, char)| word_separators.contains(&char))
-            .map(|(index, _)| start + index)
-            .unwrap_or_else(|| self.len())
-    }
-
-    fn find_prev_word_boundary(&self, index: usize, word_separators: &[char]) -> usize {
-        if index == self.len() {
-            return index;
-        }
-        self[..index]
-            .char_indices()
-            .rfind(|&(_, char)| word_separators.contains(&char))
-            .map(|(char_index, char)| char_index + char.len_utf8())
-            .unwrap_or(0)
-    }
-
     fn indent(&self) -> Option<&str> {
         self.char_indices()
             .find(|(_, char)| !char.is_whitespace())
             .map(|(index, _)| &self[..index])
     }
 
+    fn is_blank(&self) -> bool {
+        self.chars().all(|char| char.is_whitespace())
+    }
+
     fn longest_common_prefix(&self, other: &str) -> &str {
         &self[..self
             .char_indices()
@@ -83,6 +64,15 @@ impl StrExt for str {
     fn split_whitespace_boundaries(&self) -> SplitWhitespaceBoundaries<'_> {
         SplitWhitespaceBoundaries { string: self }
     }
+
+    fn word_boundaries<'a>(&'a self, word_separators: &'a [char]) -> WordBoundaries<'a> {
+        WordBoundaries {
+            grapheme_indices: self.grapheme_indices().peekable(),
+            word_separators,
+            len: self.len(),
+            is_done: false,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -179,3 +169,46 @@ impl<'a> Iterator for SplitWhitespaceBoundaries<'a> {
         Some(string_0)
     }
 }
+
+/// The byte indices of the word boundaries in a string, from its start to its end. Between two
+/// boundaries lies either a run of whitespace, a single word separator or a run of the other
+/// graphemes, the steps by which the cursor moves a word at a time.
+#[derive(Clone, Debug)]
+pub struct WordBoundaries<'a> {
+    grapheme_indices: Peekable<GraphemeIndices<'a>>,
+    word_separators: &'a [char],
+    len: usize,
+    is_done: bool,
+}
+
+impl<'a> Iterator for WordBoundaries<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some((index, grapheme)) = self.grapheme_indices.next() else {
+            if self.is_done {
+                return None;
+            }
+            self.is_done = true;
+            return Some(self.len);
+        };
+        let word_separators = self.word_separators;
+        let is_word = |grapheme: &str| {
+            !grapheme.is_blank() && !grapheme.chars().any(|char| word_separators.contains(&char))
+        };
+        if grapheme.is_blank() {
+            while self
+                .grapheme_indices
+                .next_if(|(_, grapheme)| grapheme.is_blank())
+                .is_some()
+            {}
+        } else if is_word(grapheme) {
+            while self
+                .grapheme_indices
+                .next_if(|(_, grapheme)| is_word(grapheme))
+                .is_some()
+            {}
+        }
+        Some(index)
+    }
+}
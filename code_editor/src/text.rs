@@ -1,9 +1,10 @@
+use crate::str::{StrExt, WordBoundaries};
 use std::{
     cmp::Ordering,
     fmt, io,
     io::BufRead,
     iter,
-    ops::{Add, AddAssign, Sub, SubAssign},
+    ops::{self, Add, AddAssign, Sub, SubAssign},
 };
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -57,8 +58,21 @@ impl Text {
         &self.lines
     }
 
-    pub fn slice(&self, start: Position, length: Length) -> Self {
-        let end = start + length;
+    pub fn line_at(&self, line_index: usize) -> &str {
+        &self.lines[line_index]
+    }
+
+    /// The word boundaries of the line at `line_index`, as byte indices into it.
+    pub fn word_boundaries<'a>(
+        &'a self,
+        line_index: usize,
+        word_separators: &'a [char],
+    ) -> WordBoundaries<'a> {
+        self.lines[line_index].word_boundaries(word_separators)
+    }
+
+    pub fn slice(&self, range: Range) -> Self {
+        let (start, end) = (range.start(), range.end());
         let mut lines = Vec::new();
         if start.line_index == end.line_index {
             lines.push(self.lines[start.line_index][start.byte_index..end.byte_index].to_string());
@@ -75,6 +89,79 @@ impl Text {
         Text { lines }
     }
 
+    /// The start of every occurrence of `needle`, in order and without overlaps.
+    pub fn find<'a>(&'a self, needle: &'a str) -> TextMatches<'a> {
+        self.find_from(needle, Position::zero())
+    }
+
+    /// Like `find`, but only the occurrences at or after `start`.
+    pub fn find_from<'a>(&'a self, needle: &'a str, start: Position) -> TextMatches<'a> {
+        TextMatches {
+            lines: &self.lines,
+            needle_lines: if needle.is_empty() {
+                Vec::new()
+            } else {
+                needle.split('\n').collect()
+            },
+            position: start,
+        }
+    }
+
+    /// The kind of line breaks in the first 512 bytes, `Lf` when there are none.
+    pub fn detect_line_ending(&self) -> LineEnding {
+        let mut has_lf = false;
+        let mut has_crlf = false;
+        let mut byte_count = 0;
+        for line in &self.lines[..self.lines.len() - 1] {
+            byte_count += line.len() + 1;
+            if byte_count > 512 {
+                break;
+            }
+            if line.ends_with('\r') {
+                has_crlf = true;
+            } else {
+                has_lf = true;
+            }
+        }
+        match (has_lf, has_crlf) {
+            (_, false) => LineEnding::Lf,
+            (false, true) => LineEnding::Crlf,
+            (true, true) => LineEnding::Mixed,
+        }
+    }
+
+    /// The edits that turn `\r\n` and lone `\r` line breaks into `\n`, from the end of the text
+    /// to the start so that each one can be applied without moving the ones after it.
+    pub fn line_ending_edits(&self) -> Vec<Edit> {
+        let mut edits = Vec::new();
+        let last_line_index = self.lines.len() - 1;
+        for (line_index, line) in self.lines.iter().enumerate().rev() {
+            for (byte_index, _) in line.rmatch_indices('\r') {
+                let position = Position {
+                    line_index,
+                    byte_index,
+                };
+                edits.push(Edit {
+                    change: Change::Delete(
+                        position,
+                        Length {
+                            line_count: 0,
+                            byte_count: 1,
+                        },
+                    ),
+                    drift: Drift::Before,
+                });
+                if line_index == last_line_index || byte_index + 1 < line.len() {
+                    edits.push(Edit {
+                        change: Change::Insert(position, Text::newline()),
+                        drift: Drift::Before,
+                    });
+                }
+            }
+        }
+        edits
+    }
+
     pub fn apply_change(&mut self, change: Change) {
         match change {
             Change::Insert(position, text) => self.insert(position, text),
@@ -174,6 +261,74 @@ impl FromIterator<char> for Text {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    Mixed,
+}
+
+/// The iterator returned by `Text::find`. It looks at one line at a time, a needle that spans
+/// several lines can only start at the end of a line, so the lines after that are just compared.
+#[derive(Clone, Debug)]
+pub struct TextMatches<'a> {
+    lines: &'a [String],
+    needle_lines: Vec<&'a str>,
+    position: Position,
+}
+
+impl<'a> Iterator for TextMatches<'a> {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first_needle_line, other_needle_lines) = self.needle_lines.split_first()?;
+        while self.position.line_index < self.lines.len() {
+            let Position {
+                line_index,
+                byte_index,
+            } = self.position;
+            let line = &self.lines[line_index];
+            if other_needle_lines.is_empty() {
+                if let Some(index) = line[byte_index..].find(first_needle_line) {
+                    let byte_index = byte_index + index;
+                    self.position.byte_index = byte_index + first_needle_line.len();
+                    return Some(Position {
+                        line_index,
+                        byte_index,
+                    });
+                }
+            } else if line.len() - byte_index >= first_needle_line.len()
+                && line.ends_with(first_needle_line)
+                && line_index + other_needle_lines.len() < self.lines.len()
+            {
+                let (last_needle_line, middle_needle_lines) =
+                    other_needle_lines.split_last().unwrap();
+                let last_line_index = line_index + other_needle_lines.len();
+                if self.lines[line_index + 1..last_line_index]
+                    .iter()
+                    .zip(middle_needle_lines)
+                    .all(|(line, needle_line)| line == needle_line)
+                    && self.lines[last_line_index].starts_with(last_needle_line)
+                {
+                    self.position = Position {
+                        line_index: last_line_index,
+                        byte_index: last_needle_line.len(),
+                    };
+                    return Some(Position {
+                        line_index,
+                        byte_index: line.len() - first_needle_line.len(),
+                    });
+                }
+            }
+            self.position = Position {
+                line_index: line_index + 1,
+                byte_index: 0,
+            };
+        }
+        None
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Edit {
     pub change: Change,
@@ -181,6 +336,10 @@ pub struct Edit {
 }
 
 impl Edit {
+    pub fn is_empty(&self) -> bool {
+        self.change.is_empty()
+    }
+
     pub fn invert(self, text: &Text) -> Self {
         Self {
             change: self.change.invert(text),
@@ -196,10 +355,41 @@ pub enum Change {
 }
 
 impl Change {
+    /// Whether the change leaves the text as it is.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Insert(_, text) => text.is_empty(),
+            Self::Delete(_, length) => *length == Length::zero(),
+        }
+    }
+
+    /// The number of bytes inserted, counting a byte for every newline.
+    pub fn inserted_byte_count(&self) -> usize {
+        match self {
+            Self::Insert(_, text) => {
+                text.lines.iter().map(|line| line.len()).sum::<usize>() + text.lines.len() - 1
+            }
+            Self::Delete(_, _) => 0,
+        }
+    }
+
+    /// The number of bytes deleted from `text`, counting a byte for every newline.
+    pub fn deleted_byte_count(&self, text: &Text) -> usize {
+        match *self {
+            Self::Insert(_, _) => 0,
+            Self::Delete(start, length) => {
+                Range::from_start_and_length(start, length).byte_len(text)
+            }
+        }
+    }
+
     pub fn invert(self, text: &Text) -> Self {
         match self {
             Self::Insert(position, text) => Change::Delete(position, text.length()),
-            Self::Delete(start, length) => Change::Insert(start, text.slice(start, length)),
+            Self::Delete(start, length) => Change::Insert(
+                start,
+                text.slice(Range::from_start_and_length(start, length)),
+            ),
         }
     }
 }
@@ -215,6 +405,54 @@ impl Position {
         Self::default()
     }
 
+    /// The position of `byte_index` in `string`, which holds the lines separated by newlines.
+    pub fn from_byte_index(string: &str, byte_index: usize) -> Self {
+        let line_start = string[..byte_index]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        Self {
+            line_index: string[..byte_index].matches('\n').count(),
+            byte_index: byte_index - line_start,
+        }
+    }
+
+    /// The bytes of the line this position is on, without the newline.
+    pub fn line_byte_range(self, text: &Text) -> ops::Range<usize> {
+        0..text.lines[self.line_index].len()
+    }
+
+    /// The bytes of the line this position is on, including the newline if the line has one.
+    pub fn full_line_byte_range(self, text: &Text) -> ops::Range<usize> {
+        let range = self.line_byte_range(text);
+        if self.line_index + 1 < text.lines.len() {
+            range.start..range.end + 1
+        } else {
+            range
+        }
+    }
+
+    /// Whether this position lies within `text`, on a char boundary of its line.
+    pub fn is_valid(self, text: &Text) -> bool {
+        text.lines
+            .get(self.line_index)
+            .map_or(false, |line| line.is_char_boundary(self.byte_index))
+    }
+
+    /// The nearest valid position in `text`: on the last line if the line index is out of bounds,
+    /// and at or before this byte index on a char boundary.
+    pub fn clamp(self, text: &Text) -> Self {
+        let line_index = self.line_index.min(text.lines.len() - 1);
+        let line = &text.lines[line_index];
+        let mut byte_index = self.byte_index.min(line.len());
+        while !line.is_char_boundary(byte_index) {
+            byte_index -= 1;
+        }
+        Self {
+            line_index,
+            byte_index,
+        }
+    }
+
     pub fn apply_edit(self, edit: &Edit) -> Self {
         match edit.change {
             Change::Insert(point, ref text) => match self.cmp(&point) {
@@ -279,6 +517,118 @@ impl Sub for Position {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Range {
+    start: Position,
+    end: Position,
+}
+
+impl Range {
+    pub fn new(start: Position, end: Position) -> Option<Self> {
+        if start > end {
+            return None;
+        }
+        Some(Self { start, end })
+    }
+
+    pub fn from_start_and_length(start: Position, length: Length) -> Self {
+        Self {
+            start,
+            end: start + length,
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn start(self) -> Position {
+        self.start
+    }
+
+    pub fn end(self) -> Position {
+        self.end
+    }
+
+    pub fn length(self) -> Length {
+        self.end - self.start
+    }
+
+    pub fn is_single_line(self) -> bool {
+        self.start.line_index == self.end.line_index
+    }
+
+    /// The indices of the lines the range starts, ends or lies on.
+    pub fn line_span(self) -> ops::RangeInclusive<usize> {
+        self.start.line_index..=self.end.line_index
+    }
+
+    /// The number of bytes the range covers in `text`, counting a byte for every newline.
+    pub fn byte_len(self, text: &Text) -> usize {
+        if self.is_single_line() {
+            return self.end.byte_index - self.start.byte_index;
+        }
+        text.lines[self.start.line_index..self.end.line_index]
+            .iter()
+            .map(|line| line.len() + 1)
+            .sum::<usize>()
+            - self.start.byte_index
+            + self.end.byte_index
+    }
+
+    /// The part covered by both ranges. Ranges that only touch have no intersection, unless one
+    /// of them is empty.
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        if start < end || (start == end && (self.is_empty() || other.is_empty())) {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+
+    /// The range covering both, which must overlap or touch. See `hull` for ranges with a gap
+    /// in between.
+    pub fn union(self, other: Self) -> Self {
+        assert!(
+            self.start <= other.end && other.start <= self.end,
+            "cannot unite disjoint ranges {:?} and {:?}",
+            self,
+            other
+        );
+        self.hull(other)
+    }
+
+    /// The smallest range covering both.
+    pub fn hull(self, other: Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// The parts of this range before and after `other`. Subtracting an empty range leaves this
+    /// range as it is.
+    pub fn subtract(self, other: Self) -> (Option<Self>, Option<Self>) {
+        if other.is_empty() || self.intersection(other).is_none() {
+            return (Some(self), None);
+        }
+        let before = Self {
+            start: self.start,
+            end: other.start,
+        };
+        let after = Self {
+            start: other.end,
+            end: self.end,
+        };
+        (
+            (before.start < before.end).then_some(before),
+            (after.start < after.end).then_some(after),
+        )
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Length {
     pub line_count: usize,
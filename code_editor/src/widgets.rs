@@ -1,9 +1,24 @@
+use std::fmt;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct InlineWidget {
+    /// Chosen by whoever inserts the widget, clicks on it report this id.
+    pub id: usize,
     pub column_count: usize,
+    /// Like `InlineInlay::Text`, widgets above 0 are overlays that take up no columns.
+    pub z_offset: i8,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct BlockWidget {
+    /// Chosen by whoever inserts the widget, it is passed on to `BlockWidgetSizing`.
+    pub id: usize,
+    /// The height in lines, unless a `BlockWidgetSizing` decides otherwise.
     pub height: f64,
 }
+
+/// Lets block widgets reflow when the editor is resized, see `Session::set_block_widget_sizing`.
+pub trait BlockWidgetSizing: fmt::Debug {
+    /// The height in lines of `widget` when it is `available_width` columns wide.
+    fn preferred_height(&self, widget: &BlockWidget, available_width: f64) -> f64;
+}
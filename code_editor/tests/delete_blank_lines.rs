@@ -0,0 +1,101 @@
+use makepad_code_editor::{
+    decoration::DecorationSet,
+    selection::Affinity,
+    session::SelectionMode,
+    text::{Position, Text},
+    Document, Session,
+};
+
+fn position(line_index: usize, byte_index: usize) -> Position {
+    Position {
+        line_index,
+        byte_index,
+    }
+}
+
+/// Deletes the blank lines in `text` with a cursor at `cursor`, returning the new text and where
+/// the cursor ends up.
+fn delete_blank_lines(text: &str, cursor: Position) -> (String, Position) {
+    let mut session = Session::new(Document::new(Text::from(text), DecorationSet::new()));
+    session.set_selection(cursor, Affinity::Before, SelectionMode::Simple);
+    session.delete_blank_lines();
+    session.handle_changes();
+    let cursor = session.selections()[0].cursor.position;
+    let text = session.document().as_text().to_string();
+    (text, cursor)
+}
+
+#[test]
+fn delete_blank_lines_deletes_whitespace_only_lines() {
+    assert_eq!(
+        delete_blank_lines("a\n  \n\tb\n \t \nc", position(0, 0)),
+        ("a\n\tb\nc".to_string(), position(0, 0))
+    );
+}
+
+#[test]
+fn delete_blank_lines_deletes_consecutive_blank_lines() {
+    assert_eq!(
+        delete_blank_lines("a\n\n  \n\nb", position(0, 1)),
+        ("a\nb".to_string(), position(0, 1))
+    );
+}
+
+#[test]
+fn delete_blank_lines_deletes_a_blank_last_line() {
+    assert_eq!(
+        delete_blank_lines("a\nb\n", position(0, 0)),
+        ("a\nb".to_string(), position(0, 0))
+    );
+    assert_eq!(
+        delete_blank_lines("a\n\n  ", position(0, 0)),
+        ("a".to_string(), position(0, 0))
+    );
+}
+
+#[test]
+fn delete_blank_lines_leaves_a_single_blank_line() {
+    assert_eq!(
+        delete_blank_lines("\n  \n", position(0, 0)),
+        ("".to_string(), position(0, 0))
+    );
+    assert_eq!(
+        delete_blank_lines("  ", position(0, 2)),
+        ("  ".to_string(), position(0, 2))
+    );
+}
+
+#[test]
+fn delete_blank_lines_moves_cursors_up() {
+    assert_eq!(
+        delete_blank_lines("a\n\n\nb c", position(3, 2)),
+        ("a\nb c".to_string(), position(1, 2))
+    );
+}
+
+#[test]
+fn delete_blank_lines_moves_cursors_on_deleted_lines() {
+    assert_eq!(
+        delete_blank_lines("a\n  \nb", position(1, 1)),
+        ("a\nb".to_string(), position(1, 0))
+    );
+    assert_eq!(
+        delete_blank_lines("a\n  ", position(1, 2)),
+        ("a".to_string(), position(0, 1))
+    );
+}
+
+#[test]
+fn delete_blank_lines_is_undone_at_once() {
+    let mut session = Session::new(Document::new(
+        Text::from("a\n\nb\n  \nc"),
+        DecorationSet::new(),
+    ));
+    session.delete_blank_lines();
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "a\nb\nc");
+    assert!(session.undo());
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "a\n\nb\n  \nc");
+    assert!(!session.undo());
+}
@@ -0,0 +1,125 @@
+use makepad_code_editor::{
+    decoration::DecorationSet,
+    selection::Affinity,
+    session::SelectionMode,
+    text::{Position, Text},
+    Document, Session,
+};
+
+fn position(line_index: usize, byte_index: usize) -> Position {
+    Position {
+        line_index,
+        byte_index,
+    }
+}
+
+/// Deletes the word left of a cursor at `cursor` in `text`, returning the new text and where the
+/// cursor ends up.
+fn delete_word_left(text: &str, cursor: Position) -> (String, Position) {
+    let mut session = Session::new(Document::new(Text::from(text), DecorationSet::new()));
+    session.set_selection(cursor, Affinity::Before, SelectionMode::Simple);
+    session.delete_word_left();
+    session.handle_changes();
+    let cursor = session.selections()[0].cursor.position;
+    let text = session.document().as_text().to_string();
+    (text, cursor)
+}
+
+/// Deletes the word right of a cursor at `cursor` in `text`, returning the new text and where the
+/// cursor ends up.
+fn delete_word_right(text: &str, cursor: Position) -> (String, Position) {
+    let mut session = Session::new(Document::new(Text::from(text), DecorationSet::new()));
+    session.set_selection(cursor, Affinity::Before, SelectionMode::Simple);
+    session.delete_word_right();
+    session.handle_changes();
+    let cursor = session.selections()[0].cursor.position;
+    let text = session.document().as_text().to_string();
+    (text, cursor)
+}
+
+#[test]
+fn delete_word_left_inside_word() {
+    assert_eq!(
+        delete_word_left("foo barbaz", position(0, 7)),
+        ("foo baz".to_string(), position(0, 4))
+    );
+}
+
+#[test]
+fn delete_word_left_at_word_boundary() {
+    assert_eq!(
+        delete_word_left("foo bar", position(0, 7)),
+        ("foo ".to_string(), position(0, 4))
+    );
+    assert_eq!(
+        delete_word_left("foo bar", position(0, 3)),
+        (" bar".to_string(), position(0, 0))
+    );
+}
+
+#[test]
+fn delete_word_left_at_line_start_joins_lines() {
+    assert_eq!(
+        delete_word_left("foo\nbar", position(1, 0)),
+        ("foobar".to_string(), position(0, 3))
+    );
+}
+
+#[test]
+fn delete_word_left_across_whitespace() {
+    assert_eq!(
+        delete_word_left("foo   bar", position(0, 6)),
+        ("bar".to_string(), position(0, 0))
+    );
+}
+
+#[test]
+fn delete_word_left_deletes_non_empty_selection() {
+    let mut session = Session::new(Document::new(
+        Text::from("foo bar baz"),
+        DecorationSet::new(),
+    ));
+    session.set_selection(position(0, 2), Affinity::Before, SelectionMode::Simple);
+    session.move_right(false);
+    session.move_right(false);
+    session.move_right(false);
+    session.delete_word_left();
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "foar baz");
+}
+
+#[test]
+fn delete_word_right_inside_word() {
+    assert_eq!(
+        delete_word_right("foobar baz", position(0, 3)),
+        ("foo baz".to_string(), position(0, 3))
+    );
+}
+
+#[test]
+fn delete_word_right_at_word_boundary() {
+    assert_eq!(
+        delete_word_right("foo bar", position(0, 0)),
+        (" bar".to_string(), position(0, 0))
+    );
+    assert_eq!(
+        delete_word_right("foo bar", position(0, 3)),
+        ("foo".to_string(), position(0, 3))
+    );
+}
+
+#[test]
+fn delete_word_right_across_whitespace() {
+    assert_eq!(
+        delete_word_right("foo   bar", position(0, 3)),
+        ("foo".to_string(), position(0, 3))
+    );
+}
+
+#[test]
+fn delete_word_right_at_line_end_joins_lines() {
+    assert_eq!(
+        delete_word_right("foo\nbar", position(0, 3)),
+        ("foobar".to_string(), position(0, 3))
+    );
+}
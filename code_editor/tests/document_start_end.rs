@@ -0,0 +1,52 @@
+use makepad_code_editor::{
+    decoration::DecorationSet,
+    selection::Affinity,
+    session::SelectionMode,
+    text::{Position, Text},
+    Document, Session,
+};
+
+fn position(line_index: usize, byte_index: usize) -> Position {
+    Position {
+        line_index,
+        byte_index,
+    }
+}
+
+fn session(text: &str, cursor: Position) -> Session {
+    let session = Session::new(Document::new(Text::from(text), DecorationSet::new()));
+    session.set_selection(cursor, Affinity::Before, SelectionMode::Simple);
+    session
+}
+
+#[test]
+fn move_to_document_end_lands_after_last_byte() {
+    let session = session("abc\nde\nfghi", position(0, 1));
+    session.move_to_document_end(true);
+    let selection = session.selections()[0];
+    assert_eq!(selection.cursor.position, position(2, 4));
+    assert!(selection.is_empty());
+}
+
+#[test]
+fn move_to_document_end_with_trailing_empty_line() {
+    let session = session("abc\nde\n", position(0, 1));
+    session.move_to_document_end(true);
+    assert_eq!(session.selections()[0].cursor.position, position(2, 0));
+}
+
+#[test]
+fn move_to_document_end_keeps_anchor_when_extending() {
+    let session = session("abc\nde", position(0, 1));
+    session.move_to_document_end(false);
+    let selection = session.selections()[0];
+    assert_eq!(selection.anchor, position(0, 1));
+    assert_eq!(selection.cursor.position, position(1, 2));
+}
+
+#[test]
+fn move_to_document_start_lands_at_origin() {
+    let session = session("abc\nde", position(1, 1));
+    session.move_to_document_start(true);
+    assert_eq!(session.selections()[0].cursor.position, Position::zero());
+}
@@ -0,0 +1,31 @@
+use makepad_code_editor::{selection::Cursor, text::Position};
+
+fn cursor(byte_index: usize) -> Cursor {
+    Cursor::from(Position {
+        line_index: 0,
+        byte_index,
+    })
+}
+
+fn lines() -> Vec<String> {
+    vec!["    foo bar".to_string()]
+}
+
+#[test]
+fn smart_home_goes_to_first_non_whitespace_then_to_line_start() {
+    let lines = lines();
+    let cursor = cursor(8).home(&lines, true);
+    assert_eq!(cursor.position.byte_index, 4);
+    let cursor = cursor.home(&lines, true);
+    assert_eq!(cursor.position.byte_index, 0);
+}
+
+#[test]
+fn smart_home_from_inside_indentation_goes_to_line_start() {
+    assert_eq!(cursor(2).home(&lines(), true).position.byte_index, 0);
+}
+
+#[test]
+fn plain_home_goes_to_line_start() {
+    assert_eq!(cursor(8).home(&lines(), false).position.byte_index, 0);
+}
@@ -0,0 +1,74 @@
+use makepad_code_editor::{
+    decoration::DecorationSet,
+    selection::Affinity,
+    session::SelectionMode,
+    text::{Position, Text},
+    Document, Session,
+};
+
+fn position(line_index: usize, byte_index: usize) -> Position {
+    Position {
+        line_index,
+        byte_index,
+    }
+}
+
+#[test]
+fn tentative_text_stays_out_of_the_document() {
+    let document = Document::new(Text::from("ab"), DecorationSet::new());
+    let mut session = Session::new(document.clone());
+    let mut other_session = Session::new(document);
+    session.set_selection(position(0, 1), Affinity::Before, SelectionMode::Simple);
+    session.update_ime_composition("n");
+    session.update_ime_composition("ni");
+    session.handle_changes();
+    other_session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "ab");
+    assert_eq!(other_session.document().as_text().to_string(), "ab");
+    assert_eq!(session.ime_composition().unwrap().1, "ni");
+}
+
+#[test]
+fn ending_the_composition_commits_a_single_edit() {
+    let mut session = Session::new(Document::new(Text::from("ab"), DecorationSet::new()));
+    session.set_selection(position(0, 1), Affinity::Before, SelectionMode::Simple);
+    session.update_ime_composition("n");
+    session.update_ime_composition("ni");
+    session.end_ime_composition("你");
+    session.handle_changes();
+    assert!(session.ime_composition().is_none());
+    assert_eq!(session.document().as_text().to_string(), "a你b");
+    assert_eq!(
+        session.selections()[0].cursor.position,
+        position(0, 1 + "你".len())
+    );
+    assert!(session.undo());
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "ab");
+    assert!(!session.undo());
+}
+
+#[test]
+fn composition_follows_external_edits() {
+    let mut session = Session::new(Document::new(Text::from("ab"), DecorationSet::new()));
+    session.set_selection(position(0, 1), Affinity::Before, SelectionMode::Simple);
+    session.update_ime_composition("ni");
+    session.set_selection(position(0, 0), Affinity::Before, SelectionMode::Simple);
+    session.insert(Text::from("xy"));
+    session.handle_changes();
+    session.end_ime_composition("你");
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "xya你b");
+}
+
+#[test]
+fn empty_text_cancels_the_composition() {
+    let mut session = Session::new(Document::new(Text::from("ab"), DecorationSet::new()));
+    session.set_selection(position(0, 1), Affinity::Before, SelectionMode::Simple);
+    session.update_ime_composition("ni");
+    session.end_ime_composition("");
+    session.handle_changes();
+    assert!(session.ime_composition().is_none());
+    assert_eq!(session.document().as_text().to_string(), "ab");
+    assert!(!session.undo());
+}
@@ -0,0 +1,38 @@
+use makepad_code_editor::text::{Position, Text};
+
+fn position(line_index: usize, byte_index: usize) -> Position {
+    Position {
+        line_index,
+        byte_index,
+    }
+}
+
+#[test]
+fn line_byte_range_excludes_the_newline() {
+    let text = Text::from("abc\nde\nfghi");
+    assert_eq!(position(0, 1).line_byte_range(&text), 0..3);
+    assert_eq!(position(1, 0).line_byte_range(&text), 0..2);
+    assert_eq!(position(2, 4).line_byte_range(&text), 0..4);
+}
+
+#[test]
+fn full_line_byte_range_includes_the_newline_except_on_the_last_line() {
+    let text = Text::from("abc\nde\nfghi");
+    assert_eq!(position(0, 1).full_line_byte_range(&text), 0..4);
+    assert_eq!(position(1, 0).full_line_byte_range(&text), 0..3);
+    assert_eq!(position(2, 4).full_line_byte_range(&text), 0..4);
+}
+
+#[test]
+fn full_line_byte_range_of_an_empty_last_line() {
+    let text = Text::from("abc\n");
+    assert_eq!(position(0, 0).full_line_byte_range(&text), 0..4);
+    assert_eq!(position(1, 0).line_byte_range(&text), 0..0);
+    assert_eq!(position(1, 0).full_line_byte_range(&text), 0..0);
+}
+
+#[test]
+fn full_line_byte_range_of_a_single_line() {
+    let text = Text::from("abc");
+    assert_eq!(position(0, 2).full_line_byte_range(&text), 0..3);
+}
@@ -0,0 +1,58 @@
+use {
+    makepad_code_editor::{
+        decoration::DecorationSet,
+        text::{LineEnding, Text},
+        Document, Session, Settings,
+    },
+    std::rc::Rc,
+};
+
+fn normalize(string: &str) -> String {
+    let mut text = Text::from(string);
+    for edit in text.line_ending_edits() {
+        text.apply_change(edit.change);
+    }
+    text.to_string()
+}
+
+#[test]
+fn line_ending_edits_turn_every_line_break_into_lf() {
+    assert_eq!(normalize("a\r\nb\r\nc"), "a\nb\nc");
+    assert_eq!(normalize("a\rb\rc"), "a\nb\nc");
+    assert_eq!(normalize("a\r\nb\rc\nd\r"), "a\nb\nc\nd\n");
+    assert_eq!(normalize("\r\r\n\r\n"), "\n\n\n");
+}
+
+#[test]
+fn line_ending_edits_only_touch_carriage_returns() {
+    let text = Text::from("a\nb\r\nc");
+    assert!(Text::from("a\nb\nc").line_ending_edits().is_empty());
+    assert_eq!(text.line_ending_edits().len(), 1);
+}
+
+#[test]
+fn opening_a_session_normalizes_outside_the_history() {
+    let document = Document::new(Text::from("a\r\nb\r\nc"), DecorationSet::new());
+    let settings = Rc::new(Settings::default().normalize_line_endings(true));
+    let session = Session::with_settings(document, settings);
+    assert_eq!(session.document().as_text().to_string(), "a\nb\nc");
+    assert_eq!(
+        session.document().as_text().detect_line_ending(),
+        LineEnding::Lf
+    );
+    assert!(!session.undo());
+}
+
+#[test]
+fn normalize_line_endings_can_be_undone() {
+    let mut session = Session::new(Document::new(
+        Text::from("a\r\nb\rc"),
+        DecorationSet::new(),
+    ));
+    session.normalize_line_endings();
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "a\nb\nc");
+    assert!(session.undo());
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "a\r\nb\rc");
+}
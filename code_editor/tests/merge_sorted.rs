@@ -0,0 +1,132 @@
+use makepad_code_editor::{selection::Cursor, text::Position, Selection};
+
+const LINE_COUNT: usize = 3;
+const LINE_LEN: usize = 6;
+
+/// A small linear congruential generator, so the test is deterministic and needs no crates.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self, n: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % n
+    }
+
+    fn position(&mut self) -> Position {
+        Position {
+            line_index: self.next(LINE_COUNT),
+            byte_index: self.next(LINE_LEN + 1),
+        }
+    }
+
+    fn selection(&mut self) -> Selection {
+        let anchor = self.position();
+        // Empty selections are common enough to deserve being tested often.
+        let cursor = if self.next(4) == 0 {
+            anchor
+        } else {
+            self.position()
+        };
+        Selection {
+            anchor,
+            ..Selection::from(Cursor::from(cursor))
+        }
+    }
+
+    fn selections(&mut self) -> Vec<Selection> {
+        let mut selections = (0..self.next(8) + 1)
+            .map(|_| self.selection())
+            .collect::<Vec<_>>();
+        selections.sort_by_key(|selection| selection.start());
+        selections
+    }
+}
+
+fn contains(outer: Selection, inner: Selection) -> bool {
+    outer.start() <= inner.start() && inner.end() <= outer.end()
+}
+
+fn assert_merged(original: &[Selection], merged: &[Selection]) {
+    assert!(!merged.is_empty());
+    for pair in merged.windows(2) {
+        assert!(
+            pair[0].end() < pair[1].start(),
+            "{:?} and {:?} overlap or touch in {:?}",
+            pair[0],
+            pair[1],
+            merged
+        );
+    }
+    for &selection in original {
+        assert!(
+            merged.iter().any(|&merged| contains(merged, selection)),
+            "{:?} was lost in {:?}",
+            selection,
+            merged
+        );
+    }
+    for &merged in merged {
+        assert!(original
+            .iter()
+            .any(|selection| selection.start() == merged.start()));
+        assert!(original
+            .iter()
+            .any(|selection| selection.end() == merged.end()));
+    }
+}
+
+#[test]
+fn merge_sorted_leaves_sorted_selections_with_gaps() {
+    let mut rng = Rng(1);
+    for _ in 0..10_000 {
+        let original = rng.selections();
+        let mut merged = original.clone();
+        Selection::merge_sorted(&mut merged);
+        assert_merged(&original, &merged);
+    }
+}
+
+#[test]
+fn merge_sorted_with_last_tracks_the_last_selection() {
+    let mut rng = Rng(2);
+    for _ in 0..10_000 {
+        let original = rng.selections();
+        let original_last_index = rng.next(original.len());
+        let mut merged = original.clone();
+        let mut last_index = original_last_index;
+        Selection::merge_sorted_with_last(&mut merged, &mut last_index);
+        assert_merged(&original, &merged);
+        assert!(
+            contains(merged[last_index], original[original_last_index]),
+            "{:?} at {} is not in {:?} at {}",
+            original[original_last_index],
+            original_last_index,
+            merged[last_index],
+            last_index
+        );
+    }
+}
+
+#[test]
+fn merge_keeps_the_direction_of_the_first_selection() {
+    let position = |byte_index| Position {
+        line_index: 0,
+        byte_index,
+    };
+    let backward = Selection {
+        anchor: position(4),
+        ..Selection::from(Cursor::from(position(0)))
+    };
+    let forward = Selection {
+        anchor: position(2),
+        ..Selection::from(Cursor::from(position(6)))
+    };
+    let mut selections = vec![backward, forward];
+    Selection::merge_sorted(&mut selections);
+    assert_eq!(selections.len(), 1);
+    assert_eq!(selections[0].anchor, position(6));
+    assert_eq!(selections[0].cursor.position, position(0));
+}
@@ -0,0 +1,159 @@
+use {
+    makepad_code_editor::text::{Position, Range},
+    std::collections::BTreeSet,
+};
+
+const LINE_COUNT: usize = 3;
+const LINE_LEN: usize = 4;
+
+/// A small linear congruential generator, so the test is deterministic and needs no crates.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self, n: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % n
+    }
+
+    fn position(&mut self) -> Position {
+        Position {
+            line_index: self.next(LINE_COUNT),
+            byte_index: self.next(LINE_LEN + 1),
+        }
+    }
+
+    fn range(&mut self) -> Range {
+        let start = self.position();
+        // Empty ranges are common enough to deserve being tested often.
+        if self.next(4) == 0 {
+            return Range::new(start, start).unwrap();
+        }
+        let end = self.position();
+        Range::new(start.min(end), start.max(end)).unwrap()
+    }
+}
+
+/// The naive reference: the positions of the grid a range covers, without its end.
+fn covered(range: Range) -> BTreeSet<Position> {
+    (0..LINE_COUNT)
+        .flat_map(|line_index| {
+            (0..=LINE_LEN).map(move |byte_index| Position {
+                line_index,
+                byte_index,
+            })
+        })
+        .filter(|&position| range.start() <= position && position < range.end())
+        .collect()
+}
+
+fn covered_all(ranges: impl IntoIterator<Item = Option<Range>>) -> BTreeSet<Position> {
+    ranges.into_iter().flatten().flat_map(covered).collect()
+}
+
+#[test]
+fn intersection_matches_reference() {
+    let mut rng = Rng(1);
+    for _ in 0..10_000 {
+        let (a, b) = (rng.range(), rng.range());
+        let intersection = a.intersection(b);
+        if a.is_empty() || b.is_empty() {
+            let touches = a.start() <= b.end() && b.start() <= a.end();
+            assert_eq!(intersection.is_some(), touches, "{:?} {:?}", a, b);
+            if let Some(intersection) = intersection {
+                assert!(intersection.is_empty(), "{:?} {:?}", a, b);
+            }
+        } else {
+            let expected = &covered(a) & &covered(b);
+            assert_eq!(
+                intersection.is_some(),
+                !expected.is_empty(),
+                "{:?} {:?}",
+                a,
+                b
+            );
+            assert_eq!(covered_all([intersection]), expected, "{:?} {:?}", a, b);
+        }
+        assert_eq!(intersection, b.intersection(a));
+    }
+}
+
+#[test]
+fn union_matches_reference() {
+    let mut rng = Rng(2);
+    for _ in 0..10_000 {
+        let (a, b) = (rng.range(), rng.range());
+        if a.start() > b.end() || b.start() > a.end() {
+            continue;
+        }
+        let union = a.union(b);
+        assert_eq!(union.start(), a.start().min(b.start()));
+        assert_eq!(union.end(), a.end().max(b.end()));
+        assert_eq!(covered(union), &covered(a) | &covered(b), "{:?} {:?}", a, b);
+        assert_eq!(union, b.union(a));
+    }
+}
+
+#[test]
+fn subtract_matches_reference() {
+    let mut rng = Rng(3);
+    for _ in 0..10_000 {
+        let (a, b) = (rng.range(), rng.range());
+        let (before, after) = a.subtract(b);
+        if b.is_empty() {
+            assert_eq!((before, after), (Some(a), None), "{:?} {:?}", a, b);
+            continue;
+        }
+        if a.is_empty() {
+            let expected = if b.start() <= a.start() && a.start() <= b.end() {
+                (None, None)
+            } else {
+                (Some(a), None)
+            };
+            assert_eq!((before, after), expected, "{:?} {:?}", a, b);
+            continue;
+        }
+        assert_eq!(
+            covered_all([before, after]),
+            &covered(a) - &covered(b),
+            "{:?} {:?}",
+            a,
+            b
+        );
+        for part in [before, after].into_iter().flatten() {
+            assert!(!part.is_empty(), "{:?} {:?}", a, b);
+        }
+        if let (Some(before), Some(after)) = (before, after) {
+            assert!(before.end() <= b.start() && b.end() <= after.start());
+        }
+    }
+}
+
+#[test]
+fn subtract_empty_range_inside() {
+    let range = Range::new(
+        Position {
+            line_index: 0,
+            byte_index: 1,
+        },
+        Position {
+            line_index: 1,
+            byte_index: 2,
+        },
+    )
+    .unwrap();
+    let empty = Range::new(
+        Position {
+            line_index: 0,
+            byte_index: 3,
+        },
+        Position {
+            line_index: 0,
+            byte_index: 3,
+        },
+    )
+    .unwrap();
+    assert_eq!(range.subtract(empty), (Some(range), None));
+}
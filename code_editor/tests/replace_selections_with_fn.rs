@@ -0,0 +1,56 @@
+use makepad_code_editor::{
+    decoration::DecorationSet,
+    selection::Affinity,
+    session::SelectionMode,
+    text::{Position, Text},
+    Document, Session,
+};
+
+fn position(line_index: usize, byte_index: usize) -> Position {
+    Position {
+        line_index,
+        byte_index,
+    }
+}
+
+fn to_uppercase(string: &str) -> Text {
+    Text::from(string.to_uppercase())
+}
+
+#[test]
+fn replace_with_uppercase_keeps_the_selection_over_the_new_text() {
+    let mut session = Session::new(Document::new(Text::from("hello"), DecorationSet::new()));
+    session.set_selection(position(0, 0), Affinity::Before, SelectionMode::Simple);
+    session.move_to_document_end(false);
+    session.replace_selections_with_fn(to_uppercase);
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "HELLO");
+    let selection = session.selections()[0];
+    assert_eq!(selection.anchor, position(0, 0));
+    assert_eq!(selection.cursor.position, position(0, 5));
+}
+
+#[test]
+fn replace_keeps_a_backward_selection_backward() {
+    let mut session = Session::new(Document::new(Text::from("say hello"), DecorationSet::new()));
+    session.set_selection(position(0, 9), Affinity::Before, SelectionMode::Simple);
+    session.move_word_left(false);
+    session.replace_selections_with_fn(|_| Text::from("hi"));
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "say hi");
+    let selection = session.selections()[0];
+    assert_eq!(selection.anchor, position(0, 6));
+    assert_eq!(selection.cursor.position, position(0, 4));
+}
+
+#[test]
+fn replace_is_undone_as_a_single_edit() {
+    let mut session = Session::new(Document::new(Text::from("hello"), DecorationSet::new()));
+    session.set_selection(position(0, 0), Affinity::Before, SelectionMode::Simple);
+    session.move_to_document_end(false);
+    session.replace_selections_with_fn(to_uppercase);
+    session.handle_changes();
+    assert!(session.undo());
+    session.handle_changes();
+    assert_eq!(session.document().as_text().to_string(), "hello");
+}
@@ -0,0 +1,82 @@
+use makepad_code_editor::{
+    decoration::DecorationSet,
+    selection::Affinity,
+    session::SelectionMode,
+    text::{Position, Text},
+    Document, Session,
+};
+
+fn position(line_index: usize, byte_index: usize) -> Position {
+    Position {
+        line_index,
+        byte_index,
+    }
+}
+
+/// Selects the word at `position` in `session`, as a double click does, returning the anchor and
+/// the cursor of the selection.
+fn select_word(session: &Session, position: Position) -> (Position, Position) {
+    session.set_selection(position, Affinity::Before, SelectionMode::Word);
+    let selection = session.selections()[0];
+    (selection.anchor, selection.cursor.position)
+}
+
+fn session(text: &str) -> Session {
+    Session::new(Document::new(Text::from(text), DecorationSet::new()))
+}
+
+#[test]
+fn select_word_inside_word() {
+    let session = session("foo_bar.baz qux");
+    assert_eq!(
+        select_word(&session, position(0, 2)),
+        (position(0, 0), position(0, 7))
+    );
+    assert_eq!(
+        select_word(&session, position(0, 9)),
+        (position(0, 8), position(0, 11))
+    );
+}
+
+#[test]
+fn select_word_on_separator_or_whitespace() {
+    let session = session("foo.bar  baz");
+    assert_eq!(
+        select_word(&session, position(0, 3)),
+        (position(0, 3), position(0, 4))
+    );
+    assert_eq!(
+        select_word(&session, position(0, 7)),
+        (position(0, 7), position(0, 9))
+    );
+}
+
+#[test]
+fn select_word_at_line_end_selects_the_last_word() {
+    let session = session("foo bar\n");
+    assert_eq!(
+        select_word(&session, position(0, 7)),
+        (position(0, 4), position(0, 7))
+    );
+    assert_eq!(
+        select_word(&session, position(1, 0)),
+        (position(1, 0), position(1, 0))
+    );
+}
+
+#[test]
+fn select_word_respects_word_separators() {
+    let mut session = session("foo_bar.baz");
+    session.set_session_setting(|settings| settings.word_separators = vec!['_', ' ']);
+    assert_eq!(
+        select_word(&session, position(0, 2)),
+        (position(0, 0), position(0, 3))
+    );
+    assert_eq!(
+        select_word(&session, position(0, 5)),
+        (position(0, 4), position(0, 11))
+    );
+    session.set_selection(position(0, 11), Affinity::Before, SelectionMode::Simple);
+    session.move_word_left(true);
+    assert_eq!(session.selections()[0].cursor.position, position(0, 4));
+}
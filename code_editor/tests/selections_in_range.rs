@@ -0,0 +1,62 @@
+use makepad_code_editor::{
+    selection::{Cursor, SelectionSet},
+    text::{Position, Range},
+    Selection,
+};
+
+fn position(byte_index: usize) -> Position {
+    Position {
+        line_index: 0,
+        byte_index,
+    }
+}
+
+fn range(start: usize, end: usize) -> Range {
+    Range::new(position(start), position(end)).unwrap()
+}
+
+fn selection(start: usize, end: usize) -> Selection {
+    Selection {
+        anchor: position(start),
+        ..Selection::from(Cursor::from(position(end)))
+    }
+}
+
+/// Selections over bytes 2..4, 6..8 and 10..12 of a line.
+fn selections() -> SelectionSet {
+    let mut selections = SelectionSet::new();
+    selections.set_selections(vec![selection(2, 4), selection(6, 8), selection(10, 12)]);
+    selections
+}
+
+#[test]
+fn selection_ending_at_range_start_is_included() {
+    assert_eq!(selections().in_range(range(4, 5)), &[selection(2, 4)]);
+}
+
+#[test]
+fn selection_starting_at_range_end_is_included() {
+    assert_eq!(selections().in_range(range(5, 6)), &[selection(6, 8)]);
+}
+
+#[test]
+fn selections_touching_both_ends_are_included() {
+    assert_eq!(
+        selections().in_range(range(4, 10)),
+        &[selection(2, 4), selection(6, 8), selection(10, 12)]
+    );
+}
+
+#[test]
+fn range_in_a_gap_has_no_selections() {
+    assert!(selections().in_range(range(5, 5)).is_empty());
+    assert!(selections().in_range(range(0, 1)).is_empty());
+    assert!(selections().in_range(range(13, 14)).is_empty());
+}
+
+#[test]
+fn empty_range_inside_or_at_the_edge_of_a_selection() {
+    assert_eq!(selections().in_range(range(7, 7)), &[selection(6, 8)]);
+    assert_eq!(selections().in_range(range(6, 6)), &[selection(6, 8)]);
+    assert_eq!(selections().in_range(range(8, 8)), &[selection(6, 8)]);
+}
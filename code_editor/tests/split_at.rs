@@ -0,0 +1,72 @@
+use makepad_code_editor::{selection::Cursor, text::Position, Selection};
+
+fn position(byte_index: usize) -> Position {
+    Position {
+        line_index: 0,
+        byte_index,
+    }
+}
+
+fn selection(anchor: usize, cursor: usize) -> Selection {
+    Selection {
+        anchor: position(anchor),
+        ..Selection::from(Cursor::from(position(cursor)))
+    }
+}
+
+/// The anchor and cursor byte indices of a selection.
+fn ends(selection: Selection) -> (usize, usize) {
+    (
+        selection.anchor.byte_index,
+        selection.cursor.position.byte_index,
+    )
+}
+
+#[test]
+fn split_forward_selection() {
+    let (first, second) = selection(2, 8).split_at(position(5)).unwrap();
+    assert_eq!(ends(first), (2, 5));
+    assert_eq!(ends(second), (5, 8));
+}
+
+#[test]
+fn split_backward_selection() {
+    let (first, second) = selection(8, 2).split_at(position(5)).unwrap();
+    assert_eq!(ends(first), (5, 2));
+    assert_eq!(ends(second), (8, 5));
+}
+
+#[test]
+fn split_keeps_the_total_range() {
+    for (anchor, cursor) in [(2, 8), (8, 2)] {
+        let selection = selection(anchor, cursor);
+        for byte_index in 3..8 {
+            let (first, second) = selection.split_at(position(byte_index)).unwrap();
+            assert_eq!(first.start(), selection.start());
+            assert_eq!(first.end(), position(byte_index));
+            assert_eq!(second.start(), position(byte_index));
+            assert_eq!(second.end(), selection.end());
+        }
+    }
+}
+
+#[test]
+fn split_at_start_or_end_is_none() {
+    for (anchor, cursor) in [(2, 8), (8, 2)] {
+        assert_eq!(selection(anchor, cursor).split_at(position(2)), None);
+        assert_eq!(selection(anchor, cursor).split_at(position(8)), None);
+    }
+}
+
+#[test]
+fn split_outside_is_none() {
+    for (anchor, cursor) in [(2, 8), (8, 2)] {
+        assert_eq!(selection(anchor, cursor).split_at(position(0)), None);
+        assert_eq!(selection(anchor, cursor).split_at(position(9)), None);
+    }
+}
+
+#[test]
+fn split_empty_selection_is_none() {
+    assert_eq!(selection(4, 4).split_at(position(4)), None);
+}
@@ -0,0 +1,51 @@
+use crate::{
+    makepad_platform::*,
+    cx_2d::Cx2d,
+};
+
+/// A range of window widths, from narrow phones up to wide desktop windows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Live, LiveHook)]
+#[live_ignore]
+pub enum Breakpoint {
+    #[pick] Xs,
+    Sm,
+    Md,
+    Lg,
+    Xl,
+}
+
+/// The window widths at which each breakpoint starts.
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook, LiveRegister)]
+#[live_ignore]
+pub struct Breakpoints {
+    #[live(0.0)] pub xs: f64,
+    #[live(576.0)] pub sm: f64,
+    #[live(768.0)] pub md: f64,
+    #[live(992.0)] pub lg: f64,
+    #[live(1200.0)] pub xl: f64,
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self {xs: 0.0, sm: 576.0, md: 768.0, lg: 992.0, xl: 1200.0}
+    }
+}
+
+impl Breakpoints {
+    /// The largest breakpoint that `width` reaches.
+    pub fn breakpoint_for_width(&self, width: f64) -> Breakpoint {
+        if width >= self.xl {Breakpoint::Xl}
+        else if width >= self.lg {Breakpoint::Lg}
+        else if width >= self.md {Breakpoint::Md}
+        else if width >= self.sm {Breakpoint::Sm}
+        else {Breakpoint::Xs}
+    }
+}
+
+impl<'a> Cx2d<'a> {
+    /// The breakpoint of the window being drawn. Windows redraw entirely when resized, so
+    /// anything drawn from the breakpoint follows it across thresholds.
+    pub fn current_breakpoint(&self, breakpoints: &Breakpoints) -> Breakpoint {
+        breakpoints.breakpoint_for_width(self.current_pass_size().x)
+    }
+}
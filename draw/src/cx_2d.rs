@@ -4,7 +4,7 @@ use {
         ops::DerefMut
     },
     crate::{
-        makepad_math::DVec2,
+        makepad_math::{DVec2, Rect},
         makepad_platform::{
             DrawEvent,
             Area,
@@ -41,6 +41,9 @@ pub struct Cx2d<'a> {
     pub (crate) turtles: Vec<Turtle>,
     pub (crate) turtle_walks: Vec<TurtleWalk>,
     pub (crate) turtle_clips: Vec<(DVec2, DVec2)>,
+    pub (crate) clip_stack: Vec<Rect>,
+    pub (crate) layer_stack: Vec<Option<DrawListId>>,
+    pub (crate) pending_layers: Vec<(DrawListId, i32, DrawListId)>,
     pub (crate) align_list: Vec<AlignEntry>,
     pub fonts_atlas_rc: CxFontsAtlasRc,
     pub icon_atlas_rc: CxIconAtlasRc,
@@ -87,6 +90,9 @@ impl<'a> Cx2d<'a> {
             pass_stack: Vec::new(),
             draw_list_stack: Vec::new(),
             turtle_clips: Vec::new(),
+            clip_stack: Vec::new(),
+            layer_stack: Vec::new(),
+            pending_layers: Vec::new(),
             turtle_walks: Vec::new(),
             turtles: Vec::new(),
             align_list: Vec::new(),
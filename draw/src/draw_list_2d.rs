@@ -1,4 +1,5 @@
 use {
+    std::collections::HashMap,
     crate::{
         makepad_platform::*,
         nav::*,
@@ -198,6 +199,7 @@ impl DrawList2d {
         if cx.cx.draw_lists[draw_list_id].redraw_id != cx.cx.redraw_id {
             panic!("calling end on a view that didnt get begin called this redraw cycle");
         }
+        cx.append_pending_layers(draw_list_id);
     }
     
     pub fn get_view_transform(&self, cx: &Cx) -> Mat4 {
@@ -215,8 +217,70 @@ impl DrawList2d {
 }
 
 
+/// The draw lists backing `Cx2d::begin_layer`, one per parent draw list and z level.
+#[derive(Default)]
+pub struct CxDrawLayers {
+    lists: HashMap<(DrawListId, i32), DrawList2d>,
+}
+
 impl<'a> Cx2d<'a> {
     
+    /// Draws everything until the matching `end_layer` into a layer of the current draw list.
+    /// Layers are painted after the rest of their draw list, lower `z` first and layers with
+    /// the same `z` together, in the order they were first begun. Layout is not affected, only
+    /// the order things are painted in. A `z` of zero or less draws in place.
+    pub fn begin_layer(&mut self, z: i32) {
+        if z <= 0 {
+            self.layer_stack.push(None);
+            return
+        }
+        let parent_id = *self.draw_list_stack.last().unwrap();
+        let redraw_id = self.cx.redraw_id;
+        let pass_id = self.pass_stack.last().unwrap().pass_id;
+        if !self.cx.global::<CxDrawLayers>().lists.contains_key(&(parent_id, z)) {
+            let list = DrawList2d::new(self.cx);
+            self.cx.global::<CxDrawLayers>().lists.insert((parent_id, z), list);
+        }
+        let layer_id = self.cx.global::<CxDrawLayers>().lists[&(parent_id, z)].draw_list.id();
+        // the first layer of this z in the redraw clears it, the others add to it
+        if self.cx.draw_lists[layer_id].redraw_id != redraw_id {
+            self.cx.draw_lists[layer_id].pass_id = Some(pass_id);
+            self.cx.draw_lists[layer_id].codeflow_parent_id = Some(parent_id);
+            self.cx.draw_lists[layer_id].clear_draw_items(redraw_id);
+            self.nav_list_clear(layer_id);
+            self.pending_layers.push((parent_id, z, layer_id));
+        }
+        self.draw_list_stack.push(layer_id);
+        self.layer_stack.push(Some(layer_id));
+    }
+    
+    pub fn end_layer(&mut self) {
+        let layer_id = self.layer_stack.pop().expect("end_layer without a begin_layer");
+        if let Some(layer_id) = layer_id {
+            if self.draw_list_stack.pop() != Some(layer_id) {
+                panic!("Mismatch in drawlist id in end_layer, check your begin/end pairs");
+            }
+        }
+    }
+    
+    /// Adds the layers begun within a draw list once it ends, so they paint after it.
+    pub (crate) fn append_pending_layers(&mut self, parent_id: DrawListId) {
+        if self.pending_layers.iter().all( | (parent, _, _) | *parent != parent_id) {
+            return
+        }
+        let mut layers: Vec<(i32, DrawListId)> = self.pending_layers.iter()
+            .filter( | (parent, _, _) | *parent == parent_id)
+            .map( | (_, z, layer_id) | (*z, *layer_id))
+            .collect();
+        self.pending_layers.retain( | (parent, _, _) | *parent != parent_id);
+        layers.sort_by_key( | (z, _) | *z);
+        let redraw_id = self.cx.redraw_id;
+        for (_, layer_id) in layers {
+            self.cx.draw_lists[parent_id].append_sub_list(redraw_id, layer_id);
+            self.nav_list_item_push(parent_id, NavItem::Child(layer_id));
+        }
+    }
+    
     pub fn new_draw_call(&mut self, draw_vars: &DrawVars) -> Option<&mut CxDrawItem> {
         return self.get_draw_call(false, draw_vars);
     }
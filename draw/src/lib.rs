@@ -12,6 +12,7 @@ pub mod font_atlas;
 pub mod geometry;
 pub mod nav;
 pub mod icon_atlas;
+pub mod breakpoints;
 mod owned_font_face;
  
 pub use crate::{
@@ -30,6 +31,10 @@ pub use crate::{
     overlay::{
         Overlay
     },
+    breakpoints::{
+        Breakpoint,
+        Breakpoints
+    },
     nav::{
         NavRole,
         NavOrder,
@@ -51,6 +56,12 @@ pub use crate::{
         draw_icon::DrawIcon,
         draw_quad::DrawQuad,
         draw_line::DrawLine,
+        draw_bezier::{flatten_cubic_bezier, flatten_arc},
+        draw_svg_path::DrawSvgPath,
+        draw_brush::{DrawBrush, Brush, GradientStop},
+        draw_shadow::{DrawShadow, Shadow},
+        draw_nine_slice::DrawNineSlice,
+        draw_blur::{DrawBlur, BlurLayer, EffectSettings},
         draw_text::DrawText,
         draw_color::DrawColor,
     },
@@ -66,6 +77,11 @@ pub fn live_design(cx: &mut Cx) {
     crate::shader::draw_icon::live_design(cx);
     crate::shader::draw_text::live_design(cx);
     crate::shader::draw_line::live_design(cx);
+    crate::shader::draw_svg_path::live_design(cx);
+    crate::shader::draw_brush::live_design(cx);
+    crate::shader::draw_shadow::live_design(cx);
+    crate::shader::draw_nine_slice::live_design(cx);
+    crate::shader::draw_blur::live_design(cx);
     crate::geometry::geometry_gen::live_design(cx);
     crate::shader::std::live_design(cx);
     crate::shader::draw_trapezoid::live_design(cx);
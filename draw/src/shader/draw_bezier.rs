@@ -0,0 +1,100 @@
+use {
+    crate::{
+        makepad_platform::*,
+        cx_2d::Cx2d,
+        shader::draw_line::DrawLine,
+        shader::draw_brush::Brush,
+    },
+};
+
+/// How far the flattened polyline may deviate from the true curve, in pixels
+const FLATTEN_TOLERANCE: f64 = 0.25;
+const MAX_SUBDIVISION_DEPTH: usize = 16;
+
+/// Flattens a cubic Bézier into points along the curve, subdividing more where it bends
+/// more. `from` itself is not pushed, so successive curves can share an output buffer.
+pub fn flatten_cubic_bezier(from: DVec2, cp1: DVec2, cp2: DVec2, to: DVec2, tolerance: f64, out: &mut Vec<DVec2>) {
+    fn distance_to_chord(p: DVec2, a: DVec2, b: DVec2) -> f64 {
+        let chord = b - a;
+        let len = chord.length();
+        if len < 1e-9 {
+            return (p - a).length()
+        }
+        ((p.x - a.x) * chord.y - (p.y - a.y) * chord.x).abs() / len
+    }
+
+    fn subdivide(p0: DVec2, p1: DVec2, p2: DVec2, p3: DVec2, tolerance: f64, depth: usize, out: &mut Vec<DVec2>) {
+        let flatness = distance_to_chord(p1, p0, p3).max(distance_to_chord(p2, p0, p3));
+        if flatness <= tolerance || depth >= MAX_SUBDIVISION_DEPTH {
+            out.push(p3);
+            return
+        }
+        // de Casteljau split at t = 0.5
+        let p01 = (p0 + p1) * 0.5;
+        let p12 = (p1 + p2) * 0.5;
+        let p23 = (p2 + p3) * 0.5;
+        let p012 = (p01 + p12) * 0.5;
+        let p123 = (p12 + p23) * 0.5;
+        let mid = (p012 + p123) * 0.5;
+        subdivide(p0, p01, p012, mid, tolerance, depth + 1, out);
+        subdivide(mid, p123, p23, p3, tolerance, depth + 1, out);
+    }
+
+    subdivide(from, cp1, cp2, to, tolerance.max(0.01), 0, out);
+}
+
+/// Flattens a circular arc into points, `start_angle` and `end_angle` are in radians
+/// and the arc runs from start to end. The first point is the start of the arc.
+pub fn flatten_arc(center: DVec2, radius: f64, start_angle: f64, end_angle: f64, tolerance: f64, out: &mut Vec<DVec2>) {
+    let sweep = end_angle - start_angle;
+    // the sagitta of a segment spanning angle a is r * (1 - cos(a / 2))
+    let max_step = if radius > tolerance {
+        2.0 * (1.0 - tolerance.max(0.01) / radius).acos()
+    }
+    else {
+        std::f64::consts::PI * 0.5
+    };
+    let steps = (sweep.abs() / max_step.max(1e-3)).ceil().max(1.0) as usize;
+    for i in 0..=steps {
+        let angle = start_angle + sweep * (i as f64 / steps as f64);
+        out.push(center + dvec2(angle.cos(), angle.sin()) * radius);
+    }
+}
+
+impl DrawLine {
+    /// Draws a cubic Bézier as line segments. Coordinates are absolute, the turtle clip applies.
+    pub fn draw_bezier_abs(&mut self, cx: &mut Cx2d, from: DVec2, cp1: DVec2, cp2: DVec2, to: DVec2, line_width: f64, brush: &Brush) {
+        let mut points = vec![from];
+        flatten_cubic_bezier(from, cp1, cp2, to, FLATTEN_TOLERANCE, &mut points);
+        self.draw_polyline_abs(cx, &points, line_width, brush);
+    }
+
+    /// Draws a circular arc as line segments, angles are in radians.
+    pub fn draw_arc_abs(&mut self, cx: &mut Cx2d, center: DVec2, radius: f64, start_angle: f64, end_angle: f64, line_width: f64, brush: &Brush) {
+        let mut points = Vec::new();
+        flatten_arc(center, radius, start_angle, end_angle, FLATTEN_TOLERANCE, &mut points);
+        self.draw_polyline_abs(cx, &points, line_width, brush);
+    }
+
+    /// Draws connected line segments. Gradients span the bounds of the polyline, each
+    /// segment takes the color of the brush at its midpoint.
+    pub fn draw_polyline_abs(&mut self, cx: &mut Cx2d, points: &[DVec2], line_width: f64, brush: &Brush) {
+        let (min, max) = points.iter().fold(
+            (dvec2(f64::MAX, f64::MAX), dvec2(f64::MIN, f64::MIN)),
+            | (min, max), p | (dvec2(min.x.min(p.x), min.y.min(p.y)), dvec2(max.x.max(p.x), max.y.max(p.y)))
+        );
+        let size = dvec2((max.x - min.x).max(1e-6), (max.y - min.y).max(1e-6));
+        for segment in points.windows(2) {
+            if segment[0] != segment[1] {
+                let color = match brush {
+                    Brush::Solid(color) => *color,
+                    _ => {
+                        let mid = (segment[0] + segment[1]) * 0.5;
+                        brush.color_at(dvec2((mid.x - min.x) / size.x, (mid.y - min.y) / size.y))
+                    }
+                };
+                self.draw_line_abs(cx, segment[0], segment[1], color, line_width);
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use {
+    crate::{
+        makepad_platform::*,
+        cx_2d::Cx2d,
+        draw_list_2d::DrawList2d,
+        turtle::{Walk, Layout},
+        DrawQuad
+    },
+};
+
+live_design! {
+    DrawBlur = {{DrawBlur}} {
+        texture image: texture2d
+
+        fn pixel(self) -> vec4 {
+            // taps spread over three sigma either side, sigma being half the radius
+            let sigma = max(self.blur_radius * 0.5, 0.001);
+            let spacing = self.blur_radius * 1.5 / 16.0;
+            let texel = self.blur_dir / max(self.rect_size, vec2(1.0, 1.0));
+            let sum = sample2d(self.image, self.pos);
+            let total = 1.0;
+            for i in 1.0..17.0 {
+                let d = i * spacing;
+                let w = exp(-0.5 * d * d / (sigma * sigma));
+                sum = sum + w * (sample2d(self.image, self.pos + texel * d) + sample2d(self.image, self.pos - texel * d));
+                total = total + 2.0 * w;
+            }
+            // the pass textures are premultiplied already
+            return sum / total;
+        }
+    }
+}
+
+/// One direction of a separable gaussian blur over a texture filling the rect.
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawBlur {
+    #[deref] pub draw_super: DrawQuad,
+    /// (1, 0) blurs horizontally, (0, 1) vertically
+    #[calc] pub blur_dir: Vec2,
+    #[calc] pub blur_radius: f32,
+}
+
+/// App wide switches for expensive effects, reach them with `cx.global::<EffectSettings>()`.
+pub struct EffectSettings {
+    /// When off, blur layers draw their content as is. Meant for low end hardware.
+    pub enable_blur_effects: bool,
+}
+
+impl Default for EffectSettings {
+    fn default() -> Self {
+        Self {enable_blur_effects: true}
+    }
+}
+
+struct BlurPasses {
+    content_pass: Pass,
+    content_texture: Texture,
+    blur_pass: Pass,
+    blur_texture: Texture,
+}
+
+impl BlurPasses {
+    fn new(cx: &mut Cx) -> Self {
+        let new_target = | cx: &mut Cx | {
+            let pass = Pass::new(cx);
+            let texture = Texture::new_with_format(cx, TextureFormat::RenderBGRAu8 {
+                size: TextureSize::Auto,
+            });
+            pass.add_color_texture(cx, &texture, PassClearColor::ClearWith(vec4(0.0, 0.0, 0.0, 0.0)));
+            (pass, texture)
+        };
+        let (content_pass, content_texture) = new_target(cx);
+        let (blur_pass, blur_texture) = new_target(cx);
+        Self {content_pass, content_texture, blur_pass, blur_texture}
+    }
+}
+
+/// Draws everything between `begin` and `end` blurred, for frosted glass backgrounds.
+///
+/// The GPU can't read back what a pass has drawn so far, so a layer blurs what is drawn inside
+/// it rather than what is behind it: draw the backdrop to frost between `begin` and `end`,
+/// then the overlay content on top after `end`. The content renders into a texture which is
+/// blurred horizontally into a second texture, and that one is drawn blurred vertically in
+/// place of the content. That's two extra passes each time the layer redraws, so keep layers
+/// to things that aren't redrawn every frame.
+#[derive(Live, LiveHook, LiveRegister)]
+pub struct BlurLayer {
+    #[live] draw_blur_h: DrawBlur,
+    #[live] draw_blur_v: DrawBlur,
+    #[live] content_list: DrawList2d,
+    #[live] blur_list: DrawList2d,
+    #[rust] passes: Option<BlurPasses>,
+    #[rust] blur_radius: f64,
+    #[rust] active: bool,
+}
+
+impl BlurLayer {
+    /// Begins a turtle like `DrawQuad::begin`, `blur_radius` is in pixels.
+    pub fn begin(&mut self, cx: &mut Cx2d, walk: Walk, layout: Layout, blur_radius: f64) {
+        self.blur_radius = blur_radius;
+        self.active = blur_radius > 0.0 && cx.global::<EffectSettings>().enable_blur_effects;
+        if self.active {
+            let passes = self.passes.get_or_insert_with( || BlurPasses::new(cx));
+            cx.make_child_pass(&passes.content_pass);
+            cx.begin_pass(&passes.content_pass, None);
+            self.content_list.begin_always(cx);
+        }
+        cx.begin_turtle(walk, layout);
+    }
+
+    /// Ends the turtle and draws the blurred content, returning its rect.
+    pub fn end(&mut self, cx: &mut Cx2d) -> Rect {
+        let rect = cx.end_turtle();
+        if !self.active {
+            return rect
+        }
+        let passes = self.passes.as_ref().unwrap();
+        self.content_list.end(cx);
+        cx.end_pass(&passes.content_pass);
+
+        cx.make_child_pass(&passes.blur_pass);
+        cx.begin_pass(&passes.blur_pass, None);
+        self.blur_list.begin_always(cx);
+        self.draw_blur_h.draw_vars.set_texture(0, &passes.content_texture);
+        self.draw_blur_h.blur_dir = vec2(1.0, 0.0);
+        self.draw_blur_h.blur_radius = self.blur_radius as f32;
+        self.draw_blur_h.draw_abs(cx, rect);
+        self.blur_list.end(cx);
+        cx.end_pass(&passes.blur_pass);
+
+        self.draw_blur_v.draw_vars.set_texture(0, &passes.blur_texture);
+        self.draw_blur_v.blur_dir = vec2(0.0, 1.0);
+        self.draw_blur_v.blur_radius = self.blur_radius as f32;
+        self.draw_blur_v.draw_abs(cx, rect);
+        // both passes follow the rect the result is drawn at
+        let area = self.draw_blur_v.area();
+        cx.set_pass_area(&passes.content_pass, area);
+        cx.set_pass_area(&passes.blur_pass, area);
+        rect
+    }
+
+    pub fn redraw(&self, cx: &mut Cx) {
+        self.content_list.redraw(cx);
+        self.blur_list.redraw(cx);
+    }
+}
@@ -0,0 +1,229 @@
+use {
+    crate::{
+        makepad_platform::*,
+        cx_2d::Cx2d,
+        DrawQuad
+    },
+};
+
+live_design! {
+    import makepad_draw::shader::std::*;
+
+    DrawBrush = {{DrawBrush}} {
+        fn brush_t(self, uv: vec2) -> float {
+            if self.brush_kind < 1.5 {
+                let d = self.brush_p1 - self.brush_p0;
+                return dot(uv - self.brush_p0, d) / max(dot(d, d), 0.000001);
+            }
+            return length(uv - self.brush_p0) / max(self.brush_p1.x, 0.000001);
+        }
+
+        fn brush_color(self, uv: vec2) -> vec4 {
+            if self.brush_kind < 0.5 {
+                return self.stop_color0;
+            }
+            // stops are sorted, so blending them in order leaves the right pair on top
+            let t = self.brush_t(uv);
+            let color = self.stop_color0;
+            if self.stop_count > 1.5 {
+                color = mix(color, self.stop_color1, clamp((t - self.stop_pos.x) / max(self.stop_pos.y - self.stop_pos.x, 0.000001), 0., 1.));
+            }
+            if self.stop_count > 2.5 {
+                color = mix(color, self.stop_color2, clamp((t - self.stop_pos.y) / max(self.stop_pos.z - self.stop_pos.y, 0.000001), 0., 1.));
+            }
+            if self.stop_count > 3.5 {
+                color = mix(color, self.stop_color3, clamp((t - self.stop_pos.z) / max(self.stop_pos.w - self.stop_pos.z, 0.000001), 0., 1.));
+            }
+            return color;
+        }
+
+        fn pixel(self) -> vec4 {
+            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+            sdf.box(0., 0., self.rect_size.x, self.rect_size.y, self.corner_radius * 0.5);
+            return sdf.fill(self.brush_color(self.pos));
+        }
+    }
+}
+
+/// The number of gradient stops the shader evaluates, gradients with more stops are resampled.
+pub const MAX_SHADER_GRADIENT_STOPS: usize = 4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Live, LiveHook, LiveRegister)]
+#[live_ignore]
+pub struct GradientStop {
+    /// Position along the gradient, 0 at the start and 1 at the end
+    #[live] pub position: f64,
+    #[live] pub color: Vec4,
+}
+
+/// How a shape is filled. Gradient coordinates are relative to the bounds of the shape being
+/// drawn, (0, 0) being its top left and (1, 1) its bottom right corner.
+#[derive(Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum Brush {
+    #[pick(vec4(1.0, 1.0, 1.0, 1.0))]
+    Solid(Vec4),
+    #[live {from: vec2(0.0, 0.0), to: vec2(1.0, 0.0), stops: Vec::new()}]
+    LinearGradient {from: Vec2, to: Vec2, stops: Vec<GradientStop>},
+    #[live {center: vec2(0.5, 0.5), radius: 0.5, stops: Vec::new()}]
+    RadialGradient {center: Vec2, radius: f64, stops: Vec<GradientStop>},
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Brush::Solid(vec4(1.0, 1.0, 1.0, 1.0))
+    }
+}
+
+impl From<Vec4> for Brush {
+    fn from(color: Vec4) -> Self {
+        Brush::Solid(color)
+    }
+}
+
+impl GradientStop {
+    pub fn new(position: f64, color: Vec4) -> Self {
+        Self {position, color}
+    }
+}
+
+impl Brush {
+    pub fn linear(from: Vec2, to: Vec2, stops: Vec<GradientStop>) -> Self {
+        Brush::LinearGradient {from, to, stops}
+    }
+
+    pub fn radial(center: Vec2, radius: f64, stops: Vec<GradientStop>) -> Self {
+        Brush::RadialGradient {center, radius, stops}
+    }
+
+    fn stops(&self) -> &[GradientStop] {
+        match self {
+            Brush::Solid(_) => &[],
+            Brush::LinearGradient {stops, ..} | Brush::RadialGradient {stops, ..} => stops
+        }
+    }
+
+    /// The position along the gradient of a point in unit coordinates
+    fn gradient_t(&self, uv: DVec2) -> f64 {
+        match self {
+            Brush::Solid(_) => 0.0,
+            Brush::LinearGradient {from, to, ..} => {
+                let from = from.into_dvec2();
+                let d = to.into_dvec2() - from;
+                let len_sq = d.x * d.x + d.y * d.y;
+                if len_sq <= 1e-12 {
+                    return 0.0
+                }
+                ((uv.x - from.x) * d.x + (uv.y - from.y) * d.y) / len_sq
+            }
+            Brush::RadialGradient {center, radius, ..} => {
+                (uv - center.into_dvec2()).length() / radius.max(1e-6)
+            }
+        }
+    }
+
+    /// The color of the gradient at position `t`, stops are expected in increasing order.
+    fn color_at_t(stops: &[GradientStop], t: f64) -> Vec4 {
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return vec4(0.0, 0.0, 0.0, 0.0)
+        };
+        if t <= first.position {
+            return first.color
+        }
+        for pair in stops.windows(2) {
+            if t <= pair[1].position {
+                let span = pair[1].position - pair[0].position;
+                let f = if span > 1e-9 {(t - pair[0].position) / span} else {1.0};
+                return Vec4::from_lerp(pair[0].color, pair[1].color, f as f32)
+            }
+        }
+        last.color
+    }
+
+    /// Evaluates the brush at a point in unit coordinates of the shape's bounds.
+    pub fn color_at(&self, uv: DVec2) -> Vec4 {
+        match self {
+            Brush::Solid(color) => *color,
+            _ => Self::color_at_t(self.stops(), self.gradient_t(uv))
+        }
+    }
+
+    /// The stops handed to the shader. Gradients with too many stops are resampled evenly
+    /// over the range their stops cover.
+    fn shader_stops(&self) -> Vec<GradientStop> {
+        let stops = self.stops();
+        if stops.len() <= MAX_SHADER_GRADIENT_STOPS {
+            return stops.to_vec()
+        }
+        let start = stops[0].position;
+        let end = stops[stops.len() - 1].position;
+        (0..MAX_SHADER_GRADIENT_STOPS).map( | i | {
+            let position = start + (end - start) * i as f64 / (MAX_SHADER_GRADIENT_STOPS - 1) as f64;
+            GradientStop::new(position, Self::color_at_t(stops, position))
+        }).collect()
+    }
+}
+
+/// Fills rects and rounded rects with a `Brush`.
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawBrush {
+    #[deref] pub draw_super: DrawQuad,
+    #[calc] pub brush_kind: f32,
+    #[calc] pub brush_p0: Vec2,
+    #[calc] pub brush_p1: Vec2,
+    #[calc] pub stop_count: f32,
+    #[calc] pub stop_pos: Vec4,
+    #[calc] pub stop_color0: Vec4,
+    #[calc] pub stop_color1: Vec4,
+    #[calc] pub stop_color2: Vec4,
+    #[calc] pub stop_color3: Vec4,
+    #[calc] pub corner_radius: f32,
+}
+
+impl DrawBrush {
+    pub fn set_brush(&mut self, brush: &Brush) {
+        match brush {
+            Brush::Solid(color) => {
+                self.brush_kind = 0.0;
+                self.stop_count = 1.0;
+                self.stop_color0 = *color;
+                return
+            }
+            Brush::LinearGradient {from, to, ..} => {
+                self.brush_kind = 1.0;
+                self.brush_p0 = *from;
+                self.brush_p1 = *to;
+            }
+            Brush::RadialGradient {center, radius, ..} => {
+                self.brush_kind = 2.0;
+                self.brush_p0 = *center;
+                self.brush_p1 = vec2(*radius as f32, 0.0);
+            }
+        }
+        let stops = brush.shader_stops();
+        let mut positions = [0.0f32; MAX_SHADER_GRADIENT_STOPS];
+        let mut colors = [vec4(0.0, 0.0, 0.0, 0.0); MAX_SHADER_GRADIENT_STOPS];
+        for (i, stop) in stops.iter().enumerate() {
+            positions[i] = stop.position as f32;
+            colors[i] = stop.color;
+        }
+        self.stop_count = stops.len() as f32;
+        self.stop_pos = vec4(positions[0], positions[1], positions[2], positions[3]);
+        self.stop_color0 = colors[0];
+        self.stop_color1 = colors[1];
+        self.stop_color2 = colors[2];
+        self.stop_color3 = colors[3];
+    }
+
+    pub fn draw_rect_abs(&mut self, cx: &mut Cx2d, rect: Rect, brush: &Brush) {
+        self.draw_rounded_rect_abs(cx, rect, 0.0, brush);
+    }
+
+    pub fn draw_rounded_rect_abs(&mut self, cx: &mut Cx2d, rect: Rect, radius: f64, brush: &Brush) {
+        self.set_brush(brush);
+        self.corner_radius = radius as f32;
+        self.draw_abs(cx, rect);
+    }
+}
@@ -0,0 +1,91 @@
+use {
+    crate::{
+        makepad_platform::*,
+        cx_2d::Cx2d,
+        DrawQuad
+    },
+};
+
+live_design! {
+    import makepad_draw::shader::std::*;
+
+    DrawNineSlice = {{DrawNineSlice}} {
+        texture image: texture2d
+        varying uv: vec2
+
+        fn slice_span(slice: float, size: float, start: float, end: float) -> vec2 {
+            // the start and end of a slice along an axis whose first and last slices are fixed
+            let is_mid = step(0.5, slice) - step(1.5, slice);
+            let is_last = step(1.5, slice);
+            let is_first = 1.0 - is_mid - is_last;
+            return vec2(
+                is_mid * start + is_last * (size - end),
+                is_first * start + is_mid * (size - end) + is_last * size
+            );
+        }
+
+        fn vertex(self) -> vec4 {
+            let m0 = self.slice_margins.xy;
+            let m1 = self.slice_margins.zw;
+            // the corners shrink evenly when the rect is too small to fit them
+            let fit = min(1.0, min(
+                self.rect_size.x / max(m0.x + m1.x, 0.001),
+                self.rect_size.y / max(m0.y + m1.y, 0.001)
+            ));
+            let sx = slice_span(self.slice.x, self.rect_size.x, m0.x * fit, m1.x * fit);
+            let sy = slice_span(self.slice.y, self.rect_size.y, m0.y * fit, m1.y * fit);
+            let tex = max(self.texture_size, vec2(1.0, 1.0));
+            let ux = slice_span(self.slice.x, 1.0, m0.x / tex.x, m1.x / tex.x);
+            let uy = slice_span(self.slice.y, 1.0, m0.y / tex.y, m1.y / tex.y);
+            let v = self.clip_and_transform_vertex(
+                self.rect_pos + vec2(sx.x, sy.x),
+                vec2(sx.y - sx.x, sy.y - sy.x)
+            );
+            self.uv = vec2(mix(ux.x, ux.y, self.pos.x), mix(uy.x, uy.y, self.pos.y));
+            return v;
+        }
+
+        fn pixel(self) -> vec4 {
+            let color = sample2d(self.image, self.uv);
+            return Pal::premul(vec4(color.xyz, color.w * self.opacity));
+        }
+    }
+}
+
+/// Draws a texture stretched over a rect without distorting its corners. The margins cut the
+/// texture into nine slices: the corners are drawn at their size, the edges stretch along one
+/// axis and the center along both. Each slice is its own instance, the vertex shader works
+/// out its position and texture coordinates from the margins.
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawNineSlice {
+    #[deref] pub draw_super: DrawQuad,
+    #[live(1.0)] pub opacity: f32,
+    #[calc] pub slice: Vec2,
+    #[calc] pub slice_margins: Vec4,
+    #[calc] pub texture_size: Vec2,
+}
+
+impl DrawNineSlice {
+    /// `margins` are in texture pixels and are drawn at that many logical pixels.
+    pub fn draw_nine_slice_abs(&mut self, cx: &mut Cx2d, rect: Rect, texture: &Texture, margins: Margin) {
+        let (width, height) = match texture.get_format(cx).vec_width_height() {
+            Some(size) => size,
+            None => return
+        };
+        self.draw_vars.set_texture(0, texture);
+        self.texture_size = vec2(width as f32, height as f32);
+        self.slice_margins = vec4(
+            margins.left as f32,
+            margins.top as f32,
+            margins.right as f32,
+            margins.bottom as f32
+        );
+        for row in 0..3 {
+            for col in 0..3 {
+                self.slice = vec2(col as f32, row as f32);
+                self.draw_abs(cx, rect);
+            }
+        }
+    }
+}
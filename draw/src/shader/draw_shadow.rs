@@ -0,0 +1,129 @@
+use {
+    crate::{
+        makepad_platform::*,
+        cx_2d::Cx2d,
+        DrawQuad
+    },
+};
+
+live_design! {
+    DrawShadow = {{DrawShadow}} {
+        fn shadow_extent(self) -> float {
+            // the gaussian is negligible beyond three sigma, sigma being half the blur
+            return self.shadow_spread + 1.5 * self.shadow_blur + 1.0;
+        }
+
+        fn erf_approx(x: float) -> float {
+            // Winitzki's approximation of the error function, built on an exponential
+            let x2 = x * x;
+            let ax2 = 0.147 * x2;
+            return sign(x) * sqrt(1.0 - exp(-x2 * (1.2732395 + ax2) / (1.0 + ax2)));
+        }
+
+        fn vertex(self) -> vec4 {
+            let extent = self.shadow_extent();
+            return self.clip_and_transform_vertex(
+                self.rect_pos + self.shadow_offset - vec2(extent, extent),
+                self.rect_size + vec2(2.0 * extent, 2.0 * extent)
+            );
+        }
+
+        fn pixel(self) -> vec4 {
+            let extent = self.shadow_extent();
+            let p = self.pos * (self.rect_size + vec2(2.0 * extent, 2.0 * extent)) - vec2(extent, extent);
+            // signed distance to the rounded rect, grown by the spread
+            let half = self.rect_size * 0.5 + vec2(self.shadow_spread, self.shadow_spread);
+            let r = max(min(self.corner_radius + self.shadow_spread, min(half.x, half.y)), 0.0);
+            let q = abs(p - self.rect_size * 0.5) - half + vec2(r, r);
+            let d = length(max(q, vec2(0.0, 0.0))) + min(max(q.x, q.y), 0.0) - r;
+            // a box blurred with a gaussian falls off like the complementary error function
+            let sigma = max(self.shadow_blur * 0.5, 0.001);
+            let a = 0.5 - 0.5 * erf_approx(d / (sigma * 1.4142136));
+            return vec4(self.shadow_color.rgb * self.shadow_color.a * a, self.shadow_color.a * a);
+        }
+    }
+}
+
+/// A box shadow, like the CSS `box-shadow` property.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Live, LiveHook, LiveRegister)]
+#[live_ignore]
+pub struct Shadow {
+    #[live] pub offset: Vec2,
+    /// Blur radius in pixels, the gaussian's standard deviation is half of it
+    #[live] pub blur: f64,
+    /// Grows the shadow beyond the rect on every side
+    #[live] pub spread: f64,
+    #[live] pub color: Vec4,
+}
+
+/// Draws blurred box shadows. Shadows have to be drawn before the content they belong to,
+/// so for content whose size is known only after drawing it, `begin` reserves the shadow
+/// instances and `end` moves them under the final rect.
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawShadow {
+    #[rust] pending: Vec<Area>,
+    #[deref] pub draw_super: DrawQuad,
+    #[calc] pub shadow_offset: Vec2,
+    #[calc] pub shadow_blur: f32,
+    #[calc] pub shadow_spread: f32,
+    #[calc] pub shadow_color: Vec4,
+    #[calc] pub corner_radius: f32,
+}
+
+impl Shadow {
+    /// The same shadow, faded by `opacity`
+    pub fn with_opacity(&self, opacity: f32) -> Shadow {
+        Shadow {color: vec4(self.color.x, self.color.y, self.color.z, self.color.w * opacity), ..*self}
+    }
+}
+
+impl DrawShadow {
+    fn set_shadow(&mut self, corner_radius: f64, shadow: &Shadow) {
+        self.shadow_offset = shadow.offset;
+        self.shadow_blur = shadow.blur as f32;
+        self.shadow_spread = shadow.spread as f32;
+        self.shadow_color = shadow.color;
+        self.corner_radius = corner_radius as f32;
+    }
+
+    pub fn draw_shadow(&mut self, cx: &mut Cx2d, rect: Rect, corner_radius: f64, shadow: &Shadow) {
+        if shadow.color.w <= 0.0 {
+            return
+        }
+        self.set_shadow(corner_radius, shadow);
+        self.draw_abs(cx, rect);
+    }
+
+    /// Draws the shadows in order, so the first one ends up at the bottom.
+    pub fn draw_shadows(&mut self, cx: &mut Cx2d, rect: Rect, corner_radius: f64, shadows: &[Shadow]) {
+        for shadow in shadows {
+            self.draw_shadow(cx, rect, corner_radius, shadow);
+        }
+    }
+
+    /// Reserves the shadows ahead of the content they belong to, call `end` with the rect of
+    /// the content once it is drawn.
+    pub fn begin(&mut self, cx: &mut Cx2d, corner_radius: f64, shadows: &[Shadow]) {
+        self.pending.clear();
+        if !self.draw_vars.can_instance() {
+            return
+        }
+        for shadow in shadows.iter().filter( | shadow | shadow.color.w > 0.0) {
+            self.set_shadow(corner_radius, shadow);
+            self.rect_pos = vec2(0.0, 0.0);
+            self.rect_size = vec2(0.0, 0.0);
+            let area = cx.add_aligned_instance(&self.draw_vars);
+            self.pending.push(area);
+        }
+        if let Some(area) = self.pending.last() {
+            self.draw_vars.area = cx.update_area_refs(self.draw_vars.area, *area);
+        }
+    }
+
+    pub fn end(&mut self, cx: &mut Cx2d, rect: Rect) {
+        for area in self.pending.drain(..) {
+            area.set_rect(cx, &rect);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use {
+    crate::{
+        makepad_platform::*,
+        cx_2d::Cx2d,
+        makepad_vector::svg::{FillRule, Mesh, SvgPath},
+        DrawQuad
+    },
+};
+
+live_design! {
+    DrawSvgPath = {{DrawSvgPath}} {
+        fn edge(p: vec2, a: vec2, b: vec2) -> float {
+            return (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+        }
+
+        fn pixel(self) -> vec4 {
+            let p = self.pos * self.rect_size;
+            let e0 = edge(p, self.tri_p0, self.tri_p1);
+            let e1 = edge(p, self.tri_p1, self.tri_p2);
+            let e2 = edge(p, self.tri_p2, self.tri_p0);
+            // inside when the edge functions agree in sign, the triangles of a mesh can wind either way
+            let lo = min(e0, min(e1, e2));
+            let hi = max(e0, max(e1, e2));
+            if lo * hi >= 0.0 {
+                return vec4(self.color.rgb * self.color.a, self.color.a);
+            }
+            return vec4(0., 0., 0., 0.);
+        }
+    }
+}
+
+/// Fills triangle meshes, drawing one instance per triangle over its bounding rect.
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawSvgPath {
+    #[deref] pub draw_super: DrawQuad,
+    #[calc] pub tri_p0: Vec2,
+    #[calc] pub tri_p1: Vec2,
+    #[calc] pub tri_p2: Vec2,
+    #[calc] pub color: Vec4,
+}
+
+impl DrawSvgPath {
+    /// Tessellates `path` with the non-zero fill rule and draws it. `transform` maps path
+    /// coordinates to absolute coordinates. Tessellating is not free, so widgets that draw the
+    /// same path every frame should keep the mesh around and use `draw_mesh`.
+    pub fn draw_svg_path(&mut self, cx: &mut Cx2d, path: &SvgPath, transform: Mat4, color: Vec4) {
+        let mesh = path.tessellate(FillRule::NonZero);
+        self.draw_mesh(cx, &mesh, transform, color);
+    }
+
+    pub fn draw_mesh(&mut self, cx: &mut Cx2d, mesh: &Mesh, transform: Mat4, color: Vec4) {
+        self.color = color;
+        let project = | p: makepad_vector::geometry::Point | {
+            let v = transform.transform_vec4(vec4(p.x as f32, p.y as f32, 0.0, 1.0));
+            let w = if v.w.abs() > 1e-6 {v.w} else {1.0};
+            dvec2((v.x / w) as f64, (v.y / w) as f64)
+        };
+        for [p0, p1, p2] in mesh.triangles() {
+            let (p0, p1, p2) = (project(p0), project(p1), project(p2));
+            let min = dvec2(p0.x.min(p1.x).min(p2.x), p0.y.min(p1.y).min(p2.y));
+            let max = dvec2(p0.x.max(p1.x).max(p2.x), p0.y.max(p1.y).max(p2.y));
+            let rect = Rect {pos: min, size: max - min};
+            if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+                continue
+            }
+            self.tri_p0 = (p0 - min).into_vec2();
+            self.tri_p1 = (p1 - min).into_vec2();
+            self.tri_p2 = (p2 - min).into_vec2();
+            self.draw_abs(cx, rect);
+        }
+    }
+}
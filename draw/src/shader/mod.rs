@@ -2,6 +2,12 @@ pub mod draw_color;
 pub mod draw_quad;
 pub mod draw_icon;
 pub mod draw_line;
+pub mod draw_bezier;
+pub mod draw_brush;
+pub mod draw_shadow;
+pub mod draw_svg_path;
+pub mod draw_nine_slice;
+pub mod draw_blur;
 //pub mod draw_shape;
 pub mod draw_text;
 pub mod std;
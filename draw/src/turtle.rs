@@ -102,6 +102,9 @@ pub enum DeferWalk{
     Resolved(Walk)
 }
 
+/// How deep `push_clip_rect` may nest, deeper usually means a missing `pop_clip_rect`
+pub const MAX_CLIP_STACK_DEPTH: usize = 32;
+
 #[derive(Debug)]
 pub enum AlignEntry{
     Unset,
@@ -246,6 +249,33 @@ impl<'a> Cx2d<'a> {
         self.turtle_walks.truncate(turtle.turtle_walks_start);
     }
     
+    /// Clips everything drawn until the matching `pop_clip_rect` to `rect`, intersected with the
+    /// clips of the enclosing turtles and clip rects. Like turtle clipping this ends up in the
+    /// `draw_clip` of every instance when the pass turtle ends, so it moves along with alignment.
+    /// Push and pop have to be balanced within the same turtle.
+    pub fn push_clip_rect(&mut self, rect: Rect) {
+        debug_assert!(self.clip_stack.len() < MAX_CLIP_STACK_DEPTH, "clip stack deeper than {} levels, is pop_clip_rect missing?", MAX_CLIP_STACK_DEPTH);
+        let clipped = match self.clip_stack.last() {
+            Some(parent) => rect.clip((parent.pos, parent.pos + parent.size)),
+            None => rect
+        };
+        self.clip_stack.push(clipped);
+        self.align_list.push(AlignEntry::BeginTurtle(rect.pos, rect.pos + rect.size));
+    }
+    
+    pub fn pop_clip_rect(&mut self) {
+        debug_assert!(!self.clip_stack.is_empty(), "pop_clip_rect without push_clip_rect");
+        if self.clip_stack.pop().is_some() {
+            self.align_list.push(AlignEntry::EndTurtle);
+        }
+    }
+    
+    /// The innermost pushed clip rect, intersected with the ones around it. Turtle clips are
+    /// not included since they are only known once the turtles end.
+    pub fn current_clip_rect(&self) -> Option<Rect> {
+        self.clip_stack.last().cloned()
+    }
+    
     pub fn begin_turtle_with_guard(&mut self, walk: Walk, layout: Layout, guard_area: Area) {
         let (origin, width, height, draw_clip) = if let Some(parent) = self.turtles.last() {
             
@@ -1161,6 +1191,15 @@ impl Walk {
 }
 
 impl Padding {
+    pub fn uniform(v: f64) -> Self {
+        Self {left: v, top: v, right: v, bottom: v}
+    }
+    
+    /// `h` on the left and right, `v` on the top and bottom
+    pub fn axes(h: f64, v: f64) -> Self {
+        Self {left: h, top: v, right: h, bottom: v}
+    }
+    
     pub fn left_top(&self) -> DVec2 {
         dvec2(self.left, self.top)
     }
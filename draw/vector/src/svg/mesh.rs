@@ -0,0 +1,95 @@
+use crate::geometry::{Point, Rectangle, Transform, Transformation, Trapezoid};
+
+/// A triangle mesh in 2-dimensional Euclidian space. Every three consecutive indices form a
+/// triangle.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    pub vertices: Vec<Point>,
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+    /// Creates a new empty mesh.
+    pub fn new() -> Mesh {
+        Mesh::default()
+    }
+
+    /// Returns true if `self` contains no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the number of triangles in `self`.
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Returns an iterator over the corner points of each triangle in `self`.
+    pub fn triangles(&self) -> impl Iterator<Item = [Point; 3]> + '_ {
+        self.indices.chunks_exact(3).map(move |triangle| {
+            [
+                self.vertices[triangle[0] as usize],
+                self.vertices[triangle[1] as usize],
+                self.vertices[triangle[2] as usize],
+            ]
+        })
+    }
+
+    /// Returns the total area covered by the triangles in `self`.
+    pub fn area(&self) -> f64 {
+        self.triangles()
+            .map(|[p0, p1, p2]| (p1 - p0).cross(p2 - p0).abs() * 0.5)
+            .sum()
+    }
+
+    /// Returns the smallest rectangle that contains all vertices of `self`, or `None` if `self`
+    /// is empty.
+    pub fn bounds(&self) -> Option<Rectangle> {
+        let mut vertices = self.vertices.iter();
+        let first = *vertices.next()?;
+        Some(vertices.fold(Rectangle::new(first, first), |bounds, p| {
+            Rectangle::new(
+                Point::new(bounds.p_min.x.min(p.x), bounds.p_min.y.min(p.y)),
+                Point::new(bounds.p_max.x.max(p.x), bounds.p_max.y.max(p.y)),
+            )
+        }))
+    }
+
+    /// Appends the two triangles that make up the given trapezoid. Degenerate trapezoids are
+    /// skipped.
+    pub fn push_trapezoid(&mut self, trapezoid: Trapezoid) {
+        let [x0, x1] = trapezoid.xs;
+        let [y0, y1, y2, y3] = trapezoid.ys;
+        if x0 >= x1 || (y0 >= y2 && y1 >= y3) {
+            return;
+        }
+        let base = self.vertices.len() as u32;
+        self.vertices.extend_from_slice(&[
+            Point::new(x0 as f64, y0 as f64),
+            Point::new(x1 as f64, y1 as f64),
+            Point::new(x1 as f64, y3 as f64),
+            Point::new(x0 as f64, y2 as f64),
+        ]);
+        self.indices
+            .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+}
+
+impl Transform for Mesh {
+    fn transform<T>(mut self, t: &T) -> Mesh
+    where
+        T: Transformation,
+    {
+        self.transform_mut(t);
+        self
+    }
+
+    fn transform_mut<T>(&mut self, t: &T)
+    where
+        T: Transformation,
+    {
+        for vertex in &mut self.vertices {
+            vertex.transform_mut(t);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod mesh;
+mod svg_path;
+
+pub use self::mesh::Mesh;
+pub use self::svg_path::{SvgParseError, SvgPath};
+pub use crate::trapezoidator::FillRule;
@@ -0,0 +1,435 @@
+use crate::geometry::{Point, Transform, Transformation};
+use crate::internal_iter::InternalIterator;
+use crate::path::{PathCommand, PathIterator};
+use crate::svg::{FillRule, Mesh};
+use crate::trapezoidator::Trapezoidator;
+use std::fmt;
+
+/// The default tolerance used to approximate curves with line segments when tessellating.
+const DEFAULT_TOLERANCE: f64 = 0.1;
+
+/// A path parsed from SVG path data, as found in the `d` attribute of a `<path>` element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SvgPath {
+    commands: Vec<PathCommand>,
+}
+
+/// An error that occurred while parsing SVG path data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgParseError {
+    /// The byte offset in the path data at which the error occurred.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SvgParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for SvgParseError {}
+
+impl SvgPath {
+    /// Creates a new empty path.
+    pub fn new() -> SvgPath {
+        SvgPath::default()
+    }
+
+    /// Parses SVG path data. Supports the `M`, `L`, `H`, `V`, `C`, `Q` and `Z` commands, in both
+    /// their absolute and relative forms, including implicitly repeated commands.
+    pub fn from_svg_d(d: &str) -> Result<SvgPath, SvgParseError> {
+        SvgPathParser::new(d).parse().map(|commands| SvgPath { commands })
+    }
+
+    /// Returns a slice of the commands that make up `self`.
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    /// Returns true if `self` contains no commands.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Converts `self` to a triangle mesh, filling the contours according to `fill_rule`.
+    pub fn tessellate(&self, fill_rule: FillRule) -> Mesh {
+        self.tessellate_with_tolerance(fill_rule, DEFAULT_TOLERANCE)
+    }
+
+    /// Converts `self` to a triangle mesh, approximating curves with line segments that deviate
+    /// at most `tolerance` from the curve.
+    ///
+    /// The contours are trapezoidated with a sweep line, after which each trapezoid is split into
+    /// two triangles. The resulting triangles never overlap. Like the glyph outlines the
+    /// trapezoidator was written for, contours must not cross themselves or each other.
+    pub fn tessellate_with_tolerance(&self, fill_rule: FillRule, tolerance: f64) -> Mesh {
+        let mut mesh = Mesh::new();
+        let mut trapezoidator = Trapezoidator::with_fill_rule(fill_rule);
+        if let Some(trapezoidate) =
+            trapezoidator.trapezoidate(self.closed_commands().into_iter().linearize(tolerance))
+        {
+            trapezoidate.for_each(&mut |trapezoid| {
+                mesh.push_trapezoid(trapezoid);
+                true
+            });
+        }
+        mesh
+    }
+
+    /// Returns the commands of `self` with every contour closed, since filling only makes sense
+    /// for closed contours.
+    fn closed_commands(&self) -> Vec<PathCommand> {
+        let mut commands = Vec::with_capacity(self.commands.len() + 1);
+        let mut is_open = false;
+        for &command in &self.commands {
+            match command {
+                PathCommand::MoveTo(_) if is_open => commands.push(PathCommand::Close),
+                _ => (),
+            }
+            is_open = command != PathCommand::Close;
+            commands.push(command);
+        }
+        if is_open {
+            commands.push(PathCommand::Close);
+        }
+        commands
+    }
+}
+
+impl Transform for SvgPath {
+    fn transform<T>(mut self, t: &T) -> SvgPath
+    where
+        T: Transformation,
+    {
+        self.transform_mut(t);
+        self
+    }
+
+    fn transform_mut<T>(&mut self, t: &T)
+    where
+        T: Transformation,
+    {
+        for command in &mut self.commands {
+            command.transform_mut(t);
+        }
+    }
+}
+
+struct SvgPathParser<'a> {
+    data: &'a [u8],
+    position: usize,
+    commands: Vec<PathCommand>,
+    current_point: Point,
+    initial_point: Point,
+}
+
+impl<'a> SvgPathParser<'a> {
+    fn new(d: &'a str) -> Self {
+        SvgPathParser {
+            data: d.as_bytes(),
+            position: 0,
+            commands: Vec::new(),
+            current_point: Point::origin(),
+            initial_point: Point::origin(),
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<PathCommand>, SvgParseError> {
+        let mut last_command = None;
+        loop {
+            self.skip_separators();
+            let start = self.position;
+            let Some(c) = self.peek() else {
+                break;
+            };
+            let command = if c.is_ascii_alphabetic() {
+                self.position += 1;
+                c
+            } else {
+                // numbers without a command letter repeat the previous command, where a repeated
+                // moveto becomes a lineto
+                match last_command {
+                    Some(b'M') => b'L',
+                    Some(b'm') => b'l',
+                    Some(b'Z' | b'z') | None => {
+                        return Err(self.error("expected a command"));
+                    }
+                    Some(command) => command,
+                }
+            };
+            if last_command.is_none() && !matches!(command, b'M' | b'm') {
+                return Err(SvgParseError {
+                    position: start,
+                    message: "path data must start with a moveto".to_string(),
+                });
+            }
+            self.parse_command(command)?;
+            last_command = Some(command);
+        }
+        Ok(self.commands)
+    }
+
+    fn parse_command(&mut self, command: u8) -> Result<(), SvgParseError> {
+        let relative = command.is_ascii_lowercase();
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let p = self.parse_point(relative)?;
+                self.initial_point = p;
+                self.current_point = p;
+                self.commands.push(PathCommand::MoveTo(p));
+            }
+            b'L' => {
+                let p = self.parse_point(relative)?;
+                self.line_to(p);
+            }
+            b'H' => {
+                let mut x = self.parse_number()?;
+                if relative {
+                    x += self.current_point.x;
+                }
+                self.line_to(Point::new(x, self.current_point.y));
+            }
+            b'V' => {
+                let mut y = self.parse_number()?;
+                if relative {
+                    y += self.current_point.y;
+                }
+                self.line_to(Point::new(self.current_point.x, y));
+            }
+            b'C' => {
+                let p1 = self.parse_point(relative)?;
+                let p2 = self.parse_point(relative)?;
+                let p = self.parse_point(relative)?;
+                self.current_point = p;
+                self.commands.push(PathCommand::CubicTo(p1, p2, p));
+            }
+            b'Q' => {
+                let p1 = self.parse_point(relative)?;
+                let p = self.parse_point(relative)?;
+                self.current_point = p;
+                self.commands.push(PathCommand::QuadraticTo(p1, p));
+            }
+            b'Z' => {
+                self.current_point = self.initial_point;
+                self.commands.push(PathCommand::Close);
+            }
+            _ => {
+                self.position -= 1;
+                return Err(self.error(&format!(
+                    "unsupported command '{}'",
+                    command as char
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn line_to(&mut self, p: Point) {
+        self.current_point = p;
+        self.commands.push(PathCommand::LineTo(p));
+    }
+
+    fn parse_point(&mut self, relative: bool) -> Result<Point, SvgParseError> {
+        let x = self.parse_number()?;
+        let y = self.parse_number()?;
+        Ok(if relative {
+            Point::new(self.current_point.x + x, self.current_point.y + y)
+        } else {
+            Point::new(x, y)
+        })
+    }
+
+    fn parse_number(&mut self) -> Result<f64, SvgParseError> {
+        self.skip_separators();
+        let start = self.position;
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+        let mut has_digits = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            has_digits |= self.skip_digits();
+        }
+        if !has_digits {
+            self.position = start;
+            return Err(self.error("expected a number"));
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if !self.skip_digits() {
+                self.position = mantissa_end;
+            }
+        }
+        // the slice only contains ascii, so it is always valid utf8
+        std::str::from_utf8(&self.data[start..self.position])
+            .unwrap()
+            .parse()
+            .map_err(|_| SvgParseError {
+                position: start,
+                message: "invalid number".to_string(),
+            })
+    }
+
+    fn skip_digits(&mut self) -> bool {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.position > start
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_ascii_whitespace() || c == b',') {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.position).cloned()
+    }
+
+    fn error(&self, message: &str) -> SvgParseError {
+        SvgParseError {
+            position: self.position,
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FillRule, PathCommand, Point, SvgPath};
+    use std::f64::consts::PI;
+
+    fn assert_approx_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+    }
+
+    /// The outline of a five pointed star, its points on a circle of radius 100 and its inner
+    /// corners on a circle of radius `r`.
+    fn star(r: f64) -> SvgPath {
+        let points: Vec<String> = (0..10)
+            .map(|i| {
+                let radius = if i % 2 == 0 { 100.0 } else { r };
+                let angle = -PI / 2.0 + i as f64 * PI / 5.0;
+                format!("{} {}", radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+        SvgPath::from_svg_d(&format!("M {} L {} Z", points[0], points[1..].join(" "))).unwrap()
+    }
+
+    #[test]
+    fn parse_absolute_and_relative_commands() {
+        let path = SvgPath::from_svg_d("M10,20 l5-5 H30 v10 C1 2 3 4 5 6 q1 1 2 2 z").unwrap();
+        assert_eq!(
+            path.commands(),
+            &[
+                PathCommand::MoveTo(Point::new(10.0, 20.0)),
+                PathCommand::LineTo(Point::new(15.0, 15.0)),
+                PathCommand::LineTo(Point::new(30.0, 15.0)),
+                PathCommand::LineTo(Point::new(30.0, 25.0)),
+                PathCommand::CubicTo(
+                    Point::new(1.0, 2.0),
+                    Point::new(3.0, 4.0),
+                    Point::new(5.0, 6.0)
+                ),
+                PathCommand::QuadraticTo(Point::new(6.0, 7.0), Point::new(7.0, 8.0)),
+                PathCommand::Close,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_implicit_commands_and_compact_numbers() {
+        let path = SvgPath::from_svg_d("m1-2.5.5 1e1-1,0").unwrap();
+        assert_eq!(
+            path.commands(),
+            &[
+                PathCommand::MoveTo(Point::new(1.0, -2.5)),
+                PathCommand::LineTo(Point::new(1.5, 7.5)),
+                PathCommand::LineTo(Point::new(0.5, 7.5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(SvgPath::from_svg_d("L 1 2").unwrap_err().position, 0);
+        assert_eq!(SvgPath::from_svg_d("M 1 2 X").unwrap_err().position, 6);
+        assert_eq!(SvgPath::from_svg_d("M 1 ,").unwrap_err().position, 5);
+        assert!(SvgPath::from_svg_d("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn tessellate_rectangle() {
+        let mesh = SvgPath::from_svg_d("M0 0 H10 V20 H0 Z")
+            .unwrap()
+            .tessellate(FillRule::NonZero);
+        assert_eq!(mesh.triangle_count(), 2);
+        assert_approx_eq(mesh.area(), 200.0);
+    }
+
+    #[test]
+    fn tessellate_star() {
+        // the inner corners of a regular pentagram
+        let r = 100.0 * (2.0 * PI / 5.0).cos() / (PI / 5.0).cos();
+        let path = star(r);
+        let area = 5.0 * 100.0 * r * (PI / 5.0).sin();
+        for fill_rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            let mesh = path.tessellate(fill_rule);
+            assert_approx_eq(mesh.area(), area);
+            let bounds = mesh.bounds().unwrap();
+            assert_approx_eq(bounds.p_min.y, -100.0);
+            assert_approx_eq(bounds.p_max.x, 100.0 * (PI / 10.0).cos());
+        }
+    }
+
+    #[test]
+    fn tessellate_rectangle_with_hole() {
+        // the hole winds in the opposite direction, so both fill rules leave it empty
+        let path = SvgPath::from_svg_d("M0 0 H100 V100 H0 Z M25 25 V75 H75 V25 Z").unwrap();
+        assert_approx_eq(path.tessellate(FillRule::NonZero).area(), 7500.0);
+        assert_approx_eq(path.tessellate(FillRule::EvenOdd).area(), 7500.0);
+
+        // when the hole winds in the same direction, only even-odd leaves it empty
+        let path = SvgPath::from_svg_d("M0 0 H100 V100 H0 Z M25 25 H75 V75 H25 Z").unwrap();
+        assert_approx_eq(path.tessellate(FillRule::NonZero).area(), 10000.0);
+        assert_approx_eq(path.tessellate(FillRule::EvenOdd).area(), 7500.0);
+
+        // no triangle may cover the hole
+        for triangle in path.tessellate(FillRule::EvenOdd).triangles() {
+            let centroid = Point::new(
+                (triangle[0].x + triangle[1].x + triangle[2].x) / 3.0,
+                (triangle[0].y + triangle[1].y + triangle[2].y) / 3.0,
+            );
+            assert!(!(centroid.x > 25.0 && centroid.x < 75.0 && centroid.y > 25.0 && centroid.y < 75.0));
+        }
+    }
+
+    #[test]
+    fn tessellate_closes_open_contours() {
+        let mesh = SvgPath::from_svg_d("M0 0 L10 0 L10 10 M20 0 L30 0 L30 10")
+            .unwrap()
+            .tessellate(FillRule::NonZero);
+        assert_approx_eq(mesh.area(), 100.0);
+    }
+
+    #[test]
+    fn tessellate_curves() {
+        // a circle of radius 10 made of four cubic arcs
+        let k = 10.0 * 0.5522847498;
+        let d = format!(
+            "M10 0 C10 {k} {k} 10 0 10 C-{k} 10 -10 {k} -10 0 C-10 -{k} -{k} -10 0 -10 C{k} -10 10 -{k} 10 0 Z",
+            k = k
+        );
+        let mesh = SvgPath::from_svg_d(&d)
+            .unwrap()
+            .tessellate_with_tolerance(FillRule::NonZero, 0.01);
+        assert!((mesh.area() - PI * 100.0).abs() < 1.0);
+    }
+}
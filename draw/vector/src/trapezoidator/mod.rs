@@ -12,6 +12,26 @@ use std::ops::Range;
 pub struct Trapezoidator {
     event_queue: BinaryHeap<Event>,
     active_segments: Vec<ActiveSegment>,
+    fill_rule: FillRule,
+}
+
+/// The rule that decides which regions enclosed by a set of contours are inside the shape.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum FillRule {
+    /// A region is inside if the contours wind around it a non-zero number of times.
+    #[default]
+    NonZero,
+    /// A region is inside if a ray from it crosses the contours an odd number of times.
+    EvenOdd,
+}
+
+impl FillRule {
+    fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
 }
 
 impl Trapezoidator {
@@ -20,6 +40,24 @@ impl Trapezoidator {
         Trapezoidator::default()
     }
 
+    /// Creates a new trapezoidator that fills according to the given fill rule.
+    pub fn with_fill_rule(fill_rule: FillRule) -> Trapezoidator {
+        Trapezoidator {
+            fill_rule,
+            ..Trapezoidator::default()
+        }
+    }
+
+    /// Returns the fill rule used by `self`.
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    /// Sets the fill rule used by `self`.
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.fill_rule = fill_rule;
+    }
+
     /// Returns an iterator over trapezoids corresponding to the given iterator over line path
     /// commands.
     pub fn trapezoidate<P: LinePathIterator>(&mut self, path: P)->Option<Trapezoidate>{
@@ -220,13 +258,14 @@ impl Trapezoidator {
         } else {
             self.active_segments[incident_segment_range.end - 1].upper_region
         };
+        let fill_rule = self.fill_rule;
         self.active_segments.splice(
             incident_segment_range.end..incident_segment_range.end,
             Iterator::map(right_segments.iter(), |right_segment| {
                 let upper_region = {
                     let winding = lower_region.winding + right_segment.winding;
                     Region {
-                        is_inside: winding != 0,
+                        is_inside: fill_rule.is_inside(winding),
                         winding,
                     }
                 };
@@ -7,6 +7,7 @@ live_design!{
     import makepad_draw::shader::std::*;
     import crate::drawarc::VectorArc;  
     import crate::drawarc::VectorCornerArc;
+    import crate::bezier::SineWaveBezier;
     import makepad_widgets::vectorline::*;
     
     App = {{App}} {
@@ -39,7 +40,7 @@ live_design!{
                 }
                         
                 graph_tabs = Tabs {
-                    tabs: [arctest, boxtest, linesoverview],
+                    tabs: [arctest, boxtest, linesoverview, beziertest],
                     selected: 1
                 }
 
@@ -56,6 +57,11 @@ live_design!{
                     name: "Box Test"
                     kind: Line3
                 }
+                beziertest = Tab {
+                    name: "Bezier Test"
+                    kind: BezierTest
+                }
+                BezierTest = <SineWaveBezier> {}
                 log_view = Tab {
                     name: ""
                     kind: LogView
@@ -323,6 +329,7 @@ impl LiveRegister for App {
     fn live_register(cx: &mut Cx) {
         crate::makepad_widgets::live_design(cx);
         crate::drawarc::live_design(cx);
+        crate::bezier::live_design(cx);
     }
 }
 
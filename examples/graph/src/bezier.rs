@@ -0,0 +1,75 @@
+use crate::{makepad_draw::*, makepad_widgets::*};
+
+live_design! {
+    SineWaveBezier = {{SineWaveBezier}} {
+        width: Fill,
+        height: Fill,
+        periods: 3.0,
+        segments_per_period: 4,
+        line_width: 3.0,
+        color: #0ff
+        color_end: #f0f
+    }
+}
+
+/// Draws a sine wave out of Bézier segments, with an arc marking each crest.
+#[derive(Live, LiveHook, Widget)]
+pub struct SineWaveBezier {
+    #[walk] walk: Walk,
+    #[live] draw_line: DrawLine,
+    #[redraw] #[rust] area: Area,
+    #[live(3.0)] periods: f64,
+    #[live(4)] segments_per_period: usize,
+    #[live(3.0)] line_width: f64,
+    #[live] color: Vec4,
+    #[live] color_end: Vec4,
+}
+
+impl Widget for SineWaveBezier {
+    fn handle_event(&mut self, _cx: &mut Cx, _event: &Event, _scope: &mut Scope) {
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        let rect = cx.walk_turtle_with_area(&mut self.area, walk);
+        let amplitude = rect.size.y * 0.4;
+        let mid_y = rect.pos.y + rect.size.y * 0.5;
+        let segments = ((self.periods * self.segments_per_period as f64).ceil() as usize).max(1);
+        let dx = rect.size.x / segments as f64;
+        let phase_per_x = self.periods * 2.0 * std::f64::consts::PI / rect.size.x;
+        let brush = Brush::linear(vec2(0.0, 0.0), vec2(1.0, 0.0), vec![
+            GradientStop::new(0.0, self.color),
+            GradientStop::new(1.0, self.color_end),
+        ]);
+
+        // each segment is the Hermite interpolation of the sine, converted to Bézier form
+        let point = | x: f64 | dvec2(rect.pos.x + x, mid_y - amplitude * (x * phase_per_x).sin());
+        let slope = | x: f64 | -amplitude * phase_per_x * (x * phase_per_x).cos();
+        // the segments each span a slice of the gradient, so it has to be evaluated per segment
+        for i in 0..segments {
+            let x0 = i as f64 * dx;
+            let x1 = x0 + dx;
+            let p0 = point(x0);
+            let p3 = point(x1);
+            let cp1 = p0 + dvec2(dx, slope(x0) * dx) / 3.0;
+            let cp2 = p3 - dvec2(dx, slope(x1) * dx) / 3.0;
+            let t0 = x0 / rect.size.x;
+            let t1 = x1 / rect.size.x;
+            let segment_brush = Brush::linear(vec2(0.0, 0.0), vec2(1.0, 0.0), vec![
+                GradientStop::new(0.0, brush.color_at(dvec2(t0, 0.0))),
+                GradientStop::new(1.0, brush.color_at(dvec2(t1, 0.0))),
+            ]);
+            self.draw_line.draw_bezier_abs(cx, p0, cp1, cp2, p3, self.line_width, &segment_brush);
+        }
+
+        for crest in 0..self.periods.ceil() as usize {
+            let x = (crest as f64 + 0.25) * 2.0 * std::f64::consts::PI / phase_per_x;
+            if x > rect.size.x {
+                break
+            }
+            let center = point(x);
+            let crest_brush = Brush::Solid(brush.color_at(dvec2(x / rect.size.x, 0.0)));
+            self.draw_line.draw_arc_abs(cx, center, 8.0, 0.0, 2.0 * std::f64::consts::PI, 1.5, &crest_brush);
+        }
+        DrawStep::done()
+    }
+}
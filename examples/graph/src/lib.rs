@@ -6,4 +6,5 @@ pub use makepad_widgets::makepad_micro_serde;
 pub use makepad_widgets::makepad_live_id;
 pub mod candlestick;
 pub mod drawarc;
+pub mod bezier;
 pub mod app;
@@ -50,6 +50,7 @@ live_design!{
     
     SdxlDropDown = <DropDown> {
         width: Fit
+        selected_item: 0
         padding: {top: (SSPACING_2), right: (SSPACING_4), bottom: (SSPACING_2), left: (SSPACING_2)}
         
         draw_text: {
@@ -61,6 +61,47 @@ impl Rect {
         )
     }
     
+    /// The smallest rect holding both rects.
+    pub fn union(&self, other: Rect) -> Rect {
+        let x1 = self.pos.x.min(other.pos.x);
+        let y1 = self.pos.y.min(other.pos.y);
+        let x2 = (self.pos.x + self.size.x).max(other.pos.x + other.size.x);
+        let y2 = (self.pos.y + self.size.y).max(other.pos.y + other.size.y);
+        Rect {pos: dvec2(x1, y1), size: dvec2(x2 - x1, y2 - y1)}
+    }
+    
+    /// The area both rects cover, `None` where `intersects` is false. Rects that only touch
+    /// give an empty rect along the shared edge.
+    pub fn intersection(&self, other: Rect) -> Option<Rect> {
+        let x1 = self.pos.x.max(other.pos.x);
+        let y1 = self.pos.y.max(other.pos.y);
+        let x2 = (self.pos.x + self.size.x).min(other.pos.x + other.size.x);
+        let y2 = (self.pos.y + self.size.y).min(other.pos.y + other.size.y);
+        if x1 > x2 || y1 > y2 {
+            return None
+        }
+        Some(Rect {pos: dvec2(x1, y1), size: dvec2(x2 - x1, y2 - y1)})
+    }
+    
+    pub fn contains_rect(&self, other: Rect) -> bool {
+        other.pos.x >= self.pos.x && other.pos.x + other.size.x <= self.pos.x + self.size.x &&
+        other.pos.y >= self.pos.y && other.pos.y + other.size.y <= self.pos.y + self.size.y
+    }
+    
+    /// Shrinks the rect by `amount` on every side, down to an empty rect at the center.
+    pub fn inset(self, amount: f64) -> Rect {
+        let size = dvec2(
+            (self.size.x - 2.0 * amount).max(0.0),
+            (self.size.y - 2.0 * amount).max(0.0)
+        );
+        Rect {pos: self.center() - size * 0.5, size}
+    }
+    
+    /// Grows the rect by `amount` on every side.
+    pub fn outset(self, amount: f64) -> Rect {
+        self.inset(-amount)
+    }
+    
     pub fn add_margin(self, size: DVec2) -> Rect {
         Rect {pos: self.pos - size, size: self.size + 2.0 * size}
     }
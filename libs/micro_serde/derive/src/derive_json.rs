@@ -1,6 +1,45 @@
 use proc_macro::TokenStream;
 use makepad_micro_proc_macro::*;
 
+/// The type named by `#[nserde(proxy = "Type")]` on a field, which is (de)serialized in its place.
+fn field_proxy(field: &StructField) -> Result<Option<String>, TokenStream> {
+    for attr in &field.attrs{
+        if attr.name != "nserde"{
+            continue
+        }
+        let mut parser = TokenParser::new(attr.args.clone().unwrap_or_default());
+        if parser.eat_ident("proxy") && parser.eat_punct_alone('='){
+            if let Some(proxy) = parser.eat_literal(){
+                let proxy = proxy.to_string();
+                if let Some(proxy) = proxy.strip_prefix('"').and_then(|proxy| proxy.strip_suffix('"')){
+                    if parser.eat_eot(){
+                        return Ok(Some(proxy.to_string()))
+                    }
+                }
+            }
+        }
+        return Err(error(&format!("Expected #[nserde(proxy = \"Type\")] on field {}", field.name)))
+    }
+    Ok(None)
+}
+
+/// The deserialized value of `field` wrapped in `Some`, converted from its proxy if it has one.
+fn de_field_value(tb: &mut TokenBuilder, field: &StructField) -> Result<(), TokenStream> {
+    let is_option = field.ty.clone().into_iter().next().unwrap().to_string() == "Option";
+    match field_proxy(field)?{
+        Some(proxy) if is_option => {
+            tb.add("Some ( < Option <").add(&proxy).add("> as DeJson > :: de_json ( s , i ) ? . map ( From :: from ) )");
+        }
+        Some(proxy) => {
+            tb.add("Some ( From :: from ( <").add(&proxy).add("as DeJson > :: de_json ( s , i ) ? ) )");
+        }
+        None => {
+            tb.add("Some ( DeJson :: de_json ( s , i ) ? )");
+        }
+    }
+    Ok(())
+}
+
 pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
 
     let mut parser = TokenParser::new(input);
@@ -34,10 +73,19 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
                 tb.add("s . st_pre ( ) ;");
                 // named struct
                 for (i, field) in fields.into_iter().enumerate() {
+                    let proxy = match field_proxy(&field){
+                        Ok(proxy) => proxy,
+                        Err(err) => return err
+                    };
                     if field.ty.into_iter().next().unwrap().to_string() == "Option"{
                         tb.add("if let Some ( t ) = ").add("& self .").ident(&field.name).add("{");
                         tb.add("s . field ( d + 1 ,").string(&field.name).add(") ;");
-                        tb.add("t . ser_json ( d + 1 , s ) ;");
+                        if let Some(proxy) = &proxy{
+                            tb.add("<").add(proxy).add("> :: from ( t ) . ser_json ( d + 1 , s ) ;");
+                        }
+                        else{
+                            tb.add("t . ser_json ( d + 1 , s ) ;");
+                        }
                         if i != fields_len - 1 {
                             tb.add("s . conl ( ) ;");
                         }
@@ -45,7 +93,12 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
                     }
                     else{
                         tb.add("s . field ( d + 1 ,").string(&field.name).add(" ) ;");
-                        tb.add("self .").ident(&field.name).add(". ser_json ( d + 1 , s ) ;");
+                        if let Some(proxy) = &proxy{
+                            tb.add("<").add(proxy).add("> :: from ( & self .").ident(&field.name).add(") . ser_json ( d + 1 , s ) ;");
+                        }
+                        else{
+                            tb.add("self .").ident(&field.name).add(". ser_json ( d + 1 , s ) ;");
+                        }
                         if i != fields_len - 1 {
                             tb.add("s . conl ( ) ;");
                         }
@@ -113,10 +166,19 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
                         let fields_len = fields.len();
                         for (i, field) in fields.into_iter().enumerate() {
                             let field_strip = if let Some(v) = field.name.strip_prefix("_"){v}else{&field.name};
+                            let proxy = match field_proxy(&field){
+                                Ok(proxy) => proxy,
+                                Err(err) => return err
+                            };
                             if field.ty.into_iter().next().unwrap().to_string() == "Option"{
                                 tb.add("if let Some ( t ) = ").ident(&field.name).add("{");
                                 tb.add("s . field ( d + 1 ,").string(&field_strip).add(") ;");
-                                tb.add("t . ser_json ( d + 1 , s ) ;");
+                                if let Some(proxy) = &proxy{
+                                    tb.add("<").add(proxy).add("> :: from ( t ) . ser_json ( d + 1 , s ) ;");
+                                }
+                                else{
+                                    tb.add("t . ser_json ( d + 1 , s ) ;");
+                                }
                                 if i != fields_len - 1 {
                                     tb.add("s . conl ( ) ;");
                                 }
@@ -124,7 +186,12 @@ pub fn derive_ser_json_impl(input: TokenStream) -> TokenStream {
                             }
                             else{
                                 tb.add("s . field ( d + 1 ,").string(&field_strip).add(" ) ;");
-                                tb.ident(&field.name).add(". ser_json ( d + 1 , s ) ;");
+                                if let Some(proxy) = &proxy{
+                                    tb.add("<").add(proxy).add("> :: from (").ident(&field.name).add(") . ser_json ( d + 1 , s ) ;");
+                                }
+                                else{
+                                    tb.ident(&field.name).add(". ser_json ( d + 1 , s ) ;");
+                                }
                                 if i != fields_len - 1 {
                                     tb.add("s . conl ( ) ;");
                                 }
@@ -193,7 +260,11 @@ pub fn derive_de_json_impl(input: TokenStream) -> TokenStream {
                 for field in &fields{
                     let field_strip = if let Some(v) = field.name.strip_prefix("_"){v}else{&field.name};
                     tb.string(&field_strip).add("=> { s . next_colon ( i ) ? ;");
-                    tb.ident(&format!("_{}",field.name)).add("= Some (DeJson :: de_json ( s , i ) ? ) ; } ,");
+                    tb.ident(&format!("_{}",field.name)).add("=");
+                    if let Err(err) = de_field_value(&mut tb, field){
+                        return err
+                    }
+                    tb.add("; } ,");
                 }
                 tb.add("_ => return std :: result :: Result :: Err ( s . err_exp ( & s . strbuf ) )");
                 tb.add("} ; s . eat_comma_curly ( i ) ? ;");
@@ -263,7 +334,11 @@ pub fn derive_de_json_impl(input: TokenStream) -> TokenStream {
                         tb.add("match s . strbuf . as_ref ( ) {");
                         for field in &fields{
                             tb.string(&field.name).add("=> { s . next_colon ( i ) ? ;");
-                            tb.ident(&format!("_{}",field.name)).add("= Some (DeJson :: de_json ( s , i ) ? ) ; } ,");
+                            tb.ident(&format!("_{}",field.name)).add("=");
+                            if let Err(err) = de_field_value(&mut tb, field){
+                                return err
+                            }
+                            tb.add("; } ,");
                         }
                         tb.add("_ => return std :: result :: Result :: Err ( s . err_exp ( & s . strbuf ) )");
                         tb.add("} s . eat_comma_curly ( i ) ? ;");
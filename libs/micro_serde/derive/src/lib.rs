@@ -20,12 +20,18 @@ pub fn derive_de_bin(input: TokenStream) -> TokenStream {
     derive_de_bin_impl(input)
 }
 
-#[proc_macro_derive(SerJson)]
+/// A field of a type without `SerJson` can be written through a proxy type that has it with
+/// `#[nserde(proxy = "MyProxy")]`, where `MyProxy` implements `From<&T>` for the field type
+/// `T`. The proxy needs to derive `SerJson` as well as `DeJson`.
+#[proc_macro_derive(SerJson, attributes(nserde))]
 pub fn derive_ser_json(input: TokenStream) -> TokenStream {
     derive_ser_json_impl(input)
 }
 
-#[proc_macro_derive(DeJson)]
+/// A field of a type without `DeJson` can be read through a proxy type that has it with
+/// `#[nserde(proxy = "MyProxy")]`, where the field type implements `From<MyProxy>`. The proxy
+/// needs to derive `SerJson` as well as `DeJson`.
+#[proc_macro_derive(DeJson, attributes(nserde))]
 pub fn derive_de_json(input: TokenStream) -> TokenStream {
     derive_de_json_impl(input)
 }
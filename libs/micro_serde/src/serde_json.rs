@@ -1,16 +1,49 @@
-use std::collections::{HashMap};
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
 use std::str::Chars;
 
 pub struct SerJsonState {
-    pub out: String
+    pub out: String,
+    pretty_indent: Option<String>,
+    float_precision: Option<usize>,
 }
 
 impl SerJsonState {
-    pub fn indent(&mut self, _d: usize) {
-        //for _ in 0..d {
-        //    self.out.push_str("    ");
-        //}
+    /// Writes compact JSON without any whitespace.
+    pub fn new() -> Self {
+        Self {
+            out: String::new(),
+            pretty_indent: None,
+            float_precision: None,
+        }
+    }
+    
+    /// Writes every field and array item on a line of its own, indented with `indent` per level.
+    pub fn pretty(indent: &str) -> Self {
+        Self {
+            out: String::new(),
+            pretty_indent: Some(indent.to_string()),
+            float_precision: None,
+        }
+    }
+    
+    /// Rounds floats to `digits` decimal places, or with `None` writes as many digits as it
+    /// takes to read the same value back, which is the default.
+    pub fn set_float_precision(&mut self, digits: Option<usize>) {
+        self.float_precision = digits;
+    }
+    
+    pub fn float_precision(&self) -> Option<usize> {
+        self.float_precision
+    }
+    
+    pub fn indent(&mut self, d: usize) {
+        if let Some(indent) = &self.pretty_indent {
+            self.out.push('\n');
+            for _ in 0..d {
+                self.out.push_str(indent);
+            }
+        }
     }
     
     pub fn field(&mut self, d: usize, field: &str) {
@@ -19,6 +52,9 @@ impl SerJsonState {
         self.out.push_str(field);
         self.out.push('"');
         self.out.push(':');
+        if self.pretty_indent.is_some() {
+            self.out.push(' ');
+        }
     }
     
     pub fn label(&mut self, label:&str){
@@ -36,18 +72,35 @@ impl SerJsonState {
     }
     
     pub fn st_post(&mut self, d: usize) {
-        self.indent(d);
+        // a skipped None in the last fields leaves the comma of the field before it
+        if self.out.ends_with(',') {
+            self.out.pop();
+        }
+        if !self.out.ends_with('{') {
+            self.indent(d);
+        }
         self.out.push('}');
     }
     
 }
 
+impl Default for SerJsonState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serializes `value` as pretty printed JSON, see `SerJsonState::pretty`.
+pub fn ser_json_pretty(value: &impl SerJson, indent: &str) -> String {
+    let mut s = SerJsonState::pretty(indent);
+    value.ser_json(0, &mut s);
+    s.out
+}
+
 pub trait SerJson {
     
     fn serialize_json(&self) -> String {
-        let mut s = SerJsonState {
-            out: String::new()
-        };
+        let mut s = SerJsonState::new();
         self.ser_json(0, &mut s);
         s.out
     }
@@ -526,7 +579,16 @@ macro_rules!impl_ser_de_json_float {
     ( $ ty: ident) => {
         impl SerJson for $ ty {
             fn ser_json(&self, _d: usize, s: &mut SerJsonState) {
-                s.out.push_str(&self.to_string());
+                if !self.is_finite() {
+                    // JSON has no NaN or infinity, so these are written as null
+                    s.out.push_str("null");
+                    return
+                }
+                match s.float_precision() {
+                    // Display writes the shortest digits that read back as the same value
+                    None => s.out.push_str(&self.to_string()),
+                    Some(digits) => s.out.push_str(&format!("{:.*}", digits, self)),
+                }
             }
         }
         
@@ -633,6 +695,7 @@ impl<T> SerJson for Vec<T> where T: SerJson {
                     s.out.push(',');
                 }
             }
+            s.indent(d);
         }
         s.out.push(']');
     }
@@ -776,12 +839,16 @@ impl DeJson for JsonValue{
 impl<T> SerJson for [T] where T: SerJson {
     fn ser_json(&self, d: usize, s: &mut SerJsonState) {
         s.out.push('[');
-        let last = self.len() -1;
-        for (index,item) in self.iter().enumerate() {
-            item.ser_json(d + 1, s);
-            if index != last{
-                s.out.push(',');
+        if !self.is_empty(){
+            let last = self.len() -1;
+            for (index,item) in self.iter().enumerate() {
+                s.indent(d + 1);
+                item.ser_json(d + 1, s);
+                if index != last{
+                    s.out.push(',');
+                }
             }
+            s.indent(d);
         }
         s.out.push(']');
     }
@@ -890,39 +957,96 @@ D: DeJson {
     }
 }
 
-impl<K, V> SerJson for HashMap<K, V> where K: SerJson,
-V: SerJson {
-    fn ser_json(&self, d: usize, s: &mut SerJsonState) {
-        s.out.push('{');
-        let last = self.len() - 1;
-        for (index, (k, v)) in self.iter().enumerate() {
-            s.indent(d + 1);
+// Maps with keys that serialize to strings, like String, are written as objects. Other keys
+// cannot be object keys in JSON, so those maps are written as arrays of [key, value] pairs.
+fn ser_json_map<'a, K, V>(mut iter: impl ExactSizeIterator<Item = (&'a K, &'a V)>, d: usize, s: &mut SerJsonState) where K: SerJson + 'a,
+V: SerJson + 'a {
+    let Some((first_k, first_v)) = iter.next() else {
+        s.out.push_str("{}");
+        return
+    };
+    let mut first_key = SerJsonState::new();
+    first_k.ser_json(d + 1, &mut first_key);
+    let is_object = first_key.out.starts_with('"');
+    s.out.push(if is_object {'{'} else {'['});
+    for (index, (k, v)) in std::iter::once((first_k, first_v)).chain(iter).enumerate() {
+        if index != 0 {
+            s.conl();
+        }
+        s.indent(d + 1);
+        if is_object {
             k.ser_json(d + 1, s);
             s.out.push(':');
-            v.ser_json(d + 1, s);
-            if index != last{
-                s.conl();
+            if s.pretty_indent.is_some() {
+                s.out.push(' ');
             }
+            v.ser_json(d + 1, s);
+        }
+        else {
+            s.out.push('[');
+            k.ser_json(d + 1, s);
+            s.out.push(',');
+            v.ser_json(d + 1, s);
+            s.out.push(']');
         }
-        s.indent(d);
-        s.out.push('}');
+    }
+    s.indent(d);
+    s.out.push(if is_object {'}'} else {']'});
+}
+
+// Reads both forms `ser_json_map` writes.
+fn de_json_map<K, V>(s: &mut DeJsonState, i: &mut Chars, mut insert: impl FnMut(K, V)) -> Result<(), DeJsonErr> where K: DeJson,
+V: DeJson {
+    if s.tok == DeJsonTok::BlockOpen {
+        s.block_open(i) ?;
+        while s.tok != DeJsonTok::BlockClose {
+            let (k, v) = DeJson::de_json(s, i) ?;
+            s.eat_comma_block(i) ?;
+            insert(k, v);
+        }
+        return s.block_close(i)
+    }
+    s.curly_open(i) ?;
+    while s.tok != DeJsonTok::CurlyClose {
+        let k = DeJson::de_json(s, i) ?;
+        s.colon(i) ?;
+        let v = DeJson::de_json(s, i) ?;
+        s.eat_comma_curly(i) ?;
+        insert(k, v);
+    }
+    s.curly_close(i)
+}
+
+impl<K, V> SerJson for HashMap<K, V> where K: SerJson,
+V: SerJson {
+    fn ser_json(&self, d: usize, s: &mut SerJsonState) {
+        ser_json_map(self.iter(), d, s)
     }
 }
 
 impl<K, V> DeJson for HashMap<K, V> where K: DeJson + Eq + Hash,
-V: DeJson  {
+V: DeJson {
     fn de_json(s: &mut DeJsonState, i: &mut Chars) -> Result<Self,
     DeJsonErr> {
         let mut h = HashMap::new();
-        s.curly_open(i) ?;
-        while s.tok != DeJsonTok::CurlyClose {
-            let k = DeJson::de_json(s, i) ?;
-            s.colon(i) ?;
-            let v = DeJson::de_json(s, i) ?;
-            s.eat_comma_curly(i) ?;
-            h.insert(k, v);
-        }
-        s.curly_close(i) ?;
+        de_json_map(s, i, | k, v | {h.insert(k, v);}) ?;
+        Ok(h)
+    }
+}
+
+impl<K, V> SerJson for BTreeMap<K, V> where K: SerJson,
+V: SerJson {
+    fn ser_json(&self, d: usize, s: &mut SerJsonState) {
+        ser_json_map(self.iter(), d, s)
+    }
+}
+
+impl<K, V> DeJson for BTreeMap<K, V> where K: DeJson + Ord,
+V: DeJson {
+    fn de_json(s: &mut DeJsonState, i: &mut Chars) -> Result<Self,
+    DeJsonErr> {
+        let mut h = BTreeMap::new();
+        de_json_map(s, i, | k, v | {h.insert(k, v);}) ?;
         Ok(h)
     }
 }
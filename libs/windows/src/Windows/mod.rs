@@ -308,6 +308,8 @@ where
 
 pub const GWL_EXSTYLE: WINDOW_LONG_PTR_INDEX = WINDOW_LONG_PTR_INDEX(-20i32);
 
+pub const GWL_STYLE: WINDOW_LONG_PTR_INDEX = WINDOW_LONG_PTR_INDEX(-16i32);
+
 pub const HWND_TOPMOST: super::super::Foundation::HWND = super::super::Foundation::HWND(-1i32 as _);
 
 pub const HWND_NOTOPMOST: super::super::Foundation::HWND = super::super::Foundation::HWND(-2i32 as _);
@@ -350,6 +352,12 @@ pub const SWP_NOMOVE: SET_WINDOW_POS_FLAGS = SET_WINDOW_POS_FLAGS(2u32);
 
 pub const SWP_NOSIZE: SET_WINDOW_POS_FLAGS = SET_WINDOW_POS_FLAGS(1u32);
 
+pub const SWP_NOZORDER: SET_WINDOW_POS_FLAGS = SET_WINDOW_POS_FLAGS(4u32);
+
+pub const SWP_NOOWNERZORDER: SET_WINDOW_POS_FLAGS = SET_WINDOW_POS_FLAGS(512u32);
+
+pub const SWP_FRAMECHANGED: SET_WINDOW_POS_FLAGS = SET_WINDOW_POS_FLAGS(32u32);
+
 pub const WM_ACTIVATE: u32 = 6u32;
 
 pub const WM_NCCALCSIZE: u32 = 131u32;
@@ -1632,6 +1640,35 @@ where
 
 pub const MONITOR_DEFAULTTONEAREST: MONITOR_FROM_FLAGS = MONITOR_FROM_FLAGS(2u32);
 
+pub unsafe fn GetMonitorInfoW<P0>(hmonitor: P0, lpmi: *mut MONITORINFO) -> super::super::Foundation::BOOL
+where
+    P0: ::windows_core::IntoParam<HMONITOR>,
+{
+    ::windows_targets::link!("user32.dll" "system" fn GetMonitorInfoW(hmonitor : HMONITOR, lpmi : *mut MONITORINFO) -> super::super::Foundation:: BOOL);
+    GetMonitorInfoW(hmonitor.into_param().abi(), lpmi)
+}
+
+#[repr(C)]pub struct MONITORINFO {
+    pub cbSize: u32,
+    pub rcMonitor: super::super::Foundation::RECT,
+    pub rcWork: super::super::Foundation::RECT,
+    pub dwFlags: u32,
+}
+impl ::core::marker::Copy for MONITORINFO {}
+impl ::core::clone::Clone for MONITORINFO {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl ::core::default::Default for MONITORINFO {
+    fn default() -> Self {
+        unsafe { ::core::mem::zeroed() }
+    }
+}
+impl ::windows_core::TypeKind for MONITORINFO {
+    type TypeKind = ::windows_core::CopyType;
+}
+
 pub const LOGPIXELSX: GET_DEVICE_CAPS_INDEX = GET_DEVICE_CAPS_INDEX(88u32);
 
 pub unsafe fn ScreenToClient<P0>(hwnd: P0, lppoint: *mut super::super::Foundation::POINT) -> super::super::Foundation::BOOL
@@ -27,6 +27,9 @@ pub fn derive_live_component_registry_impl(input: TokenStream) -> TokenStream {
             tb.add("    fn get_component_info(&self, name: LiveId) -> Option<LiveComponentInfo> {");
             tb.add("        self.map.values().find( | (info, _) | info.name == name).map( | (info, _) | info.clone())");
             tb.add("    }");
+            tb.add("    fn unregister(&mut self, ty: LiveType) -> Option<LiveComponentInfo> {");
+            tb.add("        self.map.remove(&ty).map( | (info, _) | info)");
+            tb.add("    }");
             tb.add("}");
             
             tb.add("impl ").ident(&struct_name).stream(generic).stream(where_clause).add("{");
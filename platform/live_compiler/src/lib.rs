@@ -42,6 +42,8 @@ pub use {
         live_registry::{
             LiveFileChange,
             LiveRegistry,
+            LiveTypeDep,
+            LiveTypeCycle,
             //LiveDocNodes,
         },
         live_ptr::{
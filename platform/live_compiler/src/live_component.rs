@@ -28,6 +28,7 @@ pub trait LiveComponentRegistry {
     fn get_component_info(&self, name: LiveId) -> Option<LiveComponentInfo>;
     fn component_type(&self) -> LiveId;
     fn get_module_set(&self, set: &mut BTreeSet<LiveModuleId>);
+    fn unregister(&mut self, ty: LiveType) -> Option<LiveComponentInfo>;
 }
 
 #[derive(Default, Clone)]
@@ -50,6 +51,12 @@ impl LiveComponentRegistries {
         Self (Rc::new(RefCell::new(HashMap::new())))
     }
     
+    /// Removes the component type `ty` from the registry holding it, after which it can no
+    /// longer be created from the live design. Returns what it was registered with.
+    pub fn unregister(&self, ty: LiveType) -> Option<LiveComponentInfo> {
+        self.0.borrow_mut().values_mut().find_map( | entry | entry.unregister(ty))
+    }
+    
     pub fn get<T: 'static + LiveComponentRegistry>(&self) -> std::cell::Ref<'_, T> {
         std::cell::Ref::map(
             self.0.borrow(),
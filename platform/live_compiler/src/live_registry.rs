@@ -1,6 +1,6 @@
 //use crate::id::Id;
 use {
-    std::collections::{HashMap, BTreeSet},
+    std::collections::{HashMap, BTreeMap, BTreeSet},
     crate::{
         makepad_live_id::*,
        // makepad_error_log::*,
@@ -46,7 +46,13 @@ pub struct LiveRegistry {
     //pub ignore_no_dsl: HashSet<LiveId>,
     pub main_module: Option<(LiveModuleId, LiveId)>,
     pub components: LiveComponentRegistries,
-    pub package_root: Option<String>
+    pub package_root: Option<String>,
+    /// The types whose live definitions the last `process_file_changes` reloaded and the types
+    /// that have fields of them, each type after the types it depends on. Instances are not
+    /// tracked here, the app re-applies them from the main module on `Event::LiveEdit`.
+    pub reloaded_types: Vec<LiveType>,
+    /// The modules the last `expand_all_documents` expanded, dependencies first.
+    expanded_modules: Vec<LiveModuleId>,
 }
 
 impl Default for LiveRegistry {
@@ -58,7 +64,9 @@ impl Default for LiveRegistry {
             live_files: Vec::new(),
             live_type_infos: HashMap::new(),
             components: LiveComponentRegistries::default(),
-            package_root: None
+            package_root: None,
+            reloaded_types: Vec::new(),
+            expanded_modules: Vec::new(),
         }
     }
 }
@@ -76,6 +84,17 @@ pub enum LiveScopeTarget {
 }
 
 
+/// `from` has a field of type `to`, so reloading `to` has to re-apply `from` as well.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiveTypeDep {
+    pub from: LiveType,
+    pub to: LiveType,
+}
+
+/// Live types that depend on each other in a circle, each one has a field of the next.
+#[derive(Clone, Debug)]
+pub struct LiveTypeCycle(pub Vec<LiveType>);
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct LiveFileChange {
     pub file_name: String,
@@ -209,6 +228,75 @@ impl LiveRegistry {
         None
     }
     
+    pub fn get_type_info(&self, live_type: LiveType) -> Option<&LiveTypeInfo> {
+        self.live_type_infos.get(&live_type)
+    }
+    
+    /// Finds a type by the name of its struct. Types from different modules can share a name,
+    /// then this returns any one of them.
+    pub fn get_type_info_by_id(&self, type_name: LiveId) -> Option<&LiveTypeInfo> {
+        self.live_type_infos.values().find( | info | info.type_name == type_name)
+    }
+    
+    pub fn all_type_infos(&self) -> impl Iterator<Item = &LiveTypeInfo> {
+        self.live_type_infos.values()
+    }
+    
+    /// The dependencies between the registered types, from their fields. Fields of types that
+    /// are not registered, like numbers and strings, are left out.
+    pub fn type_deps(&self) -> Vec<LiveTypeDep> {
+        let mut deps = Vec::new();
+        for info in self.live_type_infos.values() {
+            for field in &info.fields {
+                let to = field.live_type_info.live_type;
+                if self.live_type_infos.contains_key(&to) {
+                    deps.push(LiveTypeDep {from: info.live_type, to});
+                }
+            }
+        }
+        deps
+    }
+    
+    /// The `changed` types and all types that transitively depend on them, every type after
+    /// the types it depends on. Types that depend on each other in a circle are an error.
+    pub fn type_reload_order(&self, changed: &[LiveType]) -> Result<Vec<LiveType>, LiveTypeCycle> {
+        let mut dependents: BTreeMap<LiveType, Vec<LiveType>> = BTreeMap::new();
+        for dep in self.type_deps() {
+            dependents.entry(dep.to).or_default().push(dep.from);
+        }
+        
+        fn visit(
+            current: LiveType,
+            dependents: &BTreeMap<LiveType, Vec<LiveType>>,
+            stack: &mut Vec<LiveType>,
+            done: &mut BTreeSet<LiveType>,
+            post_order: &mut Vec<LiveType>
+        ) -> Result<(), LiveTypeCycle> {
+            if done.contains(&current) {
+                return Ok(())
+            }
+            if let Some(index) = stack.iter().position( | v | *v == current) {
+                return Err(LiveTypeCycle(stack[index..].iter().rev().cloned().collect()))
+            }
+            stack.push(current);
+            for dependent in dependents.get(&current).into_iter().flatten() {
+                visit(*dependent, dependents, stack, done, post_order)?;
+            }
+            stack.pop();
+            done.insert(current);
+            post_order.push(current);
+            Ok(())
+        }
+        
+        let mut done = BTreeSet::new();
+        let mut post_order = Vec::new();
+        for live_type in changed.iter().rev() {
+            visit(*live_type, &dependents, &mut Vec::new(), &mut done, &mut post_order)?;
+        }
+        post_order.reverse();
+        Ok(post_order)
+    }
+    
     pub fn live_node_as_string(&self, node: &LiveNode) -> Option<String> {
         match &node.value {
             LiveValue::Str(v) => {
@@ -567,6 +655,16 @@ impl LiveRegistry {
         if any_changes{
             // try to re-expand
             self.expand_all_documents(errors);
+            let mut reloaded_types = Vec::new();
+            for module_id in &self.expanded_modules {
+                reloaded_types.extend(
+                    self.live_type_infos.values()
+                        .filter( | info | info.module_id == *module_id)
+                        .map( | info | info.live_type)
+                );
+            }
+            // with circular types the order of the modules is the best there is
+            self.reloaded_types = self.type_reload_order(&reloaded_types).unwrap_or(reloaded_types);
         }
     }
 
@@ -727,6 +825,7 @@ impl LiveRegistry {
             }
         }
        
+        self.expanded_modules.clear();
         for module_id in dep_order {
             let file_id = if let Some(file_id) = self.module_id_to_file_id.get(&module_id) {
                 file_id
@@ -756,6 +855,7 @@ impl LiveRegistry {
             
             self.live_files[file_id.to_index()].reexpand = false;
             std::mem::swap(&mut out_doc, &mut self.live_files[file_id.to_index()].expanded);
+            self.expanded_modules.push(module_id);
         }
     }
 }
@@ -1,4 +1,100 @@
+use {
+    std::collections::{BTreeMap, BTreeSet},
+    makepad_live_compiler::{
+        live_component::LiveComponentRegistries,
+        LiveComponentInfo,
+        LiveComponentRegistry,
+        LiveFieldKind,
+        LiveId,
+        LiveModuleId,
+        LiveRegistry,
+        LiveType,
+        LiveTypeField,
+        LiveTypeInfo,
+    },
+};
+
 #[test]
 fn main() {
     // todo :)
-}
\ No newline at end of file
+}
+
+#[derive(Default)]
+struct TestRegistry {
+    map: BTreeMap<LiveType, LiveComponentInfo>,
+}
+
+impl LiveComponentRegistry for TestRegistry {
+    fn ref_cast_type_id(&self) -> LiveType {LiveType::of::<TestRegistry>()}
+    fn component_type(&self) -> LiveId {LiveId::from_str("Test")}
+    fn get_module_set(&self, set: &mut BTreeSet<LiveModuleId>) {
+        self.map.values().for_each( | info | {set.insert(info.module_id);});
+    }
+    fn get_component_info(&self, name: LiveId) -> Option<LiveComponentInfo> {
+        self.map.values().find( | info | info.name == name).cloned()
+    }
+    fn unregister(&mut self, ty: LiveType) -> Option<LiveComponentInfo> {
+        self.map.remove(&ty)
+    }
+}
+
+struct Component;
+
+#[test]
+fn unregister_component() {
+    let registries = LiveComponentRegistries::new();
+    let info = LiveComponentInfo {
+        name: LiveId::from_str("Component"),
+        module_id: LiveModuleId::from_str("test::integration").unwrap(),
+    };
+    registries.get_or_create::<TestRegistry>().map.insert(LiveType::of::<Component>(), info);
+    let test = LiveId::from_str("Test");
+    assert!(registries.find_component(test, LiveId::from_str("Component")).is_some());
+    
+    let info = registries.unregister(LiveType::of::<Component>()).unwrap();
+    assert_eq!(info.name, LiveId::from_str("Component"));
+    assert!(registries.find_component(test, LiveId::from_str("Component")).is_none());
+    assert!(registries.get::<TestRegistry>().map.is_empty());
+    assert!(registries.unregister(LiveType::of::<Component>()).is_none());
+}
+
+struct Theme;
+struct Button;
+struct Toolbar;
+struct Unrelated;
+
+fn type_info<T: 'static>(name: &str, fields: Vec<LiveTypeInfo>) -> LiveTypeInfo {
+    LiveTypeInfo {
+        live_type: LiveType::of::<T>(),
+        type_name: LiveId::from_str(name),
+        module_id: LiveModuleId::from_str("test::integration").unwrap(),
+        live_ignore: false,
+        fields: fields.into_iter().map( | live_type_info | LiveTypeField {
+            id: live_type_info.type_name,
+            live_type_info,
+            live_field_kind: LiveFieldKind::Live,
+        }).collect(),
+    }
+}
+
+#[test]
+fn type_reload_order() {
+    let mut registry = LiveRegistry::default();
+    let theme = type_info::<Theme>("Theme", vec![type_info::<f32>("f32", vec![])]);
+    let button = type_info::<Button>("Button", vec![theme.clone()]);
+    let toolbar = type_info::<Toolbar>("Toolbar", vec![button.clone(), theme.clone()]);
+    for info in [theme, button, toolbar, type_info::<Unrelated>("Unrelated", vec![])] {
+        registry.live_type_infos.insert(info.live_type, info);
+    }
+    assert_eq!(registry.type_deps().len(), 3);
+    
+    let order = registry.type_reload_order(&[LiveType::of::<Theme>()]).unwrap();
+    assert_eq!(order, vec![LiveType::of::<Theme>(), LiveType::of::<Button>(), LiveType::of::<Toolbar>()]);
+    let order = registry.type_reload_order(&[LiveType::of::<Button>()]).unwrap();
+    assert_eq!(order, vec![LiveType::of::<Button>(), LiveType::of::<Toolbar>()]);
+    
+    let theme = type_info::<Theme>("Theme", vec![type_info::<Toolbar>("Toolbar", vec![])]);
+    registry.live_type_infos.insert(theme.live_type, theme);
+    let cycle = registry.type_reload_order(&[LiveType::of::<Button>()]).unwrap_err();
+    assert!(cycle.0.contains(&LiveType::of::<Theme>()));
+}
@@ -0,0 +1,140 @@
+use {
+    std::collections::HashMap,
+    crate::{
+        cx::Cx,
+        cx_api::CxOsOp,
+    },
+};
+
+/// How an announcement competes with what the screen reader is saying.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnnouncePriority {
+    /// Spoken once the current speech finishes.
+    #[default]
+    Polite,
+    /// Interrupts the current speech.
+    Assertive,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AccessibilityRole {
+    Window,
+    #[default]
+    Group,
+    Label,
+    Button,
+    CheckBox,
+    RadioButton,
+    Slider,
+    TextInput,
+    Link,
+    Image,
+    List,
+    ListItem,
+    Menu,
+    MenuItem,
+    Tab,
+    ScrollArea,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibilityNode {
+    pub role: AccessibilityRole,
+    pub label: String,
+    pub value: Option<String>,
+    pub parent: Option<u64>,
+    pub children: Vec<u64>,
+}
+
+/// The widget hierarchy as screen readers see it, keyed by widget id. Widgets describe
+/// themselves while drawing, see `Cx::accessibility_tree_mut`.
+#[derive(Clone, Debug, Default)]
+pub struct AccessibilityTree {
+    nodes: HashMap<u64, AccessibilityNode>,
+    roots: Vec<u64>,
+}
+
+impl AccessibilityTree {
+    /// Adds or updates `widget_id` under `parent`, keeping the children it already had.
+    pub fn insert(&mut self, widget_id: u64, parent: Option<u64>, role: AccessibilityRole, label: &str, value: Option<String>) {
+        let prev_parent = self.nodes.get(&widget_id).and_then( | node | node.parent);
+        if self.nodes.contains_key(&widget_id) && prev_parent != parent {
+            self.detach(widget_id);
+        }
+        let is_new = !self.nodes.contains_key(&widget_id) || prev_parent != parent;
+        let node = self.nodes.entry(widget_id).or_default();
+        node.role = role;
+        node.label.clear();
+        node.label.push_str(label);
+        node.value = value;
+        node.parent = parent;
+        if is_new {
+            match parent.and_then( | parent | self.nodes.get_mut(&parent)) {
+                Some(parent) => parent.children.push(widget_id),
+                None => self.roots.push(widget_id),
+            }
+        }
+    }
+
+    /// Removes `widget_id` along with its descendants.
+    pub fn remove(&mut self, widget_id: u64) {
+        self.detach(widget_id);
+        let mut stack = vec![widget_id];
+        while let Some(widget_id) = stack.pop() {
+            if let Some(node) = self.nodes.remove(&widget_id) {
+                stack.extend(node.children);
+            }
+        }
+    }
+
+    pub fn get(&self, widget_id: u64) -> Option<&AccessibilityNode> {
+        self.nodes.get(&widget_id)
+    }
+
+    pub fn set_value(&mut self, widget_id: u64, value: Option<String>) {
+        if let Some(node) = self.nodes.get_mut(&widget_id) {
+            node.value = value;
+        }
+    }
+
+    pub fn roots(&self) -> &[u64] {
+        &self.roots
+    }
+
+    pub fn children(&self, widget_id: u64) -> &[u64] {
+        self.nodes.get(&widget_id).map_or(&[], | node | node.children.as_slice())
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.roots.clear();
+    }
+
+    fn detach(&mut self, widget_id: u64) {
+        let parent = self.nodes.get(&widget_id).and_then( | node | node.parent);
+        let siblings = match parent.and_then( | parent | self.nodes.get_mut(&parent)) {
+            Some(parent) => &mut parent.children,
+            None => &mut self.roots,
+        };
+        siblings.retain( | id | *id != widget_id);
+    }
+}
+
+impl Cx {
+    /// Has the screen reader speak `message`, for state changes that have no focusable widget
+    /// of their own such as "File saved".
+    pub fn announce(&mut self, message: &str, priority: AnnouncePriority) {
+        self.platform_ops.push(CxOsOp::Announce {
+            message: message.to_string(),
+            priority,
+        });
+    }
+
+    pub fn accessibility_tree(&self) -> &AccessibilityTree {
+        &self.accessibility_tree
+    }
+
+    pub fn accessibility_tree_mut(&mut self) -> &mut AccessibilityTree {
+        &mut self.accessibility_tree
+    }
+}
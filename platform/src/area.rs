@@ -8,6 +8,8 @@ use {
             LiveId,
         },
         draw_list::DrawListId,
+        pass::CxPassParent,
+        window::WindowId,
         cx::Cx
     }
 };
@@ -101,6 +103,18 @@ impl Area {
         }
     }
     
+    /// The window this area is drawn in, found through the pass it is drawn into.
+    pub fn window_id(&self, cx: &Cx) -> Option<WindowId> {
+        let mut pass_id = cx.draw_lists[self.draw_list_id()?].pass_id?;
+        loop {
+            match cx.passes[pass_id].parent {
+                CxPassParent::Window(window_id) => return Some(window_id),
+                CxPassParent::Pass(parent_id) => pass_id = parent_id,
+                CxPassParent::None => return None
+            }
+        }
+    }
+    
     pub fn is_first_instance(&self) -> bool {
         return match self {
             Area::Instance(inst) => {
@@ -0,0 +1,124 @@
+use {
+    std::{
+        collections::HashMap,
+        sync::mpsc::{channel, Receiver, Sender, SendError, TryRecvError},
+    },
+    crate::{
+        cx::Cx,
+        event::Event,
+        thread::{ToUIReceiver, ToUISender},
+    },
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ChannelId(pub u64);
+
+/// The sending side of `Cx::create_channel`, clone it into as many threads as needed.
+pub struct CxSender<T> {
+    channel_id: ChannelId,
+    sender: Sender<T>,
+    messages: ToUISender<ChannelId>,
+}
+
+impl<T> Clone for CxSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            channel_id: self.channel_id,
+            sender: self.sender.clone(),
+            messages: self.messages.clone(),
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for CxSender<T> {}
+
+impl<T> CxSender<T> {
+    /// Queues `value` and wakes the UI thread, which posts `Event::ChannelMessage`.
+    pub fn send(&self, value: T) -> Result<(), SendError<T >> {
+        self.sender.send(value)?;
+        let _ = self.messages.send(self.channel_id);
+        Ok(())
+    }
+}
+
+/// The receiving side of `Cx::create_channel`. Either hand it to `Cx::handle_channel` or
+/// drain it yourself on `Event::ChannelMessage`.
+pub struct CxReceiver<T> {
+    channel_id: ChannelId,
+    receiver: Receiver<T>,
+}
+
+impl<T> CxReceiver<T> {
+    pub fn channel_id(&self) -> ChannelId {
+        self.channel_id
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.receiver.try_recv()
+    }
+}
+
+#[derive(Default)]
+pub struct CxChannels {
+    messages: ToUIReceiver<ChannelId>,
+    handlers: HashMap<ChannelId, Box<dyn FnMut(&mut Cx)>>,
+    last_id: u64,
+}
+
+impl Cx {
+    /// A channel for sending values from background threads to the UI thread, this is the way
+    /// to report back from threads that are not started with `spawn_task`.
+    pub fn create_channel<T: Send + 'static>(&mut self) -> (CxSender<T>, CxReceiver<T>) {
+        self.channels.last_id += 1;
+        let channel_id = ChannelId(self.channels.last_id);
+        let (sender, receiver) = channel();
+        (
+            CxSender {
+                channel_id,
+                sender,
+                messages: self.channels.messages.sender(),
+            },
+            CxReceiver {
+                channel_id,
+                receiver,
+            }
+        )
+    }
+
+    /// Calls `handler` on the UI thread for every value sent to the channel of `receiver`,
+    /// including the ones already waiting.
+    pub fn handle_channel<T: 'static>(&mut self, receiver: CxReceiver<T>, handler: impl Fn(&mut Cx, T) + 'static) {
+        let channel_id = receiver.channel_id;
+        self.channels.handlers.insert(channel_id, Box::new(move | cx | {
+            while let Ok(value) = receiver.try_recv() {
+                handler(cx, value);
+            }
+        }));
+        self.drain_channel(channel_id);
+    }
+
+    /// Stops handling the channel, the senders can still send but nothing is received anymore.
+    pub fn remove_channel_handler(&mut self, channel_id: ChannelId) {
+        self.channels.handlers.remove(&channel_id);
+    }
+
+    pub (crate) fn handle_channel_messages(&mut self) {
+        let mut channel_ids = Vec::new();
+        while let Ok(channel_id) = self.channels.messages.try_recv() {
+            if !channel_ids.contains(&channel_id) {
+                channel_ids.push(channel_id);
+            }
+        }
+        for channel_id in channel_ids {
+            self.call_event_handler(&Event::ChannelMessage(channel_id));
+        }
+    }
+
+    pub (crate) fn drain_channel(&mut self, channel_id: ChannelId) {
+        // the handler may register other channels, so it is taken out while it runs
+        if let Some(mut handler) = self.channels.handlers.remove(&channel_id) {
+            handler(self);
+            self.channels.handlers.entry(channel_id).or_insert(handler);
+        }
+    }
+}
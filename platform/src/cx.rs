@@ -22,10 +22,23 @@ use {
         os::{CxOs},
         debug::Debug,
         performance_stats::PerformanceStats,
+        accessibility::AccessibilityTree,
+        file_dialogs::CxFileDialogs,
+        task::CxTasks,
+        channel::CxChannels,
+        storage::LocalStorage,
+        net::CxNet,
+        headless::CxHeadless,
+        live_cx::LivePropertyHandler,
         event::{
             DrawEvent,
             CxFingers,
             CxDragDrop,
+            CxKeyMap,
+            CxHitRects,
+            CxGestures,
+            CxClipboard,
+            CxKeyRepeat,
             Event,
             Trigger,
             CxKeyboard,
@@ -83,6 +96,18 @@ pub struct Cx {
     pub fingers: CxFingers,
     pub (crate) ime_area: Area,
     pub (crate) drag_drop: CxDragDrop,
+    pub (crate) key_map: CxKeyMap,
+    pub (crate) hit_rects: CxHitRects,
+    pub (crate) gestures: CxGestures,
+    pub (crate) clipboard: CxClipboard,
+    pub (crate) key_repeat: CxKeyRepeat,
+    pub (crate) accessibility_tree: AccessibilityTree,
+    pub (crate) file_dialogs: CxFileDialogs,
+    pub (crate) tasks: CxTasks,
+    pub (crate) channels: CxChannels,
+    pub (crate) local_storage: LocalStorage,
+    pub (crate) net: CxNet,
+    pub (crate) headless: Option<CxHeadless>,
     
     pub (crate) platform_ops: Vec<CxOsOp>,
     
@@ -98,6 +123,7 @@ pub struct Cx {
 
     pub (crate) live_file_change_receiver: std::sync::mpsc::Receiver<Vec<LiveFileChange>>,
     pub (crate) live_file_change_sender: std::sync::mpsc::Sender<Vec<LiveFileChange >>,
+    pub (crate) property_handlers: Vec<LivePropertyHandler>,
 
     pub shader_registry: ShaderRegistry,
     
@@ -242,6 +268,18 @@ impl Cx {
             keyboard: Default::default(),
             fingers: Default::default(),
             drag_drop: Default::default(),
+            key_map: Default::default(),
+            hit_rects: Default::default(),
+            gestures: Default::default(),
+            clipboard: Default::default(),
+            key_repeat: Default::default(),
+            accessibility_tree: Default::default(),
+            file_dialogs: Default::default(),
+            tasks: Default::default(),
+            channels: Default::default(),
+            local_storage: Default::default(),
+            net: Default::default(),
+            headless: None,
             ime_area: Default::default(),
             platform_ops: Default::default(),
             studio_web_socket: None,
@@ -256,6 +294,7 @@ impl Cx {
             
             live_file_change_receiver: recv,
             live_file_change_sender: send,
+            property_handlers: Vec::new(),
             
             shader_registry: ShaderRegistry::new(),
             
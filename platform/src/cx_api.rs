@@ -1,4 +1,5 @@
-use crate::file_dialogs::FileDialog;
+use crate::file_dialogs::{FileDialog, FileDialogOptions};
+use crate::accessibility::AnnouncePriority;
 
 use {
     crate::{
@@ -30,6 +31,16 @@ pub trait CxOsApi {
         F: FnOnce() + Send + 'static;
 
     fn start_stdin_service(&mut self) {}
+
+    /// Puts `text` on the system clipboard.
+    fn clipboard_set_text(&mut self, _text: &str) {}
+
+    /// Reads the text on the system clipboard. Where the clipboard can only be read
+    /// asynchronously (the web, X11 when another application owns it) this returns `None` and
+    /// sends `Event::ClipboardTextReceived` once the text arrives.
+    fn clipboard_get_text(&mut self) -> Option<String> {
+        None
+    }
     fn pre_start() -> bool {
         false
     }
@@ -82,10 +93,15 @@ pub enum CxOsOp {
     CleanupVideoPlaybackResources(LiveId),
     UpdateVideoSurfaceTexture(LiveId),
     
-    SaveFileDialog(FileDialog),
-    SelectFileDialog(FileDialog),
+    SaveFileDialog(FileDialogOptions),
+    SelectFileDialog(FileDialogOptions),
     SaveFolderDialog(FileDialog),
     SelectFolderDialog(FileDialog),    
+
+    Announce {
+        message: String,
+        priority: AnnouncePriority,
+    },
 }
 
 impl Cx {
@@ -528,11 +544,11 @@ impl Cx {
     }
 
     pub fn open_system_savefile_dialog(&mut self) {
-        self.platform_ops.push(CxOsOp::SaveFileDialog(FileDialog::new()));
+        self.show_file_save_dialog(FileDialogOptions::default());
     }
 
     pub fn open_system_openfile_dialog(&mut self) {
-        self.platform_ops.push(CxOsOp::SelectFileDialog(FileDialog::new()));
+        self.show_file_open_dialog(FileDialogOptions::default());
     }
 
     pub fn open_system_savefolder_dialog(&mut self) {
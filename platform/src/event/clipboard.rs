@@ -0,0 +1,98 @@
+use {
+    crate::{
+        cx::Cx,
+        cx_api::CxOsApi,
+        event::drag_drop::DragData,
+    },
+};
+
+pub const MIME_TEXT_PLAIN: &str = "text/plain";
+pub const MIME_URI_LIST: &str = "text/uri-list";
+
+/// Data in one or more formats, each keyed by its MIME type, for `Cx::clipboard_set`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClipboardData {
+    pub formats: Vec<(String, Vec<u8>)>,
+}
+
+impl ClipboardData {
+    pub fn from_text(text: &str) -> Self {
+        Self::default().with_format(MIME_TEXT_PLAIN, text.as_bytes().to_vec())
+    }
+
+    /// Adds `format`, replacing what was there for it.
+    pub fn with_format(mut self, format: &str, data: Vec<u8>) -> Self {
+        self.set(format, data);
+        self
+    }
+
+    pub fn set(&mut self, format: &str, data: Vec<u8>) {
+        if let Some(entry) = self.formats.iter_mut().find( | (f, _) | f == format) {
+            entry.1 = data;
+        }
+        else {
+            self.formats.push((format.to_string(), data));
+        }
+    }
+
+    pub fn get(&self, format: &str) -> Option<&[u8]> {
+        self.formats.iter().find( | (f, _) | f == format).map( | (_, data) | data.as_slice())
+    }
+
+    /// The plain text format, which is what platforms without custom formats get.
+    pub fn text(&self) -> Option<&str> {
+        self.get(MIME_TEXT_PLAIN).and_then( | data | std::str::from_utf8(data).ok())
+    }
+
+    /// The formats of an in-app drag payload, so drops and pastes can share their code.
+    /// Custom payloads have no formats.
+    pub fn from_drag_data(data: &DragData) -> Self {
+        match data {
+            DragData::Text(text) => Self::from_text(text),
+            DragData::FilePaths(paths) => {
+                let list: Vec<String> = paths.iter().map( | path | format!("file://{}", path.display())).collect();
+                let text: Vec<String> = paths.iter().map( | path | path.display().to_string()).collect();
+                Self::default()
+                    .with_format(MIME_URI_LIST, list.join("\r\n").into_bytes())
+                    .with_format(MIME_TEXT_PLAIN, text.join("\n").into_bytes())
+            }
+            DragData::Custom(_) => Self::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CxClipboard {
+    data: Option<ClipboardData>,
+}
+
+impl Cx {
+    /// Puts all formats of `data` on the clipboard at once. The system clipboard only gets the
+    /// plain text, the other formats are kept by the application for as long as the system
+    /// clipboard still holds that text.
+    pub fn clipboard_set(&mut self, data: ClipboardData) {
+        let text = data.text().unwrap_or("").to_string();
+        self.clipboard_set_text(&text);
+        self.clipboard.data = Some(data);
+    }
+
+    /// The clipboard contents in `format`, falling back to the system clipboard for plain text
+    /// when something else was copied since the last `clipboard_set`.
+    pub fn clipboard_get(&mut self, format: &str) -> Option<Vec<u8>> {
+        let system_text = self.clipboard_get_text();
+        if let Some(data) = &self.clipboard.data {
+            // without a synchronous answer from the system we trust what we set last
+            let current = match &system_text {
+                Some(system_text) => data.text() == Some(system_text.as_str()),
+                None => true
+            };
+            if current {
+                return data.get(format).map( | data | data.to_vec())
+            }
+        }
+        if format == MIME_TEXT_PLAIN {
+            return system_text.map( | text | text.into_bytes())
+        }
+        None
+    }
+}
@@ -1,6 +1,9 @@
 use {
     std::cell::Cell,
     std::rc::Rc,
+    std::any::Any,
+    std::fmt,
+    std::path::PathBuf,
     crate::{
         makepad_live_id::*,
         makepad_math::*,
@@ -11,6 +14,7 @@ use {
         },
         cx::Cx,
         area::Area,
+        window::WindowId,
     },
 };
 
@@ -71,6 +75,52 @@ pub enum DragItem {
     String {value: String, internal_id: Option<LiveId>}
 }
 
+/// The payload of an in-app drag started with `Cx::begin_drag`.
+pub enum DragData {
+    Text(String),
+    FilePaths(Vec<PathBuf>),
+    Custom(Box<dyn Any + Send>),
+}
+
+impl fmt::Debug for DragData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Self::FilePaths(paths) => f.debug_tuple("FilePaths").field(paths).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Sent when an in-app drag is released, the payload stays in `Cx` until a drop target takes it.
+#[derive(Clone, Debug)]
+pub struct AppDropEvent {
+    pub drag_id: u64,
+    pub abs: DVec2,
+    /// The window it was released in, drags can move between the windows of the application.
+    pub window_id: Option<WindowId>,
+}
+
+/// Implemented by widgets that can receive in-app drags, see `Event::drop_target_hits`.
+pub trait DropTarget {
+    fn accepts_drag(&self, data: &DragData) -> bool;
+    fn on_drop(&mut self, cx: &mut Cx, data: DragData, point: DVec2);
+}
+
+pub type DragVisual = Box<dyn Fn(&mut Cx, Rect)>;
+
+struct AppDrag {
+    drag_id: u64,
+    data: DragData,
+    visual: Option<DragVisual>,
+    abs: DVec2,
+}
+
+struct AppDrop {
+    drag_id: u64,
+    data: DragData,
+}
+
 /*
 pub enum HitTouch {
     Single,
@@ -85,6 +135,10 @@ pub enum HitTouch {
 pub struct CxDragDrop {
     drag_area: Area,
     next_drag_area: Area,
+    app_drag: Option<AppDrag>,
+    app_drop: Option<AppDrop>,
+    mouse_abs: DVec2,
+    mouse_window_id: Option<WindowId>,
 }
 
 impl CxDragDrop {
@@ -101,7 +155,110 @@ impl CxDragDrop {
     }
 }
 
+impl Cx {
+    /// Starts an in-app drag, typically from a `MouseDown` or `FingerDown` handler.
+    /// Whilst the drag is active `visual` is called with a rect at the cursor whenever it moves,
+    /// it can use this to position whatever represents the dragged item. Drags only live
+    /// within this application, they can move between its windows but not to other applications.
+    pub fn begin_drag(&mut self, drag_id: u64, data: DragData, visual: DragVisual) {
+        let abs = self.drag_drop.mouse_abs;
+        self.drag_drop.app_drag = Some(AppDrag {
+            drag_id,
+            data,
+            visual: Some(visual),
+            abs,
+        });
+        if let Some(window_id) = self.drag_drop.mouse_window_id {
+            self.app_drag_moved(abs, window_id);
+        }
+    }
+    
+    /// Releases the active in-app drag at the last cursor position, call this on `MouseUp`.
+    /// Drop targets receive the payload from the `Event::AppDrop` that follows.
+    pub fn end_drag(&mut self) {
+        if let Some(drag) = self.drag_drop.app_drag.take() {
+            self.drag_drop.app_drop = Some(AppDrop {
+                drag_id: drag.drag_id,
+                data: drag.data,
+            });
+        }
+    }
+    
+    /// Abandons the active in-app drag without dropping it.
+    pub fn cancel_drag(&mut self) -> Option<DragData> {
+        self.drag_drop.app_drag.take().map( | drag | drag.data)
+    }
+    
+    pub fn is_dragging(&self) -> bool {
+        self.drag_drop.app_drag.is_some()
+    }
+    
+    pub fn drag_id(&self) -> Option<u64> {
+        self.drag_drop.app_drag.as_ref().map( | drag | drag.drag_id)
+    }
+    
+    /// The payload of the active drag, use this to highlight targets that accept it.
+    pub fn drag_data(&self) -> Option<&DragData> {
+        self.drag_drop.app_drag.as_ref().map( | drag | &drag.data)
+    }
+    
+    pub fn drag_position(&self) -> Option<DVec2> {
+        self.drag_drop.app_drag.as_ref().map( | drag | drag.abs)
+    }
+    
+    /// The window the active drag is over, `drag_position` is relative to it.
+    pub fn drag_window_id(&self) -> Option<WindowId> {
+        self.drag_drop.app_drag.as_ref().and(self.drag_drop.mouse_window_id)
+    }
+    
+    pub (crate) fn app_drag_moved(&mut self, abs: DVec2, window_id: WindowId) {
+        self.drag_drop.mouse_abs = abs;
+        self.drag_drop.mouse_window_id = Some(window_id);
+        let Some(drag) = &mut self.drag_drop.app_drag else {return};
+        drag.abs = abs;
+        // the visual is taken out whilst it runs so it can use cx freely
+        if let Some(visual) = drag.visual.take() {
+            visual(self, Rect {pos: abs, size: DVec2::default()});
+            if let Some(drag) = &mut self.drag_drop.app_drag {
+                drag.visual = Some(visual);
+            }
+        }
+    }
+    
+    pub (crate) fn take_app_drop_event(&mut self) -> Option<Event> {
+        let drop = self.drag_drop.app_drop.as_ref() ?;
+        Some(Event::AppDrop(AppDropEvent {
+            drag_id: drop.drag_id,
+            abs: self.drag_drop.mouse_abs,
+            window_id: self.drag_drop.mouse_window_id,
+        }))
+    }
+    
+    pub (crate) fn clear_app_drop(&mut self) {
+        self.drag_drop.app_drop = None;
+    }
+}
+
 impl Event {
+    /// Delivers an in-app drop to `target` if it was released over `area` and the target
+    /// accepts the payload. Returns true when the drop was taken.
+    pub fn drop_target_hits(&self, cx: &mut Cx, area: Area, target: &mut dyn DropTarget) -> bool {
+        let Event::AppDrop(event) = self else {return false};
+        // positions are window relative, so the target has to be in the window of the drop
+        if event.window_id.is_some() && area.window_id(cx) != event.window_id {
+            return false
+        }
+        if !area.clipped_rect(cx).contains(event.abs) {
+            return false
+        }
+        let accepts = cx.drag_drop.app_drop.as_ref().map_or(false, | drop | target.accepts_drag(&drop.data));
+        if !accepts {
+            return false
+        }
+        let drop = cx.drag_drop.app_drop.take().unwrap();
+        target.on_drop(cx, drop.data, event.abs);
+        true
+    }
     
     pub fn drag_hits(&self, cx: &mut Cx, area: Area) -> DragHit {
         self.drag_hits_with_options(cx, area, HitOptions::default())
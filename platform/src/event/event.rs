@@ -14,10 +14,16 @@ use {
             window::*,
             xr::*,
             drag_drop::*,
+            hit_rect::*,
+            gesture::*,
             network::*,
             video_playback::*,
         },
         action::ActionsBuf,
+        file_dialogs::FileDialogResult,
+        task::TaskId,
+        channel::ChannelId,
+        window::WindowId,
         animator::Ease,
         audio::AudioDevicesEvent,
         midi::MidiPortsEvent,
@@ -134,6 +140,8 @@ pub enum Event {
     WindowCloseRequested(WindowCloseRequestedEvent),
     WindowClosed(WindowClosedEvent),
     WindowGeomChange(WindowGeomChangeEvent),
+    WindowFullscreenChanged(WindowFullscreenChangedEvent),
+    DpiChanged(DpiChangedEvent),
     VirtualKeyboard(VirtualKeyboardEvent),
     ClearAtlasses,
 
@@ -141,7 +149,13 @@ pub enum Event {
     MouseMove(MouseMoveEvent),
     MouseUp(MouseUpEvent),
     MouseLeave(MouseLeaveEvent),
+    /// The cursor entered a rect registered with `Cx::register_hit_rect`
+    MouseEnterWidget(MouseHitRectEvent),
+    /// The cursor left a rect registered with `Cx::register_hit_rect`
+    MouseLeaveWidget(MouseHitRectEvent),
     TouchUpdate(TouchUpdateEvent),
+    /// A gesture recognized for a widget, see `Cx::add_gesture_recognizer`
+    Gesture(GestureEvent),
     Scroll(ScrollEvent), // this is the MouseWheel / touch scroll event sent by the OS
 
     Timer(TimerEvent),
@@ -154,12 +168,27 @@ pub enum Event {
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
     TextInput(TextInputEvent),
+    /// An input method started composing text, the committed text arrives with
+    /// `ImeCompositionEnd` rather than as `TextInput`
+    ImeCompositionStart,
+    ImeCompositionUpdate(ImeCompositionEvent),
+    ImeCompositionEnd(ImeCompositionEndEvent),
     TextCopy(TextClipboardEvent),
     TextCut(TextClipboardEvent),
+    /// The text asked for with `Cx::clipboard_get_text` on platforms that read it asynchronously
+    ClipboardTextReceived(String),
+    /// The outcome of `Cx::show_file_open_dialog` or `Cx::show_file_save_dialog`
+    FileDialogResult(FileDialogResult),
+    /// A future from `Cx::spawn_future` can make progress, `Cx` polls it again
+    WakeTask(TaskId),
+    /// Values arrived on a channel from `Cx::create_channel`
+    ChannelMessage(ChannelId),
 
     Drag(DragEvent),
     Drop(DropEvent),
     DragEnd,
+    /// An in-app drag started with `Cx::begin_drag` was released, see `Event::drop_target_hits`
+    AppDrop(AppDropEvent),
 
     Actions(ActionsBuf),
     AudioDevices(AudioDevicesEvent),
@@ -184,6 +213,28 @@ impl Event{
         Self::name_from_u32(self.to_u32())
     }
 
+    /// The window this event happened in, for events that belong to one. Keyboard and text
+    /// events go to the window with the key focus.
+    pub fn window_id(&self)->Option<WindowId>{
+        match self{
+            Self::MouseDown(e)=>Some(e.window_id),
+            Self::MouseMove(e)=>Some(e.window_id),
+            Self::MouseUp(e)=>Some(e.window_id),
+            Self::MouseLeave(e)=>Some(e.window_id),
+            Self::MouseEnterWidget(e)=>Some(e.window_id),
+            Self::MouseLeaveWidget(e)=>Some(e.window_id),
+            Self::Scroll(e)=>Some(e.window_id),
+            Self::TouchUpdate(e)=>Some(e.window_id),
+            Self::WindowDragQuery(e)=>Some(e.window_id),
+            Self::WindowCloseRequested(e)=>Some(e.window_id),
+            Self::WindowClosed(e)=>Some(e.window_id),
+            Self::WindowGeomChange(e)=>Some(e.window_id),
+            Self::WindowFullscreenChanged(e)=>Some(e.window_id),
+            Self::DpiChanged(e)=>Some(e.window_id),
+            _=>None
+        }
+    }
+
     pub fn name_from_u32(v:u32)->&'static str{
         match v{
             1=>"Startup",
@@ -246,6 +297,19 @@ impl Event{
             48=>"MouseLeave",
             49=>"Actions",
             50=>"BackPressed",
+            52=>"AppDrop",
+            53=>"MouseEnterWidget",
+            54=>"MouseLeaveWidget",
+            55=>"Gesture",
+            56=>"ClipboardTextReceived",
+            57=>"ImeCompositionStart",
+            58=>"ImeCompositionUpdate",
+            59=>"ImeCompositionEnd",
+            60=>"FileDialogResult",
+            61=>"WindowFullscreenChanged",
+            62=>"DpiChanged",
+            63=>"WakeTask",
+            64=>"ChannelMessage",
 
             #[cfg(target_arch = "wasm32")]
             51=>"ToWasmMsg",
@@ -315,6 +379,19 @@ impl Event{
             Self::MouseLeave(_)=>48,
             Self::Actions(_)=>49,
             Self::BackPressed=>50,
+            Self::AppDrop(_)=>52,
+            Self::MouseEnterWidget(_)=>53,
+            Self::MouseLeaveWidget(_)=>54,
+            Self::Gesture(_)=>55,
+            Self::ClipboardTextReceived(_)=>56,
+            Self::ImeCompositionStart=>57,
+            Self::ImeCompositionUpdate(_)=>58,
+            Self::ImeCompositionEnd(_)=>59,
+            Self::FileDialogResult(_)=>60,
+            Self::WindowFullscreenChanged(_)=>61,
+            Self::DpiChanged(_)=>62,
+            Self::WakeTask(_)=>63,
+            Self::ChannelMessage(_)=>64,
 
             #[cfg(target_arch = "wasm32")]
             Self::ToWasmMsg(_)=>51,
@@ -330,6 +407,9 @@ pub enum Hit{
     KeyUp(KeyEvent),
     Trigger(TriggerHitEvent),
     TextInput(TextInputEvent),
+    ImeCompositionStart,
+    ImeCompositionUpdate(ImeCompositionEvent),
+    ImeCompositionEnd(ImeCompositionEndEvent),
     TextCopy(TextClipboardEvent),
     TextCut(TextClipboardEvent),
 
@@ -32,7 +32,7 @@ use {
 // Mouse events
 
 
-#[derive(Clone, Copy, Debug, Default, SerBin, DeBin, SerJson, DeJson, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, SerBin, DeBin, SerJson, DeJson, PartialEq, Eq, Hash)]
 pub struct KeyModifiers {
     pub shift: bool,
     pub control: bool,
@@ -149,6 +149,15 @@ impl LiveHook for Margin {
 }
 
 impl Margin {
+    pub fn uniform(v: f64) -> Self {
+        Self {left: v, top: v, right: v, bottom: v}
+    }
+    
+    /// `h` on the left and right, `v` on the top and bottom
+    pub fn axes(h: f64, v: f64) -> Self {
+        Self {left: h, top: v, right: h, bottom: v}
+    }
+    
     pub fn left_top(&self) -> DVec2 {
         dvec2(self.left, self.top)
     }
@@ -656,6 +665,21 @@ impl Event {
                     return Hit::TextInput(ti.clone())
                 }
             },
+            Event::ImeCompositionStart => {
+                if cx.keyboard.has_key_focus(area) {
+                    return Hit::ImeCompositionStart
+                }
+            },
+            Event::ImeCompositionUpdate(e) => {
+                if cx.keyboard.has_key_focus(area) {
+                    return Hit::ImeCompositionUpdate(e.clone())
+                }
+            },
+            Event::ImeCompositionEnd(e) => {
+                if cx.keyboard.has_key_focus(area) {
+                    return Hit::ImeCompositionEnd(e.clone())
+                }
+            },
             Event::TextCopy(tc) => {
                 if cx.keyboard.has_key_focus(area) {
                     return Hit::TextCopy(tc.clone());
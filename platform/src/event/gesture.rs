@@ -0,0 +1,661 @@
+use {
+    std::f64::consts::PI,
+    crate::{
+        makepad_math::*,
+        event::{
+            event::Event,
+            finger::{TouchState, TouchUpdateEvent},
+        },
+        cx::Cx,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchPhase {
+    Began,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+/// A single touch as fed to the gesture recognizers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchEvent {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: DVec2,
+    pub time: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GesturePhase {
+    Began,
+    Changed,
+    Ended,
+    Cancelled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    Tap {position: DVec2},
+    LongPress {position: DVec2},
+    /// `translation` is the movement of the center of the touches since the pan began
+    Pan {phase: GesturePhase, translation: DVec2, position: DVec2},
+    /// `scale` is the distance between the first two touches relative to where it started
+    Pinch {phase: GesturePhase, scale: f64, center: DVec2},
+    /// `angle` is the clockwise rotation of the first two touches in radians
+    Rotation {phase: GesturePhase, angle: f64, center: DVec2},
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GestureKind {
+    Tap,
+    LongPress,
+    Pan,
+    Pinch,
+    Rotation,
+}
+
+/// The state machine of a recognizer. It starts out `Possible` and either fails or, once
+/// recognized, moves through `Began` and `Changed` to `Ended`. Discrete gestures like a tap go
+/// straight to `Ended`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GestureState {
+    Possible,
+    Began,
+    Changed,
+    Ended,
+    Failed,
+}
+
+impl GestureState {
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Began | Self::Changed)
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Ended | Self::Failed)
+    }
+}
+
+/// Sent to the widget the recognizer was added to with `Cx::add_gesture_recognizer`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GestureEvent {
+    pub widget_id: u64,
+    pub gesture: Gesture,
+}
+
+pub trait GestureRecognizer {
+    fn kind(&self) -> GestureKind;
+
+    fn state(&self) -> GestureState;
+
+    /// Feeds a touch that belongs to the widget, returns a gesture when one is recognized or
+    /// progresses.
+    fn touch(&mut self, touch: &TouchEvent) -> Option<Gesture>;
+
+    /// Called as time passes whilst touches are down, for gestures that recognize on a timeout.
+    fn update(&mut self, _time: f64) -> Option<Gesture> {
+        None
+    }
+
+    /// Called when another recognizer of the same widget won.
+    fn fail(&mut self);
+
+    /// Called once all touches are lifted, to get ready for the next gesture.
+    fn reset(&mut self);
+
+    /// Whether this recognizer may stay active whilst a gesture of `other` kind is.
+    fn simultaneous_with(&self, _other: GestureKind) -> bool {
+        false
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct TouchSet {
+    touches: Vec<(u64, DVec2)>,
+}
+
+impl TouchSet {
+    fn apply(&mut self, touch: &TouchEvent) {
+        match touch.phase {
+            TouchPhase::Began => if !self.touches.iter().any( | (id, _) | *id == touch.id) {
+                self.touches.push((touch.id, touch.position));
+            }
+            TouchPhase::Moved => if let Some(entry) = self.touches.iter_mut().find( | (id, _) | *id == touch.id) {
+                entry.1 = touch.position;
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.touches.retain( | (id, _) | *id != touch.id);
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.touches.len()
+    }
+
+    fn centroid(&self) -> DVec2 {
+        if self.touches.is_empty() {
+            return DVec2::default()
+        }
+        let mut sum = DVec2::default();
+        for (_, position) in &self.touches {
+            sum += *position;
+        }
+        sum / self.touches.len() as f64
+    }
+
+    /// The first two touches, with their ids
+    fn pair(&self) -> Option<((u64, u64), DVec2, DVec2)> {
+        match self.touches.as_slice() {
+            [(id_a, a), (id_b, b), ..] => Some(((*id_a, *id_b), *a, *b)),
+            _ => None
+        }
+    }
+
+    fn clear(&mut self) {
+        self.touches.clear();
+    }
+}
+
+/// Recognizes a single finger that is lifted quickly without moving.
+pub struct TapRecognizer {
+    pub max_duration: f64,
+    pub slop: f64,
+    state: GestureState,
+    touch: Option<(u64, DVec2, f64)>,
+}
+
+impl Default for TapRecognizer {
+    fn default() -> Self {
+        Self {
+            max_duration: 0.3,
+            slop: 10.0,
+            state: GestureState::Possible,
+            touch: None,
+        }
+    }
+}
+
+impl GestureRecognizer for TapRecognizer {
+    fn kind(&self) -> GestureKind {GestureKind::Tap}
+
+    fn state(&self) -> GestureState {self.state}
+
+    fn touch(&mut self, touch: &TouchEvent) -> Option<Gesture> {
+        if self.state != GestureState::Possible {
+            return None
+        }
+        match (touch.phase, self.touch) {
+            (TouchPhase::Began, None) => {
+                self.touch = Some((touch.id, touch.position, touch.time));
+            }
+            // a second finger makes it something else
+            (TouchPhase::Began, Some(_)) => self.state = GestureState::Failed,
+            (TouchPhase::Moved, Some((id, start, _))) if id == touch.id => {
+                if (touch.position - start).length() > self.slop {
+                    self.state = GestureState::Failed;
+                }
+            }
+            (TouchPhase::Ended, Some((id, _, start_time))) if id == touch.id => {
+                if touch.time - start_time <= self.max_duration {
+                    self.state = GestureState::Ended;
+                    return Some(Gesture::Tap {position: touch.position})
+                }
+                self.state = GestureState::Failed;
+            }
+            (TouchPhase::Cancelled, _) => self.state = GestureState::Failed,
+            _ => ()
+        }
+        None
+    }
+
+    fn fail(&mut self) {
+        self.state = GestureState::Failed;
+    }
+
+    fn reset(&mut self) {
+        self.state = GestureState::Possible;
+        self.touch = None;
+    }
+}
+
+/// Recognizes a single finger held down without moving for `duration` seconds.
+pub struct LongPressRecognizer {
+    pub duration: f64,
+    pub slop: f64,
+    state: GestureState,
+    touch: Option<(u64, DVec2, f64)>,
+}
+
+impl Default for LongPressRecognizer {
+    fn default() -> Self {
+        Self {
+            duration: 0.5,
+            slop: 10.0,
+            state: GestureState::Possible,
+            touch: None,
+        }
+    }
+}
+
+impl GestureRecognizer for LongPressRecognizer {
+    fn kind(&self) -> GestureKind {GestureKind::LongPress}
+
+    fn state(&self) -> GestureState {self.state}
+
+    fn touch(&mut self, touch: &TouchEvent) -> Option<Gesture> {
+        if self.state != GestureState::Possible {
+            return None
+        }
+        match (touch.phase, self.touch) {
+            (TouchPhase::Began, None) => {
+                self.touch = Some((touch.id, touch.position, touch.time));
+            }
+            (TouchPhase::Began, Some(_)) => self.state = GestureState::Failed,
+            (TouchPhase::Moved, Some((id, start, _))) if id == touch.id => {
+                if (touch.position - start).length() > self.slop {
+                    self.state = GestureState::Failed;
+                }
+            }
+            (TouchPhase::Ended, Some((id, ..))) if id == touch.id => self.state = GestureState::Failed,
+            (TouchPhase::Cancelled, _) => self.state = GestureState::Failed,
+            _ => ()
+        }
+        self.update(touch.time)
+    }
+
+    fn update(&mut self, time: f64) -> Option<Gesture> {
+        if self.state != GestureState::Possible {
+            return None
+        }
+        let (_, position, start_time) = self.touch?;
+        if time - start_time >= self.duration {
+            self.state = GestureState::Ended;
+            return Some(Gesture::LongPress {position})
+        }
+        None
+    }
+
+    fn fail(&mut self) {
+        self.state = GestureState::Failed;
+    }
+
+    fn reset(&mut self) {
+        self.state = GestureState::Possible;
+        self.touch = None;
+    }
+}
+
+/// Recognizes one or more fingers moving together by at least `min_distance`.
+pub struct PanRecognizer {
+    pub min_distance: f64,
+    state: GestureState,
+    touches: TouchSet,
+    origin: DVec2,
+}
+
+impl Default for PanRecognizer {
+    fn default() -> Self {
+        Self {
+            min_distance: 10.0,
+            state: GestureState::Possible,
+            touches: TouchSet::default(),
+            origin: DVec2::default(),
+        }
+    }
+}
+
+impl GestureRecognizer for PanRecognizer {
+    fn kind(&self) -> GestureKind {GestureKind::Pan}
+
+    fn state(&self) -> GestureState {self.state}
+
+    fn touch(&mut self, touch: &TouchEvent) -> Option<Gesture> {
+        if self.state.is_done() {
+            return None
+        }
+        let before = self.touches.centroid();
+        let count_before = self.touches.len();
+        self.touches.apply(touch);
+        if self.touches.len() == 0 {
+            if !self.state.is_active() {
+                return None
+            }
+            let phase = if touch.phase == TouchPhase::Cancelled {GesturePhase::Cancelled} else {GesturePhase::Ended};
+            self.state = GestureState::Ended;
+            return Some(Gesture::Pan {phase, translation: before - self.origin, position: before})
+        }
+        let position = self.touches.centroid();
+        if count_before == 0 {
+            self.origin = position;
+        }
+        else if count_before != self.touches.len() {
+            // fingers coming and going move the center, that isn't a pan
+            self.origin += position - before;
+        }
+        if touch.phase != TouchPhase::Moved {
+            return None
+        }
+        let translation = position - self.origin;
+        match self.state {
+            GestureState::Possible if translation.length() >= self.min_distance => {
+                self.state = GestureState::Began;
+                Some(Gesture::Pan {phase: GesturePhase::Began, translation, position})
+            }
+            GestureState::Began | GestureState::Changed => {
+                self.state = GestureState::Changed;
+                Some(Gesture::Pan {phase: GesturePhase::Changed, translation, position})
+            }
+            _ => None
+        }
+    }
+
+    fn fail(&mut self) {
+        self.state = GestureState::Failed;
+    }
+
+    fn reset(&mut self) {
+        self.state = GestureState::Possible;
+        self.touches.clear();
+    }
+
+    fn simultaneous_with(&self, other: GestureKind) -> bool {
+        matches!(other, GestureKind::Pinch | GestureKind::Rotation)
+    }
+}
+
+/// Recognizes two fingers moving apart or together, once the distance between them changed
+/// by `threshold` relative to where it started.
+pub struct PinchRecognizer {
+    pub threshold: f64,
+    state: GestureState,
+    touches: TouchSet,
+    pair: Option<(u64, u64)>,
+    last_distance: f64,
+    scale: f64,
+    center: DVec2,
+}
+
+impl Default for PinchRecognizer {
+    fn default() -> Self {
+        Self {
+            threshold: 0.05,
+            state: GestureState::Possible,
+            touches: TouchSet::default(),
+            pair: None,
+            last_distance: 0.0,
+            scale: 1.0,
+            center: DVec2::default(),
+        }
+    }
+}
+
+impl GestureRecognizer for PinchRecognizer {
+    fn kind(&self) -> GestureKind {GestureKind::Pinch}
+
+    fn state(&self) -> GestureState {self.state}
+
+    fn touch(&mut self, touch: &TouchEvent) -> Option<Gesture> {
+        if self.state.is_done() {
+            return None
+        }
+        self.touches.apply(touch);
+        let Some((pair, a, b)) = self.touches.pair() else {
+            self.pair = None;
+            if !self.state.is_active() {
+                return None
+            }
+            let phase = if touch.phase == TouchPhase::Cancelled {GesturePhase::Cancelled} else {GesturePhase::Ended};
+            self.state = GestureState::Ended;
+            return Some(Gesture::Pinch {phase, scale: self.scale, center: self.center})
+        };
+        let distance = (b - a).length();
+        self.center = (a + b) / 2.0;
+        if self.pair != Some(pair) {
+            // a different pair of fingers continues from the current scale
+            self.pair = Some(pair);
+            self.last_distance = distance;
+            return None
+        }
+        if self.last_distance > 0.0 {
+            self.scale *= distance / self.last_distance;
+        }
+        self.last_distance = distance;
+        match self.state {
+            GestureState::Possible if (self.scale - 1.0).abs() >= self.threshold => {
+                self.state = GestureState::Began;
+                Some(Gesture::Pinch {phase: GesturePhase::Began, scale: self.scale, center: self.center})
+            }
+            GestureState::Began | GestureState::Changed => {
+                self.state = GestureState::Changed;
+                Some(Gesture::Pinch {phase: GesturePhase::Changed, scale: self.scale, center: self.center})
+            }
+            _ => None
+        }
+    }
+
+    fn fail(&mut self) {
+        self.state = GestureState::Failed;
+    }
+
+    fn reset(&mut self) {
+        *self = Self {threshold: self.threshold, ..Self::default()};
+    }
+
+    fn simultaneous_with(&self, other: GestureKind) -> bool {
+        matches!(other, GestureKind::Pan | GestureKind::Rotation)
+    }
+}
+
+/// Recognizes two fingers turning around each other by at least `threshold` radians.
+pub struct RotationRecognizer {
+    pub threshold: f64,
+    state: GestureState,
+    touches: TouchSet,
+    pair: Option<(u64, u64)>,
+    last_angle: f64,
+    angle: f64,
+    center: DVec2,
+}
+
+impl Default for RotationRecognizer {
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            state: GestureState::Possible,
+            touches: TouchSet::default(),
+            pair: None,
+            last_angle: 0.0,
+            angle: 0.0,
+            center: DVec2::default(),
+        }
+    }
+}
+
+impl GestureRecognizer for RotationRecognizer {
+    fn kind(&self) -> GestureKind {GestureKind::Rotation}
+
+    fn state(&self) -> GestureState {self.state}
+
+    fn touch(&mut self, touch: &TouchEvent) -> Option<Gesture> {
+        if self.state.is_done() {
+            return None
+        }
+        self.touches.apply(touch);
+        let Some((pair, a, b)) = self.touches.pair() else {
+            self.pair = None;
+            if !self.state.is_active() {
+                return None
+            }
+            let phase = if touch.phase == TouchPhase::Cancelled {GesturePhase::Cancelled} else {GesturePhase::Ended};
+            self.state = GestureState::Ended;
+            return Some(Gesture::Rotation {phase, angle: self.angle, center: self.center})
+        };
+        let angle = (b - a).angle_in_radians();
+        self.center = (a + b) / 2.0;
+        if self.pair != Some(pair) {
+            self.pair = Some(pair);
+            self.last_angle = angle;
+            return None
+        }
+        // accumulate the change, wrapped to -PI..PI, so turning past half a circle works
+        self.angle += (angle - self.last_angle + PI).rem_euclid(2.0 * PI) - PI;
+        self.last_angle = angle;
+        match self.state {
+            GestureState::Possible if self.angle.abs() >= self.threshold => {
+                self.state = GestureState::Began;
+                Some(Gesture::Rotation {phase: GesturePhase::Began, angle: self.angle, center: self.center})
+            }
+            GestureState::Began | GestureState::Changed => {
+                self.state = GestureState::Changed;
+                Some(Gesture::Rotation {phase: GesturePhase::Changed, angle: self.angle, center: self.center})
+            }
+            _ => None
+        }
+    }
+
+    fn fail(&mut self) {
+        self.state = GestureState::Failed;
+    }
+
+    fn reset(&mut self) {
+        *self = Self {threshold: self.threshold, ..Self::default()};
+    }
+
+    fn simultaneous_with(&self, other: GestureKind) -> bool {
+        matches!(other, GestureKind::Pan | GestureKind::Pinch)
+    }
+}
+
+struct WidgetGestures {
+    widget_id: u64,
+    touches: Vec<u64>,
+    recognizers: Vec<Box<dyn GestureRecognizer>>,
+}
+
+impl WidgetGestures {
+    fn simultaneous(&self, a: usize, b: usize) -> bool {
+        self.recognizers[a].simultaneous_with(self.recognizers[b].kind())
+            || self.recognizers[b].simultaneous_with(self.recognizers[a].kind())
+    }
+
+    /// Lets the gesture of recognizer `index` through unless a gesture it can't run alongside
+    /// is already in progress. Once through, the recognizers it can't run alongside that are
+    /// still undecided fail. Recognizers added earlier have priority when both recognize on
+    /// the same touch.
+    fn arbitrate(&mut self, index: usize, gesture: Gesture, out: &mut Vec<GestureEvent>) {
+        let blocked = (0..self.recognizers.len()).any( | other | {
+            other != index && self.recognizers[other].state().is_active() && !self.simultaneous(index, other)
+        });
+        if blocked {
+            self.recognizers[index].fail();
+            return
+        }
+        for other in 0..self.recognizers.len() {
+            if other != index && self.recognizers[other].state() == GestureState::Possible && !self.simultaneous(index, other) {
+                self.recognizers[other].fail();
+            }
+        }
+        out.push(GestureEvent {widget_id: self.widget_id, gesture});
+    }
+
+    fn touch(&mut self, touch: &TouchEvent, out: &mut Vec<GestureEvent>) {
+        for index in 0..self.recognizers.len() {
+            if self.recognizers[index].state().is_done() {
+                continue
+            }
+            if let Some(gesture) = self.recognizers[index].touch(touch) {
+                self.arbitrate(index, gesture, out);
+            }
+        }
+    }
+
+    fn update(&mut self, time: f64, out: &mut Vec<GestureEvent>) {
+        for index in 0..self.recognizers.len() {
+            if self.recognizers[index].state().is_done() {
+                continue
+            }
+            if let Some(gesture) = self.recognizers[index].update(time) {
+                self.arbitrate(index, gesture, out);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CxGestures {
+    widgets: Vec<WidgetGestures>,
+}
+
+impl Cx {
+    /// Attaches `recognizer` to `widget_id`. Touches that start within the rect the widget
+    /// registered with `register_hit_rect` are fed to its recognizers, and what they recognize
+    /// is sent as `Event::Gesture`. The order in which recognizers are added is their priority.
+    pub fn add_gesture_recognizer(&mut self, widget_id: u64, recognizer: impl GestureRecognizer + 'static) {
+        let recognizer: Box<dyn GestureRecognizer> = Box::new(recognizer);
+        if let Some(widget) = self.gestures.widgets.iter_mut().find( | widget | widget.widget_id == widget_id) {
+            widget.recognizers.push(recognizer);
+        }
+        else {
+            self.gestures.widgets.push(WidgetGestures {
+                widget_id,
+                touches: Vec::new(),
+                recognizers: vec![recognizer],
+            });
+        }
+    }
+
+    pub fn remove_gesture_recognizers(&mut self, widget_id: u64) {
+        self.gestures.widgets.retain( | widget | widget.widget_id != widget_id);
+    }
+
+    pub (crate) fn handle_touch_gestures(&mut self, e: &TouchUpdateEvent) {
+        if self.gestures.widgets.is_empty() {
+            return
+        }
+        let mut out = Vec::new();
+        for touch in &e.touches {
+            let phase = match touch.state {
+                TouchState::Start => TouchPhase::Began,
+                TouchState::Move => TouchPhase::Moved,
+                TouchState::Stop => TouchPhase::Ended,
+                TouchState::Stable => continue,
+            };
+            let touch = TouchEvent {id: touch.uid, phase, position: touch.abs, time: touch.time};
+            for widget in &mut self.gestures.widgets {
+                if phase == TouchPhase::Began {
+                    if !self.hit_rects.rect(widget.widget_id).is_some_and( | rect | rect.contains(touch.position)) {
+                        continue
+                    }
+                    widget.touches.push(touch.id);
+                }
+                else if !widget.touches.contains(&touch.id) {
+                    continue
+                }
+                widget.touch(&touch, &mut out);
+                if phase == TouchPhase::Ended {
+                    widget.touches.retain( | id | *id != touch.id);
+                    if widget.touches.is_empty() {
+                        for recognizer in &mut widget.recognizers {
+                            recognizer.reset();
+                        }
+                    }
+                }
+            }
+        }
+        self.update_gestures(e.time, out);
+    }
+
+    pub (crate) fn update_gestures(&mut self, time: f64, mut out: Vec<GestureEvent>) {
+        for widget in &mut self.gestures.widgets {
+            if !widget.touches.is_empty() {
+                widget.update(time, &mut out);
+            }
+        }
+        for gesture in out {
+            self.call_event_handler(&Event::Gesture(gesture));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use {
+    std::collections::HashMap,
+    crate::{
+        makepad_math::*,
+        event::{
+            event::Event,
+            finger::{MouseMoveEvent, MouseUpEvent},
+        },
+        window::WindowId,
+        cx::Cx,
+    },
+};
+
+/// Sent when the cursor enters or leaves a rect registered with `Cx::register_hit_rect`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MouseHitRectEvent {
+    pub widget_id: u64,
+    pub abs: DVec2,
+    pub window_id: WindowId,
+}
+
+/// A mouse event for the widget holding the pointer capture, see `Event::captured_pointer`.
+#[derive(Clone, Copy, Debug)]
+pub enum CapturedPointerEvent<'a> {
+    Move(&'a MouseMoveEvent),
+    Up(&'a MouseUpEvent),
+}
+
+#[derive(Default)]
+pub struct CxHitRects {
+    rects: HashMap<u64, Rect>,
+    hovered: Vec<u64>,
+    abs: DVec2,
+    window_id: Option<WindowId>,
+    mouse_inside: bool,
+    captured: Option<u64>,
+}
+
+impl CxHitRects {
+    pub (crate) fn mouse_moved(&mut self, abs: DVec2, window_id: WindowId) {
+        self.abs = abs;
+        self.window_id = Some(window_id);
+        self.mouse_inside = true;
+    }
+
+    pub (crate) fn rect(&self, widget_id: u64) -> Option<Rect> {
+        self.rects.get(&widget_id).copied()
+    }
+
+    pub (crate) fn mouse_left(&mut self) {
+        self.mouse_inside = false;
+    }
+
+    /// Updates the hovered set, returning the events for what changed. Leaves go first.
+    fn diff_hovered(&mut self) -> Vec<Event> {
+        let Some(window_id) = self.window_id else {
+            return Vec::new()
+        };
+        // hovering is frozen whilst a widget holds the pointer
+        if self.captured.is_some() {
+            return Vec::new()
+        }
+        let abs = self.abs;
+        let mut hovered: Vec<u64> = if self.mouse_inside {
+            self.rects.iter()
+                .filter( | (_, rect) | rect.contains(abs))
+                .map( | (widget_id, _) | *widget_id)
+                .collect()
+        }
+        else {
+            Vec::new()
+        };
+        hovered.sort_unstable();
+        let mut events = Vec::new();
+        for widget_id in &self.hovered {
+            if !hovered.contains(widget_id) {
+                events.push(Event::MouseLeaveWidget(MouseHitRectEvent {widget_id: *widget_id, abs, window_id}));
+            }
+        }
+        for widget_id in &hovered {
+            if !self.hovered.contains(widget_id) {
+                events.push(Event::MouseEnterWidget(MouseHitRectEvent {widget_id: *widget_id, abs, window_id}));
+            }
+        }
+        self.hovered = hovered;
+        events
+    }
+}
+
+impl Cx {
+    /// Registers the window relative rect of `widget_id` for `Event::MouseEnterWidget` and
+    /// `Event::MouseLeaveWidget`, call this while drawing the widget. The rect stays registered
+    /// until the widget registers a new one or calls `remove_hit_rect`.
+    pub fn register_hit_rect(&mut self, widget_id: u64, rect: Rect) {
+        self.hit_rects.rects.insert(widget_id, rect);
+    }
+
+    pub fn remove_hit_rect(&mut self, widget_id: u64) {
+        self.hit_rects.rects.remove(&widget_id);
+    }
+
+    pub fn is_hit_rect_hovered(&self, widget_id: u64) -> bool {
+        self.hit_rects.hovered.contains(&widget_id)
+    }
+
+    /// Routes the `MouseMove` and `MouseUp` events to `widget_id` wherever the cursor goes,
+    /// see `Event::captured_pointer`. Call this from a `MouseDown` handler and `release_pointer`
+    /// on `MouseUp`. Returns false if another widget holds the capture. Losing the application
+    /// focus releases the capture.
+    pub fn capture_pointer(&mut self, widget_id: u64) -> bool {
+        match self.hit_rects.captured {
+            Some(captured) if captured != widget_id => false,
+            _ => {
+                self.hit_rects.captured = Some(widget_id);
+                true
+            }
+        }
+    }
+
+    pub fn release_pointer(&mut self) {
+        self.hit_rects.captured = None;
+    }
+
+    pub fn pointer_capture(&self) -> Option<u64> {
+        self.hit_rects.captured
+    }
+
+    /// True if a widget other than `widget_id` holds the pointer, it should then ignore mouse
+    /// movement.
+    pub fn pointer_captured_by_other(&self, widget_id: u64) -> bool {
+        self.hit_rects.captured.is_some_and( | captured | captured != widget_id)
+    }
+
+    pub (crate) fn dispatch_hit_rect_changes(&mut self) {
+        for event in self.hit_rects.diff_hovered() {
+            self.call_event_handler(&event);
+        }
+    }
+}
+
+impl Event {
+    /// The `MouseMove` or `MouseUp` of a pointer captured by `widget_id`, regardless of where
+    /// the cursor is.
+    pub fn captured_pointer(&self, cx: &Cx, widget_id: u64) -> Option<CapturedPointerEvent> {
+        if cx.hit_rects.captured != Some(widget_id) {
+            return None
+        }
+        match self {
+            Event::MouseMove(e) => Some(CapturedPointerEvent::Move(e)),
+            Event::MouseUp(e) => Some(CapturedPointerEvent::Up(e)),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use {
+    std::collections::HashMap,
+    crate::{
+        cx::Cx,
+        event::{
+            finger::KeyModifiers,
+            keyboard::KeyCode,
+        },
+    },
+};
+
+/// Binds a key combination to an application defined action id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub key: KeyCode,
+    pub modifiers: KeyModifiers,
+    pub action_id: u64,
+}
+
+/// Sent as an action whenever a key binding fires or `Cx::dispatch_action` is called.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyBindingAction {
+    pub action_id: u64,
+}
+
+pub type KeyActionHandler = Box<dyn FnMut(&mut Cx)>;
+
+#[derive(Clone, Debug, Default)]
+pub struct KeyMap {
+    bindings: HashMap<(KeyCode, KeyModifiers), u64>,
+}
+
+impl KeyMap {
+    /// Adds `binding`, returning the action id it replaced, if any.
+    pub fn insert(&mut self, binding: KeyBinding) -> Option<u64> {
+        self.bindings.insert((binding.key, binding.modifiers), binding.action_id)
+    }
+
+    pub fn remove(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Option<u64> {
+        self.bindings.remove(&(key, modifiers))
+    }
+
+    pub fn get(&self, key: KeyCode, modifiers: KeyModifiers) -> Option<u64> {
+        self.bindings.get(&(key, modifiers)).copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = KeyBinding> + '_ {
+        self.bindings.iter().map( | ((key, modifiers), action_id) | KeyBinding {
+            key: *key,
+            modifiers: *modifiers,
+            action_id: *action_id,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct CxKeyMap {
+    key_map: KeyMap,
+    handlers: HashMap<u64, Vec<KeyActionHandler >>,
+}
+
+impl CxKeyMap {
+    pub (crate) fn action_for_key(&self, key: KeyCode, modifiers: KeyModifiers) -> Option<u64> {
+        self.key_map.get(key, modifiers)
+    }
+}
+
+impl Cx {
+    /// Adds a keyboard shortcut. A `KeyDown` matching it is consumed before it reaches the
+    /// event handler and `action_id` is dispatched instead, see `dispatch_action`.
+    pub fn register_key_binding(&mut self, binding: KeyBinding) -> Option<u64> {
+        self.key_map.key_map.insert(binding)
+    }
+
+    pub fn unregister_key_binding(&mut self, key: KeyCode, modifiers: KeyModifiers) -> Option<u64> {
+        self.key_map.key_map.remove(key, modifiers)
+    }
+
+    pub fn key_map(&self) -> &KeyMap {
+        &self.key_map.key_map
+    }
+
+    /// Replaces all key bindings at once, for instance with a user configured set.
+    pub fn set_key_map(&mut self, key_map: KeyMap) {
+        self.key_map.key_map = key_map;
+    }
+
+    /// Calls `handler` every time `action_id` is dispatched.
+    pub fn subscribe_action(&mut self, action_id: u64, handler: impl FnMut(&mut Cx) + 'static) {
+        self.key_map.handlers.entry(action_id).or_default().push(Box::new(handler));
+    }
+
+    pub fn unsubscribe_action(&mut self, action_id: u64) {
+        self.key_map.handlers.remove(&action_id);
+    }
+
+    /// Calls the handlers subscribed to `action_id` and sends a `KeyBindingAction`, so widgets
+    /// can also pick it up from `Event::Actions`.
+    pub fn dispatch_action(&mut self, action_id: u64) {
+        if let Some(mut handlers) = self.key_map.handlers.remove(&action_id) {
+            for handler in &mut handlers {
+                handler(self);
+            }
+            // keep handlers subscribed from within a handler
+            if let Some(added) = self.key_map.handlers.remove(&action_id) {
+                handlers.extend(added);
+            }
+            self.key_map.handlers.insert(action_id, handlers);
+        }
+        self.action(KeyBindingAction {action_id});
+    }
+}
@@ -0,0 +1,108 @@
+use {
+    crate::{
+        cx::Cx,
+        event::{
+            event::{Event, Timer},
+            keyboard::{KeyCode, KeyEvent},
+        },
+    },
+};
+
+/// Timing of the software key repeat, see `Cx::set_key_repeat`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyRepeatSettings {
+    pub initial_delay_ms: u64,
+    pub rate_ms: u64,
+}
+
+impl Default for KeyRepeatSettings {
+    fn default() -> Self {
+        Self {
+            initial_delay_ms: 500,
+            rate_ms: 30,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CxKeyRepeat {
+    settings: Option<KeyRepeatSettings>,
+    held: Option<KeyEvent>,
+    timer: Timer,
+    repeating: bool,
+    synthesizing: bool,
+}
+
+impl CxKeyRepeat {
+    fn stop(&mut self) -> Timer {
+        self.held = None;
+        self.repeating = false;
+        std::mem::take(&mut self.timer)
+    }
+}
+
+impl Cx {
+    /// Replaces the key repeat of the OS with our own, so holding a key repeats the same on every
+    /// platform. Synthetic `KeyDown` events with `is_repeat` set start `initial_delay_ms` after the
+    /// key went down and follow every `rate_ms`. `None` goes back to the OS repeat.
+    pub fn set_key_repeat(&mut self, settings: Option<KeyRepeatSettings>) {
+        if self.key_repeat.settings == settings {
+            return
+        }
+        self.key_repeat.settings = settings;
+        let timer = self.key_repeat.stop();
+        self.stop_timer(timer);
+    }
+
+    pub fn key_repeat(&self) -> Option<KeyRepeatSettings> {
+        self.key_repeat.settings
+    }
+
+    /// Tracks the held key and fires the repeats, returns true if `event` is consumed.
+    pub (crate) fn handle_key_repeat(&mut self, event: &Event) -> bool {
+        let Some(settings) = self.key_repeat.settings else {
+            return false
+        };
+        match event {
+            Event::KeyDown(e) => {
+                if e.is_repeat {
+                    // the OS repeat is replaced by ours
+                    return !self.key_repeat.synthesizing
+                }
+                let timer = self.key_repeat.stop();
+                self.stop_timer(timer);
+                if !matches!(e.key_code, KeyCode::Shift | KeyCode::Control | KeyCode::Alt | KeyCode::Logo) {
+                    self.key_repeat.held = Some(*e);
+                    self.key_repeat.timer = self.start_timeout(settings.initial_delay_ms as f64 / 1000.0);
+                }
+            }
+            Event::KeyUp(e) => if self.key_repeat.held.is_some_and( | held | held.key_code == e.key_code) {
+                let timer = self.key_repeat.stop();
+                self.stop_timer(timer);
+            }
+            Event::AppLostFocus => {
+                let timer = self.key_repeat.stop();
+                self.stop_timer(timer);
+            }
+            Event::Timer(e) if self.key_repeat.timer.is_timer(e).is_some() => {
+                let Some(held) = self.key_repeat.held else {
+                    return true
+                };
+                if !self.key_repeat.repeating {
+                    self.key_repeat.repeating = true;
+                    self.key_repeat.timer = self.start_interval(settings.rate_ms as f64 / 1000.0);
+                }
+                self.key_repeat.synthesizing = true;
+                self.call_event_handler(&Event::KeyDown(KeyEvent {
+                    is_repeat: true,
+                    time: e.time.unwrap_or(held.time),
+                    ..held
+                }));
+                self.key_repeat.synthesizing = false;
+                return true
+            }
+            _ => ()
+        }
+        false
+    }
+}
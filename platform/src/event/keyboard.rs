@@ -120,6 +120,20 @@ pub struct TextInputEvent {
     pub was_paste: bool
 }
 
+/// The tentative text of an input method composition, replacing the previous update.
+#[derive(Clone, Debug, SerBin, DeBin, SerJson, DeJson, PartialEq)]
+pub struct ImeCompositionEvent {
+    pub text: String,
+    pub cursor_byte_offset: usize,
+}
+
+/// Ends a composition, `text` replaces the tentative text. An empty text means the composition
+/// was cancelled.
+#[derive(Clone, Debug, SerBin, DeBin, SerJson, DeJson, PartialEq)]
+pub struct ImeCompositionEndEvent {
+    pub text: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct TextClipboardEvent {
     pub response: Rc<RefCell<Option<String>>>
@@ -131,7 +145,7 @@ impl Default for KeyCode {
 
 
 // lowest common denominator keymap between desktop and web
-#[derive(Live, LiveHook, Clone, Copy, Debug, SerBin, DeBin, SerJson, DeJson, PartialEq)]
+#[derive(Live, LiveHook, Clone, Copy, Debug, SerBin, DeBin, SerJson, DeJson, PartialEq, Eq, Hash)]
 pub enum KeyCode {
     #[pick] Escape,
 
@@ -4,6 +4,11 @@ pub mod keyboard;
 pub mod window;
 pub mod xr;
 pub mod drag_drop;
+pub mod key_map;
+pub mod hit_rect;
+pub mod gesture;
+pub mod clipboard;
+pub mod key_repeat;
 pub mod network;
 pub mod video_playback;
 
@@ -13,5 +18,10 @@ pub use keyboard::*;
 pub use window::*;
 pub use xr::*;
 pub use drag_drop::*;
+pub use key_map::*;
+pub use hit_rect::*;
+pub use gesture::*;
+pub use clipboard::*;
+pub use key_repeat::*;
 pub use network::*;
 pub use video_playback::*;
@@ -28,6 +28,21 @@ pub struct WindowGeomChangeEvent {
     pub new_geom: WindowGeom,
 }
 
+/// Sent whenever a window enters or leaves fullscreen, also when the user toggles it.
+#[derive(Clone, Debug)]
+pub struct WindowFullscreenChangedEvent {
+    pub window_id: WindowId,
+    pub is_fullscreen: bool,
+}
+
+/// Sent when the scale factor of a window changes, for instance when it moves to a display
+/// with a different DPI. Font and icon atlasses are rebuilt and everything is redrawn.
+#[derive(Clone, Debug)]
+pub struct DpiChangedEvent {
+    pub window_id: WindowId,
+    pub dpi: f64,
+}
+
 #[derive(Clone, Debug)]
 pub struct WindowMovedEvent {
     pub window_id: WindowId,
@@ -1,5 +1,11 @@
 // mildly stripped down version of native_dialog_rs dialog interface.
 use std::path::{PathBuf};
+use crate::{
+    cx::Cx,
+    cx_api::CxOsOp,
+    event::Event,
+    thread::{ToUIReceiver, ToUISender},
+};
 
 
 /// Represents a set of file extensions and their description.
@@ -93,3 +99,52 @@ impl Default for FileDialog {
     }
 }
 
+pub type FileFilter = Filter;
+
+/// What to show in the dialogs of `Cx::show_file_open_dialog` and `Cx::show_file_save_dialog`.
+#[derive(Debug, Default, PartialEq)]
+pub struct FileDialogOptions {
+    pub title: String,
+    pub filters: Vec<FileFilter>,
+    pub initial_dir: Option<PathBuf>,
+    /// Only used by the open dialog.
+    pub multi_select: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileDialogError {
+    Cancelled,
+    /// This platform has no native file dialog we can show.
+    Unsupported,
+    Failed(String),
+}
+
+pub type FileDialogResult = Result<Vec<PathBuf>, FileDialogError>;
+
+#[derive(Default)]
+pub struct CxFileDialogs {
+    results: ToUIReceiver<FileDialogResult>,
+}
+
+impl Cx {
+    /// Shows the native file picker, the chosen paths arrive as `Event::FileDialogResult`.
+    pub fn show_file_open_dialog(&mut self, options: FileDialogOptions) {
+        self.platform_ops.push(CxOsOp::SelectFileDialog(options));
+    }
+
+    /// Shows the native save dialog, the chosen path arrives as `Event::FileDialogResult`.
+    pub fn show_file_save_dialog(&mut self, options: FileDialogOptions) {
+        self.platform_ops.push(CxOsOp::SaveFileDialog(options));
+    }
+
+    /// For the platforms to post a result from whichever thread the dialog finished on.
+    pub (crate) fn file_dialog_sender(&self) -> ToUISender<FileDialogResult> {
+        self.file_dialogs.results.sender()
+    }
+
+    pub (crate) fn handle_file_dialog_results(&mut self) {
+        while let Ok(result) = self.file_dialogs.results.try_recv() {
+            self.call_event_handler(&Event::FileDialogResult(result));
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use {
+    crate::{
+        makepad_math::*,
+        makepad_live_id::*,
+        cx::Cx,
+        cx_api::CxOsOp,
+        event::{
+            Event,
+            KeyCode,
+            KeyEvent,
+            KeyModifiers,
+            MouseDownEvent,
+            MouseUpEvent,
+            TextInputEvent,
+            WindowGeom,
+        },
+        thread::SignalToUI,
+        texture::Texture,
+        window::CxWindowPool,
+    },
+};
+
+/// The state of a Cx made with `Cx::new_headless`.
+pub struct CxHeadless {
+    pub (crate) width: u32,
+    pub (crate) height: u32,
+    pub (crate) time: f64,
+    pub (crate) frame: u64,
+    /// Simulated events with the frame they are due in.
+    pub (crate) queue: Vec<(u64, Event)>,
+    #[cfg(feature = "snapshot")]
+    pub (crate) snapshot_tolerance: u8,
+    #[allow(dead_code)]
+    pub (crate) texture: Option<Texture>,
+}
+
+impl Cx {
+    /// A Cx without OS windows for automated tests, its windows are `width` by `height` pixels
+    /// at a dpi factor of 1. Register the live design and call `init_cx_os` as usual, then hand
+    /// the application over with `set_event_handler` and drive it with `simulate_event`.
+    pub fn new_headless(width: u32, height: u32) -> Cx {
+        let mut cx = Cx::new(Box::new( | _, _ | {}));
+        cx.headless = Some(CxHeadless {
+            width,
+            height,
+            time: 0.0,
+            frame: 0,
+            queue: Vec::new(),
+            #[cfg(feature = "snapshot")]
+            snapshot_tolerance: crate::snapshot::DEFAULT_SNAPSHOT_TOLERANCE,
+            texture: None,
+        });
+        cx
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.headless.is_some()
+    }
+
+    pub fn set_event_handler(&mut self, event_handler: Box<dyn FnMut(&mut Cx, &Event)>) {
+        self.event_handler = Some(event_handler);
+    }
+
+    /// Sends `event` through the normal dispatch as if the OS sent it.
+    pub fn simulate_event(&mut self, event: Event) {
+        self.handle_headless_platform_ops();
+        match &event {
+            Event::MouseDown(e) => {
+                self.fingers.process_tap_count(e.abs, e.time);
+                self.fingers.mouse_down(e.button);
+                self.call_event_handler(&event);
+            }
+            Event::MouseMove(_) => {
+                self.call_event_handler(&event);
+                self.fingers.cycle_hover_area(live_id!(mouse).into());
+                self.fingers.switch_captures();
+            }
+            Event::MouseUp(e) => {
+                self.call_event_handler(&event);
+                self.fingers.mouse_up(e.button);
+                self.fingers.cycle_hover_area(live_id!(mouse).into());
+            }
+            Event::KeyDown(e) => {
+                self.keyboard.process_key_down(e.clone());
+                self.call_event_handler(&event);
+            }
+            Event::KeyUp(e) => {
+                self.keyboard.process_key_up(e.clone());
+                self.call_event_handler(&event);
+            }
+            _ => self.call_event_handler(&event)
+        }
+        self.handle_headless_platform_ops();
+    }
+
+    /// Presses `button` at `abs` in the next `advance_frame` and releases it in the one after.
+    pub fn simulate_mouse_click(&mut self, abs: DVec2, button: usize) {
+        let window_id = CxWindowPool::id_zero();
+        let time = self.headless_time();
+        self.queue_simulated_event(0, Event::MouseDown(MouseDownEvent {
+            abs,
+            button,
+            window_id,
+            modifiers: Default::default(),
+            handled: Default::default(),
+            time,
+        }));
+        self.queue_simulated_event(1, Event::MouseUp(MouseUpEvent {
+            abs,
+            button,
+            window_id,
+            modifiers: Default::default(),
+            time,
+        }));
+    }
+
+    /// Presses and releases `key_code` in the next `advance_frame`.
+    pub fn simulate_key_press(&mut self, key_code: KeyCode, modifiers: KeyModifiers) {
+        let key_event = KeyEvent {
+            key_code,
+            is_repeat: false,
+            modifiers,
+            time: self.headless_time(),
+        };
+        self.queue_simulated_event(0, Event::KeyDown(key_event));
+        self.queue_simulated_event(0, Event::KeyUp(key_event));
+    }
+
+    /// Types `text` in the next `advance_frame`, one `TextInput` event per character.
+    pub fn simulate_text_input(&mut self, text: &str) {
+        for c in text.chars() {
+            self.queue_simulated_event(0, Event::TextInput(TextInputEvent {
+                input: c.to_string(),
+                replace_last: false,
+                was_paste: false,
+            }));
+        }
+    }
+
+    /// Moves the clock on by `dt` seconds and runs one frame: the simulated events that are due
+    /// in the order they were queued, signals from other threads, the `NextFrame` event and
+    /// the draw.
+    pub fn advance_frame(&mut self, dt: f64) {
+        let headless = self.headless.as_mut().expect("advance_frame needs a Cx from Cx::new_headless");
+        headless.time += dt;
+        let (time, frame) = (headless.time, headless.frame);
+        let (due, queue) = std::mem::take(&mut headless.queue)
+            .into_iter()
+            .partition::<Vec<_>, _>( | (due_frame, _) | *due_frame <= frame);
+        headless.queue = queue;
+        for (_, event) in due {
+            self.simulate_event(event);
+        }
+        if SignalToUI::check_and_clear_ui_signal() {
+            self.simulate_event(Event::Signal);
+        }
+        if !self.new_next_frames.is_empty() {
+            self.call_next_frame_event(time);
+        }
+        if self.need_redrawing() {
+            self.call_draw_event();
+        }
+        self.handle_headless_platform_ops();
+        self.headless.as_mut().unwrap().frame += 1;
+    }
+
+    fn headless_time(&self) -> f64 {
+        self.headless.as_ref().map_or(0.0, | headless | headless.time)
+    }
+
+    /// Queues `event` for `frames_later` frames after the next `advance_frame`.
+    fn queue_simulated_event(&mut self, frames_later: u64, event: Event) {
+        let headless = self.headless.as_mut().expect("simulated input needs a Cx from Cx::new_headless");
+        let frame = headless.frame + frames_later;
+        headless.queue.push((frame, event));
+    }
+
+    /// Draws what needs drawing and returns the pixels of the main window, row by row from the
+    /// top, 4 bytes of RGBA each.
+    pub fn render_to_rgba(&mut self) -> Vec<u8> {
+        assert!(self.is_headless(), "render_to_rgba needs a Cx from Cx::new_headless");
+        self.handle_headless_platform_ops();
+        if self.need_redrawing() {
+            self.call_draw_event();
+            self.handle_headless_platform_ops();
+        }
+        self.headless_render()
+    }
+
+    #[cfg(not(all(target_os = "linux", not(linux_direct))))]
+    pub (crate) fn headless_render(&mut self) -> Vec<u8> {
+        panic!("Headless rendering is only available in the Linux X11 build")
+    }
+
+    /// Stands in for the platform layer, there are no OS windows or timers to manage.
+    pub (crate) fn handle_headless_platform_ops(&mut self) {
+        let Some(headless) = &self.headless else {
+            return
+        };
+        let size = dvec2(headless.width as f64, headless.height as f64);
+        for op in std::mem::take(&mut self.platform_ops) {
+            match op {
+                CxOsOp::CreateWindow(window_id) => {
+                    let window = &mut self.windows[window_id];
+                    window.window_geom = WindowGeom {
+                        dpi_factor: 1.0,
+                        inner_size: size,
+                        outer_size: size,
+                        ..Default::default()
+                    };
+                    window.is_created = true;
+                }
+                CxOsOp::CloseWindow(window_id) => {
+                    self.windows[window_id].is_created = false;
+                }
+                _ => ()
+            }
+        }
+    }
+}
@@ -50,6 +50,21 @@ pub mod audio_stream;
 
 pub mod file_dialogs;
 
+pub mod accessibility;
+
+pub mod task;
+
+pub mod channel;
+
+pub mod storage;
+
+pub mod net;
+
+pub mod headless;
+
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+
 mod media_api;
 
 #[macro_use]
@@ -65,6 +80,9 @@ pub use makepad_objc_sys;
 pub use ::makepad_windows as windows;
 
 pub use makepad_futures;
+
+#[cfg(feature = "snapshot")]
+pub use crate::snapshot::SnapshotDiff;
  
 pub use {
     makepad_shader_compiler,
@@ -126,6 +144,22 @@ pub use {
         os::*,
         cx_api::CxOsApi,
         media_api::CxMediaApi,
+        accessibility::{
+            AnnouncePriority,
+            AccessibilityRole,
+            AccessibilityNode,
+            AccessibilityTree,
+        },
+        task::{TaskHandle, TaskId, CxWaker},
+        channel::{ChannelId, CxSender, CxReceiver},
+        storage::LocalStorage,
+        net::{FetchRequest, FetchResponse, FetchError, FetchFuture},
+        file_dialogs::{
+            FileDialogOptions,
+            FileFilter,
+            FileDialogError,
+            FileDialogResult,
+        },
         scope::*,
         draw_list::{
             CxDrawItem,
@@ -179,12 +213,16 @@ pub use {
             FingerHoverEvent,
             FingerScrollEvent,
             WindowGeomChangeEvent,
+            WindowFullscreenChangedEvent,
+            DpiChangedEvent,
             WindowMovedEvent,
             NextFrameEvent,
             TimerEvent,
             KeyEvent,
             KeyFocusEvent,
             TextInputEvent,
+            ImeCompositionEvent,
+            ImeCompositionEndEvent,
             TextClipboardEvent,
             WindowCloseRequestedEvent,
             WindowClosedEvent,
@@ -201,6 +239,28 @@ pub use {
             HitOptions,
             DragHitEvent,
             DropHitEvent,
+            KeyBinding,
+            KeyBindingAction,
+            KeyMap,
+            MouseHitRectEvent,
+            CapturedPointerEvent,
+            TouchPhase,
+            TouchEvent,
+            Gesture,
+            GestureEvent,
+            GestureKind,
+            GesturePhase,
+            GestureState,
+            GestureRecognizer,
+            TapRecognizer,
+            LongPressRecognizer,
+            PanRecognizer,
+            PinchRecognizer,
+            RotationRecognizer,
+            ClipboardData,
+            MIME_TEXT_PLAIN,
+            MIME_URI_LIST,
+            KeyRepeatSettings,
         },
         action::{
             Action,
@@ -212,7 +272,7 @@ pub use {
         cursor::MouseCursor,
         macos_menu::MacosMenu,
         draw_matrix::DrawMatrix,
-        window::WindowHandle,
+        window::{WindowHandle, WindowOptions},
         pass::{
             PassId,
             CxPassParent,
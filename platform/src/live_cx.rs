@@ -15,6 +15,9 @@ use {
             LivePtr,
             /*LiveTokenId,*/
             LiveFileId,
+            LiveType,
+            LiveIdAsProp,
+            LiveNodeSliceApi,
         },
         makepad_live_compiler::LiveTypeInfo,
         /*makepad_math::*,*/
@@ -33,6 +36,12 @@ pub struct LiveBody {
     pub live_type_infos: Vec<LiveTypeInfo>
 }
 
+pub (crate) struct LivePropertyHandler {
+    live_type: LiveType,
+    field_id: LiveId,
+    handler: Box<dyn Fn(&mut Cx) + Send>,
+}
+
 
 #[cfg(not(lines))]
 fn line_nr_error_once(){
@@ -231,6 +240,7 @@ impl Cx {
             all_changes.extend(changes);
         }
         if all_changes.len()>0{
+            let old_values = self.property_handler_values();
             let mut live_registry = self.live_registry.borrow_mut();
             let mut errs = Vec::new();
             live_registry.process_file_changes(all_changes, &mut errs);
@@ -253,6 +263,8 @@ impl Cx {
                 error!("check_live_file_watcher: Error expanding live file {}", err);
             }
             self.draw_shaders.reset_for_live_reload();
+            drop(live_registry);
+            self.call_property_handlers(old_values);
             true
         }
         else{
@@ -260,6 +272,47 @@ impl Cx {
         }
     }
     
+    /// Calls `handler` whenever a live edit changes `field_id` in a class of `live_type`, before
+    /// the `LiveEdit` event goes out. Values the field inherits count as well.
+    pub fn on_property_change(&mut self, live_type: LiveType, field_id: LiveId, handler: Box<dyn Fn(&mut Cx) + Send>) {
+        self.property_handlers.push(LivePropertyHandler {live_type, field_id, handler});
+    }
+    
+    /// For each property handler the nodes of its field in all classes of its type.
+    fn property_handler_values(&self) -> Vec<Vec<(LiveId, LiveValue)>> {
+        let live_registry = self.live_registry.borrow();
+        self.property_handlers.iter().map( | handler | {
+            let mut values = Vec::new();
+            for file in &live_registry.live_files {
+                let nodes = &file.expanded.nodes;
+                for (index, node) in nodes.iter().enumerate() {
+                    match node.value {
+                        LiveValue::Class {live_type, ..} if live_type == handler.live_type => {
+                            if let Some(field_index) = nodes.child_by_name(index, handler.field_id.as_field()) {
+                                values.extend(nodes.node_slice(field_index).iter().map( | node | (node.id, node.value.clone())));
+                            }
+                        }
+                        _ => ()
+                    }
+                }
+            }
+            values
+        }).collect()
+    }
+    
+    fn call_property_handlers(&mut self, old_values: Vec<Vec<(LiveId, LiveValue)>>) {
+        let new_values = self.property_handler_values();
+        let handlers = std::mem::take(&mut self.property_handlers);
+        for (handler, (old, new)) in handlers.iter().zip(old_values.iter().zip(&new_values)) {
+            if old != new {
+                (handler.handler)(self);
+            }
+        }
+        // keep the handlers that were added by the ones we called
+        let added = std::mem::replace(&mut self.property_handlers, handlers);
+        self.property_handlers.extend(added);
+    }
+    
     // ok so now what. now we should run the expansion
     pub fn live_expand(&mut self) {
         let mut errs = Vec::new();
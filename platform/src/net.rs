@@ -0,0 +1,233 @@
+use {
+    std::{
+        collections::HashMap,
+        future::Future,
+        pin::Pin,
+        sync::{Arc, Mutex},
+        task::{Context, Poll, Waker},
+    },
+    crate::{
+        cx::Cx,
+        event::{
+            Event,
+            HttpMethod,
+            HttpRequest,
+            HttpResponse,
+            NetworkResponse,
+            NetworkResponseItem,
+        },
+        makepad_live_id::LiveId,
+        thread::ToUIReceiver,
+    },
+};
+
+#[derive(Debug)]
+pub struct FetchRequest {
+    pub url: String,
+    pub method: HttpMethod,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl FetchRequest {
+    pub fn get(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            method: HttpMethod::GET,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FetchResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl FetchResponse {
+    fn from_http_response(response: HttpResponse) -> Self {
+        let mut headers = Vec::new();
+        for (name, values) in response.headers {
+            for value in values {
+                headers.push((name.clone(), value.trim().to_string()));
+            }
+        }
+        Self {
+            status: response.status_code,
+            headers,
+            body: response.body.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FetchError {
+    /// The request never got a response, the message says why.
+    Network(String),
+}
+
+#[derive(Default)]
+struct FetchState {
+    result: Option<Result<FetchResponse, FetchError >>,
+    waker: Option<Waker>,
+}
+
+/// Resolves once the response of `Cx::fetch` arrived. The response comes in on the UI thread,
+/// so `Cx::spawn_future` or any executor on another thread can wait for it.
+pub struct FetchFuture {
+    state: Arc<Mutex<FetchState >>,
+}
+
+impl Future for FetchFuture {
+    type Output = Result<FetchResponse, FetchError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(result) = state.result.take() {
+            return Poll::Ready(result)
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[derive(Default)]
+pub struct CxNet {
+    fetches: HashMap<LiveId, Arc<Mutex<FetchState >>>,
+    responses: ToUIReceiver<NetworkResponseItem>,
+}
+
+impl Cx {
+    /// Sends an HTTP request, the same on every platform. Linux and Windows have no TLS yet, so
+    /// they only do plain `http://` requests.
+    pub fn fetch(&mut self, request: FetchRequest) -> FetchFuture {
+        let request_id = LiveId::unique();
+        let mut http_request = HttpRequest::new(request.url, request.method);
+        for (name, value) in request.headers {
+            http_request.set_header(name, value);
+        }
+        http_request.body = request.body;
+        let state: Arc<Mutex<FetchState >> = Default::default();
+        self.net.fetches.insert(request_id, state.clone());
+        self.http_request(request_id, http_request);
+        FetchFuture {state}
+    }
+
+    /// Runs `request` on a thread for the platforms without an HTTP client of their own.
+    #[allow(dead_code)]
+    pub (crate) fn native_http_request(&mut self, request_id: LiveId, request: HttpRequest) {
+        let sender = self.net.responses.sender();
+        std::thread::spawn(move || {
+            let response = match tcp_http_request(&request) {
+                Ok((status, headers, body)) => NetworkResponse::HttpResponse(HttpResponse::new(request.metadata_id, status, headers, Some(body))),
+                Err(err) => NetworkResponse::HttpRequestError(format!("Cannot request {}: {}", request.url, err)),
+            };
+            let _ = sender.send(NetworkResponseItem {request_id, response});
+        });
+    }
+
+    pub (crate) fn handle_net_responses(&mut self) {
+        let mut responses = Vec::new();
+        while let Ok(response) = self.net.responses.try_recv() {
+            responses.push(response);
+        }
+        if !responses.is_empty() {
+            self.call_event_handler(&Event::NetworkResponses(responses));
+        }
+    }
+
+    pub (crate) fn handle_fetch_responses(&mut self, responses: &[NetworkResponseItem]) {
+        for item in responses {
+            let result = match &item.response {
+                NetworkResponse::HttpResponse(response) => Ok(FetchResponse::from_http_response(response.clone())),
+                NetworkResponse::HttpRequestError(err) => Err(FetchError::Network(err.clone())),
+                NetworkResponse::HttpProgress {..} => continue,
+            };
+            if let Some(state) = self.net.fetches.remove(&item.request_id) {
+                let mut state = state.lock().unwrap();
+                state.result = Some(result);
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+/// A plain HTTP/1.1 request over a `TcpStream`, returns the status, the headers as
+/// `name: value` lines and the body.
+#[allow(dead_code)]
+fn tcp_http_request(request: &HttpRequest) -> Result<(u16, String, Vec<u8>), String> {
+    use std::{io::{Read, Write}, net::TcpStream};
+    
+    let Some(rest) = request.url.strip_prefix("http://") else {
+        return Err("only http:// urls are supported on this platform".to_string())
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+    let address = if authority.contains(':') {authority.to_string()} else {format!("{}:80", authority)};
+    let mut stream = TcpStream::connect(address).map_err( | err | err.to_string())?;
+    
+    let mut head = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", request.method.to_string(), path, authority);
+    for (name, values) in &request.headers {
+        head.push_str(&format!("{}: {}\r\n", name, values.join(",")));
+    }
+    let body = request.body.as_deref().unwrap_or(&[]);
+    if !body.is_empty() {
+        head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).and_then( | _ | stream.write_all(body)).map_err( | err | err.to_string())?;
+    
+    // we asked the server to close the connection, so the response ends with the stream
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).map_err( | err | err.to_string())?;
+    let Some(head_len) = response.windows(4).position( | w | w == b"\r\n\r\n") else {
+        return Err("malformed response".to_string())
+    };
+    let head = String::from_utf8_lossy(&response[..head_len]);
+    let mut lines = head.split("\r\n");
+    let status = lines.next()
+        .and_then( | line | line.split(' ').nth(1))
+        .and_then( | status | status.parse().ok())
+        .ok_or_else( || "malformed status line".to_string())?;
+    let mut headers = String::new();
+    let mut is_chunked = false;
+    for line in lines.filter( | line | line.contains(':')) {
+        let (name, value) = line.split_once(':').unwrap();
+        if name.eq_ignore_ascii_case("transfer-encoding") && value.trim().eq_ignore_ascii_case("chunked") {
+            is_chunked = true;
+        }
+        headers.push_str(line);
+        headers.push_str("\r\n");
+    }
+    let body = &response[head_len + 4..];
+    let body = if is_chunked {decode_chunked(body)?} else {body.to_vec()};
+    Ok((status, headers, body))
+}
+
+#[allow(dead_code)]
+fn decode_chunked(mut input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut body = Vec::new();
+    loop {
+        let line_len = input.windows(2).position( | w | w == b"\r\n").ok_or("malformed chunk")?;
+        let size = String::from_utf8_lossy(&input[..line_len]);
+        // chunk extensions follow a ';'
+        let size = size.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size, 16).map_err( | _ | "malformed chunk size")?;
+        input = &input[line_len + 2..];
+        if size == 0 {
+            return Ok(body)
+        }
+        if input.len() < size + 2 {
+            return Err("truncated chunk".to_string())
+        }
+        body.extend_from_slice(&input[..size]);
+        input = &input[size + 2..];
+    }
+}
@@ -91,6 +91,8 @@ extern "C" {
 #[cfg(any(target_os = "ios", target_os = "tvos"))]
 #[link(name = "UIKit", kind = "framework")]
 extern "C" {
+    pub static UIAccessibilityAnnouncementNotification: u32;
+    pub fn UIAccessibilityPostNotification(notification: u32, argument: ObjcId);
 pub fn UIApplicationMain(
         argc: i32,
         argv: *mut *mut i8,
@@ -147,6 +149,10 @@ extern {
     pub static NSPasteboardTypeFileURL: ObjcId;
     pub static NSPasteboardTypeURL: ObjcId;
     pub static NSPasteboardTypeString: ObjcId;
+    pub static NSAccessibilityAnnouncementRequestedNotification: ObjcId;
+    pub static NSAccessibilityAnnouncementKey: ObjcId;
+    pub static NSAccessibilityPriorityKey: ObjcId;
+    pub fn NSAccessibilityPostNotificationWithUserInfo(element: ObjcId, notification: ObjcId, user_info: ObjcId);
 }
 
 #[link(name = "Vision", kind = "framework")]
@@ -11,7 +11,7 @@ use {
         makepad_objc_sys::runtime::{ObjcId},
         os::{
             apple::apple_sys::*,
-            apple::apple_util::nsstring_to_string,
+            apple::apple_util::{nsstring_to_string, str_to_nsstring},
             cx_native::EventFlow,
             apple::{
                 ios::{
@@ -32,6 +32,7 @@ use {
             NetworkResponseChannel
         },
         cx_api::{CxOsApi, CxOsOp},
+        file_dialogs::FileDialogError,
         cx::{Cx, OsType},
     }
 };
@@ -320,10 +321,14 @@ impl Cx {
                 CxOsOp::CleanupVideoPlaybackResources(_) => todo!(),
                 CxOsOp::UpdateVideoSurfaceTexture(_) => todo!(),
 
-                CxOsOp::SaveFileDialog(_) => todo!(),
-                CxOsOp::SelectFileDialog(_) => todo!(),
+                CxOsOp::SaveFileDialog(_) | CxOsOp::SelectFileDialog(_) => {
+                    let _ = self.file_dialog_sender().send(Err(FileDialogError::Unsupported));
+                }
                 CxOsOp::SaveFolderDialog(_) => todo!(),
                 CxOsOp::SelectFolderDialog(_) => todo!(),
+                CxOsOp::Announce {message, priority: _} => {
+                    unsafe {UIAccessibilityPostNotification(UIAccessibilityAnnouncementNotification, str_to_nsstring(&message))};
+                }
                 
             }
         }
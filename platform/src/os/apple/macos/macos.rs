@@ -246,7 +246,9 @@ impl Cx {
             MacosEvent::Scroll(_) |
             MacosEvent::KeyDown(_) |
             MacosEvent::KeyUp(_) |
-            MacosEvent::TextInput(_) => {
+            MacosEvent::TextInput(_) |
+            MacosEvent::ImeCompositionUpdate(_) |
+            MacosEvent::ImeCompositionEnd(_) => {
                 self.os.keep_alive_counter = KEEP_ALIVE_COUNT;
             }
             MacosEvent::Timer(te) => {
@@ -374,6 +376,15 @@ impl Cx {
             MacosEvent::TextInput(e) => {
                 self.call_event_handler(&Event::TextInput(e))
             }
+            MacosEvent::ImeCompositionStart => {
+                self.call_event_handler(&Event::ImeCompositionStart)
+            }
+            MacosEvent::ImeCompositionUpdate(e) => {
+                self.call_event_handler(&Event::ImeCompositionUpdate(e))
+            }
+            MacosEvent::ImeCompositionEnd(e) => {
+                self.call_event_handler(&Event::ImeCompositionEnd(e))
+            }
             MacosEvent::Drag(e) => {
                 self.call_event_handler(&Event::Drag(e));
                 self.drag_drop.cycle_drag();
@@ -468,11 +479,15 @@ impl Cx {
                         metal_window.cocoa_window.restore();
                     }
                 },
-                CxOsOp::FullscreenWindow(_window_id) => {
-                    todo!()
+                CxOsOp::FullscreenWindow(window_id) => {
+                    if let Some(metal_window) = metal_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        metal_window.cocoa_window.set_fullscreen(true);
+                    }
                 },
-                CxOsOp::NormalizeWindow(_window_id) => {
-                    todo!()
+                CxOsOp::NormalizeWindow(window_id) => {
+                    if let Some(metal_window) = metal_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        metal_window.cocoa_window.set_fullscreen(false);
+                    }
                 }
                 CxOsOp::SetTopmost(_window_id, _is_topmost) => {
                     todo!()
@@ -535,14 +550,16 @@ impl Cx {
                 CxOsOp::CleanupVideoPlaybackResources(_) => todo!(),
                 CxOsOp::UpdateVideoSurfaceTexture(_) => todo!(),
 
-                CxOsOp::SaveFileDialog(settings) => 
+                CxOsOp::SaveFileDialog(options) => 
                 {
-                    get_macos_app_global().open_save_file_dialog(settings);
+                    let result = get_macos_app_global().open_save_file_dialog(options);
+                    let _ = self.file_dialog_sender().send(result);
                 }
                 
-                CxOsOp::SelectFileDialog(settings) => 
+                CxOsOp::SelectFileDialog(options) => 
                 {
-                    get_macos_app_global().open_select_file_dialog(settings);                   
+                    let result = get_macos_app_global().open_select_file_dialog(options);
+                    let _ = self.file_dialog_sender().send(result);
                 }
                 
                 CxOsOp::SaveFolderDialog(settings) => 
@@ -554,6 +571,10 @@ impl Cx {
                 {
                     get_macos_app_global().open_select_folder_dialog(settings);
                 }
+                
+                CxOsOp::Announce {message, priority} => {
+                    get_macos_app_global().announce(&message, priority);
+                }
             }
         }
         EventFlow::Poll
@@ -589,6 +610,14 @@ impl CxOsApi for Cx {
         self.start_xpc_service()
     }
     
+    fn clipboard_set_text(&mut self, text: &str) {
+        get_macos_app_global().copy_to_clipboard(text);
+    }
+    
+    fn clipboard_get_text(&mut self) -> Option<String> {
+        get_macos_app_global().read_clipboard()
+    }
+    
     /*
     fn web_socket_open(&mut self, _url: String, _rec: WebSocketAutoReconnect) -> WebSocket {
         todo!()
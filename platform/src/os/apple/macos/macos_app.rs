@@ -1,4 +1,5 @@
-use crate::file_dialogs::FileDialog;
+use crate::file_dialogs::{FileDialog, FileDialogOptions, FileDialogResult, FileDialogError};
+use crate::accessibility::AnnouncePriority;
 
 use {
     std::{
@@ -7,6 +8,7 @@ use {
         time::Instant,
         collections::{HashMap},
         os::raw::{c_void},
+        path::PathBuf,
     },
     crate::{
         makepad_live_id::*,
@@ -629,6 +631,41 @@ impl MacosApp {
         }
     }
     
+    pub fn copy_to_clipboard(&self, text: &str) {
+        unsafe {
+            let nsstring = str_to_nsstring(text);
+            let array: ObjcId = msg_send![class!(NSArray), arrayWithObject: NSStringPboardType];
+            let () = msg_send![self.pasteboard, declareTypes: array owner: nil];
+            let () = msg_send![self.pasteboard, setString: nsstring forType: NSStringPboardType];
+        }
+    }
+    
+    pub fn read_clipboard(&self) -> Option<String> {
+        unsafe {
+            let nsstring: ObjcId = msg_send![self.pasteboard, stringForType: NSStringPboardType];
+            if nsstring == nil {
+                return None
+            }
+            Some(nsstring_to_string(nsstring))
+        }
+    }
+    
+    pub fn announce(&self, message: &str, priority: AnnouncePriority) {
+        unsafe {
+            // NSAccessibilityPriorityMedium and NSAccessibilityPriorityHigh
+            let level: i64 = match priority {
+                AnnouncePriority::Polite => 50,
+                AnnouncePriority::Assertive => 90,
+            };
+            let user_info: ObjcId = msg_send![class!(NSMutableDictionary), dictionary];
+            let () = msg_send![user_info, setObject: str_to_nsstring(message) forKey: NSAccessibilityAnnouncementKey];
+            let level: ObjcId = msg_send![class!(NSNumber), numberWithLongLong: level];
+            let () = msg_send![user_info, setObject: level forKey: NSAccessibilityPriorityKey];
+            let ns_app: ObjcId = msg_send![class!(NSApplication), sharedApplication];
+            NSAccessibilityPostNotificationWithUserInfo(ns_app, NSAccessibilityAnnouncementRequestedNotification, user_info);
+        }
+    }
+    
     pub fn start_timer(&mut self, timer_id: u64, interval: f64, repeats: bool) {
         unsafe {
             let pool: ObjcId = msg_send![class!(NSAutoreleasePool), new];
@@ -746,14 +783,44 @@ impl MacosApp {
     }*/
 
 
-    pub fn open_save_file_dialog(&mut self, _settings: FileDialog)
+    pub fn open_save_file_dialog(&mut self, options: FileDialogOptions) -> FileDialogResult
     {
-        println!("open save file dialog!");
+        unsafe {
+            let panel: ObjcId = msg_send![class!(NSSavePanel), savePanel];
+            configure_file_panel(panel, &options);
+            let response: i64 = msg_send![panel, runModal];
+            // NSModalResponseOK
+            if response != 1 {
+                return Err(FileDialogError::Cancelled)
+            }
+            let url: ObjcId = msg_send![panel, URL];
+            let path: ObjcId = msg_send![url, path];
+            Ok(vec![PathBuf::from(nsstring_to_string(path))])
+        }
     }
 
-    pub fn open_select_file_dialog(&mut self, _settings: FileDialog )
+    pub fn open_select_file_dialog(&mut self, options: FileDialogOptions) -> FileDialogResult
     {
-        println!("open select file dialog!");
+        unsafe {
+            let panel: ObjcId = msg_send![class!(NSOpenPanel), openPanel];
+            configure_file_panel(panel, &options);
+            let () = msg_send![panel, setCanChooseFiles: true];
+            let () = msg_send![panel, setCanChooseDirectories: false];
+            let () = msg_send![panel, setAllowsMultipleSelection: options.multi_select];
+            let response: i64 = msg_send![panel, runModal];
+            if response != 1 {
+                return Err(FileDialogError::Cancelled)
+            }
+            let urls: ObjcId = msg_send![panel, URLs];
+            let count: usize = msg_send![urls, count];
+            let mut paths = Vec::with_capacity(count);
+            for index in 0..count {
+                let url: ObjcId = msg_send![urls, objectAtIndex: index];
+                let path: ObjcId = msg_send![url, path];
+                paths.push(PathBuf::from(nsstring_to_string(path)));
+            }
+            Ok(paths)
+        }
     }
 
     pub fn open_save_folder_dialog(&mut self,  _settings: FileDialog)
@@ -767,3 +834,26 @@ impl MacosApp {
     }
 
 }
+
+unsafe fn configure_file_panel(panel: ObjcId, options: &FileDialogOptions) {
+    if !options.title.is_empty() {
+        // recent macOS versions no longer show panel titles, the message is shown instead
+        let () = msg_send![panel, setTitle: str_to_nsstring(&options.title)];
+        let () = msg_send![panel, setMessage: str_to_nsstring(&options.title)];
+    }
+    if let Some(initial_dir) = &options.initial_dir {
+        let url: ObjcId = msg_send![
+            class!(NSURL),
+            fileURLWithPath: str_to_nsstring(&initial_dir.to_string_lossy())
+            isDirectory: true
+        ];
+        let () = msg_send![panel, setDirectoryURL: url];
+    }
+    if !options.filters.is_empty() {
+        let types: ObjcId = msg_send![class!(NSMutableArray), array];
+        for extension in options.filters.iter().flat_map( | filter | filter.extensions.iter()) {
+            let () = msg_send![types, addObject: str_to_nsstring(extension)];
+        }
+        let () = msg_send![panel, setAllowedFileTypes: types];
+    }
+}
@@ -507,19 +507,47 @@ pub fn define_cocoa_view_class() -> *const Class {
         }
     }
     
-    extern fn set_marked_text(this: &mut Object, _sel: Sel, string: ObjcId, _selected_range: NSRange, _replacement_range: NSRange) {
-        unsafe {
+    extern fn set_marked_text(this: &mut Object, _sel: Sel, string: ObjcId, selected_range: NSRange, _replacement_range: NSRange) {
+        let was_composing = has_marked_text(this, _sel) != NO;
+        let text = unsafe {
             let marked_text_ref: &mut ObjcId = this.get_mut_ivar("markedText");
             let _: () = msg_send![(*marked_text_ref), release];
             let marked_text = NSMutableAttributedString::alloc(nil);
             let has_attr = msg_send![string, isKindOfClass: class!(NSAttributedString)];
-            if has_attr {
+            let characters = if has_attr {
                 marked_text.init_with_attributed_string(string);
+                msg_send![string, string]
             } else {
                 marked_text.init_with_string(string);
+                string
             };
             *marked_text_ref = marked_text;
+            nsstring_to_string(characters)
+        };
+        let cw = get_cocoa_window(this);
+        if text.is_empty() {
+            if was_composing {
+                cw.send_ime_composition_end(String::new());
+            }
+            return
+        }
+        if !was_composing {
+            cw.send_ime_composition_start();
         }
+        // the range is in utf16 code units
+        let cursor_byte_offset = utf16_offset_to_byte_offset(&text, selected_range.location as usize);
+        cw.send_ime_composition_update(text, cursor_byte_offset);
+    }
+    
+    fn utf16_offset_to_byte_offset(text: &str, utf16_offset: usize) -> usize {
+        let mut units = 0;
+        for (byte_offset, char) in text.char_indices() {
+            if units >= utf16_offset {
+                return byte_offset
+            }
+            units += char.len_utf16();
+        }
+        text.len()
     }
     
     extern fn unmark_text(this: &Object, _sel: Sel) {
@@ -575,7 +603,13 @@ pub fn define_cocoa_view_class() -> *const Class {
                 string
             };
             let string = nsstring_to_string(characters);
-            cw.send_text_input(string, replacement_range.length != 0);
+            if has_marked_text(this, _sel) != NO {
+                // committing a composition
+                cw.send_ime_composition_end(string);
+            }
+            else {
+                cw.send_text_input(string, replacement_range.length != 0);
+            }
             let input_context: ObjcId = msg_send![this, inputContext];
             let () = msg_send![input_context, invalidateCharacterCoordinates];
             let () = msg_send![cw.view, setNeedsDisplay: YES];
@@ -12,6 +12,8 @@ use crate::{
         WindowCloseRequestedEvent,
         WindowClosedEvent,
         TextInputEvent,
+        ImeCompositionEvent,
+        ImeCompositionEndEvent,
         KeyEvent,
         DragEvent,
         DropEvent,
@@ -38,6 +40,9 @@ pub enum MacosEvent {
     WindowDragQuery(WindowDragQueryEvent),
     WindowCloseRequested(WindowCloseRequestedEvent),
     TextInput(TextInputEvent),
+    ImeCompositionStart,
+    ImeCompositionUpdate(ImeCompositionEvent),
+    ImeCompositionEnd(ImeCompositionEndEvent),
     Drag(DragEvent),
     Drop(DropEvent),
     DragEnd,
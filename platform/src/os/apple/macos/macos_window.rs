@@ -35,6 +35,8 @@ use {
             WindowCloseRequestedEvent,
             WindowClosedEvent,
             TextInputEvent,
+            ImeCompositionEvent,
+            ImeCompositionEndEvent,
             DragItem,
         },
     }
@@ -218,6 +220,14 @@ impl MacosWindow {
         }
     }
     
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        if self.is_fullscreen != fullscreen {
+            unsafe {
+                let () = msg_send![self.window, toggleFullScreen: nil];
+            }
+        }
+    }
+    
     pub fn minimize(&mut self) {
         unsafe {
             let () = msg_send![self.window, miniaturize: nil];
@@ -412,6 +422,21 @@ impl MacosWindow {
         }))
     }
     
+    pub fn send_ime_composition_start(&mut self) {
+        self.do_callback(MacosEvent::ImeCompositionStart);
+    }
+    
+    pub fn send_ime_composition_update(&mut self, text: String, cursor_byte_offset: usize) {
+        self.do_callback(MacosEvent::ImeCompositionUpdate(ImeCompositionEvent {
+            text,
+            cursor_byte_offset
+        }))
+    }
+    
+    pub fn send_ime_composition_end(&mut self, text: String) {
+        self.do_callback(MacosEvent::ImeCompositionEnd(ImeCompositionEndEvent {text}))
+    }
+    
     pub fn send_text_input(&mut self, input: String, replace_last: bool) {
         self.do_callback(MacosEvent::TextInput(TextInputEvent {
             input: input,
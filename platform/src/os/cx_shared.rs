@@ -14,6 +14,8 @@ use {
             Event,
             KeyFocusEvent,
             NextFrameEvent,
+            WindowFullscreenChangedEvent,
+            DpiChangedEvent,
         },
         studio::{AppToStudio,EventSample},
     }
@@ -174,10 +176,80 @@ impl Cx {
     }
     
     pub (crate) fn call_event_handler(&mut self, event: &Event) {
+        if self.handle_key_repeat(event) {
+            return
+        }
+        match event {
+            Event::MouseDown(e) => {
+                self.app_drag_moved(e.abs, e.window_id);
+                self.hit_rects.mouse_moved(e.abs, e.window_id);
+            }
+            Event::MouseMove(e) => {
+                self.app_drag_moved(e.abs, e.window_id);
+                self.hit_rects.mouse_moved(e.abs, e.window_id);
+            }
+            Event::MouseUp(e) => {
+                self.app_drag_moved(e.abs, e.window_id);
+                self.hit_rects.mouse_moved(e.abs, e.window_id);
+            }
+            Event::MouseLeave(_) => self.hit_rects.mouse_left(),
+            Event::AppLostFocus => self.release_pointer(),
+            Event::KeyDown(e) => if let Some(action_id) = self.key_map.action_for_key(e.key_code, e.modifiers) {
+                // a bound shortcut consumes the key
+                self.dispatch_action(action_id);
+                self.handle_triggers();
+                self.handle_actions();
+                return
+            }
+            _ => ()
+        }
         self.inner_call_event_handler(event);
         self.inner_key_focus_change();
         self.handle_triggers();
         self.handle_actions();
+        // a drag released during this event gets delivered to the drop targets right away
+        if let Some(drop_event) = self.take_app_drop_event() {
+            self.inner_call_event_handler(&drop_event);
+            self.clear_app_drop();
+            self.handle_triggers();
+            self.handle_actions();
+        }
+        match event {
+            Event::MouseDown(_) | Event::MouseMove(_) | Event::MouseUp(_) | Event::MouseLeave(_) => {
+                self.dispatch_hit_rect_changes();
+            }
+            Event::TouchUpdate(e) => self.handle_touch_gestures(e),
+            Event::NextFrame(e) => self.update_gestures(e.time, Vec::new()),
+            Event::Signal => {
+                self.handle_file_dialog_results();
+                self.handle_task_results();
+                self.handle_channel_messages();
+                self.handle_net_responses();
+            }
+            Event::NetworkResponses(responses) => self.handle_fetch_responses(responses),
+            Event::WakeTask(task_id) => self.poll_future_task(*task_id),
+            Event::ChannelMessage(channel_id) => self.drain_channel(*channel_id),
+            Event::WindowClosed(e) => self.handle_window_closed(e.window_id),
+            Event::WindowGeomChange(e) => {
+                if e.old_geom.is_fullscreen != e.new_geom.is_fullscreen {
+                    self.call_event_handler(&Event::WindowFullscreenChanged(WindowFullscreenChangedEvent {
+                        window_id: e.window_id,
+                        is_fullscreen: e.new_geom.is_fullscreen,
+                    }));
+                }
+                // the first geom of a window comes with a dpi factor of 0
+                if e.old_geom.dpi_factor != 0.0 && e.old_geom.dpi_factor != e.new_geom.dpi_factor {
+                    self.call_event_handler(&Event::DpiChanged(DpiChangedEvent {
+                        window_id: e.window_id,
+                        dpi: e.new_geom.dpi_factor,
+                    }));
+                    // glyphs and icons were rasterized at the old scale
+                    self.call_event_handler(&Event::ClearAtlasses);
+                    self.redraw_all();
+                }
+            }
+            _ => ()
+        }
     }
 
     // helpers
@@ -194,6 +266,8 @@ impl Cx {
         let mut draw_event = DrawEvent::default();
         std::mem::swap(&mut draw_event, &mut self.new_draw_event);
         self.call_event_handler(&Event::Draw(draw_event));
+        // the hit rects may have moved under a still cursor
+        self.dispatch_hit_rect_changes();
     }
 
     pub (crate) fn call_next_frame_event(&mut self, time: f64) {
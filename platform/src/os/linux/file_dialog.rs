@@ -0,0 +1,46 @@
+use {
+    std::{
+        path::PathBuf,
+        process::Command,
+    },
+    crate::{
+        file_dialogs::{FileDialogOptions, FileDialogResult, FileDialogError},
+        thread::ToUISender,
+    },
+};
+
+// zenity draws a GTK file chooser and prints the chosen paths, one per line
+pub fn show_file_dialog(options: FileDialogOptions, save: bool, sender: ToUISender<FileDialogResult>) {
+    std::thread::spawn(move || {
+        let mut command = Command::new("zenity");
+        command.arg("--file-selection").arg("--separator=\n");
+        if save {
+            command.arg("--save").arg("--confirm-overwrite");
+        }
+        else if options.multi_select {
+            command.arg("--multiple");
+        }
+        if !options.title.is_empty() {
+            command.arg(format!("--title={}", options.title));
+        }
+        if let Some(initial_dir) = &options.initial_dir {
+            // a trailing slash opens the directory instead of selecting it
+            command.arg(format!("--filename={}/", initial_dir.display()));
+        }
+        for filter in &options.filters {
+            let patterns: Vec<String> = filter.extensions.iter().map( | ext | format!("*.{}", ext)).collect();
+            command.arg(format!("--file-filter={} | {}", filter.description, patterns.join(" ")));
+        }
+        let result = match command.output() {
+            Ok(output) if output.status.success() => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                Ok(stdout.lines().filter( | line | !line.is_empty()).map(PathBuf::from).collect())
+            }
+            // zenity exits with 1 when the dialog is cancelled
+            Ok(output) if output.status.code() == Some(1) => Err(FileDialogError::Cancelled),
+            Ok(output) => Err(FileDialogError::Failed(String::from_utf8_lossy(&output.stderr).into_owned())),
+            Err(err) => Err(FileDialogError::Failed(format!("Cannot run zenity: {}", err))),
+        };
+        let _ = sender.send(result);
+    });
+}
@@ -69,6 +69,7 @@ pub const CLAMP_TO_EDGE: types::GLenum = 0x812F;
 pub const PROGRAM_BINARY_LENGTH: types::GLenum = 0x8741;
 pub const NO_ERROR: types::GLenum = 0x0;
 pub const UNPACK_ALIGNMENT: types::GLenum = 0x0CF5;
+pub const PACK_ALIGNMENT: types::GLenum = 0x0D05;
 pub const UNPACK_ROW_LENGTH: types::GLenum = 0x0CF2;
 pub const TEXTURE_EXTERNAL_OES: types::GLenum = 0x8D65;
 pub const EXTENSIONS: types::GLenum = 0x1F03;
@@ -132,6 +133,7 @@ pub const RENDERER: types::GLenum = 0x1F01;
 #[inline] pub unsafe fn DeleteVertexArrays(n: types::GLsizei, arrays: *const types::GLuint) -> () { mem::transmute::<_, extern "system" fn(types::GLsizei, *const types::GLuint) -> ()>(storage::DeleteVertexArrays.f)(n, arrays) }
 #[inline] pub unsafe fn GenerateMipmap(target: types::GLenum) -> () { mem::transmute::<_, extern "system" fn(types::GLenum) -> ()>( storage::GenerateMipmap.f)(target)}
 #[inline] pub unsafe fn PixelStorei(pname: types::GLenum, param: types::GLint) -> () { mem::transmute::<_, extern "system" fn(types::GLenum, types::GLint) -> ()>(storage::PixelStorei.f)(pname, param)}
+#[inline] pub unsafe fn ReadPixels(x: types::GLint, y: types::GLint, width: types::GLsizei, height: types::GLsizei, format: types::GLenum, type_: types::GLenum, pixels: *mut raw::c_void) -> () { mem::transmute::<_, extern "system" fn(types::GLint, types::GLint, types::GLsizei, types::GLsizei, types::GLenum, types::GLenum, *mut raw::c_void) -> ()>(storage::ReadPixels.f)(x, y, width, height, format, type_, pixels)}
 #[inline] pub unsafe fn GetString(name: types::GLenum) -> *const types::GLubyte { mem::transmute::<_, extern "system" fn(types::GLenum) -> *const types::GLubyte>(storage::GetString.f)(name)}
 
 mod storage {
@@ -194,6 +196,7 @@ mod storage {
     pub static mut DeleteVertexArrays: FnPtr = FnPtr::default();
     pub static mut GenerateMipmap: FnPtr = FnPtr::default();
     pub static mut PixelStorei: FnPtr = FnPtr::default();
+    pub static mut ReadPixels: FnPtr = FnPtr::default();
     pub static mut GetString: FnPtr = FnPtr::default();
 }
 
@@ -256,6 +259,7 @@ pub unsafe fn load_with<F>(mut loadfn: F) where F: FnMut(&'static str) -> *const
     storage::DeleteVertexArrays = FnPtr::new(metaloadfn(&mut loadfn, "glDeleteVertexArrays", &["glDeleteVertexArraysAPPLE", "glDeleteVertexArraysOES"]));
     storage::GenerateMipmap = FnPtr::new(metaloadfn(&mut loadfn, "glGenerateMipmap", &[]));
     storage::PixelStorei = FnPtr::new(metaloadfn(&mut loadfn, "glPixelStorei", &[]));
+    storage::ReadPixels = FnPtr::new(metaloadfn(&mut loadfn, "glReadPixels", &[]));
     storage::GetString = FnPtr::new(metaloadfn(&mut loadfn, "glGetString", &[]));
 }
 
@@ -25,6 +25,8 @@ pub mod alsa_audio;
 pub mod alsa_midi;
 #[cfg(not(target_os="android"))]
 pub mod select_timer;
+#[cfg(not(target_os="android"))]
+pub mod file_dialog;
 #[cfg(not(target_os="android"))] 
 pub mod pulse_audio; 
 #[cfg(not(target_os="android"))]
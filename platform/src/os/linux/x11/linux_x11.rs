@@ -9,7 +9,8 @@ use {
         egl_sys,
         x11::xlib_event::*,
         x11::xlib_app::*,
-        linux_media::CxLinuxMedia
+        linux_media::CxLinuxMedia,
+        file_dialog::show_file_dialog,
     },
     crate::{
         cx_api::{CxOsOp, CxOsApi}, 
@@ -168,6 +169,9 @@ impl Cx {
             XlibEvent::TextInput(e) => {
                 self.call_event_handler(&Event::TextInput(e))
             }
+            XlibEvent::ClipboardTextReceived(text) => {
+                self.call_event_handler(&Event::ClipboardTextReceived(text))
+            }
             XlibEvent::Drag(e) => {
                 self.call_event_handler(&Event::Drag(e))
             }
@@ -289,11 +293,15 @@ impl Cx {
                 },
                 CxOsOp::ShowClipboardActions(_) =>{
                 }
-                CxOsOp::FullscreenWindow(_window_id) => {
-                    todo!()
+                CxOsOp::FullscreenWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.xlib_window.set_fullscreen(true);
+                    }
                 },
-                CxOsOp::NormalizeWindow(_window_id) => {
-                    todo!()
+                CxOsOp::NormalizeWindow(window_id) => {
+                    if let Some(window) = opengl_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.xlib_window.set_fullscreen(false);
+                    }
                 }
                 CxOsOp::SetTopmost(_window_id, _is_topmost) => {
                     todo!()
@@ -323,8 +331,8 @@ impl Cx {
                 },
                 CxOsOp::UpdateMacosMenu(_menu) => {
                 },
-                CxOsOp::HttpRequest{request_id:_, request:_} => {
-                    todo!()
+                CxOsOp::HttpRequest{request_id, request} => {
+                    self.native_http_request(request_id, request);
                 },
                 CxOsOp::PrepareVideoPlayback(_, _, _, _, _) => todo!(),
                 CxOsOp::BeginVideoPlayback(_) => todo!(),
@@ -335,10 +343,17 @@ impl Cx {
                 CxOsOp::CleanupVideoPlaybackResources(_) => todo!(),
                 CxOsOp::UpdateVideoSurfaceTexture(_) => todo!(),
 
-                CxOsOp::SaveFileDialog(_) => todo!(),
-                CxOsOp::SelectFileDialog(_) => todo!(),
+                CxOsOp::SaveFileDialog(options) => {
+                    show_file_dialog(options, true, self.file_dialog_sender());
+                }
+                CxOsOp::SelectFileDialog(options) => {
+                    show_file_dialog(options, false, self.file_dialog_sender());
+                }
                 CxOsOp::SaveFolderDialog(_) => todo!(),
                 CxOsOp::SelectFolderDialog(_) => todo!(),
+                CxOsOp::Announce {..} => {
+                    crate::log!("Announcing to screen readers is not supported on Linux yet");
+                }
             }
         }
         ret
@@ -355,6 +370,14 @@ impl CxOsApi for Cx {
     fn spawn_thread<F>(&mut self, f: F) where F: FnOnce() + Send + 'static {
         std::thread::spawn(f);
     }
+    
+    fn clipboard_set_text(&mut self, text: &str) {
+        unsafe {get_xlib_app_global().set_clipboard_text(text)}
+    }
+    
+    fn clipboard_get_text(&mut self) -> Option<String> {
+        unsafe {get_xlib_app_global().request_clipboard_text()}
+    }
 }
 
 #[derive(Default)]
@@ -0,0 +1,80 @@
+use {
+    std::os::raw::c_void,
+    self::super::{
+        opengl_x11::OpenglCx,
+    },
+    self::super::super::{
+        egl_sys,
+        gl_sys,
+    },
+    crate::{
+        window::CxWindowPool,
+        texture::{Texture, TextureFormat, TextureSize},
+        pass::CxPassParent,
+        cx::Cx,
+    }
+};
+
+impl Cx {
+    /// Renders the main window into an offscreen texture and reads it back. Mesa's surfaceless
+    /// platform needs no display server, with llvmpipe it runs on any CI machine.
+    pub (crate) fn headless_render(&mut self) -> Vec<u8> {
+        if self.os.opengl_cx.is_none() {
+            self.os.opengl_cx = Some(unsafe {
+                OpenglCx::from_egl_platform_display(
+                    egl_sys::EGL_PLATFORM_SURFACELESS_MESA,
+                    std::ptr::null_mut::<c_void>(),
+                )
+            });
+        }
+        let headless = self.headless.as_ref().unwrap();
+        let (width, height, time) = (headless.width as usize, headless.height as usize, headless.time);
+        let texture = match &headless.texture {
+            Some(texture) => texture.clone(),
+            None => {
+                let texture = Texture::new_with_format(self, TextureFormat::RenderBGRAu8 {
+                    size: TextureSize::Auto
+                });
+                self.headless.as_mut().unwrap().texture = Some(texture.clone());
+                texture
+            }
+        };
+
+        self.os.opengl_cx.as_ref().unwrap().make_current();
+        self.opengl_compile_shaders();
+        let mut passes_todo = Vec::new();
+        self.compute_pass_repaint_order(&mut passes_todo);
+        self.repaint_id += 1;
+        for &pass_id in &passes_todo {
+            self.passes[pass_id].set_time(time as f32);
+            match self.passes[pass_id].parent.clone() {
+                CxPassParent::Window(_) => {
+                    self.draw_pass_to_texture(pass_id, &texture);
+                }
+                CxPassParent::Pass(_) | CxPassParent::None => {
+                    self.draw_pass_to_magic_texture(pass_id);
+                }
+            }
+        }
+
+        let mut pixels = vec![0u8; width * height * 4];
+        let framebuffer = self.windows[CxWindowPool::id_zero()].main_pass_id
+            .and_then( | pass_id | self.passes[pass_id].os.gl_framebuffer);
+        let Some(framebuffer) = framebuffer else {
+            return pixels
+        };
+        unsafe {
+            gl_sys::BindFramebuffer(gl_sys::FRAMEBUFFER, framebuffer);
+            gl_sys::PixelStorei(gl_sys::PACK_ALIGNMENT, 1);
+            gl_sys::ReadPixels(0, 0, width as i32, height as i32, gl_sys::RGBA, gl_sys::UNSIGNED_BYTE, pixels.as_mut_ptr() as *mut _);
+            gl_sys::BindFramebuffer(gl_sys::FRAMEBUFFER, 0);
+        }
+        // GL reads the rows bottom up
+        let row_len = width * 4;
+        for y in 0..height / 2 {
+            let (top, bottom) = pixels.split_at_mut((height - 1 - y) * row_len);
+            top[y * row_len..(y + 1) * row_len].swap_with_slice(&mut bottom[..row_len]);
+        }
+        pixels
+    }
+}
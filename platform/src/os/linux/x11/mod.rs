@@ -5,4 +5,5 @@ pub mod xlib_window;
 pub mod xlib_event;
 pub mod linux_x11; 
 pub mod linux_x11_stdin; 
+pub mod linux_x11_headless;
 
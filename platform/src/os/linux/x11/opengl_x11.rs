@@ -317,8 +317,15 @@ impl OpenglCx {
             "can't bind EGL_OPENGL_ES_API",
         );
 
-        // Choose framebuffer configuration.
+        // Choose framebuffer configuration, without a display server there are no windows
+        let surface_type = if egl_platform == egl_sys::EGL_PLATFORM_SURFACELESS_MESA {
+            egl_sys::EGL_PBUFFER_BIT
+        } else {
+            egl_sys::EGL_WINDOW_BIT
+        };
         let cfg_attribs = [
+            egl_sys::EGL_SURFACE_TYPE,
+            surface_type,
             egl_sys::EGL_RED_SIZE,
             8,
             egl_sys::EGL_GREEN_SIZE,
@@ -404,6 +404,11 @@ extern "C" {
         arg4: Time,
     ) -> c_int;
     
+    pub fn XGetSelectionOwner(
+        arg1: *mut Display,
+        arg2: Atom,
+    ) -> Window;
+    
     pub fn Xutf8LookupString(
         arg1: XIC,
         arg2: *mut XKeyPressedEvent,
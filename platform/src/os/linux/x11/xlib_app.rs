@@ -103,60 +103,16 @@ impl XlibApp {
                     let selection = event.xselection;
                     if selection.property == self.dnd.atoms.selection {
                         self.dnd.handle_selection_event(&selection);
-                    } else {
-                        // first get the size of the thing
-                        let mut actual_type = mem::MaybeUninit::uninit();
-                        let mut actual_format = mem::MaybeUninit::uninit();
-                        let mut n_items = mem::MaybeUninit::uninit();
-                        let mut bytes_to_read = mem::MaybeUninit::uninit();
-                        let mut ret = mem::MaybeUninit::uninit();
-                        x11_sys::XGetWindowProperty(
-                            self.display,
-                            selection.requestor,
-                            selection.property,
-                            0,
-                            0,
-                            0,
-                            x11_sys::AnyPropertyType as c_ulong,
-                            actual_type.as_mut_ptr(),
-                            actual_format.as_mut_ptr(),
-                            n_items.as_mut_ptr(),
-                            bytes_to_read.as_mut_ptr(),
-                            ret.as_mut_ptr()
-                        );
-                        //let actual_type = actual_type.assume_init();
-                        //let actual_format = actual_format.assume_init();
-                        //let n_items = n_items.assume_init();
-                        let bytes_to_read = bytes_to_read.assume_init();
-                        //let mut ret = ret.assume_init();
-                        let mut bytes_after = mem::MaybeUninit::uninit();
-                        x11_sys::XGetWindowProperty(
-                            self.display,
-                            selection.requestor,
-                            selection.property,
-                            0,
-                            bytes_to_read as c_long,
-                            0,
-                            x11_sys::AnyPropertyType as c_ulong,
-                            actual_type.as_mut_ptr(),
-                            actual_format.as_mut_ptr(),
-                            n_items.as_mut_ptr(),
-                            bytes_after.as_mut_ptr(),
-                            ret.as_mut_ptr()
-                        );
-                        let ret = ret.assume_init();
-                        //let bytes_after = bytes_after.assume_init();
-                        if ret != ptr::null_mut() && bytes_to_read > 0 {
-                            let utf8_slice = std::slice::from_raw_parts::<u8>(ret as *const _ as *const u8, bytes_to_read as usize);
-                            if let Ok(utf8_string) = String::from_utf8(utf8_slice.to_vec()) {
-                                self.do_callback(XlibEvent::TextInput(TextInputEvent {
-                                    input: utf8_string,
-                                    was_paste: true,
-                                    replace_last: false
-                                }));
-                            }
-                            x11_sys::XFree(ret as *mut _ as *mut c_void);
+                    } else if selection.property == self.atoms.clipboard_read {
+                        if let Some(text) = self.read_selection_text(&selection) {
+                            self.do_callback(XlibEvent::ClipboardTextReceived(text));
                         }
+                    } else if let Some(text) = self.read_selection_text(&selection) {
+                        self.do_callback(XlibEvent::TextInput(TextInputEvent {
+                            input: text,
+                            was_paste: true,
+                            replace_last: false
+                        }));
                     }
                 },
                 x11_sys::SelectionRequest => {
@@ -857,6 +813,87 @@ impl XlibApp {
         }
     }
 
+    unsafe fn read_selection_text(&self, selection: &x11_sys::XSelectionEvent) -> Option<String> {
+        // first get the size of the thing
+        let mut actual_type = mem::MaybeUninit::uninit();
+        let mut actual_format = mem::MaybeUninit::uninit();
+        let mut n_items = mem::MaybeUninit::uninit();
+        let mut bytes_to_read = mem::MaybeUninit::uninit();
+        let mut ret = mem::MaybeUninit::uninit();
+        x11_sys::XGetWindowProperty(
+            self.display,
+            selection.requestor,
+            selection.property,
+            0,
+            0,
+            0,
+            x11_sys::AnyPropertyType as c_ulong,
+            actual_type.as_mut_ptr(),
+            actual_format.as_mut_ptr(),
+            n_items.as_mut_ptr(),
+            bytes_to_read.as_mut_ptr(),
+            ret.as_mut_ptr()
+        );
+        let bytes_to_read = bytes_to_read.assume_init();
+        let mut bytes_after = mem::MaybeUninit::uninit();
+        x11_sys::XGetWindowProperty(
+            self.display,
+            selection.requestor,
+            selection.property,
+            0,
+            bytes_to_read as c_long,
+            0,
+            x11_sys::AnyPropertyType as c_ulong,
+            actual_type.as_mut_ptr(),
+            actual_format.as_mut_ptr(),
+            n_items.as_mut_ptr(),
+            bytes_after.as_mut_ptr(),
+            ret.as_mut_ptr()
+        );
+        let ret = ret.assume_init();
+        let mut text = None;
+        if ret != ptr::null_mut() && bytes_to_read > 0 {
+            let utf8_slice = std::slice::from_raw_parts::<u8>(ret as *const _ as *const u8, bytes_to_read as usize);
+            text = String::from_utf8(utf8_slice.to_vec()).ok();
+            x11_sys::XFree(ret as *mut _ as *mut c_void);
+        }
+        text
+    }
+    
+    /// Takes ownership of the clipboard with `text`, other applications request it from us.
+    pub unsafe fn set_clipboard_text(&mut self, text: &str) {
+        self.clipboard = text.to_string();
+        if let Some(window) = self.window_map.values().next() {
+            x11_sys::XSetSelectionOwner(
+                self.display,
+                self.atoms.clipboard,
+                (**window).window.unwrap(),
+                x11_sys::CurrentTime as x11_sys::Time
+            );
+            x11_sys::XFlush(self.display);
+        }
+    }
+    
+    /// Returns the clipboard text right away when we own it, otherwise asks the owner for it
+    /// and sends `ClipboardTextReceived` once it arrives.
+    pub unsafe fn request_clipboard_text(&mut self) -> Option<String> {
+        let owner = x11_sys::XGetSelectionOwner(self.display, self.atoms.clipboard);
+        if self.window_map.contains_key(&owner) {
+            return Some(self.clipboard.clone())
+        }
+        let window = self.window_map.values().next()?;
+        x11_sys::XConvertSelection(
+            self.display,
+            self.atoms.clipboard,
+            self.atoms.utf8_string,
+            self.atoms.clipboard_read,
+            (**window).window.unwrap(),
+            x11_sys::CurrentTime as x11_sys::Time
+        );
+        x11_sys::XFlush(self.display);
+        None
+    }
+    
     unsafe fn copy_to_clipboard(&mut self, text: &String, window: &XlibWindow, event: &XEvent) {
         // store the text on the clipboard
         self.clipboard = text.clone();
@@ -873,6 +910,7 @@ impl XlibApp {
 
 pub struct XlibAtoms {
     pub clipboard: x11_sys::Atom,
+    pub clipboard_read: x11_sys::Atom,
     pub net_wm_moveresize: x11_sys::Atom,
     pub wm_delete_window: x11_sys::Atom,
     pub wm_protocols: x11_sys::Atom,
@@ -880,6 +918,7 @@ pub struct XlibAtoms {
     pub net_wm_state: x11_sys::Atom,
     pub new_wm_state_maximized_horz: x11_sys::Atom,
     pub new_wm_state_maximized_vert: x11_sys::Atom,
+    pub net_wm_state_fullscreen: x11_sys::Atom,
     pub targets: x11_sys::Atom,
     pub utf8_string: x11_sys::Atom,
     pub text: x11_sys::Atom,
@@ -892,6 +931,7 @@ impl XlibAtoms {
     fn new(display: *mut x11_sys::Display) -> Self {
         unsafe {Self {
             clipboard: x11_sys::XInternAtom(display, "CLIPBOARD\0".as_ptr() as *const _, 0),
+            clipboard_read: x11_sys::XInternAtom(display, "MAKEPAD_CLIPBOARD_READ\0".as_ptr() as *const _, 0),
             net_wm_moveresize: x11_sys::XInternAtom(display, "_NET_WM_MOVERESIZE\0".as_ptr() as *const _, 0),
             wm_delete_window: x11_sys::XInternAtom(display, "WM_DELETE_WINDOW\0".as_ptr() as *const _, 0),
             wm_protocols: x11_sys::XInternAtom(display, "WM_PROTOCOLS\0".as_ptr() as *const _, 0),
@@ -899,6 +939,7 @@ impl XlibAtoms {
             net_wm_state: x11_sys::XInternAtom(display, "_NET_WM_STATE\0".as_ptr() as *const _, 0),
             new_wm_state_maximized_horz: x11_sys::XInternAtom(display, "_NET_WM_STATE_MAXIMIZED_HORZ\0".as_ptr() as *const _, 0),
             new_wm_state_maximized_vert: x11_sys::XInternAtom(display, "_NET_WM_STATE_MAXIMIZED_VERT\0".as_ptr() as *const _, 0),
+            net_wm_state_fullscreen: x11_sys::XInternAtom(display, "_NET_WM_STATE_FULLSCREEN\0".as_ptr() as *const _, 0),
             targets: x11_sys::XInternAtom(display, "TARGETS\0".as_ptr() as *const _, 0),
             utf8_string: x11_sys::XInternAtom(display, "UTF8_STRING\0".as_ptr() as *const _, 1),
             atom: x11_sys::XInternAtom(display, "ATOM\0".as_ptr() as *const _, 0),
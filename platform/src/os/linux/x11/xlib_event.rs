@@ -42,5 +42,6 @@ pub enum XlibEvent {
     KeyUp(KeyEvent),
     TextCopy(TextClipboardEvent),
     TextCut(TextClipboardEvent),
+    ClipboardTextReceived(String),
     Timer(TimerEvent),
 }
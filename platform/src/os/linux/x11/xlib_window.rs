@@ -181,6 +181,12 @@ impl XlibWindow {
     }
     
     fn restore_or_maximize(&self, add_remove: c_long) {
+        let atoms = &get_xlib_app_global().atoms;
+        self.change_wm_state(add_remove, atoms.new_wm_state_maximized_horz, atoms.new_wm_state_maximized_vert);
+    }
+    
+    // asks the window manager to add or remove up to two _NET_WM_STATE properties
+    fn change_wm_state(&self, add_remove: c_long, first: x11_sys::Atom, second: x11_sys::Atom) {
         unsafe {
             let default_screen = x11_sys::XDefaultScreen(get_xlib_app_global().display);
             let root_window = x11_sys::XRootWindow(get_xlib_app_global().display, default_screen);
@@ -195,8 +201,8 @@ impl XlibWindow {
                 data: {
                     let mut msg = mem::zeroed::<x11_sys::XClientMessageEvent__bindgen_ty_1>();
                     msg.l[0] = add_remove;
-                    msg.l[1] = get_xlib_app_global().atoms.new_wm_state_maximized_horz as c_long;
-                    msg.l[2] = get_xlib_app_global().atoms.new_wm_state_maximized_vert as c_long;
+                    msg.l[1] = first as c_long;
+                    msg.l[2] = second as c_long;
                     msg
                 }
             };
@@ -218,6 +224,11 @@ impl XlibWindow {
         self.restore_or_maximize(_NET_WM_STATE_ADD);
     }
     
+    pub fn set_fullscreen(&self, fullscreen: bool) {
+        let add_remove = if fullscreen {_NET_WM_STATE_ADD} else {_NET_WM_STATE_REMOVE};
+        self.change_wm_state(add_remove, get_xlib_app_global().atoms.net_wm_state_fullscreen, 0);
+    }
+    
     pub fn close_window(&mut self) {
         unsafe {
             x11_sys::XDestroyWindow(get_xlib_app_global().display, self.window.unwrap());
@@ -247,7 +258,7 @@ impl XlibWindow {
             xr_is_presenting: false,
             can_fullscreen: false,
             is_topmost: self.get_is_topmost(),
-            is_fullscreen: self.get_is_maximized(),
+            is_fullscreen: self.get_is_maximized() || self.get_is_fullscreen(),
             inner_size: self.get_inner_size(),
             outer_size: self.get_outer_size(),
             dpi_factor: self.get_dpi_factor(),
@@ -256,7 +267,16 @@ impl XlibWindow {
     }
     
     pub fn get_is_maximized(&self) -> bool {
-        let mut maximized = false;
+        let atoms = &get_xlib_app_global().atoms;
+        self.has_wm_state(&[atoms.new_wm_state_maximized_horz, atoms.new_wm_state_maximized_vert])
+    }
+    
+    pub fn get_is_fullscreen(&self) -> bool {
+        self.has_wm_state(&[get_xlib_app_global().atoms.net_wm_state_fullscreen])
+    }
+    
+    fn has_wm_state(&self, states: &[x11_sys::Atom]) -> bool {
+        let mut found = false;
         unsafe {
             let mut prop_type = mem::MaybeUninit::uninit();
             let mut format = mem::MaybeUninit::uninit();
@@ -285,16 +305,15 @@ impl XlibWindow {
             if result == 0 && properties != ptr::null_mut() {
                 let items = std::slice::from_raw_parts::<c_ulong>(properties as *mut _, n_item as usize);
                 for item in items {
-                    if *item == get_xlib_app_global().atoms.new_wm_state_maximized_horz
-                        || *item == get_xlib_app_global().atoms.new_wm_state_maximized_vert {
-                        maximized = true;
+                    if states.contains(item) {
+                        found = true;
                         break;
                     }
                 }
                 x11_sys::XFree(properties as *mut _);
             }
         }
-        maximized
+        found
     }
     
     pub fn set_ime_spot(&mut self, spot: DVec2) {
@@ -98,6 +98,42 @@ pub struct FromWasmTextCopyResponse {
     pub response: String
 }
 
+#[derive(FromWasm)]
+pub struct FromWasmClipboardWriteText {
+    pub text: String
+}
+
+#[derive(FromWasm)]
+pub struct FromWasmClipboardReadText {
+}
+
+#[derive(FromWasm)]
+pub struct FromWasmSelectFiles {
+    pub accept: String,
+    pub multiple: bool
+}
+
+#[derive(FromWasm)]
+pub struct FromWasmStorageSet {
+    pub key: String,
+    pub value: String
+}
+
+#[derive(FromWasm)]
+pub struct FromWasmStorageRemove {
+    pub key: String
+}
+
+#[derive(FromWasm)]
+pub struct FromWasmStorageClear {
+}
+
+#[derive(FromWasm)]
+pub struct FromWasmAnnounce {
+    pub message: String,
+    pub assertive: bool
+}
+
 #[derive(FromWasm)]
 pub struct FromWasmShowTextIME {
     pub x: f64,
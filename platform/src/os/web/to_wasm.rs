@@ -73,6 +73,12 @@ pub struct WDepLoaded {
     pub data: WasmDataU8
 }
 
+#[derive(ToWasm)]
+pub struct WStorageEntry {
+    pub key: String,
+    pub value: String
+}
+
 #[derive(ToWasm)]
 pub struct WWindowInfo {
     pub is_fullscreen: bool,
@@ -118,6 +124,7 @@ impl Into<XrCapabilities> for WXrCapabilities {
 #[derive(ToWasm)]
 pub struct ToWasmInit {
     pub deps: Vec<WDepLoaded>,
+    pub storage: Vec<WStorageEntry>,
     pub window_info: WWindowInfo
 }
 
@@ -499,6 +506,17 @@ impl Into<TextInputEvent> for ToWasmTextInput {
 pub struct ToWasmTextCopy {
 }
 
+#[derive(ToWasm)]
+pub struct ToWasmClipboardText {
+    pub text: String
+}
+
+#[derive(ToWasm)]
+pub struct ToWasmFileDialogResult {
+    pub names: String,
+    pub cancelled: bool
+}
+
 // Keyboard API
 
 
@@ -72,10 +72,22 @@ export class WasmWebBrowser extends WasmBridge {
         }
         this.update_window_info();
         
+        let storage = [];
+        try {
+            for (let i = 0; i < window.localStorage.length; i ++) {
+                let key = window.localStorage.key(i);
+                storage.push({key, value: window.localStorage.getItem(key)});
+            }
+        }
+        catch (err) {
+            console.warn("Cannot read localStorage", err);
+        }
+        
         this.to_wasm.ToWasmInit({
             xr_capabilities: this.xr_capabilities,
             window_info: this.window_info,
-            deps: deps
+            deps: deps,
+            storage: storage
         });
         this.do_wasm_pump();
         // only bind the event handlers now
@@ -237,6 +249,103 @@ export class WasmWebBrowser extends WasmBridge {
         this.text_copy_response = args.response
     }
     
+    has_async_clipboard(method) {
+        // the async clipboard only exists in secure contexts, and firefox cannot read it
+        if (navigator.clipboard && typeof navigator.clipboard[method] === "function") {
+            return true;
+        }
+        console.warn("This browser has no navigator.clipboard." + method + ", the clipboard is left alone");
+        return false;
+    }
+    
+    FromWasmClipboardWriteText(args) {
+        if (!this.has_async_clipboard("writeText")) {
+            return;
+        }
+        navigator.clipboard.writeText(args.text).catch(err => {
+            console.error("Cannot write to the clipboard", err);
+        });
+    }
+    
+    FromWasmClipboardReadText() {
+        if (!this.has_async_clipboard("readText")) {
+            return;
+        }
+        navigator.clipboard.readText().then(text => {
+            this.to_wasm.ToWasmClipboardText({text});
+            this.do_wasm_pump();
+        }).catch(err => {
+            console.error("Cannot read the clipboard", err);
+        });
+    }
+    
+    FromWasmSelectFiles(args) {
+        // the page only ever gets the file names, not their location on disk
+        let input = document.createElement('input');
+        input.type = 'file';
+        input.accept = args.accept;
+        input.multiple = args.multiple;
+        input.addEventListener('change', () => {
+            let names = Array.from(input.files).map(file => file.name).join("\n");
+            this.to_wasm.ToWasmFileDialogResult({names, cancelled: false});
+            this.do_wasm_pump();
+        });
+        input.addEventListener('cancel', () => {
+            this.to_wasm.ToWasmFileDialogResult({names: "", cancelled: true});
+            this.do_wasm_pump();
+        });
+        input.click();
+    }
+    
+    FromWasmStorageSet(args) {
+        try {
+            window.localStorage.setItem(args.key, args.value);
+        }
+        catch (err) {
+            console.error("Cannot write " + args.key + " to localStorage", err);
+        }
+    }
+    
+    FromWasmStorageRemove(args) {
+        try {
+            window.localStorage.removeItem(args.key);
+        }
+        catch (err) {
+            console.error("Cannot remove " + args.key + " from localStorage", err);
+        }
+    }
+    
+    FromWasmStorageClear() {
+        try {
+            window.localStorage.clear();
+        }
+        catch (err) {
+            console.error("Cannot clear localStorage", err);
+        }
+    }
+    
+    FromWasmAnnounce(args) {
+        // screen readers pick up changes to aria-live regions
+        let key = args.assertive ? "assertive" : "polite";
+        if (this.live_regions === undefined) {
+            this.live_regions = {};
+        }
+        let region = this.live_regions[key];
+        if (region === undefined) {
+            region = document.createElement('div');
+            region.setAttribute('aria-live', key);
+            region.setAttribute('aria-atomic', 'true');
+            region.style.cssText = "position:absolute;width:1px;height:1px;overflow:hidden;clip:rect(0 0 0 0);white-space:nowrap";
+            document.body.appendChild(region);
+            this.live_regions[key] = region;
+        }
+        // clear first so the same message gets announced again
+        region.textContent = "";
+        setTimeout(() => {
+            region.textContent = args.message;
+        }, 50);
+    }
+    
     FromWasmShowTextIME(args) {
         this.update_text_area_pos(args);
     }
@@ -1,6 +1,7 @@
 
 use {
     std::rc::Rc,
+    std::path::PathBuf,
     std::cell::RefCell,
     self::super::{
         web_media::CxWebMedia,
@@ -12,6 +13,7 @@ use {
         makepad_live_id::*,
         makepad_wasm_bridge::{WasmDataU8, FromWasmMsg, ToWasmMsg, FromWasm, ToWasm},
         thread::SignalToUI,
+        storage::StorageOp,
         window::{
             CxWindowPool
         },
@@ -35,6 +37,8 @@ use {
         pass::CxPassParent,
         cx_api::{CxOsApi, CxOsOp},
         cx::{Cx},
+        accessibility::AnnouncePriority,
+        file_dialogs::FileDialogError,
     }
 };
 
@@ -84,6 +88,7 @@ impl Cx {
                         }
                     }
                     self.os.window_geom = tw.window_info.into();
+                    self.local_storage.init(tw.storage.into_iter().map( | entry | (entry.key, entry.value)).collect());
                     //self.default_inner_window_size = self.os.window_geom.inner_size;
                     
                     self.call_event_handler(&Event::Startup);
@@ -179,6 +184,22 @@ impl Cx {
                     }
                 }
                 
+                live_id!(ToWasmClipboardText) => {
+                    let tw = ToWasmClipboardText::read_to_wasm(&mut to_wasm);
+                    self.call_event_handler(&Event::ClipboardTextReceived(tw.text));
+                }
+                
+                live_id!(ToWasmFileDialogResult) => {
+                    let tw = ToWasmFileDialogResult::read_to_wasm(&mut to_wasm);
+                    let result = if tw.cancelled {
+                        Err(FileDialogError::Cancelled)
+                    }
+                    else {
+                        Ok(tw.names.lines().map(PathBuf::from).collect())
+                    };
+                    self.call_event_handler(&Event::FileDialogResult(result));
+                }
+                
                 live_id!(ToWasmSignal) =>{
                     self.handle_media_signals();
                     self.call_event_handler(&Event::Signal);
@@ -352,6 +373,7 @@ impl Cx {
         }
 
         self.handle_platform_ops();
+        self.handle_storage_ops();
         self.handle_media_signals();
         
         if self.any_passes_dirty() || self.need_redrawing() || self.new_next_frames.len() != 0 {
@@ -392,6 +414,16 @@ impl Cx {
     where F: FnMut(&mut Cx, Event) {
     }
     
+    fn handle_storage_ops(&mut self) {
+        for op in std::mem::take(&mut self.local_storage.ops) {
+            match op {
+                StorageOp::Set(key, value) => self.os.from_wasm(FromWasmStorageSet {key, value}),
+                StorageOp::Remove(key) => self.os.from_wasm(FromWasmStorageRemove {key}),
+                StorageOp::Clear => self.os.from_wasm(FromWasmStorageClear {}),
+            }
+        }
+    }
+    
     fn handle_platform_ops(&mut self) {
         while let Some(op) = self.platform_ops.pop() {
             match op {
@@ -502,10 +534,27 @@ impl Cx {
                 CxOsOp::UnmuteVideoPlayback(_) => todo!(),
                 CxOsOp::CleanupVideoPlaybackResources(_) => todo!(),
                 CxOsOp::UpdateVideoSurfaceTexture(_) => todo!(),
-                CxOsOp::SaveFileDialog(_) => todo!(),
-                CxOsOp::SelectFileDialog(_) => todo!(),
+                CxOsOp::SaveFileDialog(_) => {
+                    // browsers only offer downloads, there is no path to save to
+                    self.call_event_handler(&Event::FileDialogResult(Err(FileDialogError::Unsupported)));
+                }
+                CxOsOp::SelectFileDialog(options) => {
+                    let accept: Vec<String> = options.filters.iter()
+                        .flat_map( | filter | filter.extensions.iter().map( | ext | format!(".{}", ext)))
+                        .collect();
+                    self.os.from_wasm(FromWasmSelectFiles {
+                        accept: accept.join(","),
+                        multiple: options.multi_select
+                    });
+                }
                 CxOsOp::SaveFolderDialog(_) => todo!(),
                 CxOsOp::SelectFolderDialog(_) => todo!(),    
+                CxOsOp::Announce {message, priority} => {
+                    self.os.from_wasm(FromWasmAnnounce {
+                        message,
+                        assertive: priority == AnnouncePriority::Assertive
+                    });
+                }
             }
         }
     }
@@ -533,6 +582,8 @@ impl CxOsApi for Cx {
             ToWasmKeyUp::to_js_code(),
             ToWasmTextInput::to_js_code(),
             ToWasmTextCopy::to_js_code(),
+            ToWasmClipboardText::to_js_code(),
+            ToWasmFileDialogResult::to_js_code(),
             ToWasmTimerFired::to_js_code(),
             ToWasmPaintDirty::to_js_code(),
             ToWasmRedrawAll::to_js_code(),
@@ -565,6 +616,13 @@ impl CxOsApi for Cx {
             FromWasmSetDocumentTitle::to_js_code(),
             FromWasmSetMouseCursor::to_js_code(),
             FromWasmTextCopyResponse::to_js_code(),
+            FromWasmClipboardWriteText::to_js_code(),
+            FromWasmClipboardReadText::to_js_code(),
+            FromWasmAnnounce::to_js_code(),
+            FromWasmStorageSet::to_js_code(),
+            FromWasmStorageRemove::to_js_code(),
+            FromWasmStorageClear::to_js_code(),
+            FromWasmSelectFiles::to_js_code(),
             FromWasmShowTextIME::to_js_code(),
             FromWasmHideTextIME::to_js_code(),
             FromWasmCreateThread::to_js_code(),
@@ -600,6 +658,16 @@ impl CxOsApi for Cx {
         self.os.from_wasm(FromWasmCreateThread {context_ptr: context_ptr as u32});
     }
     
+    fn clipboard_set_text(&mut self, text: &str) {
+        self.os.from_wasm(FromWasmClipboardWriteText {text: text.to_string()});
+    }
+    
+    fn clipboard_get_text(&mut self) -> Option<String> {
+        // the browser only hands out the clipboard asynchronously
+        self.os.from_wasm(FromWasmClipboardReadText {});
+        None
+    }
+    
     /*
     fn start_midi_input(&mut self) {
         self.platform.from_wasm(FromWasmStartMidiInput {
@@ -76,6 +76,7 @@ use {
                         GetClientRect,
                         MoveWindow,
                         GWL_EXSTYLE,
+                        GWL_STYLE,
                         HWND_TOPMOST,
                         HWND_NOTOPMOST,
                         WS_SIZEBOX,
@@ -97,6 +98,9 @@ use {
                         SW_MINIMIZE,
                         SWP_NOMOVE,
                         SWP_NOSIZE,
+                        SWP_NOZORDER,
+                        SWP_NOOWNERZORDER,
+                        SWP_FRAMECHANGED,
                         WM_ACTIVATE,
                         WM_NCCALCSIZE,
                         WM_NCHITTEST,
@@ -254,7 +258,13 @@ use {
                 },
                 Graphics::{
                     Dwm::DwmExtendFrameIntoClientArea,
-                    Gdi::ScreenToClient,
+                    Gdi::{
+                        ScreenToClient,
+                        MonitorFromWindow,
+                        GetMonitorInfoW,
+                        MONITORINFO,
+                        MONITOR_DEFAULTTONEAREST,
+                    },
                 },
             },
         },
@@ -300,6 +310,8 @@ pub struct Win32Window {
     pub ignore_wmsize: usize,
     pub hwnd: HWND,
     pub track_mouse_event: bool,
+    // the style and rect to go back to when leaving fullscreen
+    pub fullscreen_restore: Option<(isize, RECT)>,
 }
 
 impl Win32Window {
@@ -360,6 +372,7 @@ impl Win32Window {
             ignore_wmsize: 0,
             hwnd,
             track_mouse_event: false,
+            fullscreen_restore: None,
         }
     }
 
@@ -531,30 +544,14 @@ impl Win32Window {
                 if modifiers.control || modifiers.logo {
                     match key_code {
                         KeyCode::KeyV => { // paste
-                            if let Ok(()) = OpenClipboard(None) {
-                                let mut data: Vec<u16> = Vec::new();
-                                let h_clipboard_data = GetClipboardData(CF_UNICODETEXT.0 as u32).unwrap();
-                                let h_clipboard_ptr = GlobalLock(std::mem::transmute::<_,HGLOBAL>(h_clipboard_data)) as *mut u16;
-                                let clipboard_size = GlobalSize(std::mem::transmute::<_,HGLOBAL>(h_clipboard_data));
-                                if clipboard_size > 2 {
-                                    data.resize((clipboard_size >> 1) - 1, 0);
-                                    std::ptr::copy_nonoverlapping(h_clipboard_ptr, data.as_mut_ptr(), data.len());
-                                    GlobalUnlock(std::mem::transmute::<_,HGLOBAL>(h_clipboard_data)).unwrap();
-                                    CloseClipboard().unwrap();
-                                    if let Ok(utf8) = String::from_utf16(&data) {
-                                        window.do_callback(
-                                            Win32Event::TextInput(TextInputEvent {
-                                                input: utf8,
-                                                was_paste: true,
-                                                replace_last: false
-                                            })
-                                        );
-                                    }
-                                }
-                                else {
-                                    GlobalUnlock(std::mem::transmute::<_,HGLOBAL>(h_clipboard_data)).unwrap();
-                                    CloseClipboard().unwrap();
-                                }
+                            if let Some(utf8) = Self::read_clipboard() {
+                                window.do_callback(
+                                    Win32Event::TextInput(TextInputEvent {
+                                        input: utf8,
+                                        was_paste: true,
+                                        replace_last: false
+                                    })
+                                );
                             }
                         }
                         KeyCode::KeyC => {
@@ -735,7 +732,30 @@ impl Win32Window {
         //run_catch_panic(-1, || callback_inner(window, msg, wparam, lparam))
     }
 
-    unsafe fn copy_to_clipboard(text: &String) {
+    pub (crate) unsafe fn read_clipboard() -> Option<String> {
+        if let Ok(()) = OpenClipboard(None) {
+            let mut data: Vec<u16> = Vec::new();
+            let Ok(h_clipboard_data) = GetClipboardData(CF_UNICODETEXT.0 as u32) else {
+                CloseClipboard().unwrap();
+                return None
+            };
+            let h_clipboard_ptr = GlobalLock(std::mem::transmute::<_,HGLOBAL>(h_clipboard_data)) as *mut u16;
+            let clipboard_size = GlobalSize(std::mem::transmute::<_,HGLOBAL>(h_clipboard_data));
+            if clipboard_size > 2 {
+                data.resize((clipboard_size >> 1) - 1, 0);
+                std::ptr::copy_nonoverlapping(h_clipboard_ptr, data.as_mut_ptr(), data.len());
+            }
+            GlobalUnlock(std::mem::transmute::<_,HGLOBAL>(h_clipboard_data)).unwrap();
+            CloseClipboard().unwrap();
+            if data.is_empty() {
+                return None
+            }
+            return String::from_utf16(&data).ok()
+        }
+        None
+    }
+
+    pub (crate) unsafe fn copy_to_clipboard(text: &String) {
         // plug it into the windows clipboard
         // make utf16 dta
         if let Ok(()) = OpenClipboard(None) {
@@ -806,6 +826,47 @@ impl Win32Window {
         }
     }
     
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        unsafe {
+            if fullscreen {
+                if self.fullscreen_restore.is_some() {
+                    return
+                }
+                let style = GetWindowLongPtrW(self.hwnd, GWL_STYLE);
+                let mut rect = RECT {left: 0, top: 0, bottom: 0, right: 0};
+                GetWindowRect(self.hwnd, &mut rect).unwrap();
+                let mut info = MONITORINFO {cbSize: mem::size_of::<MONITORINFO>() as u32, ..Default::default()};
+                GetMonitorInfoW(MonitorFromWindow(self.hwnd, MONITOR_DEFAULTTONEAREST), &mut info);
+                self.fullscreen_restore = Some((style, rect));
+                // drop the resize border so the window covers the whole monitor
+                let border = (WS_SIZEBOX | WS_MAXIMIZEBOX | WS_MINIMIZEBOX | WS_SYSMENU).0 as isize;
+                SetWindowLongPtrW(self.hwnd, GWL_STYLE, style & !border);
+                let m = info.rcMonitor;
+                SetWindowPos(
+                    self.hwnd,
+                    HWND(0),
+                    m.left,
+                    m.top,
+                    m.right - m.left,
+                    m.bottom - m.top,
+                    SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_FRAMECHANGED
+                ).unwrap();
+            }
+            else if let Some((style, rect)) = self.fullscreen_restore.take() {
+                SetWindowLongPtrW(self.hwnd, GWL_STYLE, style);
+                SetWindowPos(
+                    self.hwnd,
+                    HWND(0),
+                    rect.left,
+                    rect.top,
+                    rect.right - rect.left,
+                    rect.bottom - rect.top,
+                    SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_FRAMECHANGED
+                ).unwrap();
+            }
+        }
+    }
+    
     pub fn minimize(&self) {
         unsafe {
             ShowWindow(self.hwnd, SW_MINIMIZE);
@@ -854,7 +915,7 @@ impl Win32Window {
             xr_is_presenting: false,
             can_fullscreen: false,
             is_topmost: self.get_is_topmost(),
-            is_fullscreen: self.get_is_maximized(),
+            is_fullscreen: self.get_is_maximized() || self.fullscreen_restore.is_some(),
             inner_size: if self.get_is_maximized(){self.get_outer_size()}else{self.get_inner_size()},
             outer_size: self.get_outer_size(),
             dpi_factor: self.get_dpi_factor(),
@@ -14,12 +14,14 @@ use {
                 win32_event::*,
                 d3d11::{D3d11Window, D3d11Cx},
                 win32_app::*,
+                win32_window::Win32Window,
             },
             cx_native::EventFlow,
         },
         makepad_math::*,
         pass::CxPassParent,
         cx_api::{CxOsApi, CxOsOp},
+        file_dialogs::FileDialogError,
         window::CxWindowPool,
         windows::Win32::Graphics::Direct3D11::ID3D11Device,
     }
@@ -329,11 +331,15 @@ impl Cx {
                 CxOsOp::Quit=>{
                     ret = EventFlow::Exit
                 }
-                CxOsOp::FullscreenWindow(_window_id) => {
-                    todo!()
+                CxOsOp::FullscreenWindow(window_id) => {
+                    if let Some(window) = d3d11_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.win32_window.set_fullscreen(true);
+                    }
                 },
-                CxOsOp::NormalizeWindow(_window_id) => {
-                    todo!()
+                CxOsOp::NormalizeWindow(window_id) => {
+                    if let Some(window) = d3d11_windows.iter_mut().find( | w | w.window_id == window_id) {
+                        window.win32_window.set_fullscreen(false);
+                    }
                 }
                 CxOsOp::SetTopmost(_window_id, _is_topmost) => {
                     todo!()
@@ -366,8 +372,8 @@ impl Cx {
                 },
                 CxOsOp::UpdateMacosMenu(_menu) => {
                 },
-                CxOsOp::HttpRequest {request_id: _, request: _} => {
-                    todo!("HttpRequest not implemented yet on windows, we'll get there");
+                CxOsOp::HttpRequest {request_id, request} => {
+                    self.native_http_request(request_id, request);
                 },
                 CxOsOp::PrepareVideoPlayback(_, _, _, _, _) => todo!(),
                 CxOsOp::BeginVideoPlayback(_) => todo!(),
@@ -377,10 +383,14 @@ impl Cx {
                 CxOsOp::UnmuteVideoPlayback(_) => todo!(),
                 CxOsOp::CleanupVideoPlaybackResources(_) => todo!(),
                 CxOsOp::UpdateVideoSurfaceTexture(_) => todo!(),
-                CxOsOp::SaveFileDialog(_) =>  todo!(),
-                CxOsOp::SelectFileDialog(_) =>  todo!(),
+                CxOsOp::SaveFileDialog(_) | CxOsOp::SelectFileDialog(_) => {
+                    let _ = self.file_dialog_sender().send(Err(FileDialogError::Unsupported));
+                }
                 CxOsOp::SaveFolderDialog(_) =>  todo!(),
                 CxOsOp::SelectFolderDialog(_) =>  todo!(),
+                CxOsOp::Announce {..} => {
+                    crate::log!("Announcing to screen readers is not supported on Windows yet");
+                }
             }
         }
         ret
@@ -400,6 +410,14 @@ impl CxOsApi for Cx {
     fn spawn_thread<F>(&mut self, f: F) where F: FnOnce() + Send + 'static {
         std::thread::spawn(f);
     }
+
+    fn clipboard_set_text(&mut self, text: &str) {
+        unsafe {Win32Window::copy_to_clipboard(&text.to_string())}
+    }
+
+    fn clipboard_get_text(&mut self) -> Option<String> {
+        unsafe {Win32Window::read_clipboard()}
+    }
 }
 
 #[derive(Default)]
@@ -0,0 +1,152 @@
+use {
+    std::path::{Path, PathBuf},
+    crate::{
+        makepad_math::*,
+        cx::Cx,
+    },
+    makepad_zune_png::{
+        PngDecoder,
+        PngEncoder,
+        makepad_zune_core::{
+            bit_depth::BitDepth,
+            colorspace::ColorSpace,
+            options::EncoderOptions,
+        },
+    },
+};
+
+/// How far a colour channel may be off before a pixel counts as changed, enough to absorb
+/// differences in anti-aliasing between GPU drivers.
+pub const DEFAULT_SNAPSHOT_TOLERANCE: u8 = 2;
+
+/// The pixels in which a rendering differs from its golden file.
+pub struct SnapshotDiff {
+    pub width: usize,
+    pub height: usize,
+    pub mismatched_pixels: usize,
+    /// RGBA, the rendering faded out with the mismatched pixels in red.
+    pub image: Vec<u8>,
+}
+
+impl SnapshotDiff {
+    /// Compares two RGBA images of the same size, `None` when they match within `tolerance`.
+    pub fn compare(expected: &[u8], actual: &[u8], width: usize, height: usize, tolerance: u8) -> Option<SnapshotDiff> {
+        let mut mismatched_pixels = 0;
+        let mut image = Vec::with_capacity(width * height * 4);
+        for (expected, actual) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+            let matches = expected.iter().zip(actual).all( | (a, b) | a.abs_diff(*b) <= tolerance);
+            if matches {
+                image.extend(actual[0..3].iter().map( | c | c / 4 + 191));
+                image.push(255);
+            }
+            else {
+                mismatched_pixels += 1;
+                image.extend([255, 0, 0, 255]);
+            }
+        }
+        if mismatched_pixels == 0 {
+            return None
+        }
+        Some(SnapshotDiff {width, height, mismatched_pixels, image})
+    }
+}
+
+impl Cx {
+    /// Sets how far colour channels may differ in `assert_snapshot`, 0 asks for exact equality.
+    pub fn set_snapshot_tolerance(&mut self, tolerance: u8) {
+        self.headless.as_mut().expect("snapshots need a Cx from Cx::new_headless").snapshot_tolerance = tolerance;
+    }
+
+    /// Renders the main window and compares it to `tests/snapshots/{name}.png` of the crate
+    /// under test. A missing golden file is written instead, as are all of them when
+    /// `UPDATE_SNAPSHOTS=1` is set. On a mismatch the diff is saved next to the golden file as
+    /// `{name}.diff.png` before panicking. Needs the `snapshot` feature, best enabled on the
+    /// `makepad-platform` dev-dependency of the crate under test.
+    pub fn assert_snapshot(&mut self, name: &str) {
+        let (width, height) = self.headless_size();
+        self.assert_snapshot_region(name, Rect {
+            pos: dvec2(0.0, 0.0),
+            size: dvec2(width as f64, height as f64),
+        });
+    }
+
+    /// Like `assert_snapshot`, but only compares the pixels inside `rect`, which makes for tests
+    /// that do not break whenever something else on the screen moves.
+    pub fn assert_snapshot_region(&mut self, name: &str, rect: Rect) {
+        let (width, height) = self.headless_size();
+        let pixels = self.render_to_rgba();
+        // headless windows have a dpi factor of 1, so logical pixels are device pixels
+        let x0 = (rect.pos.x.max(0.0) as usize).min(width);
+        let y0 = (rect.pos.y.max(0.0) as usize).min(height);
+        let x1 = ((rect.pos.x + rect.size.x).max(0.0) as usize).clamp(x0, width);
+        let y1 = ((rect.pos.y + rect.size.y).max(0.0) as usize).clamp(y0, height);
+        let (region_width, region_height) = (x1 - x0, y1 - y0);
+        let mut actual = Vec::with_capacity(region_width * region_height * 4);
+        for y in y0..y1 {
+            actual.extend_from_slice(&pixels[(y * width + x0) * 4..(y * width + x1) * 4]);
+        }
+
+        let path = snapshot_path(name, "png");
+        let update = std::env::var("UPDATE_SNAPSHOTS").map_or(false, | value | value == "1");
+        if update || !path.exists() {
+            write_png(&path, &actual, region_width, region_height);
+            log!("Wrote snapshot {}", path.display());
+            return
+        }
+        let (expected, expected_width, expected_height) = read_png(&path);
+        if (expected_width, expected_height) != (region_width, region_height) {
+            panic!(
+                "Snapshot {} is {}x{} but the rendering is {}x{}, rerun with UPDATE_SNAPSHOTS=1 if that is intended",
+                name, expected_width, expected_height, region_width, region_height
+            );
+        }
+        let tolerance = self.headless.as_ref().unwrap().snapshot_tolerance;
+        if let Some(diff) = SnapshotDiff::compare(&expected, &actual, region_width, region_height, tolerance) {
+            let diff_path = snapshot_path(name, "diff.png");
+            write_png(&diff_path, &diff.image, diff.width, diff.height);
+            panic!(
+                "Snapshot {} differs in {} pixels, see {}, rerun with UPDATE_SNAPSHOTS=1 if that is intended",
+                name, diff.mismatched_pixels, diff_path.display()
+            );
+        }
+    }
+
+    fn headless_size(&self) -> (usize, usize) {
+        let headless = self.headless.as_ref().expect("snapshots need a Cx from Cx::new_headless");
+        (headless.width as usize, headless.height as usize)
+    }
+}
+
+fn snapshot_path(name: &str, extension: &str) -> PathBuf {
+    // cargo test runs in the directory of the crate, but CARGO_MANIFEST_DIR also holds elsewhere
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").map_or_else( || PathBuf::from("."), PathBuf::from);
+    root.join("tests").join("snapshots").join(format!("{}.{}", name, extension))
+}
+
+fn write_png(path: &Path, rgba: &[u8], width: usize, height: usize) {
+    let options = EncoderOptions::new(width, height, ColorSpace::RGBA, BitDepth::Eight);
+    let png = PngEncoder::new(rgba, options).encode();
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Err(err) = std::fs::write(path, png) {
+        panic!("Cannot write {}: {}", path.display(), err);
+    }
+}
+
+fn read_png(path: &Path) -> (Vec<u8>, usize, usize) {
+    let data = std::fs::read(path).unwrap_or_else( | err | panic!("Cannot read {}: {}", path.display(), err));
+    let mut decoder = PngDecoder::new(&data[..]);
+    let pixels = match decoder.decode().map( | image | image.u8()) {
+        Ok(Some(pixels)) => pixels,
+        Ok(None) => panic!("Snapshot {} is not an 8 bit PNG", path.display()),
+        Err(err) => panic!("Cannot decode {}: {:?}", path.display(), err),
+    };
+    let (width, height) = decoder.get_dimensions().unwrap();
+    let rgba = match decoder.get_colorspace().unwrap() {
+        ColorSpace::RGBA => pixels,
+        ColorSpace::RGB => pixels.chunks_exact(3).flat_map( | c | [c[0], c[1], c[2], 255]).collect(),
+        colorspace => panic!("Snapshot {} has unsupported colorspace {:?}", path.display(), colorspace),
+    };
+    (rgba, width, height)
+}
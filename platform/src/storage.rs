@@ -0,0 +1,159 @@
+use {
+    std::collections::HashMap,
+    crate::{
+        cx::Cx,
+        makepad_micro_serde::*,
+    },
+};
+
+/// The largest value a key can hold, browsers refuse much more than this per origin.
+pub const MAX_STORAGE_VALUE_LEN: usize = 5 * 1024 * 1024;
+
+/// Keys may hold ASCII letters, digits and `.`, `_`, `-` and `/`, see `storage_key!`.
+pub const fn is_valid_storage_key(key: &str) -> bool {
+    let bytes = key.as_bytes();
+    if bytes.is_empty() {
+        return false
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b'_' | b'-' | b'/' => (),
+            _ => return false
+        }
+        i += 1;
+    }
+    true
+}
+
+/// A storage key checked at compile time.
+///
+/// ```ignore
+/// cx.local_storage().set(storage_key!("editor/font_size"), "12");
+/// ```
+#[macro_export]
+macro_rules! storage_key {
+    ($key:literal) => {{
+        const KEY: &str = $key;
+        const _: () = assert!($crate::storage::is_valid_storage_key(KEY), "storage keys may only hold ASCII letters, digits and . _ - /");
+        KEY
+    }};
+}
+
+#[cfg(target_arch = "wasm32")]
+pub (crate) enum StorageOp {
+    Set(String, String),
+    Remove(String),
+    Clear,
+}
+
+/// String values that survive restarts. On the web this is `window.localStorage`, elsewhere
+/// it is `~/.config/makepad/storage.json`. Get it with `Cx::local_storage`.
+#[derive(Default)]
+pub struct LocalStorage {
+    entries: Option<HashMap<String, String >>,
+    #[cfg(target_arch = "wasm32")]
+    pub (crate) ops: Vec<StorageOp>,
+}
+
+impl LocalStorage {
+    pub fn get(&mut self, key: &str) -> Option<String> {
+        self.entries().get(key).cloned()
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        if !is_valid_storage_key(key) {
+            error!("Invalid storage key {:?}", key);
+            return
+        }
+        if value.len() > MAX_STORAGE_VALUE_LEN {
+            error!("Storage value for {} is {} bytes, the maximum is {}", key, value.len(), MAX_STORAGE_VALUE_LEN);
+            return
+        }
+        if self.entries().get(key).map(String::as_str) == Some(value) {
+            return
+        }
+        self.entries().insert(key.to_string(), value.to_string());
+        #[cfg(target_arch = "wasm32")]
+        self.ops.push(StorageOp::Set(key.to_string(), value.to_string()));
+        self.save();
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        if self.entries().remove(key).is_none() {
+            return
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.ops.push(StorageOp::Remove(key.to_string()));
+        self.save();
+    }
+
+    pub fn clear(&mut self) {
+        self.entries().clear();
+        #[cfg(target_arch = "wasm32")]
+        self.ops.push(StorageOp::Clear);
+        self.save();
+    }
+
+    /// Fills the storage with what the page had in `window.localStorage` at startup.
+    #[cfg(target_arch = "wasm32")]
+    pub (crate) fn init(&mut self, entries: HashMap<String, String>) {
+        self.entries = Some(entries);
+    }
+
+    fn entries(&mut self) -> &mut HashMap<String, String> {
+        if self.entries.is_none() {
+            self.entries = Some(Self::load());
+        }
+        self.entries.as_mut().unwrap()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load() -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    // the browser writes through with the ops instead
+    #[cfg(target_arch = "wasm32")]
+    fn save(&mut self) {}
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn path() -> Option<std::path::PathBuf> {
+        let home = std::env::var_os("HOME").or_else( || std::env::var_os("USERPROFILE")) ?;
+        Some(std::path::Path::new(&home).join(".config").join("makepad").join("storage.json"))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load() -> HashMap<String, String> {
+        let Some(json) = Self::path().and_then( | path | std::fs::read_to_string(path).ok()) else {
+            return HashMap::new()
+        };
+        match HashMap::<String, String>::deserialize_json(&json) {
+            Ok(entries) => entries,
+            Err(err) => {
+                error!("Cannot read the local storage {:?}", err);
+                HashMap::new()
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save(&mut self) {
+        let Some(path) = Self::path() else {
+            return
+        };
+        let json = self.entries().serialize_json();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Err(err) = std::fs::write(&path, json) {
+            error!("Cannot write the local storage to {}: {}", path.display(), err);
+        }
+    }
+}
+
+impl Cx {
+    pub fn local_storage(&mut self) -> &mut LocalStorage {
+        &mut self.local_storage
+    }
+}
@@ -0,0 +1,306 @@
+use {
+    std::{
+        any::Any,
+        cell::Cell,
+        collections::HashMap,
+        future::Future,
+        panic::{catch_unwind, AssertUnwindSafe},
+        pin::Pin,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc::{channel, Receiver, Sender},
+            Arc,
+            Mutex,
+        },
+        task::{Context, Poll, Wake, Waker},
+    },
+    crate::{
+        cx::Cx,
+        cx_api::CxOsApi,
+        event::Event,
+        thread::{ToUIReceiver, ToUISender},
+    },
+};
+
+thread_local! {
+    static CURRENT_TASK: Cell<Option<TaskHandle >> = const {Cell::new(None)};
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TaskId(pub u64);
+
+/// Identifies a task from `Cx::spawn_task` or `Cx::spawn_future`, pass it to `Cx::cancel_task`
+/// to stop it.
+#[derive(Clone, Debug)]
+pub struct TaskHandle {
+    id: TaskId,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl PartialEq for TaskHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl TaskHandle {
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// True if the task running on this thread was cancelled. Long running tasks should check
+    /// this every now and then and return early, their result is thrown away anyway.
+    pub fn current_is_cancelled() -> bool {
+        CURRENT_TASK.with( | current | {
+            let task = current.take();
+            let cancelled = task.as_ref().is_some_and( | task | task.is_cancelled());
+            current.set(task);
+            cancelled
+        })
+    }
+}
+
+/// Work for the pool threads, along with the task it belongs to so that a panic can be traced
+/// back to it.
+struct TaskJob {
+    task_id: TaskId,
+    run: Box<dyn FnOnce() + Send + 'static>,
+}
+
+type TaskDone = Box<dyn FnOnce(&mut Cx, Box<dyn Any + Send>)>;
+type TaskFuture = Pin<Box<dyn Future<Output = Box<dyn Any + Send >> + Send + 'static>>;
+
+/// A future from `Cx::spawn_future` along with what it needs to be polled on any thread.
+struct FutureTask {
+    handle: TaskHandle,
+    future: Mutex<Option<TaskFuture >>,
+    waker: Waker,
+    results: Mutex<ToUISender<(TaskId, Box<dyn Any + Send >)>>,
+}
+
+impl FutureTask {
+    fn poll(&self) {
+        let Ok(mut future) = self.future.lock() else {
+            return
+        };
+        if self.handle.is_cancelled() {
+            *future = None;
+            return
+        }
+        let Some(pending) = future.as_mut() else {
+            return
+        };
+        CURRENT_TASK.with( | current | current.set(Some(self.handle.clone())));
+        let poll = pending.as_mut().poll(&mut Context::from_waker(&self.waker));
+        CURRENT_TASK.with( | current | current.set(None));
+        if let Poll::Ready(result) = poll {
+            *future = None;
+            if let Ok(results) = self.results.lock() {
+                let _ = results.send((self.handle.id, result));
+            }
+        }
+    }
+}
+
+/// Wakes a future from `Cx::spawn_future` by posting `Event::WakeTask`, the UI thread then
+/// hands the future back to the worker threads to be polled.
+pub struct CxWaker {
+    task_id: TaskId,
+    wakes: Mutex<ToUISender<TaskId >>,
+}
+
+impl Wake for CxWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if let Ok(wakes) = self.wakes.lock() {
+            let _ = wakes.send(self.task_id);
+        }
+    }
+}
+
+pub struct CxTasks {
+    pool_size: usize,
+    jobs: Option<Sender<TaskJob >>,
+    results: ToUIReceiver<(TaskId, Box<dyn Any + Send >)>,
+    panics: ToUIReceiver<(TaskId, String)>,
+    wakes: ToUIReceiver<TaskId>,
+    pending: HashMap<TaskId, (TaskHandle, TaskDone)>,
+    futures: HashMap<TaskId, Arc<FutureTask >>,
+    last_id: u64,
+}
+
+impl Default for CxTasks {
+    fn default() -> Self {
+        Self {
+            pool_size: std::thread::available_parallelism().map_or(4, | n | n.get()),
+            jobs: None,
+            results: Default::default(),
+            panics: Default::default(),
+            wakes: Default::default(),
+            pending: Default::default(),
+            futures: Default::default(),
+            last_id: 0,
+        }
+    }
+}
+
+impl Cx {
+    /// Sets the number of worker threads for `spawn_task`, it defaults to the number of cores.
+    /// Only has an effect before the first task is spawned.
+    pub fn set_task_pool_size(&mut self, num_threads: usize) {
+        self.tasks.pool_size = num_threads.max(1);
+    }
+
+    /// Runs `task` on the worker threads and calls `on_done` with its result on the UI thread.
+    /// `on_done` is not called for a cancelled task.
+    pub fn spawn_task<T, F, D>(&mut self, task: F, on_done: D) -> TaskHandle
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+        D: Fn(&mut Cx, T) + 'static,
+    {
+        let handle = self.new_task(on_done);
+
+        let sender = self.tasks.results.sender();
+        let task_handle = handle.clone();
+        let job = TaskJob {
+            task_id: handle.id,
+            run: Box::new(move || {
+                if task_handle.is_cancelled() {
+                    return
+                }
+                CURRENT_TASK.with( | current | current.set(Some(task_handle.clone())));
+                let result = task();
+                CURRENT_TASK.with( | current | current.set(None));
+                let _ = sender.send((task_handle.id, Box::new(result)));
+            }),
+        };
+        self.task_pool_jobs().send(job).unwrap();
+        handle
+    }
+
+    /// Drives `future` on the worker threads and calls `on_done` with its output on the UI
+    /// thread. Futures that need a reactor, such as the I/O types of `tokio`, have to enter their
+    /// runtime themselves.
+    pub fn spawn_future<T, F, D>(&mut self, future: F, on_done: D) -> TaskHandle
+    where
+        T: Send + 'static,
+        F: Future<Output = T> + Send + 'static,
+        D: Fn(&mut Cx, T) + 'static,
+    {
+        let handle = self.new_task(on_done);
+        let future: TaskFuture = Box::pin(async move {
+            Box::new(future.await) as Box<dyn Any + Send>
+        });
+        let task = Arc::new(FutureTask {
+            handle: handle.clone(),
+            future: Mutex::new(Some(future)),
+            waker: Waker::from(Arc::new(CxWaker {
+                task_id: handle.id,
+                wakes: Mutex::new(self.tasks.wakes.sender()),
+            })),
+            results: Mutex::new(self.tasks.results.sender()),
+        });
+        self.tasks.futures.insert(handle.id, task);
+        self.poll_future_task(handle.id);
+        handle
+    }
+
+    /// Flags the task as cancelled, it is up to the task to check
+    /// `TaskHandle::current_is_cancelled`. A task that has not started yet never runs.
+    pub fn cancel_task(&mut self, handle: TaskHandle) {
+        handle.cancelled.store(true, Ordering::Relaxed);
+        self.tasks.pending.remove(&handle.id);
+        self.tasks.futures.remove(&handle.id);
+    }
+
+    fn new_task<T, D>(&mut self, on_done: D) -> TaskHandle
+    where
+        T: Send + 'static,
+        D: Fn(&mut Cx, T) + 'static,
+    {
+        self.tasks.last_id += 1;
+        let handle = TaskHandle {
+            id: TaskId(self.tasks.last_id),
+            cancelled: Default::default(),
+        };
+        let done: TaskDone = Box::new(move | cx, result | {
+            if let Ok(result) = result.downcast::<T>() {
+                on_done(cx, *result);
+            }
+        });
+        self.tasks.pending.insert(handle.id, (handle.clone(), done));
+        handle
+    }
+
+    pub (crate) fn poll_future_task(&mut self, task_id: TaskId) {
+        if let Some(task) = self.tasks.futures.get(&task_id).cloned() {
+            self.task_pool_jobs().send(TaskJob {
+                task_id,
+                run: Box::new(move || task.poll()),
+            }).unwrap();
+        }
+    }
+
+    fn task_pool_jobs(&mut self) -> Sender<TaskJob> {
+        if let Some(jobs) = &self.tasks.jobs {
+            return jobs.clone()
+        }
+        let (sender, receiver) = channel::<TaskJob>();
+        let receiver: Arc<Mutex<Receiver<TaskJob >>> = Arc::new(Mutex::new(receiver));
+        for _ in 0..self.tasks.pool_size {
+            let receiver = receiver.clone();
+            let panics = self.tasks.panics.sender();
+            self.spawn_thread(move || loop {
+                let job = match receiver.lock() {
+                    Ok(receiver) => match receiver.recv() {
+                        Ok(job) => job,
+                        Err(_) => return
+                    },
+                    Err(_) => return
+                };
+                // a panicking task must not take its thread down with it, or the pool shrinks
+                // until nothing runs anymore
+                if let Err(payload) = catch_unwind(AssertUnwindSafe(job.run)) {
+                    CURRENT_TASK.with( | current | current.set(None));
+                    let message = payload.downcast_ref::<&str>().map( | s | s.to_string())
+                        .or_else( || payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else( || "unknown panic".to_string());
+                    let _ = panics.send((job.task_id, message));
+                }
+            });
+        }
+        self.tasks.jobs = Some(sender.clone());
+        sender
+    }
+
+    pub (crate) fn handle_task_results(&mut self) {
+        while let Ok(task_id) = self.tasks.wakes.try_recv() {
+            self.call_event_handler(&Event::WakeTask(task_id));
+        }
+        while let Ok((id, message)) = self.tasks.panics.try_recv() {
+            // the task never finishes, so its on_done is dropped without being called
+            self.tasks.futures.remove(&id);
+            if let Some((handle, _)) = self.tasks.pending.remove(&id) {
+                if !handle.is_cancelled() {
+                    error!("Task {} panicked: {}", id.0, message);
+                }
+            }
+        }
+        while let Ok((id, result)) = self.tasks.results.try_recv() {
+            self.tasks.futures.remove(&id);
+            if let Some((handle, done)) = self.tasks.pending.remove(&id) {
+                if !handle.is_cancelled() {
+                    done(self, result);
+                }
+            }
+        }
+    }
+}
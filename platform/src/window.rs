@@ -23,11 +23,40 @@ impl WindowHandle {
     pub fn window_id(&self) -> WindowId {WindowId(self.0.id, self.0.generation)}
 }
 
+/// How to create a window with `Cx::open_window`.
+#[derive(Clone, Debug)]
+pub struct WindowOptions {
+    pub title: String,
+    pub inner_size: Option<DVec2>,
+    pub position: Option<DVec2>,
+    pub dpi_override: Option<f64>,
+    /// Close this window along with the main window, otherwise the application keeps running
+    /// until this window is closed as well.
+    pub close_with_main: bool,
+}
+
+impl Default for WindowOptions {
+    fn default() -> Self {
+        Self {
+            title: "Makepad".to_string(),
+            inner_size: None,
+            position: None,
+            dpi_override: None,
+            close_with_main: true,
+        }
+    }
+}
+
 #[derive(Default)]
-pub struct CxWindowPool(IdPool<CxWindow>);
+pub struct CxWindowPool {
+    pool: IdPool<CxWindow>,
+    // the windows opened with Cx::open_window, owned by Cx until they close
+    opened: Vec<WindowHandle>,
+}
+
 impl CxWindowPool {
     fn alloc(&mut self) -> WindowHandle {
-        WindowHandle(self.0.alloc())
+        WindowHandle(self.pool.alloc())
     }
     
     pub fn id_zero()->WindowId{
@@ -38,7 +67,7 @@ impl CxWindowPool {
 impl std::ops::Index<WindowId> for CxWindowPool {
     type Output = CxWindow;
     fn index(&self, index: WindowId) -> &Self::Output {
-        let d = &self.0.pool[index.0];
+        let d = &self.pool.pool[index.0];
         if d.generation != index.1{
             error!("Window id generation wrong {} {} {}", index.0, d.generation, index.1)
         }
@@ -48,7 +77,7 @@ impl std::ops::Index<WindowId> for CxWindowPool {
 
 impl std::ops::IndexMut<WindowId> for CxWindowPool {
     fn index_mut(&mut self, index: WindowId) -> &mut Self::Output {
-        let d = &mut self.0.pool[index.0];
+        let d = &mut self.pool.pool[index.0];
         if d.generation != index.1{
             error!("Window id generation wrong {} {} {}", index.0, d.generation, index.1)
         }
@@ -191,6 +220,68 @@ pub struct CxWindow {
     pub is_created: bool,
     pub window_geom: WindowGeom,
     pub main_pass_id: Option<PassId>,
+    pub close_with_main: bool,
+}
+
+impl Cx {
+    /// Creates another OS window with its own draw surface. The application gives it a widget
+    /// tree by setting a pass on it, as with the main window, and tells its events apart with
+    /// `Event::window_id`.
+    pub fn open_window(&mut self, options: WindowOptions) -> WindowId {
+        let window = self.windows.alloc();
+        let window_id = window.window_id();
+        let cxwindow = &mut self.windows[window_id];
+        cxwindow.is_created = false;
+        cxwindow.create_title = options.title;
+        cxwindow.create_inner_size = options.inner_size;
+        cxwindow.create_position = options.position;
+        cxwindow.dpi_override = options.dpi_override;
+        cxwindow.close_with_main = options.close_with_main;
+        self.platform_ops.push(CxOsOp::CreateWindow(window_id));
+        self.windows.opened.push(window);
+        window_id
+    }
+
+    pub fn close_window(&mut self, window_id: WindowId) {
+        self.push_unique_platform_op(CxOsOp::CloseWindow(window_id));
+    }
+
+    /// Enters or leaves fullscreen, `Event::WindowFullscreenChanged` follows once the window
+    /// changed. On Windows and Linux a maximized window counts as fullscreen too, as it
+    /// always has for `WindowGeom::is_fullscreen`.
+    pub fn set_fullscreen(&mut self, window_id: WindowId, fullscreen: bool) {
+        if fullscreen {
+            self.push_unique_platform_op(CxOsOp::FullscreenWindow(window_id));
+        }
+        else {
+            self.push_unique_platform_op(CxOsOp::NormalizeWindow(window_id));
+        }
+    }
+
+    pub fn is_fullscreen(&self, window_id: WindowId) -> bool {
+        self.windows[window_id].window_geom.is_fullscreen
+    }
+
+    /// The number of physical pixels per logical pixel of `window_id`, including its
+    /// `dpi_override`. All draw APIs take logical pixels.
+    pub fn dpi_factor(&self, window_id: WindowId) -> f64 {
+        self.windows[window_id].window_geom.dpi_factor
+    }
+
+    /// Releases a window from `open_window` once the OS closed it, and closes the windows that
+    /// go along with the main window.
+    pub (crate) fn handle_window_closed(&mut self, window_id: WindowId) {
+        if window_id == CxWindowPool::id_zero() {
+            let close: Vec<WindowId> = self.windows.opened.iter()
+                .map( | window | window.window_id())
+                .filter( | window_id | self.windows[*window_id].close_with_main)
+                .collect();
+            for window_id in close {
+                self.close_window(window_id);
+            }
+        }
+        self.windows.opened.retain( | window | window.window_id() != window_id);
+    }
 }
 
 impl CxWindow {
@@ -209,7 +209,7 @@ impl MatchEvent for App{
                     // lets write the file
                     self.data.file_system.request_save_file(action.path.get(0))
                 }
-                CodeEditorAction::None=>{}
+                CodeEditorAction::InlineWidgetClicked{..} | CodeEditorAction::None=>{}
             }
             
             match action.cast(){
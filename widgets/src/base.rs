@@ -45,6 +45,26 @@ live_design!{
     import crate::keyboard_view::KeyboardViewBase;
     import crate::window_menu::WindowMenuBase;
     import crate::html::HtmlBase;
+    import crate::rich_text::RichTextBase;
+    import crate::scroll_view::ScrollViewBase;
+    import crate::virtual_list::VirtualListBase;
+    import crate::tree_view::TreeViewBase;
+    import crate::tab_view::TabViewBase;
+    import crate::modal::ModalBase;
+    import crate::tooltip::TooltipBase;
+    import crate::context_menu::ContextMenuBase;
+    import crate::color_picker::ColorPickerBase;
+    import crate::progress_bar::ProgressBarBase;
+    import crate::spinner::SpinnerBase;
+    import crate::svg_icon::SvgIconBase;
+    import crate::transform_2d::Transform2DBase;
+    import crate::nine_slice_image::NineSliceImageBase;
+    import crate::flex::FlexContainerBase;
+    import crate::flex::FlexItemBase;
+    import crate::grid_layout::GridLayoutBase;
+    import crate::grid_layout::GridItemBase;
+    import crate::overlay_layer::OverlayLayerBase;
+    import crate::overlay_layer::AbsolutePositionBase;
     
     import makepad_draw::shader::std::*;
     import makepad_draw::shader::draw_color::DrawColor;
@@ -103,6 +123,11 @@ live_design!{
         }
     }
     
+    NineSliceImage = <NineSliceImageBase> {
+        width: 100
+        height: 100
+    }
+    
     RotatedImage = <RotatedImageBase> {
         width: Fit
         height: Fit
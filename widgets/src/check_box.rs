@@ -18,7 +18,8 @@ pub struct DrawCheckBox {
     #[live] check_type: CheckType,
     #[live] hover: f32,
     #[live] focus: f32,
-    #[live] selected: f32
+    #[live] selected: f32,
+    #[live] indeterminate: f32
 }
 
 #[derive(Live, LiveHook, LiveRegister)]
@@ -31,7 +32,30 @@ pub enum CheckType {
     None = shader_enum(4),
 }
 
-#[derive(Live, LiveHook, Widget)]
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum CheckBoxState {
+    #[pick] Unchecked,
+    Checked,
+    // used for 'select all' style boxes that represent a partially selected set
+    Indeterminate,
+}
+
+impl CheckBoxState {
+    pub fn next(&self, allow_indeterminate: bool) -> Self {
+        match self {
+            Self::Unchecked => Self::Checked,
+            Self::Checked if allow_indeterminate => Self::Indeterminate,
+            Self::Checked | Self::Indeterminate => Self::Unchecked,
+        }
+    }
+    
+    pub fn is_checked(&self) -> bool {
+        *self == Self::Checked
+    }
+}
+
+#[derive(Live, Widget)]
 pub struct CheckBox {
     
     #[walk] walk: Walk,
@@ -48,15 +72,30 @@ pub struct CheckBox {
     
     #[live] text: RcStringMut,
     
+    #[live] state: CheckBoxState,
+    #[live] allow_indeterminate: bool,
+    
     #[live] bind: String,
 }
 
 #[derive(Clone, Debug, DefaultNone)]
 pub enum CheckBoxAction {
-    Change(bool),
+    Changed {state: CheckBoxState},
     None
 }
 
+impl LiveHook for CheckBox {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        // the state field drives the animator, animator applies flow back through here so skip those
+        match apply.from {
+            ApplyFrom::NewFromDoc {..} | ApplyFrom::UpdateFromDoc {..} | ApplyFrom::Over => {
+                self.set_state(cx, self.state, Animate::No);
+            }
+            _ => ()
+        }
+    }
+}
+
 #[derive(Live, LiveHook, LiveRegister)]#[repr(C)]
 struct DrawLabelText {
     #[deref] draw_super: DrawText,
@@ -66,6 +105,16 @@ struct DrawLabelText {
 
 impl CheckBox {
     
+    pub fn set_state(&mut self, cx: &mut Cx, state: CheckBoxState, animate: Animate) {
+        if state == CheckBoxState::Indeterminate && !self.allow_indeterminate {
+            return self.set_state(cx, CheckBoxState::Unchecked, animate);
+        }
+        self.state = state;
+        self.animator_toggle(cx, state.is_checked(), animate, id!(selected.on), id!(selected.off));
+        self.draw_check.indeterminate = if state == CheckBoxState::Indeterminate {1.0} else {0.0};
+        self.draw_check.redraw(cx);
+    }
+    
     pub fn draw_walk(&mut self, cx: &mut Cx2d, walk: Walk) {
         self.draw_check.begin(cx, walk, self.layout);
         self.draw_text.draw_walk(cx, self.label_walk, self.label_align, self.text.as_ref());
@@ -78,8 +127,8 @@ impl Widget for CheckBox {
     
     fn widget_to_data(&self, _cx: &mut Cx, actions: &Actions, nodes: &mut LiveNodeVec, path: &[LiveId]) -> bool {
         match actions.find_widget_action_cast(self.widget_uid()) {
-            CheckBoxAction::Change(v) => {
-                nodes.write_field_value(path, LiveValue::Bool(v));
+            CheckBoxAction::Changed {state} => {
+                nodes.write_field_value(path, LiveValue::Bool(state.is_checked()));
                 true
             }
             _ => false
@@ -89,7 +138,8 @@ impl Widget for CheckBox {
     fn data_to_widget(&mut self, cx: &mut Cx, nodes: &[LiveNode], path: &[LiveId]) {
         if let Some(value) = nodes.read_field_value(path) {
             if let Some(value) = value.as_bool() {
-                self.animator_toggle(cx, value, Animate::Yes, id!(selected.on), id!(selected.off));
+                let state = if value {CheckBoxState::Checked} else {CheckBoxState::Unchecked};
+                self.set_state(cx, state, Animate::Yes);
             }
         }
     }
@@ -107,14 +157,9 @@ impl Widget for CheckBox {
                 self.animator_play(cx, id!(hover.off));
             },
             Hit::FingerDown(_fe) => {
-                if self.animator_in_state(cx, id!(selected.on)) {
-                    self.animator_play(cx, id!(selected.off));
-                    cx.widget_action(uid, &scope.path, CheckBoxAction::Change(false));
-                }
-                else {
-                    self.animator_play(cx, id!(selected.on));
-                    cx.widget_action(uid, &scope.path, CheckBoxAction::Change(true));
-                }
+                let state = self.state.next(self.allow_indeterminate);
+                self.set_state(cx, state, Animate::Yes);
+                cx.widget_action(uid, &scope.path, CheckBoxAction::Changed {state});
             },
             Hit::FingerUp(_fe) => {
                                 
@@ -142,8 +187,12 @@ impl Widget for CheckBox {
 
 impl CheckBoxRef {
     pub fn changed(&self, actions: &Actions) -> Option<bool> {
-        if let CheckBoxAction::Change(b) = actions.find_widget_action_cast(self.widget_uid()) {
-            return Some(b)
+        self.changed_state(actions).map(|state| state.is_checked())
+    }
+    
+    pub fn changed_state(&self, actions: &Actions) -> Option<CheckBoxState> {
+        if let CheckBoxAction::Changed {state} = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(state)
         }
         None
     }
@@ -155,18 +204,27 @@ impl CheckBoxRef {
         }
     }
     
-    pub fn selected(&self, cx: &Cx) -> bool {
+    pub fn selected(&self, _cx: &Cx) -> bool {
+        self.state().is_checked()
+    }
+    
+    pub fn set_selected(&self, cx: &mut Cx, value: bool) {
+        let state = if value {CheckBoxState::Checked} else {CheckBoxState::Unchecked};
+        self.set_state(cx, state);
+    }
+    
+    pub fn state(&self) -> CheckBoxState {
         if let Some(inner) = self.borrow() {
-            inner.animator_in_state(cx, id!(selected.on))
+            inner.state
         }
         else {
-            false
+            CheckBoxState::Unchecked
         }
     }
     
-    pub fn set_selected(&self, cx: &mut Cx, value: bool) {
+    pub fn set_state(&self, cx: &mut Cx, state: CheckBoxState) {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.animator_toggle(cx, value, Animate::Yes, id!(selected.on), id!(selected.off));
+            inner.set_state(cx, state, Animate::Yes);
         }
     }
 }
@@ -1,120 +1,20 @@
-use crate::makepad_draw::*;
-
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+    slider::*,
+    text_input::*,
+};
 
 live_design!{
-    import makepad_draw::shader::std::*;
-    
-    DrawColorWheel= {{DrawColorWheel}} {
-        instance hover: float
-        instance pressed: float
-        
-        fn circ_to_rect(u: float, v: float) -> vec2 {
-            let u2 = u * u;
-            let v2 = v * v;
-            return vec2(
-                0.5 * sqrt(2. + 2. * sqrt(2.) * u + u2 - v2) -
-                0.5 * sqrt(2. - 2. * sqrt(2.) * u + u2 - v2),
-                0.5 * sqrt(2. + 2. * sqrt(2.) * v - u2 + v2) -
-                0.5 * sqrt(2. - 2. * sqrt(2.) * v - u2 + v2)
-            );
-        }
-        
-        fn pixel(self) -> vec4 {
-            
-            let rgbv = Pal::hsv2rgb(vec4(self.hue, self.sat, self.val, 1.));
-            let w = self.rect_size.x;
-            let h = self.rect_size.y;
-            let sdf = Sdf2d::viewport(self.pos * vec2(w, h));
-            let cx = w * 0.5;
-            let cy = h * 0.5;
-            
-            let radius = w * 0.37;
-            let inner = w * 0.28;
-            
-            sdf.hexagon(cx, cy, w * 0.45);
-            sdf.hexagon(cx, cy, w * 0.4);
-            sdf.subtract();
-            let ang = atan(self.pos.x * w - cx, 0.0001 + self.pos.y * h - cy) / PI * 0.5 - 0.33333;
-            sdf.fill(Pal::hsv2rgb(vec4(ang, 1.0, 1.0, 1.0)));
-            
-            let rsize = inner / sqrt(2.0);
-            sdf.rect(cx - rsize, cy - rsize, rsize * 2.0, rsize * 2.0);
-            
-            let norm_rect = vec2(self.pos.x * w - (cx - inner), self.pos.y * h - (cy - inner)) / (2. * inner);
-            let circ = clamp(circ_to_rect(norm_rect.x * 2. - 1., norm_rect.y * 2. - 1.), vec2(-1.), vec2(1.));
-            
-            sdf.fill(Pal::hsv2rgb(vec4(self.hue, (circ.x * .5 + .5), 1. - (circ.y * .5 + .5), 1.)));
-            
-            let col_angle = (self.hue + .333333) * 2. * PI;
-            let circle_puk = vec2(sin(col_angle) * radius + cx, cos(col_angle) * radius + cy);
-            
-            let rect_puk = vec2(cx + self.sat * 2. * rsize - rsize, cy + (1. - self.val) * 2. * rsize - rsize);
-            
-            let color = mix(mix(#3, #E, self.hover), #F, self.pressed);
-            let puck_size = 0.1 * w;
-            sdf.circle(rect_puk.x, rect_puk.y, puck_size);
-            sdf.rect(cx - rsize, cy - rsize, rsize * 2.0, rsize * 2.0);
-            sdf.intersect();
-            sdf.fill(color);
-            sdf.circle(rect_puk.x, rect_puk.y, puck_size - 1. - 2. * self.hover + self.pressed);
-            sdf.rect(cx - rsize, cy - rsize, rsize * 2.0, rsize * 2.0);
-            sdf.intersect();
-            sdf.fill(rgbv);
-            
-            sdf.circle(circle_puk.x, circle_puk.y, puck_size);
-            sdf.fill(color);
-            sdf.circle(circle_puk.x, circle_puk.y, puck_size - 1. - 2. * self.hover + self.pressed);
-            sdf.fill(rgbv);
-            
-            return sdf.result;
-        }
-    }
-    
-    ColorPicker= {{ColorPicker}} {
-        
-        animator: {
-            hover = {
-                default: off
-                off = {
-                    from: {all: Forward {duration: 0.1}}
-                    apply: {
-                        draw_wheel: {pressed: 0.0, hover: 0.0}
-                    }
-                }
-                
-                on = {
-                    cursor: Arrow,
-                    from: {
-                        all: Forward {duration: 0.1}
-                        pressed: Forward {duration: 0.01}
-                    }
-                    apply: {
-                        draw_wheel: {
-                            pressed: 0.0,
-                            hover: [{time: 0.0, value: 1.0}],
-                        }
-                    }
-                }
-                
-                pressed = {
-                    cursor: Arrow,
-                    from: {all: Forward {duration: 0.2}}
-                    apply: {
-                        draw_wheel: {
-                            pressed: [{time: 0.0, value: 1.0}],
-                            hover: 1.0,
-                        }
-                    }
-                }
-            }
-        }
-    }
+    DrawColorPickerSquare = {{DrawColorPickerSquare}} {}
+    DrawColorPickerHue = {{DrawColorPickerHue}} {}
+    ColorPickerBase = {{ColorPicker}} {}
 }
 
-
 #[derive(Live, LiveHook, LiveRegister)]
 #[repr(C)]
-pub struct DrawColorWheel {
+pub struct DrawColorPickerSquare {
     #[deref] draw_super: DrawQuad,
     #[live] hue: f32,
     #[live] sat: f32,
@@ -122,143 +22,301 @@ pub struct DrawColorWheel {
 }
 
 #[derive(Live, LiveHook, LiveRegister)]
-pub struct ColorPicker {
-    #[live] draw_wheel: DrawColorWheel,
-    
-    #[animator] animator: Animator,
-    
-    #[rust] pub size: f64,
-    #[rust] hue: f32,
-    #[rust] sat: f32,
-    #[rust] val: f32,
-    #[rust(ColorPickerDragMode::None)] drag_mode: ColorPickerDragMode
+#[repr(C)]
+pub struct DrawColorPickerHue {
+    #[deref] draw_super: DrawQuad,
+    #[live] hue: f32,
 }
 
+#[derive(Clone, Debug, DefaultNone)]
 pub enum ColorPickerAction {
-    Change {rgba: Vec4},
-    DoneChanging,
+    ColorChanged {color: Vec4},
     None
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum ColorPickerDragMode {
-    Wheel,
-    Rect,
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ColorPickerDragMode {
+    Square,
+    Hue,
     None
 }
 
+/// Which control caused a color change, that control isn't rewritten whilst it is in use.
+#[derive(Clone, Copy, PartialEq)]
+enum ColorSource {
+    Palette,
+    Slider,
+    Hex,
+    Api,
+}
+
+/// Parses `#rrggbb` or `#rrggbbaa`, the leading `#` is optional.
+pub fn parse_hex_color(text: &str) -> Option<Vec4> {
+    let hex = text.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if !hex.chars().all( | c | c.is_ascii_hexdigit()) {
+        return None
+    }
+    let value = u32::from_str_radix(hex, 16).ok() ?;
+    match hex.len() {
+        6 => Some(Vec4::from_u32((value << 8) | 0xff)),
+        8 => Some(Vec4::from_u32(value)),
+        _ => None
+    }
+}
+
+/// Formats as `#rrggbb`, or `#rrggbbaa` when the color isn't opaque.
+pub fn format_hex_color(color: Vec4) -> String {
+    fn byte(v: f32) -> u8 {(v.max(0.0).min(1.0) * 255.0).round() as u8}
+    let (r, g, b, a) = (byte(color.x), byte(color.y), byte(color.z), byte(color.w));
+    if a == 255 {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+    else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
+
+#[derive(Live, Widget)]
+pub struct ColorPicker {
+    #[redraw] #[rust] area: Area,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+
+    /// The picked color as RGBA in 0..1
+    #[live] color: Vec4,
+    /// Clicking the swatch toggles between the compact and the expanded view
+    #[live(true)] expanded: bool,
+
+    #[live] draw_swatch: DrawColor,
+    #[live] swatch_walk: Walk,
+    #[live] draw_square: DrawColorPickerSquare,
+    #[live] draw_hue: DrawColorPickerHue,
+    #[live] palette_layout: Layout,
+    #[live(160.0)] square_size: f64,
+    #[live(20.0)] hue_bar_width: f64,
+
+    /// Holds the `h`, `s`, `v`, `a` sliders and the `hex` input
+    #[find] #[live] controls: WidgetRef,
+
+    /// The color as HSVA, kept alongside `color` so the hue survives greys
+    #[rust] hsva: Vec4,
+    #[rust(ColorPickerDragMode::None)] drag_mode: ColorPickerDragMode,
+    #[rust] draw_state: DrawStateWrap<()>,
+}
+
+impl LiveHook for ColorPicker {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        self.sync_hsva();
+        self.sync_controls(cx, ColorSource::Api);
+    }
+}
+
 impl ColorPicker {
-    
-    pub fn handle_finger(&mut self, cx: &mut Cx, rel: DVec2, dispatch_action: &mut dyn FnMut(&mut Cx, ColorPickerAction)) {
-        
-        fn clamp(x: f64, mi: f64, ma: f64) -> f64 {if x < mi {mi} else if x > ma {ma} else {x}}
-        
-        let vx = rel.x - 0.5 * self.size;
-        let vy = rel.y - 0.5 * self.size;
-        let rsize = (self.size * 0.28) / 2.0f64.sqrt();
-        let last_hue = self.hue;
-        let last_sat = self.sat;
-        let last_val = self.val;
-        
-        match self.drag_mode {
-            ColorPickerDragMode::Rect => {
-                self.sat = clamp((vx + rsize) / (2.0 * rsize), 0.0, 1.0) as f32;
-                self.val = 1.0 - clamp((vy + rsize) / (2.0 * rsize), 0.0, 1.0) as f32;
-            },
-            ColorPickerDragMode::Wheel => {
-                self.hue = ((vx.atan2(vy) / std::f64::consts::PI * 0.5) - 0.33333 + 1.0) as f32;
-            },
-            _ => ()
-        }
-        // lets update hue sat val directly
-        let mut changed = false;
-        
-        if last_hue != self.hue {
-            self.draw_wheel.apply_over(cx, live!{hue: (self.hue)});
-            changed = true;
+    pub fn color(&self) -> Vec4 {
+        self.color
+    }
+
+    pub fn set_color(&mut self, cx: &mut Cx, color: Vec4) {
+        self.color = color;
+        self.sync_hsva();
+        self.sync_controls(cx, ColorSource::Api);
+        self.area.redraw(cx);
+    }
+
+    pub fn set_expanded(&mut self, cx: &mut Cx, expanded: bool) {
+        self.expanded = expanded;
+        self.area.redraw(cx);
+    }
+
+    fn sync_hsva(&mut self) {
+        let mut current = Vec4::from_hsva(self.hsva);
+        current.w = self.hsva.w;
+        if !current.is_equal_enough(&self.color, 0.0001) {
+            let hsva = self.color.to_hsva();
+            // greys carry no hue, keep the one the user had picked
+            self.hsva = if hsva.y < 0.0001 || hsva.z < 0.0001 {
+                Vec4 {x: self.hsva.x, ..hsva}
+            }
+            else {
+                hsva
+            };
         }
-        if last_sat != self.sat {
-            self.draw_wheel.apply_over(cx, live!{sat: (self.sat)});
-            changed = true;
+    }
+
+    fn sync_controls(&mut self, cx: &mut Cx, source: ColorSource) {
+        if source != ColorSource::Slider {
+            self.controls.slider(id!(h)).set_text_and_redraw(cx, &format!("{}", self.hsva.x * 360.0));
+            self.controls.slider(id!(s)).set_text_and_redraw(cx, &format!("{}", self.hsva.y));
+            self.controls.slider(id!(v)).set_text_and_redraw(cx, &format!("{}", self.hsva.z));
+            self.controls.slider(id!(a)).set_text_and_redraw(cx, &format!("{}", self.hsva.w));
         }
-        if last_val != self.val {
-            self.draw_wheel.apply_over(cx, live!{val: (self.val)});
-            changed = true;
+        if source != ColorSource::Hex {
+            self.controls.text_input(id!(hex)).set_text_and_redraw(cx, &format_hex_color(self.color));
         }
-        if changed {
-            dispatch_action(cx, ColorPickerAction::Change {rgba: self.to_rgba()})
+    }
+
+    fn set_hsva(&mut self, cx: &mut Cx, hsva: Vec4, source: ColorSource, dispatch_action: &mut dyn FnMut(&mut Cx, ColorPickerAction)) {
+        let hsva = Vec4 {
+            x: hsva.x.max(0.0).min(1.0),
+            y: hsva.y.max(0.0).min(1.0),
+            z: hsva.z.max(0.0).min(1.0),
+            w: hsva.w.max(0.0).min(1.0),
+        };
+        if hsva == self.hsva {
+            return
         }
+        self.hsva = hsva;
+        self.color = Vec4::from_hsva(hsva);
+        self.color.w = hsva.w;
+        self.sync_controls(cx, source);
+        self.area.redraw(cx);
+        dispatch_action(cx, ColorPickerAction::ColorChanged {color: self.color});
     }
-    
-    pub fn to_rgba(&self) -> Vec4 {
-        Vec4::from_hsva(Vec4 {x: self.hue, y: self.sat, z: self.val, w: 1.0})
+
+    fn handle_palette_finger(&mut self, cx: &mut Cx, rel: DVec2, dispatch_action: &mut dyn FnMut(&mut Cx, ColorPickerAction)) {
+        let mut hsva = self.hsva;
+        match self.drag_mode {
+            ColorPickerDragMode::Square => {
+                hsva.y = (rel.x / self.square_size) as f32;
+                hsva.z = 1.0 - (rel.y / self.square_size) as f32;
+            }
+            ColorPickerDragMode::Hue => {
+                hsva.x = (rel.y / self.square_size) as f32;
+            }
+            ColorPickerDragMode::None => return
+        }
+        self.set_hsva(cx, hsva, ColorSource::Palette, dispatch_action);
     }
-    
-    pub fn handle_event_with(&mut self, cx: &mut Cx, event: &Event, dispatch_action: &mut dyn FnMut(&mut Cx, ColorPickerAction)) {
-        self.animator_handle_event(cx, event);
-        
-        match event.hits(cx, self.draw_wheel.area()) {
-            Hit::FingerHoverIn(_) => {
-                self.animator_play(cx, id!(hover.on));
+
+    pub fn handle_event_with(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope, dispatch_action: &mut dyn FnMut(&mut Cx, ColorPickerAction)) {
+        if let Hit::FingerUp(fe) = event.hits(cx, self.draw_swatch.area()) {
+            if fe.is_over {
+                self.set_expanded(cx, !self.expanded);
             }
-            Hit::FingerHoverOut(_) => {
-                self.animator_play(cx, id!(hover.off));
-            },
-            Hit::FingerDown(fe) => {
-                self.animator_play(cx, id!(hover.pressed));
-                let rsize = (self.size * 0.28) / 2.0f64.sqrt();
-                let rel = fe.abs - fe.rect.pos;
-                let vx = rel.x - 0.5 * self.size;
-                let vy = rel.y - 0.5 * self.size;
-                if vx >= -rsize && vx <= rsize && vy >= -rsize && vy <= rsize {
-                    self.drag_mode = ColorPickerDragMode::Rect;
+        }
+        if !self.expanded {
+            return
+        }
+
+        for (area, mode) in [
+            (self.draw_square.area(), ColorPickerDragMode::Square),
+            (self.draw_hue.area(), ColorPickerDragMode::Hue)
+        ] {
+            match event.hits(cx, area) {
+                Hit::FingerDown(fe) => {
+                    self.drag_mode = mode;
+                    self.handle_palette_finger(cx, fe.abs - fe.rect.pos, dispatch_action);
                 }
-                else if vx >= -0.5 * self.size && vx <= 0.5 * self.size && vy >= -0.5 * self.size && vy <= 0.5 * self.size {
-                    self.drag_mode = ColorPickerDragMode::Wheel;
+                Hit::FingerMove(fe) if self.drag_mode == mode => {
+                    self.handle_palette_finger(cx, fe.abs - fe.rect.pos, dispatch_action);
                 }
-                else {
+                Hit::FingerUp(_) => {
                     self.drag_mode = ColorPickerDragMode::None;
                 }
-                return self.handle_finger(cx, rel, dispatch_action);
-                // lets check where we clicked!
-            },
-            Hit::FingerUp(fe) => {
-                if fe.is_over && fe.device.has_hovers() {
-                    self.animator_play(cx, id!(hover.on));
-                }
-                else {
-                    self.animator_play(cx, id!(hover.off));
+                _ => ()
+            }
+        }
+
+        let actions = cx.capture_actions( | cx | self.controls.handle_event(cx, event, scope));
+        let mut hsva = self.hsva;
+        let mut slided = false;
+        if let Some(h) = self.controls.slider(id!(h)).slided(&actions) {
+            hsva.x = (h / 360.0) as f32;
+            slided = true;
+        }
+        for (id, component) in [(id!(s), &mut hsva.y), (id!(v), &mut hsva.z), (id!(a), &mut hsva.w)] {
+            if let Some(value) = self.controls.slider(id).slided(&actions) {
+                *component = value as f32;
+                slided = true;
+            }
+        }
+        if slided {
+            self.set_hsva(cx, hsva, ColorSource::Slider, dispatch_action);
+        }
+        if let Some(text) = self.controls.text_input(id!(hex)).changed(&actions) {
+            if let Some(color) = parse_hex_color(&text) {
+                if !color.is_equal_enough(&self.color, 0.0001) {
+                    self.color = color;
+                    self.sync_hsva();
+                    self.sync_controls(cx, ColorSource::Hex);
+                    self.area.redraw(cx);
+                    dispatch_action(cx, ColorPickerAction::ColorChanged {color});
                 }
-                self.drag_mode = ColorPickerDragMode::None;
-                dispatch_action(cx, ColorPickerAction::DoneChanging)
             }
-            Hit::FingerMove(fe) => {
-                let rel = fe.abs - fe.rect.pos;
-                return self.handle_finger(cx, rel, dispatch_action)
-                
-            },
-            _ => ()
         }
+        cx.extend_actions(actions);
+    }
+
+    fn draw_palette(&mut self, cx: &mut Cx2d) {
+        cx.begin_turtle(Walk::fit(), self.palette_layout);
+        self.draw_square.hue = self.hsva.x;
+        self.draw_square.sat = self.hsva.y;
+        self.draw_square.val = self.hsva.z;
+        self.draw_square.draw_walk(cx, Walk::fixed_size(dvec2(self.square_size, self.square_size)));
+        self.draw_hue.hue = self.hsva.x;
+        self.draw_hue.draw_walk(cx, Walk::fixed_size(dvec2(self.hue_bar_width, self.square_size)));
+        cx.end_turtle();
+    }
+}
+
+impl Widget for ColorPicker {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        let path = scope.path.clone();
+        self.handle_event_with(cx, event, scope, &mut | cx, action | {
+            cx.widget_action(uid, &path, action);
+        });
     }
-    
-    pub fn draw(&mut self, cx: &mut Cx2d, rgba: Vec4, height_scale: f64) {
-        if self.drag_mode == ColorPickerDragMode::None {
-            // lets convert to rgba
-            let old_rgba = self.to_rgba();
-            if !rgba.is_equal_enough(&old_rgba, 0.0001) {
-                let hsva = rgba.to_hsva();
-                self.hue = hsva.x;
-                self.sat = hsva.y;
-                self.val = hsva.z;
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            cx.begin_turtle(walk, self.layout);
+            self.draw_swatch.color = self.color;
+            self.draw_swatch.draw_walk(cx, self.swatch_walk);
+            if !self.expanded {
+                cx.end_turtle_with_area(&mut self.area);
+                self.draw_state.end();
+                return DrawStep::done()
             }
+            self.draw_palette(cx);
+        }
+        if self.draw_state.get().is_some() {
+            let walk = self.controls.walk(cx);
+            self.controls.draw_walk(cx, scope, walk) ?;
+            cx.end_turtle_with_area(&mut self.area);
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+
+    fn text(&self) -> String {
+        format_hex_color(self.color)
+    }
+
+    fn set_text(&mut self, v: &str) {
+        if let Some(color) = parse_hex_color(v) {
+            self.color = color;
+            self.sync_hsva();
         }
-        //self.draw_wheel.shader = live_shader!(cx, self::shader_wheel);
-        // i wanna draw a draw_wheel with 'width' set but height a fixed height.
-        self.size = cx.turtle().rect().size.y;
-        self.draw_wheel.hue = self.hue;
-        self.draw_wheel.sat = self.sat;
-        self.draw_wheel.val = self.val;
-        self.draw_wheel.draw_walk(cx, Walk::fixed_size(dvec2(self.size * height_scale, self.size * height_scale)));
     }
 }
 
+impl ColorPickerRef {
+    pub fn color(&self) -> Option<Vec4> {
+        self.borrow().map( | inner | inner.color())
+    }
+
+    pub fn set_color(&self, cx: &mut Cx, color: Vec4) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_color(cx, color);
+        }
+    }
+
+    pub fn color_changed(&self, actions: &Actions) -> Option<Vec4> {
+        if let ColorPickerAction::ColorChanged {color} = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(color)
+        }
+        None
+    }
+}
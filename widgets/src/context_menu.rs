@@ -0,0 +1,392 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    DrawContextMenuItem = {{DrawContextMenuItem}} {}
+    ContextMenuBase = {{ContextMenu}} {}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuItem {
+    pub label: String,
+    pub id: usize,
+    pub enabled: bool,
+    pub submenu: Option<Vec<MenuItem>>,
+}
+
+impl MenuItem {
+    pub fn new(id: usize, label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            id,
+            enabled: true,
+            submenu: None,
+        }
+    }
+
+    pub fn with_enabled(self, enabled: bool) -> Self {
+        Self {enabled, ..self}
+    }
+
+    pub fn with_submenu(self, items: Vec<MenuItem>) -> Self {
+        Self {submenu: Some(items), ..self}
+    }
+}
+
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawContextMenuItem {
+    #[deref] draw_super: DrawQuad,
+    #[live] highlighted: f32,
+    #[live] enabled: f32,
+    #[live] has_submenu: f32,
+}
+
+/// One open (sub)menu, the root menu is level 0.
+struct MenuLevel {
+    /// Item indices leading from the root items to this level's items
+    path: Vec<usize>,
+    pos: DVec2,
+    rect: Rect,
+    highlighted: Option<usize>,
+    item_areas: Vec<Area>,
+}
+
+impl MenuLevel {
+    fn new(path: Vec<usize>, pos: DVec2) -> Self {
+        Self {
+            path,
+            pos,
+            rect: Rect::default(),
+            highlighted: None,
+            item_areas: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ContextMenuAction {
+    Selected {id: usize},
+    Dismissed,
+    None
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ContextMenu {
+    #[find] #[live] content: WidgetRef,
+    #[redraw] #[live] draw_list: DrawList2d,
+    #[live] draw_bg: DrawQuad,
+    #[live] draw_shadow: DrawShadow,
+    #[live] draw_item: DrawContextMenuItem,
+    #[live] draw_text: DrawText,
+    #[live] draw_text_disabled: DrawText,
+    #[live] menu_walk: Walk,
+    #[live] menu_layout: Layout,
+    #[live] item_walk: Walk,
+    #[live] item_layout: Layout,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[live(0.2)] submenu_delay: f64,
+    #[live(true)] open_on_right_click: bool,
+    #[live] shadows: Vec<Shadow>,
+    /// Corner radius the shadows follow, matching the rounding of `draw_bg`
+    #[live] shadow_radius: f64,
+
+    #[rust] area: Area,
+    #[rust] items: Vec<MenuItem>,
+    #[rust] levels: Vec<MenuLevel>,
+    #[rust] submenu_timer: Timer,
+    #[rust] pending_submenu: Option<(usize, usize)>,
+    #[rust] draw_state: DrawStateWrap<()>,
+}
+
+impl ContextMenu {
+    pub fn set_items(&mut self, cx: &mut Cx, items: Vec<MenuItem>) {
+        self.items = items;
+        self.close(cx);
+    }
+
+    pub fn show_at(&mut self, cx: &mut Cx, point: DVec2) {
+        cx.stop_timer(self.submenu_timer);
+        self.pending_submenu = None;
+        self.levels.clear();
+        self.levels.push(MenuLevel::new(Vec::new(), point));
+        self.draw_list.redraw(cx);
+    }
+
+    pub fn close(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.submenu_timer);
+        self.pending_submenu = None;
+        if !self.levels.is_empty() {
+            self.levels.clear();
+            self.draw_list.redraw(cx);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.levels.is_empty()
+    }
+
+    fn level_items(&self, level: usize) -> &[MenuItem] {
+        let mut items = &self.items[..];
+        for index in &self.levels[level].path {
+            items = items[*index].submenu.as_deref().unwrap_or(&[]);
+        }
+        items
+    }
+
+    fn open_submenu(&mut self, cx: &mut Cx, level: usize, index: usize) {
+        self.levels.truncate(level + 1);
+        if self.level_items(level)[index].submenu.is_none() {
+            return
+        }
+        let rect = self.levels[level].item_areas.get(index).map(| area | area.rect(cx)).unwrap_or_default();
+        let mut path = self.levels[level].path.clone();
+        path.push(index);
+        self.levels.push(MenuLevel::new(path, dvec2(rect.pos.x + rect.size.x, rect.pos.y)));
+        self.draw_list.redraw(cx);
+    }
+
+    fn set_highlight(&mut self, cx: &mut Cx, level: usize, index: Option<usize>) {
+        if self.levels[level].highlighted != index {
+            self.levels[level].highlighted = index;
+            self.draw_list.redraw(cx);
+        }
+    }
+
+    fn move_highlight(&mut self, cx: &mut Cx, delta: isize) {
+        let level = self.levels.len() - 1;
+        let items = self.level_items(level);
+        let len = items.len() as isize;
+        if items.iter().all( | item | !item.enabled) {
+            return
+        }
+        let mut index = self.levels[level].highlighted.map_or(if delta > 0 {-1} else {len}, | i | i as isize);
+        loop {
+            index = (index + delta).rem_euclid(len);
+            if items[index as usize].enabled {
+                break
+            }
+        }
+        self.set_highlight(cx, level, Some(index as usize));
+    }
+
+    /// Activates an item: opens its submenu or selects it.
+    fn activate(&mut self, cx: &mut Cx, level: usize, index: usize, dispatch_action: &mut dyn FnMut(&mut Cx, ContextMenuAction)) {
+        let item = &self.level_items(level)[index];
+        if !item.enabled {
+            return
+        }
+        if item.submenu.is_some() {
+            self.open_submenu(cx, level, index);
+            self.move_highlight(cx, 1);
+        }
+        else {
+            let id = item.id;
+            self.close(cx);
+            dispatch_action(cx, ContextMenuAction::Selected {id});
+        }
+    }
+
+    pub fn handle_event_with(&mut self, cx: &mut Cx, event: &Event, dispatch_action: &mut dyn FnMut(&mut Cx, ContextMenuAction)) {
+        if self.levels.is_empty() {
+            return
+        }
+        if self.submenu_timer.is_event(event).is_some() {
+            self.submenu_timer = Timer::empty();
+            if let Some((level, index)) = self.pending_submenu.take() {
+                if level < self.levels.len() && self.levels[level].highlighted == Some(index) {
+                    self.open_submenu(cx, level, index);
+                }
+            }
+        }
+
+        match event {
+            Event::MouseDown(e) => if !self.levels.iter().any( | level | level.rect.contains(e.abs)) {
+                self.close(cx);
+                dispatch_action(cx, ContextMenuAction::Dismissed);
+                return
+            }
+            Event::KeyDown(ke) => {
+                let level = self.levels.len() - 1;
+                match ke.key_code {
+                    KeyCode::ArrowDown => self.move_highlight(cx, 1),
+                    KeyCode::ArrowUp => self.move_highlight(cx, -1),
+                    KeyCode::ArrowRight => if let Some(index) = self.levels[level].highlighted {
+                        if self.level_items(level)[index].submenu.is_some() {
+                            self.activate(cx, level, index, dispatch_action);
+                        }
+                    }
+                    KeyCode::ArrowLeft => if level > 0 {
+                        self.levels.truncate(level);
+                        self.draw_list.redraw(cx);
+                    }
+                    KeyCode::ReturnKey => if let Some(index) = self.levels[level].highlighted {
+                        self.activate(cx, level, index, dispatch_action);
+                    }
+                    KeyCode::Escape => {
+                        self.close(cx);
+                        dispatch_action(cx, ContextMenuAction::Dismissed);
+                    }
+                    _ => ()
+                }
+                return
+            }
+            _ => ()
+        }
+
+        let mut level = 0;
+        while level < self.levels.len() {
+            let mut index = 0;
+            while level < self.levels.len() && index < self.levels[level].item_areas.len() {
+                let area = self.levels[level].item_areas[index];
+                match event.hits(cx, area) {
+                    Hit::FingerHoverIn(_) => {
+                        let enabled = self.level_items(level)[index].enabled;
+                        self.set_highlight(cx, level, if enabled {Some(index)} else {None});
+                        if self.levels.len() > level + 1 && self.levels[level + 1].path.last() != Some(&index) {
+                            self.levels.truncate(level + 1);
+                            self.draw_list.redraw(cx);
+                        }
+                        if enabled && self.level_items(level)[index].submenu.is_some() {
+                            cx.stop_timer(self.submenu_timer);
+                            self.pending_submenu = Some((level, index));
+                            self.submenu_timer = cx.start_timeout(self.submenu_delay);
+                        }
+                    }
+                    Hit::FingerUp(fe) => if fe.is_over {
+                        self.activate(cx, level, index, dispatch_action);
+                        return
+                    }
+                    _ => ()
+                }
+                index += 1;
+            }
+            level += 1;
+        }
+    }
+
+    fn draw_menus(&mut self, cx: &mut Cx2d) {
+        self.draw_list.begin_overlay_last(cx);
+        cx.begin_pass_sized_turtle(Layout::flow_down());
+        let pass_size = cx.current_pass_size();
+        let mut needs_redraw = false;
+        for level in 0..self.levels.len() {
+            let pos = self.levels[level].pos;
+            self.draw_shadow.begin(cx, self.shadow_radius, &self.shadows);
+            self.draw_bg.begin(cx, self.menu_walk.with_abs_pos(pos), self.menu_layout);
+            let items = self.level_items(level).to_vec();
+            let highlighted = self.levels[level].highlighted;
+            let mut item_areas = Vec::with_capacity(items.len());
+            for (index, item) in items.iter().enumerate() {
+                self.draw_item.highlighted = if highlighted == Some(index) {1.0} else {0.0};
+                self.draw_item.enabled = if item.enabled {1.0} else {0.0};
+                self.draw_item.has_submenu = if item.submenu.is_some() {1.0} else {0.0};
+                self.draw_item.begin(cx, self.item_walk, self.item_layout);
+                if item.enabled {
+                    self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), &item.label);
+                }
+                else {
+                    self.draw_text_disabled.draw_walk(cx, Walk::fit(), Align::default(), &item.label);
+                }
+                self.draw_item.end(cx);
+                item_areas.push(self.draw_item.area());
+            }
+            self.draw_bg.end(cx);
+            let rect = self.draw_bg.area().rect(cx);
+            self.draw_shadow.end(cx, rect);
+            // keep the menu inside the window, this takes effect on the next draw
+            let fitted = dvec2(
+                pos.x.min(pass_size.x - rect.size.x).max(0.0),
+                pos.y.min(pass_size.y - rect.size.y).max(0.0)
+            );
+            if fitted != pos {
+                self.levels[level].pos = fitted;
+                needs_redraw = true;
+            }
+            self.levels[level].rect = rect;
+            self.levels[level].item_areas = item_areas;
+        }
+        cx.end_pass_sized_turtle();
+        self.draw_list.end(cx);
+        if needs_redraw {
+            self.draw_list.redraw(cx);
+        }
+    }
+}
+
+impl Widget for ContextMenu {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        let was_open = self.is_open();
+        self.handle_event_with(cx, event, &mut | cx, action | {
+            cx.widget_action(uid, &scope.path, action);
+        });
+        if was_open {
+            return
+        }
+
+        self.content.handle_event(cx, event, scope);
+
+        if self.open_on_right_click {
+            if let Hit::FingerDown(fe) = event.hits(cx, self.area) {
+                if fe.device.mouse_button() == Some(1) {
+                    self.show_at(cx, fe.abs);
+                }
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            cx.begin_turtle(walk, self.layout);
+        }
+        if self.draw_state.get().is_some() {
+            let walk = self.content.walk(cx);
+            self.content.draw_walk(cx, scope, walk) ?;
+            cx.end_turtle_with_area(&mut self.area);
+            if !self.levels.is_empty() {
+                self.draw_menus(cx);
+            }
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+}
+
+impl ContextMenuRef {
+    pub fn set_items(&self, cx: &mut Cx, items: Vec<MenuItem>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_items(cx, items);
+        }
+    }
+
+    pub fn show_at(&self, cx: &mut Cx, point: DVec2) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.show_at(cx, point);
+        }
+    }
+
+    pub fn close(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.close(cx);
+        }
+    }
+
+    pub fn selected(&self, actions: &Actions) -> Option<usize> {
+        if let ContextMenuAction::Selected {id} = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(id)
+        }
+        None
+    }
+
+    pub fn dismissed(&self, actions: &Actions) -> bool {
+        if let ContextMenuAction::Dismissed = actions.find_widget_action_cast(self.widget_uid()) {
+            return true
+        }
+        false
+    }
+}
@@ -919,7 +919,7 @@ impl Widget for Dock {
                     TabBarAction::TabCloseWasPressed(tab_id) => {
                         cx.widget_action(uid, &scope.path, DockAction::TabCloseWasPressed(tab_id))
                     }
-                    TabBarAction::None=>()
+                    TabBarAction::TabDragging(..) | TabBarAction::None=>()
                 }
             };
         }
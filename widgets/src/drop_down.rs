@@ -20,6 +20,7 @@ pub struct DropDown {
     
     #[redraw] #[live] draw_bg: DrawQuad,
     #[live] draw_text: DrawLabelText,
+    #[live] draw_search: DrawText,
     
     #[walk] walk: Walk,
     
@@ -33,9 +34,15 @@ pub struct DropDown {
     
     #[live] popup_shift: DVec2,
     
+    #[live] placeholder: String,
+    #[live] searchable: bool,
+    
     #[rust] is_open: bool,
+    #[rust] search: String,
+    #[rust] highlighted_item: Option<usize>,
     
-    #[live] selected_item: usize,
+    /// `None` until an item is picked, the placeholder is shown meanwhile
+    #[live] selected_item: Option<usize>,
     
     #[layout] layout: Layout,
 }
@@ -73,7 +80,7 @@ impl LiveHook for DropDown {
 }
 #[derive(Clone, Debug, DefaultNone)]
 pub enum DropDownAction {
-    Select(usize, LiveValue),
+    Selected {index: usize, value: String},
     None
 }
 
@@ -82,21 +89,79 @@ impl DropDown {
     
     pub fn set_open(&mut self, cx: &mut Cx) {
         self.is_open = true;
+        self.search.clear();
+        self.highlighted_item = self.selected_item;
         self.draw_bg.redraw(cx);
         let global = cx.global::<PopupMenuGlobal>().clone();
         let mut map = global.map.borrow_mut();
         let lb = map.get_mut(&self.popup_menu.unwrap()).unwrap();
-        let node_id = LiveId(self.selected_item as u64).into();
-        lb.init_select_item(node_id);
+        if let Some(item) = self.selected_item {
+            lb.init_select_item(LiveId(item as u64).into());
+        }
         cx.sweep_lock(self.draw_bg.area());
     }
     
     pub fn set_closed(&mut self, cx: &mut Cx) {
         self.is_open = false;
+        self.search.clear();
+        self.highlighted_item = None;
         self.draw_bg.redraw(cx);
         cx.sweep_unlock(self.draw_bg.area());
     }
     
+    /// The indices of the labels that match the current search text
+    pub fn filtered_items(&self) -> Vec<usize> {
+        let search = self.search.to_lowercase();
+        self.labels.iter().enumerate().filter_map( | (i, label) | {
+            if !self.searchable || search.is_empty() || label.to_lowercase().contains(&search) {
+                Some(i)
+            }
+            else {
+                None
+            }
+        }).collect()
+    }
+    
+    fn select_item(&mut self, cx: &mut Cx, scope: &mut Scope, item: usize) {
+        self.selected_item = Some(item);
+        let value = self.labels.get(item).cloned().unwrap_or_default();
+        cx.widget_action(self.widget_uid(), &scope.path, DropDownAction::Selected {index: item, value});
+        self.draw_bg.redraw(cx);
+    }
+    
+    fn move_highlight(&mut self, cx: &mut Cx, forward: bool) {
+        let items = self.filtered_items();
+        if items.is_empty() {
+            return
+        }
+        let next = match self.highlighted_item.and_then( | item | items.iter().position( | i | *i == item)) {
+            Some(pos) if forward => items[(pos + 1).min(items.len() - 1)],
+            Some(pos) => items[pos.max(1) - 1],
+            None => items[0]
+        };
+        self.highlighted_item = Some(next);
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        if let Some(menu) = map.get_mut(&self.popup_menu.unwrap()) {
+            menu.highlight_item(cx, LiveId(next as u64).into());
+        }
+    }
+    
+    fn update_search(&mut self, cx: &mut Cx) {
+        let items = self.filtered_items();
+        if !self.highlighted_item.map_or(false, | item | items.contains(&item)) {
+            self.highlighted_item = items.first().cloned();
+        }
+        let global = cx.global::<PopupMenuGlobal>().clone();
+        let mut map = global.map.borrow_mut();
+        if let Some(menu) = map.get_mut(&self.popup_menu.unwrap()) {
+            if let Some(item) = self.highlighted_item {
+                menu.init_select_item(LiveId(item as u64).into());
+            }
+            menu.redraw(cx);
+        }
+    }
+    
     pub fn draw_text(&mut self, cx: &mut Cx2d, label: &str) {
         self.draw_bg.begin(cx, self.walk, self.layout);
         self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), label);
@@ -109,9 +174,12 @@ impl DropDown {
         self.draw_bg.begin(cx, walk, self.layout);
         //let start_pos = cx.turtle().rect().pos;
         
-        if let Some(val) = self.labels.get(self.selected_item) {
+        if let Some(val) = self.selected_item.and_then( | item | self.labels.get(item)) {
             self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), val);
         }
+        else if !self.placeholder.is_empty() {
+            self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), &self.placeholder);
+        }
         else {
             self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), " ");
         }
@@ -132,12 +200,18 @@ impl DropDown {
             
             popup_menu.begin(cx);
             
-            for (i, item) in self.labels.iter().enumerate() {
+            if self.searchable {
+                // the search line sits on top, typed text is routed to us as we keep the key focus
+                let search = if self.search.is_empty() {" "} else {&self.search};
+                self.draw_search.draw_walk(cx, Walk::fit(), Align::default(), search);
+            }
+            
+            for i in self.filtered_items() {
                 let node_id = LiveId(i as u64).into();
-                if i == self.selected_item {
+                if Some(i) == self.selected_item {
                     item_pos = Some(cx.turtle().pos());
                 }
-                popup_menu.draw_item(cx, node_id, &item);
+                popup_menu.draw_item(cx, node_id, &self.labels[i]);
             }
             
             // ok we shift the entire menu. however we shouldnt go outside the screen area
@@ -150,8 +224,9 @@ impl Widget for DropDown {
     
     fn widget_to_data(&self, _cx: &mut Cx, actions: &Actions, nodes: &mut LiveNodeVec, path: &[LiveId]) -> bool {
         match actions.find_widget_action_cast(self.widget_uid()) {
-            DropDownAction::Select(_, value) => {
-                nodes.write_field_value(path, value.clone());
+            DropDownAction::Selected {index, ..} => {
+                let value = self.values.get(index).cloned().unwrap_or(LiveValue::None);
+                nodes.write_field_value(path, value);
                 true
             }
             _ => false
@@ -161,8 +236,8 @@ impl Widget for DropDown {
     fn data_to_widget(&mut self, cx: &mut Cx, nodes: &[LiveNode], path: &[LiveId]) {
         if let Some(value) = nodes.read_field_value(path) {
             if let Some(index) = self.values.iter().position( | v | v == value) {
-                if self.selected_item != index {
-                    self.selected_item = index;
+                if self.selected_item != Some(index) {
+                    self.selected_item = Some(index);
                     self.redraw(cx);
                 }
             }
@@ -174,7 +249,6 @@ impl Widget for DropDown {
     
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope)  {
         self.animator_handle_event(cx, event);
-        let uid = self.widget_uid();
                 
         if self.is_open && self.popup_menu.is_some() {
             // ok so how will we solve this one
@@ -189,9 +263,7 @@ impl Widget for DropDown {
                     }
                     PopupMenuAction::WasSelected(node_id) => {
                         //dispatch_action(cx, PopupMenuAction::WasSelected(node_id));
-                        self.selected_item = node_id.0.0 as usize;
-                        cx.widget_action(uid, &scope.path, DropDownAction::Select(self.selected_item, self.values.get(self.selected_item).cloned().unwrap_or(LiveValue::None)));
-                        self.draw_bg.redraw(cx);
+                        self.select_item(cx, scope, node_id.0.0 as usize);
                         close = true;
                     }
                     _ => ()
@@ -220,25 +292,53 @@ impl Widget for DropDown {
             Hit::KeyFocus(_) => {
                 self.animator_play(cx, id!(focus.on));
             }
+            Hit::KeyDown(ke) if self.is_open => match ke.key_code {
+                KeyCode::ArrowUp => {
+                    self.move_highlight(cx, false);
+                }
+                KeyCode::ArrowDown => {
+                    self.move_highlight(cx, true);
+                }
+                KeyCode::ReturnKey => {
+                    if let Some(item) = self.highlighted_item {
+                        self.select_item(cx, scope, item);
+                    }
+                    self.set_closed(cx);
+                }
+                KeyCode::Escape => {
+                    self.set_closed(cx);
+                }
+                KeyCode::Backspace if self.searchable => {
+                    self.search.pop();
+                    self.update_search(cx);
+                }
+                _ => ()
+            }
             Hit::KeyDown(ke) => match ke.key_code {
                 KeyCode::ArrowUp => {
-                    if self.selected_item > 0 {
-                        self.selected_item -= 1;
-                        cx.widget_action(uid, &scope.path, DropDownAction::Select(self.selected_item, self.values.get(self.selected_item).cloned().unwrap_or(LiveValue::None)));
-                        self.set_closed(cx);
-                        self.draw_bg.redraw(cx);
+                    if let Some(item) = self.selected_item.filter( | item | *item > 0) {
+                        self.select_item(cx, scope, item - 1);
                     }
                 }
                 KeyCode::ArrowDown => {
-                    if self.values.len() > 0 && self.selected_item < self.values.len() - 1 {
-                        self.selected_item += 1;
-                        cx.widget_action(uid, &scope.path, DropDownAction::Select(self.selected_item, self.values.get(self.selected_item).cloned().unwrap_or(LiveValue::None)));
-                        self.set_closed(cx);
-                        self.draw_bg.redraw(cx);
+                    let next = self.selected_item.map_or(0, | item | item + 1);
+                    if next < self.labels.len() {
+                        self.select_item(cx, scope, next);
                     }
                 },
+                KeyCode::ReturnKey => {
+                    self.set_open(cx);
+                }
                 _ => ()
             }
+            Hit::TextInput(te) if self.is_open && self.searchable => {
+                self.search.push_str(&te.input);
+                self.update_search(cx);
+            }
+            Hit::ImeCompositionEnd(e) if self.is_open && self.searchable => {
+                self.search.push_str(&e.text);
+                self.update_search(cx);
+            }
             Hit::FingerDown(_fe) => {
                 cx.set_key_focus(self.draw_bg.area());
                 self.set_open(cx);
@@ -287,8 +387,8 @@ impl DropDownRef {
     
     pub fn selected(&self, actions: &Actions) -> Option<usize> {
         if let Some(item) = actions.find_widget_action(self.widget_uid()) {
-            if let DropDownAction::Select(id, _) = item.cast() {
-                return Some(id)
+            if let DropDownAction::Selected {index, ..} = item.cast() {
+                return Some(index)
             }
         }
         None
@@ -296,29 +396,41 @@ impl DropDownRef {
     
     pub fn set_selected_item(&self, item: usize) {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.selected_item = item.min(inner.labels.len().max(1) - 1)
+            inner.selected_item = inner.labels.len().checked_sub(1).map( | last | item.min(last))
         }
     }
     
     pub fn set_selected_item_and_redraw(&self, cx: &mut Cx, item: usize) {
         if let Some(mut inner) = self.borrow_mut() {
-            let new_selected = item.min(inner.labels.len().max(1) - 1);
+            let new_selected = inner.labels.len().checked_sub(1).map( | last | item.min(last));
             if new_selected != inner.selected_item{
                 inner.selected_item = new_selected;
                 inner.draw_bg.redraw(cx);
             }
         }
     }
-    pub fn selected_item(&self) -> usize {
+    
+    /// Goes back to showing the placeholder
+    pub fn clear_selection(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            if inner.selected_item.take().is_some() {
+                inner.draw_bg.redraw(cx);
+            }
+        }
+    }
+    
+    pub fn selected_item(&self) -> Option<usize> {
         if let Some(inner) = self.borrow() {
             return inner.selected_item
         }
-        0
+        None
     }
     
     pub fn selected_label(&self) -> String {
         if let Some(inner) = self.borrow() {
-            return inner.labels[inner.selected_item].clone()
+            if let Some(label) = inner.selected_item.and_then( | item | inner.labels.get(item)) {
+                return label.clone()
+            }
         }
         "".to_string()
     }
@@ -326,7 +438,7 @@ impl DropDownRef {
     pub fn set_selected_by_label(&self, label: &str) {
         if let Some(mut inner) = self.borrow_mut() {
             if let Some(index) = inner.labels.iter().position( | v | v == label) {
-                inner.selected_item = index
+                inner.selected_item = Some(index)
             }
         }
     }
@@ -334,8 +446,8 @@ impl DropDownRef {
     pub fn set_selected_by_label_and_redraw(&self, label: &str, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() {
             if let Some(index) = inner.labels.iter().position( | v | v == label) {
-                if inner.selected_item != index{
-                    inner.selected_item = index;
+                if inner.selected_item != Some(index){
+                    inner.selected_item = Some(index);
                     inner.draw_bg.redraw(cx);
                 }
             }
@@ -0,0 +1,84 @@
+/// A Fenwick (binary indexed) tree over `f64` values, used to keep cumulative
+/// row heights for virtualized lists. Updating a single value, querying a prefix
+/// sum and finding the item at an offset are all O(log n).
+#[derive(Clone, Debug, Default)]
+pub struct FenwickTree {
+    values: Vec<f64>,
+    tree: Vec<f64>,
+}
+
+impl FenwickTree {
+    pub fn new(len: usize) -> Self {
+        Self {
+            values: vec![0.0; len],
+            tree: vec![0.0; len],
+        }
+    }
+
+    pub fn from_values(values: impl IntoIterator<Item = f64>) -> Self {
+        let values: Vec<f64> = values.into_iter().collect();
+        let mut tree = values.clone();
+        for i in 0..tree.len() {
+            let parent = i | (i + 1);
+            if parent < tree.len() {
+                tree[parent] += tree[i];
+            }
+        }
+        Self {values, tree}
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> f64 {
+        self.values[index]
+    }
+
+    pub fn set(&mut self, index: usize, value: f64) {
+        let delta = value - self.values[index];
+        self.values[index] = value;
+        let mut i = index;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i |= i + 1;
+        }
+    }
+
+    /// Sum of the values before `end` (exclusive).
+    pub fn prefix_sum(&self, end: usize) -> f64 {
+        let mut sum = 0.0;
+        let mut i = end.min(self.tree.len());
+        while i > 0 {
+            sum += self.tree[i - 1];
+            i &= i - 1;
+        }
+        sum
+    }
+
+    pub fn total(&self) -> f64 {
+        self.prefix_sum(self.len())
+    }
+
+    /// Index of the item that covers `offset`, i.e. the last index whose prefix sum is
+    /// at or below it. Returns `len()` when the offset lies past the end.
+    pub fn find(&self, offset: f64) -> usize {
+        let len = self.tree.len();
+        let mut pos = 0;
+        let mut rest = offset;
+        let mut step = if len == 0 {0} else {1 << (usize::BITS - 1 - len.leading_zeros())};
+        while step > 0 {
+            let next = pos + step;
+            if next <= len && self.tree[next - 1] <= rest {
+                pos = next;
+                rest -= self.tree[next - 1];
+            }
+            step >>= 1;
+        }
+        pos
+    }
+}
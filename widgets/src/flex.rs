@@ -0,0 +1,419 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    FlexContainerBase = {{FlexContainer}} {}
+    FlexItemBase = {{FlexItem}} {}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum FlexDirection {
+    #[pick] Row,
+    Column,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum FlexWrap {
+    #[pick] NoWrap,
+    Wrap,
+}
+
+/// How the free space of a line is spread along the main axis.
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum FlexJustify {
+    #[pick] Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+}
+
+/// Where items sit across the main axis within their line.
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum FlexAlign {
+    #[pick] Start,
+    Center,
+    End,
+    Stretch,
+}
+
+/// The sizes of an item going into the flex layout, along the main and cross axis.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FlexItemSize {
+    pub basis: f64,
+    pub cross: f64,
+    pub grow: f64,
+    pub shrink: f64,
+}
+
+/// Lays its children out in rows or columns, the way CSS flexbox does. Children wrapped in a
+/// `FlexItem` can grow, shrink and set their basis, other children don't grow and size by
+/// their content.
+///
+/// Children keep their margins: each child is given a box its margins fit in. Children whose
+/// size depends on their content are measured while drawing, so a change in their size takes
+/// effect a frame later.
+#[derive(Live, LiveRegisterWidget, WidgetRef, WidgetSet)]
+pub struct FlexContainer {
+    #[walk] walk: Walk,
+    /// Only the padding is used, the flex fields replace the flow
+    #[layout] layout: Layout,
+    #[live] direction: FlexDirection,
+    #[live] wrap: FlexWrap,
+    #[live] justify: FlexJustify,
+    #[live] align: FlexAlign,
+    /// Space between items along the main axis, and between lines when wrapping
+    #[live] gap: f64,
+    #[live] breakpoints: Breakpoints,
+    /// Below this breakpoint the items are laid out in a column whatever the direction, the
+    /// default of `Xs` never does
+    #[live] column_below: Breakpoint,
+
+    #[rust] area: Area,
+    #[rust] children: ComponentMap<LiveId, WidgetRef>,
+    #[rust] draw_order: Vec<LiveId>,
+    /// Content sizes of the children as measured when they were last drawn
+    #[rust] measured: Vec<DVec2>,
+    #[rust] child_rects: Vec<Rect>,
+    #[rust] child_open: bool,
+    #[rust] breakpoint: Option<Breakpoint>,
+    #[rust] draw_state: DrawStateWrap<usize>,
+}
+
+impl WidgetNode for FlexContainer {
+    fn walk(&mut self, _cx: &mut Cx) -> Walk {
+        self.walk
+    }
+
+    fn redraw(&mut self, cx: &mut Cx) {
+        self.area.redraw(cx)
+    }
+
+    fn find_widgets(&mut self, path: &[LiveId], cached: WidgetCache, results: &mut WidgetSet) {
+        for child in self.children.values_mut() {
+            child.find_widgets(path, cached, results);
+        }
+    }
+}
+
+impl LiveHook for FlexContainer {
+    fn before_apply(&mut self, _cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if let ApplyFrom::UpdateFromDoc {..} = apply.from {
+            self.draw_order.clear();
+        }
+    }
+
+    fn apply_value_instance(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) -> usize {
+        let id = nodes[index].id;
+        match apply.from {
+            ApplyFrom::Animate | ApplyFrom::Over => {
+                if let Some(component) = self.children.get_mut(&nodes[index].id) {
+                    component.apply(cx, apply, index, nodes)
+                }
+                else {
+                    nodes.skip_node(index)
+                }
+            }
+            ApplyFrom::NewFromDoc {..} | ApplyFrom::UpdateFromDoc {..} => {
+                if nodes[index].origin.has_prop_type(LivePropType::Instance) {
+                    self.draw_order.push(id);
+                    return self.children.get_or_insert(cx, id, | cx | {
+                        WidgetRef::new(cx)
+                    }).apply(cx, apply, index, nodes);
+                }
+                else {
+                    cx.apply_error_no_matching_field(live_error_origin!(), index, nodes);
+                    nodes.skip_node(index)
+                }
+            }
+            _ => {
+                nodes.skip_node(index)
+            }
+        }
+    }
+}
+
+impl Widget for FlexContainer {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        for id in &self.draw_order {
+            if let Some(child) = self.children.get(id) {
+                scope.with_id(*id, | scope | child.handle_event(cx, event, scope));
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, 0) {
+            cx.begin_turtle(walk, Layout {padding: self.layout.padding, ..Layout::flow_down()});
+            let available = cx.turtle().padded_rect().size;
+            let available = dvec2(
+                if available.x.is_nan() {f64::INFINITY} else {available.x},
+                if available.y.is_nan() {f64::INFINITY} else {available.y}
+            );
+            self.breakpoint = Some(cx.current_breakpoint(&self.breakpoints));
+            let size = self.layout_children(cx, available);
+            // the children are placed absolutely, so claim their space for fit sizing
+            cx.walk_turtle(Walk::size(Size::Fixed(size.x), Size::Fixed(size.y)));
+        }
+        while let Some(index) = self.draw_state.get() {
+            let Some(id) = self.draw_order.get(index).copied() else {
+                break
+            };
+            let (Some(child), Some(rect)) = (self.children.get(&id), self.child_rects.get(index).copied()) else {
+                self.draw_state.set(index + 1);
+                continue
+            };
+            // a child that yields mid draw resumes inside the turtle it was given
+            if !self.child_open {
+                let origin = cx.turtle().padded_rect().pos;
+                cx.begin_turtle(Walk {
+                    abs_pos: Some(origin + rect.pos),
+                    margin: Margin::default(),
+                    width: Size::Fixed(rect.size.x),
+                    height: Size::Fixed(rect.size.y),
+                }, Layout::flow_down());
+                self.child_open = true;
+            }
+            let child_walk = child.walk(cx);
+            scope.with_id(id, | scope | child.draw_walk(cx, scope, child_walk)) ?;
+            let used = cx.turtle().used();
+            cx.end_turtle();
+            self.child_open = false;
+            if let Some(measured) = self.measured.get_mut(index) {
+                if *measured != used {
+                    *measured = used;
+                    // content sizes changed, lay out again with the new sizes
+                    self.area.redraw(cx);
+                }
+            }
+            self.draw_state.set(index + 1);
+        }
+        cx.end_turtle_with_area(&mut self.area);
+        self.draw_state.end();
+        DrawStep::done()
+    }
+}
+
+impl FlexContainer {
+    /// The direction the items are laid out in at the breakpoint of the last draw.
+    pub fn current_direction(&self) -> FlexDirection {
+        match self.breakpoint {
+            Some(breakpoint) if breakpoint < self.column_below => FlexDirection::Column,
+            _ => self.direction
+        }
+    }
+
+    /// Works out the rect of every child within `available_size`, relative to the padded
+    /// origin of the container, and returns the size the children take up. Infinite sizes
+    /// leave the axis unconstrained.
+    pub fn layout_children(&mut self, cx: &mut Cx, available_size: DVec2) -> DVec2 {
+        self.measured.resize(self.draw_order.len(), DVec2::default());
+        let direction = self.current_direction();
+        let row = direction == FlexDirection::Row;
+        let along = | v: DVec2 | if row {(v.x, v.y)} else {(v.y, v.x)};
+        let mut items = Vec::with_capacity(self.draw_order.len());
+        for (index, id) in self.draw_order.iter().enumerate() {
+            let child = match self.children.get(id) {
+                Some(child) => child,
+                None => {
+                    items.push(FlexItemSize::default());
+                    continue
+                }
+            };
+            let walk = child.walk(cx);
+            let (main_size, cross_size) = if row {(walk.width, walk.height)} else {(walk.height, walk.width)};
+            let (measured_main, measured_cross) = along(self.measured[index]);
+            let (margin_main, margin_cross) = along(walk.margin.size());
+            // item sizes include the margins, the measured sizes have them already
+            let natural = | size: Size, measured: f64, margin: f64 | match size {
+                Size::Fixed(v) => v + margin,
+                Size::Fit => measured,
+                // filling children have no content size of their own
+                Size::Fill | Size::All => margin,
+            };
+            let mut item = FlexItemSize {
+                basis: natural(main_size, measured_main, margin_main),
+                cross: natural(cross_size, measured_cross, margin_cross),
+                grow: 0.0,
+                shrink: 1.0,
+            };
+            if let Some(flex_item) = child.borrow::<FlexItem>() {
+                item.grow = flex_item.flex_grow;
+                item.shrink = flex_item.flex_shrink;
+                if flex_item.flex_basis > 0.0 {
+                    item.basis = flex_item.flex_basis + margin_main;
+                }
+            }
+            items.push(item);
+        }
+        let (rects, size) = compute_flex_layout(
+            direction,
+            self.wrap,
+            self.justify,
+            self.align,
+            self.gap,
+            &items,
+            available_size
+        );
+        self.child_rects = rects;
+        size
+    }
+}
+
+/// The flexbox algorithm on its own: breaks the items into lines, resolves their main sizes
+/// from grow and shrink, then places them along both axes. Returns the item rects and the
+/// size they take up together.
+pub fn compute_flex_layout(
+    direction: FlexDirection,
+    wrap: FlexWrap,
+    justify: FlexJustify,
+    align: FlexAlign,
+    gap: f64,
+    items: &[FlexItemSize],
+    available_size: DVec2,
+) -> (Vec<Rect>, DVec2) {
+    let row = direction == FlexDirection::Row;
+    let (available_main, available_cross) = if row {
+        (available_size.x, available_size.y)
+    } else {
+        (available_size.y, available_size.x)
+    };
+
+    // break into lines, each line being a range of items
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut line_main = 0.0;
+    for (index, item) in items.iter().enumerate() {
+        let next = if index == start {item.basis} else {line_main + gap + item.basis};
+        if wrap == FlexWrap::Wrap && index > start && next > available_main {
+            lines.push(start..index);
+            start = index;
+            line_main = item.basis;
+        }
+        else {
+            line_main = next;
+        }
+    }
+    if start < items.len() {
+        lines.push(start..items.len());
+    }
+
+    let single_line = lines.len() == 1 && wrap == FlexWrap::NoWrap;
+    let mut rects = vec![Rect::default(); items.len()];
+    let mut cross_pos = 0.0;
+    let mut used_main: f64 = 0.0;
+    for (line_index, line) in lines.iter().enumerate() {
+        let line_items = &items[line.clone()];
+        let gaps = gap * (line_items.len() as f64 - 1.0);
+        let mut sizes: Vec<f64> = line_items.iter().map( | item | item.basis).collect();
+        let mut free = if available_main.is_finite() {
+            available_main - sizes.iter().sum::<f64>() - gaps
+        } else {
+            0.0
+        };
+        if free > 0.0 {
+            let total_grow: f64 = line_items.iter().map( | item | item.grow).sum();
+            if total_grow > 0.0 {
+                for (size, item) in sizes.iter_mut().zip(line_items) {
+                    *size += free * item.grow / total_grow;
+                }
+                free = 0.0;
+            }
+        }
+        else if free < 0.0 {
+            // shrinking is weighted by the basis, so small items don't vanish first
+            let total_shrink: f64 = line_items.iter().map( | item | item.shrink * item.basis).sum();
+            if total_shrink > 0.0 {
+                for (size, item) in sizes.iter_mut().zip(line_items) {
+                    *size = (*size + free * item.shrink * item.basis / total_shrink).max(0.0);
+                }
+            }
+            free = 0.0;
+        }
+
+        let count = line_items.len() as f64;
+        let (mut main_pos, spacing) = match justify {
+            FlexJustify::Start => (0.0, gap),
+            FlexJustify::End => (free, gap),
+            FlexJustify::Center => (free * 0.5, gap),
+            FlexJustify::SpaceBetween if count > 1.0 => (0.0, gap + free / (count - 1.0)),
+            FlexJustify::SpaceBetween => (0.0, gap),
+            FlexJustify::SpaceAround => (free / count * 0.5, gap + free / count),
+        };
+
+        let line_cross = if single_line && available_cross.is_finite() {
+            available_cross
+        } else {
+            line_items.iter().fold(0.0, | max: f64, item | max.max(item.cross))
+        };
+        for (offset, (main_size, item)) in sizes.iter().zip(line_items).enumerate() {
+            let (cross_offset, cross_size) = match align {
+                FlexAlign::Start => (0.0, item.cross),
+                FlexAlign::Center => ((line_cross - item.cross) * 0.5, item.cross),
+                FlexAlign::End => (line_cross - item.cross, item.cross),
+                FlexAlign::Stretch => (0.0, line_cross),
+            };
+            let (pos, size) = if row {
+                (dvec2(main_pos, cross_pos + cross_offset), dvec2(*main_size, cross_size))
+            } else {
+                (dvec2(cross_pos + cross_offset, main_pos), dvec2(cross_size, *main_size))
+            };
+            rects[line.start + offset] = Rect {pos, size};
+            main_pos += *main_size;
+            if offset + 1 < line_items.len() {
+                main_pos += spacing;
+            }
+        }
+        used_main = used_main.max(main_pos);
+        cross_pos += line_cross;
+        if line_index + 1 < lines.len() {
+            cross_pos += gap;
+        }
+    }
+    let main = if available_main.is_finite() {available_main} else {used_main};
+    let size = if row {dvec2(main, cross_pos)} else {dvec2(cross_pos, main)};
+    (rects, size)
+}
+
+/// Sets how a child of a `FlexContainer` grows and shrinks. It takes the walk of its content.
+#[derive(Live, LiveHook, LiveRegisterWidget, WidgetRef, WidgetSet)]
+pub struct FlexItem {
+    #[live] content: WidgetRef,
+    /// Share of the free space of the line this item takes
+    #[live] flex_grow: f64,
+    /// Share of the overflow of the line this item gives up, weighted by its basis
+    #[live(1.0)] flex_shrink: f64,
+    /// Main axis size before growing or shrinking, zero sizes the item by its content
+    #[live] flex_basis: f64,
+}
+
+impl WidgetNode for FlexItem {
+    fn walk(&mut self, cx: &mut Cx) -> Walk {
+        self.content.walk(cx)
+    }
+
+    fn redraw(&mut self, cx: &mut Cx) {
+        self.content.redraw(cx)
+    }
+
+    fn find_widgets(&mut self, path: &[LiveId], cached: WidgetCache, results: &mut WidgetSet) {
+        self.content.find_widgets(path, cached, results);
+    }
+}
+
+impl Widget for FlexItem {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.content.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.content.draw_walk(cx, scope, walk)
+    }
+}
@@ -0,0 +1,377 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    GridLayoutBase = {{GridLayout}} {}
+    GridItemBase = {{GridItem}} {}
+}
+
+/// The size of a column or row.
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum GridTrack {
+    /// A share of the space the other tracks leave
+    #[pick(1.0)] Fr(f64),
+    #[live(100.0)] Fixed(f64),
+    /// The size of the largest item that sits in this track alone
+    Auto,
+}
+
+/// Whether children fill the grid row by row or column by column.
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum GridAutoFlow {
+    #[pick] Row,
+    Column,
+}
+
+/// The content size and span of a child going into the grid layout.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct GridItemSize {
+    pub size: DVec2,
+    pub col_span: usize,
+    pub row_span: usize,
+}
+
+/// Places its children in a grid, left to right and top to bottom, skipping cells taken by
+/// earlier children. Children wrapped in a `GridItem` can span several cells, and keep their
+/// margins within their cells.
+///
+/// Tracks past the end of `column_sizes` are `Fr(1.0)`, tracks past the end of `row_sizes`
+/// are `Auto`. With row flow `rows` is a minimum and rows are added as the children need
+/// them, with column flow the same goes for `columns`.
+#[derive(Live, LiveRegisterWidget, WidgetRef, WidgetSet)]
+pub struct GridLayout {
+    #[walk] walk: Walk,
+    /// Only the padding is used
+    #[layout] layout: Layout,
+    #[live(1)] columns: usize,
+    #[live] rows: usize,
+    #[live] column_gap: f64,
+    #[live] row_gap: f64,
+    #[live] column_sizes: Vec<GridTrack>,
+    #[live] row_sizes: Vec<GridTrack>,
+    #[live] grid_auto_flow: GridAutoFlow,
+
+    #[rust] area: Area,
+    #[rust] children: ComponentMap<LiveId, WidgetRef>,
+    #[rust] draw_order: Vec<LiveId>,
+    /// Content sizes of the children as measured when they were last drawn
+    #[rust] measured: Vec<DVec2>,
+    #[rust] child_rects: Vec<Rect>,
+    #[rust] child_open: bool,
+    #[rust] draw_state: DrawStateWrap<usize>,
+}
+
+impl WidgetNode for GridLayout {
+    fn walk(&mut self, _cx: &mut Cx) -> Walk {
+        self.walk
+    }
+
+    fn redraw(&mut self, cx: &mut Cx) {
+        self.area.redraw(cx)
+    }
+
+    fn find_widgets(&mut self, path: &[LiveId], cached: WidgetCache, results: &mut WidgetSet) {
+        for child in self.children.values_mut() {
+            child.find_widgets(path, cached, results);
+        }
+    }
+}
+
+impl LiveHook for GridLayout {
+    fn before_apply(&mut self, _cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if let ApplyFrom::UpdateFromDoc {..} = apply.from {
+            self.draw_order.clear();
+        }
+    }
+
+    fn apply_value_instance(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) -> usize {
+        let id = nodes[index].id;
+        match apply.from {
+            ApplyFrom::Animate | ApplyFrom::Over => {
+                if let Some(component) = self.children.get_mut(&nodes[index].id) {
+                    component.apply(cx, apply, index, nodes)
+                }
+                else {
+                    nodes.skip_node(index)
+                }
+            }
+            ApplyFrom::NewFromDoc {..} | ApplyFrom::UpdateFromDoc {..} => {
+                if nodes[index].origin.has_prop_type(LivePropType::Instance) {
+                    self.draw_order.push(id);
+                    return self.children.get_or_insert(cx, id, | cx | {
+                        WidgetRef::new(cx)
+                    }).apply(cx, apply, index, nodes);
+                }
+                else {
+                    cx.apply_error_no_matching_field(live_error_origin!(), index, nodes);
+                    nodes.skip_node(index)
+                }
+            }
+            _ => {
+                nodes.skip_node(index)
+            }
+        }
+    }
+}
+
+impl Widget for GridLayout {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        for id in &self.draw_order {
+            if let Some(child) = self.children.get(id) {
+                scope.with_id(*id, | scope | child.handle_event(cx, event, scope));
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, 0) {
+            cx.begin_turtle(walk, Layout {padding: self.layout.padding, ..Layout::flow_down()});
+            let available = cx.turtle().padded_rect().size;
+            let available = dvec2(
+                if available.x.is_nan() {f64::INFINITY} else {available.x},
+                if available.y.is_nan() {f64::INFINITY} else {available.y}
+            );
+            let size = self.layout_children(cx, available);
+            // the children are placed absolutely, so claim their space for fit sizing
+            cx.walk_turtle(Walk::size(Size::Fixed(size.x), Size::Fixed(size.y)));
+        }
+        while let Some(index) = self.draw_state.get() {
+            let Some(id) = self.draw_order.get(index).copied() else {
+                break
+            };
+            let (Some(child), Some(rect)) = (self.children.get(&id), self.child_rects.get(index).copied()) else {
+                self.draw_state.set(index + 1);
+                continue
+            };
+            // a child that yields mid draw resumes inside the turtle it was given
+            if !self.child_open {
+                let origin = cx.turtle().padded_rect().pos;
+                cx.begin_turtle(Walk {
+                    abs_pos: Some(origin + rect.pos),
+                    margin: Margin::default(),
+                    width: Size::Fixed(rect.size.x),
+                    height: Size::Fixed(rect.size.y),
+                }, Layout::flow_down());
+                self.child_open = true;
+            }
+            let child_walk = child.walk(cx);
+            scope.with_id(id, | scope | child.draw_walk(cx, scope, child_walk)) ?;
+            let used = cx.turtle().used();
+            cx.end_turtle();
+            self.child_open = false;
+            if let Some(measured) = self.measured.get_mut(index) {
+                if *measured != used {
+                    *measured = used;
+                    // content sizes changed, lay out again with the new sizes
+                    self.area.redraw(cx);
+                }
+            }
+            self.draw_state.set(index + 1);
+        }
+        cx.end_turtle_with_area(&mut self.area);
+        self.draw_state.end();
+        DrawStep::done()
+    }
+}
+
+impl GridLayout {
+    /// Works out the rect of every child within `available_size`, relative to the padded
+    /// origin of the grid, and returns the size of the grid.
+    pub fn layout_children(&mut self, cx: &mut Cx, available_size: DVec2) -> DVec2 {
+        self.measured.resize(self.draw_order.len(), DVec2::default());
+        let mut items = Vec::with_capacity(self.draw_order.len());
+        for (index, id) in self.draw_order.iter().enumerate() {
+            let child = match self.children.get(id) {
+                Some(child) => child,
+                None => {
+                    items.push(GridItemSize::default());
+                    continue
+                }
+            };
+            let walk = child.walk(cx);
+            // item sizes include the margins, the measured sizes have them already
+            let natural = | size: Size, measured: f64, margin: f64 | match size {
+                Size::Fixed(v) => v + margin,
+                Size::Fit => measured,
+                // filling children have no content size of their own
+                Size::Fill | Size::All => margin,
+            };
+            let mut item = GridItemSize {
+                size: dvec2(
+                    natural(walk.width, self.measured[index].x, walk.margin.width()),
+                    natural(walk.height, self.measured[index].y, walk.margin.height())
+                ),
+                col_span: 1,
+                row_span: 1,
+            };
+            if let Some(grid_item) = child.borrow::<GridItem>() {
+                item.col_span = grid_item.col_span.max(1);
+                item.row_span = grid_item.row_span.max(1);
+            }
+            items.push(item);
+        }
+        let (rects, size) = self.compute_layout(&items, available_size);
+        self.child_rects = rects;
+        size
+    }
+
+    /// Places the items in cells, returning the column and row of each item. Spans across
+    /// the flow have to fit the grid.
+    fn place_items(&self, items: &[GridItemSize]) -> Vec<(usize, usize)> {
+        // with column flow the same algorithm runs on the transposed grid
+        let by_row = self.grid_auto_flow == GridAutoFlow::Row;
+        let width = if by_row {self.columns} else {self.rows}.max(1);
+        let mut taken: Vec<Vec<bool >> = Vec::new();
+        let mut cursor = (0, 0);
+        let mut cells = Vec::with_capacity(items.len());
+        for item in items {
+            let (span_across, span_down) = if by_row {
+                (item.col_span, item.row_span)
+            } else {
+                (item.row_span, item.col_span)
+            };
+            let (mut down, mut across) = cursor;
+            loop {
+                if across + span_across > width {
+                    across = 0;
+                    down += 1;
+                    continue
+                }
+                let free = (down..down + span_down).all( | d | {
+                    (across..across + span_across).all( | a | {
+                        !taken.get(d).map_or(false, | line | line[a])
+                    })
+                });
+                if free {
+                    break
+                }
+                across += 1;
+            }
+            while taken.len() < down + span_down {
+                taken.push(vec![false; width]);
+            }
+            for line in &mut taken[down..down + span_down] {
+                for cell in &mut line[across..across + span_across] {
+                    *cell = true;
+                }
+            }
+            cursor = (down, across + span_across);
+            cells.push(if by_row {(across, down)} else {(down, across)});
+        }
+        cells
+    }
+
+    /// Resolves the sizes of the tracks along one axis. `spans` holds the first track, the
+    /// number of tracks and the content size of each item along this axis.
+    fn resolve_tracks(tracks: &[GridTrack], default: GridTrack, count: usize, gap: f64, available: f64, spans: &[(usize, usize, f64)]) -> Vec<f64> {
+        let track = | index: usize | tracks.get(index).copied().unwrap_or(default);
+        let mut sizes = vec![0.0; count];
+        let mut total_fr = 0.0;
+        for (index, size) in sizes.iter_mut().enumerate() {
+            match track(index) {
+                GridTrack::Fixed(v) => *size = v,
+                // without a size to share, fractions size by their content like auto tracks
+                GridTrack::Fr(fr) if available.is_finite() => total_fr += fr,
+                GridTrack::Fr(_) | GridTrack::Auto => {
+                    *size = spans.iter()
+                        .filter( | (start, span, _) | *start == index && *span == 1)
+                        .fold(0.0, | max: f64, (_, _, size) | max.max(*size));
+                }
+            }
+        }
+        if total_fr > 0.0 {
+            let gaps = gap * count.saturating_sub(1) as f64;
+            let used: f64 = (0..count).filter( | i | !matches!(track(*i), GridTrack::Fr(_))).map( | i | sizes[i]).sum();
+            let free = (available - used - gaps).max(0.0);
+            for (index, size) in sizes.iter_mut().enumerate() {
+                if let GridTrack::Fr(fr) = track(index) {
+                    *size = free * fr / total_fr;
+                }
+            }
+        }
+        sizes
+    }
+
+    /// Lays the items out in cells, returning their rects and the size of the grid.
+    pub fn compute_layout(&self, items: &[GridItemSize], available_size: DVec2) -> (Vec<Rect>, DVec2) {
+        let mut columns = self.columns.max(1);
+        let mut rows = self.rows.max(1);
+        let items: Vec<_> = items.iter().map( | item | match self.grid_auto_flow {
+            GridAutoFlow::Row => GridItemSize {col_span: item.col_span.clamp(1, columns), row_span: item.row_span.max(1), ..*item},
+            GridAutoFlow::Column => GridItemSize {col_span: item.col_span.max(1), row_span: item.row_span.clamp(1, rows), ..*item},
+        }).collect();
+        let cells = self.place_items(&items);
+        for (item, (col, row)) in items.iter().zip(&cells) {
+            columns = columns.max(col + item.col_span);
+            rows = rows.max(row + item.row_span);
+        }
+        let col_spans: Vec<_> = items.iter().zip(&cells).map( | (item, (col, _)) | (*col, item.col_span, item.size.x)).collect();
+        let row_spans: Vec<_> = items.iter().zip(&cells).map( | (item, (_, row)) | (*row, item.row_span, item.size.y)).collect();
+        let col_sizes = Self::resolve_tracks(&self.column_sizes, GridTrack::Fr(1.0), columns, self.column_gap, available_size.x, &col_spans);
+        let row_sizes = Self::resolve_tracks(&self.row_sizes, GridTrack::Auto, rows, self.row_gap, available_size.y, &row_spans);
+
+        let offsets = | sizes: &[f64], gap: f64 | {
+            let mut pos = 0.0;
+            sizes.iter().map( | size | {
+                let start = pos;
+                pos += size + gap;
+                start
+            }).collect::<Vec<f64 >> ()
+        };
+        let col_pos = offsets(&col_sizes, self.column_gap);
+        let row_pos = offsets(&row_sizes, self.row_gap);
+        let extent = | pos: &[f64], sizes: &[f64], start: usize, span: usize | {
+            let end = (start + span).min(sizes.len()) - 1;
+            pos[end] + sizes[end] - pos[start]
+        };
+        let rects = items.iter().zip(&cells).map( | (item, (col, row)) | Rect {
+            pos: dvec2(col_pos[*col], row_pos[*row]),
+            size: dvec2(
+                extent(&col_pos, &col_sizes, *col, item.col_span),
+                extent(&row_pos, &row_sizes, *row, item.row_span)
+            ),
+        }).collect();
+        let size = dvec2(
+            extent(&col_pos, &col_sizes, 0, columns),
+            extent(&row_pos, &row_sizes, 0, rows)
+        );
+        (rects, size)
+    }
+}
+
+/// Lets a child of a `GridLayout` span several cells. It takes the walk of its content.
+#[derive(Live, LiveHook, LiveRegisterWidget, WidgetRef, WidgetSet)]
+pub struct GridItem {
+    #[live] content: WidgetRef,
+    #[live(1)] col_span: usize,
+    #[live(1)] row_span: usize,
+}
+
+impl WidgetNode for GridItem {
+    fn walk(&mut self, cx: &mut Cx) -> Walk {
+        self.content.walk(cx)
+    }
+
+    fn redraw(&mut self, cx: &mut Cx) {
+        self.content.redraw(cx)
+    }
+
+    fn find_widgets(&mut self, path: &[LiveId], cached: WidgetCache, results: &mut WidgetSet) {
+        self.content.find_widgets(path, cached, results);
+    }
+}
+
+impl Widget for GridItem {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.content.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.content.draw_walk(cx, scope, walk)
+    }
+}
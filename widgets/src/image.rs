@@ -19,7 +19,13 @@ pub struct Image {
     #[live(1.0)] width_scale: f64,
     #[live] fit: ImageFit,
     #[live] source: LiveDependency,
+    /// Loaded in the background, see `CxImageLoadExt::load_image_async`
+    #[live] url: String,
+    /// Drawn in place of the image whilst `url` is loading
+    #[live] placeholder_color: Vec4,
+    #[live] draw_placeholder: DrawColor,
     #[rust] texture: Option<Texture>,
+    #[rust] loaded_url: String,
 }
 
 impl ImageCacheImpl for Image {
@@ -39,10 +45,25 @@ impl LiveHook for Image{
         if source.as_str().len()>0 {
             let _ = self.load_image_dep_by_path(cx, source.as_str());
         }
+        if self.url != self.loaded_url {
+            self.load_url(cx);
+        }
     }
 }
 
 impl Widget for Image {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, _scope: &mut Scope) {
+        if self.url.len() > 0 && self.loaded_url != self.url {
+            cx.handle_image_load_event(event);
+            if let Some(texture) = cx.cached_image(&self.url) {
+                self.texture = Some(texture);
+                self.loaded_url = self.url.clone();
+                self.draw_bg.redraw(cx);
+            }
+        }
+    }
+    
+
     fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
         self.draw_walk(cx, walk)
     }
@@ -50,7 +71,58 @@ impl Widget for Image {
 
 impl Image {
     
+    pub fn set_url(&mut self, cx: &mut Cx, url: &str) {
+        if self.url != url {
+            self.url = url.to_string();
+            self.load_url(cx);
+        }
+    }
+    
+    fn load_url(&mut self, cx: &mut Cx) {
+        self.loaded_url.clear();
+        if self.url.len() == 0 {
+            return
+        }
+        if let Some(texture) = cx.cached_image(&self.url) {
+            self.texture = Some(texture);
+            self.loaded_url = self.url.clone();
+        }
+        else {
+            self.texture = None;
+            cx.load_image_async(&self.url);
+        }
+        self.draw_bg.redraw(cx);
+    }
+    
+    /// Draws the image into the part of `rect` the fit mode asks for, cropping whatever
+    /// falls outside of `rect` by adjusting the sampled region.
+    fn draw_blit(&mut self, cx: &mut Cx2d, rect: Rect, image_size: DVec2) {
+        let blit = self.fit.blit_rect(rect, image_size);
+        let pos = dvec2(blit.pos.x.max(rect.pos.x), blit.pos.y.max(rect.pos.y));
+        let end = dvec2(
+            (blit.pos.x + blit.size.x).min(rect.pos.x + rect.size.x),
+            (blit.pos.y + blit.size.y).min(rect.pos.y + rect.size.y)
+        );
+        if end.x <= pos.x || end.y <= pos.y {
+            return
+        }
+        let visible = Rect {pos, size: end - pos};
+        let scale = (visible.size / blit.size).into_vec2();
+        let pan = ((visible.pos - blit.pos) / blit.size).into_vec2();
+        self.draw_bg.apply_over(cx, live!{
+            image_scale: (scale)
+            image_pan: (pan)
+        });
+        self.draw_bg.draw_abs(cx, visible);
+    }
+    
     pub fn draw_walk(&mut self, cx: &mut Cx2d, mut walk: Walk) -> DrawStep {
+        if self.texture.is_none() && self.url.len() > 0 {
+            // still loading, or the load failed
+            self.draw_placeholder.color = self.placeholder_color;
+            self.draw_placeholder.draw_walk(cx, walk);
+            return DrawStep::done()
+        }
         // alright we get a walk. depending on our aspect ratio
         // we change either nothing, or width or height
         let rect = cx.peek_walk_turtle(walk);
@@ -67,6 +139,11 @@ impl Image {
         
         let aspect = width / height;
         match self.fit {
+            ImageFit::Contain | ImageFit::Cover | ImageFit::None => {
+                let rect = cx.walk_turtle(walk);
+                self.draw_blit(cx, rect, dvec2(width, height));
+                return DrawStep::done()
+            }
             ImageFit::Stretch => {
             }
             ImageFit::Horizontal => {
@@ -102,6 +179,13 @@ impl Image {
 }
 
 impl ImageRef {
+    /// Loads the image at `url` in the background, the placeholder color shows meanwhile.
+    pub fn set_url(&self, cx: &mut Cx, url: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_url(cx, url)
+        }
+    }
+    
     /// Loads the image at the given `image_path` into this `ImageRef`.
     pub fn load_image_dep_by_path(&self, cx: &mut Cx, image_path: &str) -> Result<(), ImageError> {
         if let Some(mut inner) = self.borrow_mut() {
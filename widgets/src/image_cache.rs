@@ -1,5 +1,5 @@
 use crate::{makepad_draw::*, ImageError};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use makepad_zune_jpeg::JpegDecoder;
 use makepad_zune_png::PngDecoder;
 
@@ -13,7 +13,37 @@ pub enum ImageFit{
     Horizontal,
     Vertical,
     Smallest,
-    Biggest
+    Biggest,
+    /// Scales to fit inside the widget keeping the aspect ratio, leaving bars on one axis
+    Contain,
+    /// Scales to cover the widget keeping the aspect ratio, cropping on one axis
+    Cover,
+    /// Draws at the image's own size
+    None,
+}
+
+impl ImageFit {
+    /// The rect the image is drawn into for the `Contain`, `Cover` and `None` modes,
+    /// centered on `rect`. Other modes size the walk instead and return `rect` as is.
+    pub fn blit_rect(&self, rect: Rect, image_size: DVec2) -> Rect {
+        if image_size.x <= 0.0 || image_size.y <= 0.0 {
+            return rect
+        }
+        let size = match self {
+            Self::Contain | Self::Cover => {
+                let scale_x = rect.size.x / image_size.x;
+                let scale_y = rect.size.y / image_size.y;
+                let scale = if let Self::Contain = self {scale_x.min(scale_y)} else {scale_x.max(scale_y)};
+                image_size * scale
+            }
+            Self::None => image_size,
+            _ => return rect
+        };
+        Rect {
+            pos: rect.pos + (rect.size - size) * 0.5,
+            size
+        }
+    }
 }
 
 
@@ -96,6 +126,22 @@ impl ImageBuffer {
         }
     }
 
+    /// Decodes PNG or JPEG data, the format is taken from the leading magic bytes.
+    pub fn from_encoded(data: &[u8]) -> Result<Self, ImageError> {
+        if data.starts_with(&[0x89, b'P', b'N', b'G']) {
+            Self::from_png(data)
+        }
+        else if data.starts_with(&[0xff, 0xd8]) {
+            Self::from_jpg(data)
+        }
+        else if data.is_empty() {
+            Err(ImageError::EmptyData)
+        }
+        else {
+            Err(ImageError::UnsupportedFormat)
+        }
+    }
+
     pub fn from_jpg(
         data: &[u8]
     ) -> Result<Self, ImageError> {
@@ -126,6 +172,204 @@ impl ImageCache {
     }
 }
 
+/// Textures keyed by url, evicting the least recently used once over capacity.
+pub struct TextureCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<String, (Texture, u64)>,
+}
+
+impl TextureCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+    
+    pub fn get(&mut self, url: &str) -> Option<Texture> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(url).map( | (texture, last_used) | {
+            *last_used = tick;
+            texture.clone()
+        })
+    }
+    
+    pub fn contains(&self, url: &str) -> bool {
+        self.entries.contains_key(url)
+    }
+    
+    pub fn insert(&mut self, url: String, texture: Texture) {
+        self.tick += 1;
+        self.entries.insert(url, (texture, self.tick));
+        self.evict();
+    }
+    
+    pub fn remove(&mut self, url: &str) -> Option<Texture> {
+        self.entries.remove(url).map( | (texture, _) | texture)
+    }
+    
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.evict();
+    }
+    
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let oldest = self.entries.iter()
+                .min_by_key( | (_, (_, last_used)) | *last_used)
+                .map( | (url, _) | url.clone());
+            if let Some(url) = oldest {
+                self.entries.remove(&url);
+            }
+        }
+    }
+}
+
+struct ImageLoadResult {
+    url: String,
+    result: Result<ImageBuffer, ImageError>,
+}
+
+/// Tracks background image loads, decoded images end up in `textures`.
+pub struct AsyncImageLoader {
+    pub textures: TextureCache,
+    pending: HashSet<String>,
+    failed: HashSet<String>,
+    decoded: ToUIReceiver<ImageLoadResult>,
+}
+
+impl Default for AsyncImageLoader {
+    fn default() -> Self {
+        Self {
+            textures: TextureCache::new(64),
+            pending: HashSet::new(),
+            failed: HashSet::new(),
+            decoded: ToUIReceiver::default(),
+        }
+    }
+}
+
+fn decode_on_thread(cx: &mut Cx, url: String, data: Vec<u8>) {
+    let sender = cx.global::<AsyncImageLoader>().decoded.sender();
+    cx.spawn_thread(move || {
+        let result = ImageBuffer::from_encoded(&data);
+        let _ = sender.send(ImageLoadResult {url, result});
+    });
+}
+
+pub trait CxImageLoadExt {
+    /// Starts loading the image at `url` unless it is cached or already loading. Local paths
+    /// and `file://` urls are read and decoded on a background thread, `http(s)://` urls
+    /// are fetched with a network request and then decoded on a background thread.
+    /// Pass every event to `handle_image_load_event` to pick up finished loads.
+    fn load_image_async(&mut self, url: &str);
+    /// Moves finished loads into the texture cache, returns true when any load finished.
+    fn handle_image_load_event(&mut self, event: &Event) -> bool;
+    fn cached_image(&mut self, url: &str) -> Option<Texture>;
+    fn image_load_failed(&mut self, url: &str) -> bool;
+    fn set_image_cache_capacity(&mut self, capacity: usize);
+}
+
+impl CxImageLoadExt for Cx {
+    fn load_image_async(&mut self, url: &str) {
+        let loader = self.global::<AsyncImageLoader>();
+        if loader.textures.contains(url) || loader.pending.contains(url) || loader.failed.contains(url) {
+            return
+        }
+        loader.pending.insert(url.to_string());
+        if url.starts_with("http://") || url.starts_with("https://") {
+            self.http_request(LiveId::from_str(url), HttpRequest::new(url.to_string(), HttpMethod::GET));
+            return
+        }
+        let sender = loader.decoded.sender();
+        let url = url.to_string();
+        self.spawn_thread(move || {
+            let path = url.strip_prefix("file://").unwrap_or(&url);
+            let result = match std::fs::read(path) {
+                Ok(data) => ImageBuffer::from_encoded(&data),
+                Err(_) => Err(ImageError::PathNotFound(path.to_string()))
+            };
+            let _ = sender.send(ImageLoadResult {url, result});
+        });
+    }
+    
+    fn handle_image_load_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::NetworkResponses(responses) => {
+                for item in responses {
+                    let url = self.global::<AsyncImageLoader>().pending.iter()
+                        .find( | url | LiveId::from_str(url) == item.request_id)
+                        .cloned();
+                    let Some(url) = url else {continue};
+                    match &item.response {
+                        NetworkResponse::HttpResponse(response) if response.status_code == 200 => {
+                            let data = response.body.clone().unwrap_or_default();
+                            decode_on_thread(self, url, data);
+                        }
+                        NetworkResponse::HttpResponse(_) | NetworkResponse::HttpRequestError(_) => {
+                            let loader = self.global::<AsyncImageLoader>();
+                            loader.pending.remove(&url);
+                            loader.failed.insert(url);
+                            return true
+                        }
+                        NetworkResponse::HttpProgress {..} => ()
+                    }
+                }
+                false
+            }
+            Event::Signal => {
+                let mut finished = false;
+                while let Ok(load) = self.global::<AsyncImageLoader>().decoded.try_recv() {
+                    finished = true;
+                    match load.result {
+                        Ok(buffer) => {
+                            let texture = buffer.into_new_texture(self);
+                            let loader = self.global::<AsyncImageLoader>();
+                            loader.pending.remove(&load.url);
+                            loader.textures.insert(load.url, texture);
+                        }
+                        Err(err) => {
+                            error!("load_image_async: cannot load image {}: {}", load.url, err);
+                            let loader = self.global::<AsyncImageLoader>();
+                            loader.pending.remove(&load.url);
+                            loader.failed.insert(load.url);
+                        }
+                    }
+                }
+                finished
+            }
+            _ => false
+        }
+    }
+    
+    fn cached_image(&mut self, url: &str) -> Option<Texture> {
+        self.global::<AsyncImageLoader>().textures.get(url)
+    }
+    
+    fn image_load_failed(&mut self, url: &str) -> bool {
+        self.global::<AsyncImageLoader>().failed.contains(url)
+    }
+    
+    fn set_image_cache_capacity(&mut self, capacity: usize) {
+        self.global::<AsyncImageLoader>().textures.set_capacity(capacity);
+    }
+}
+
 pub trait ImageCacheImpl {
     fn get_texture(&self) -> &Option<Texture>;
     fn set_texture(&mut self, texture: Option<Texture>);
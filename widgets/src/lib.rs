@@ -11,12 +11,16 @@ pub mod image;
 pub mod link_label;
 pub mod drop_down;
 pub mod popup_menu;
+pub mod modal;
+pub mod tooltip;
+pub mod context_menu;
 pub mod check_box;
 pub mod radio_button;
 pub mod text_input;
 pub mod slider;
 pub mod scroll_bar;
 pub mod scroll_bars;
+pub mod scroll_view;
 pub mod splitter;
 pub mod vectorline;
 pub mod fold_header;
@@ -26,8 +30,12 @@ pub mod designer;
 pub mod dock;
 pub mod tab;
 pub mod tab_bar;
+pub mod tab_view;
 pub mod tab_close_button;
 pub mod portal_list;
+pub mod fenwick_tree;
+pub mod virtual_list;
+pub mod tree_view;
 pub mod stack_navigation;
 pub mod expandable_panel;
 pub mod desktop_button;
@@ -48,6 +56,15 @@ pub mod flat_list;
 pub mod file_tree;
 pub mod slides_view;
 pub mod color_picker;
+pub mod progress_bar;
+pub mod spinner;
+pub mod svg_icon;
+pub mod transform_2d;
+pub mod nine_slice_image;
+pub mod flex;
+pub mod grid_layout;
+pub mod overlay_layer;
+pub mod rich_text;
 
 pub mod debug_view;
 pub mod performance_view;
@@ -71,15 +88,32 @@ pub use crate::{
     button::*,
     view::*,
     image::*,
+    image_cache::{CxImageLoadExt, TextureCache},
     label::*,
     slider::*,
     check_box::*,
     drop_down::*,
+    modal::*,
+    tooltip::*,
+    context_menu::*,
+    color_picker::*,
+    progress_bar::*,
+    spinner::*,
+    svg_icon::*,
+    transform_2d::*,
+    nine_slice_image::*,
+    flex::*,
+    grid_layout::*,
+    overlay_layer::*,
+    rich_text::*,
     video::*,
     radio_button::*,
     text_input::*,
     link_label::*,
     portal_list::*,
+    virtual_list::*,
+    tree_view::*,
+    fenwick_tree::FenwickTree,
     flat_list::*,
     page_flip::*,
     slide_panel::*,
@@ -89,7 +123,9 @@ pub use crate::{
     expandable_panel::*,
     window::*,
     tab::TabClosable,
+    tab_view::*,
     scroll_bars::{ScrollBars},
+    scroll_view::*,
     scroll_shadow::{DrawScrollShadow},
     scroll_bar::{ScrollBar},
     slides_view::{SlidesView},
@@ -144,19 +180,35 @@ pub fn live_design(cx: &mut Cx) {
     crate::window_menu::live_design(cx);
     crate::scroll_bar::live_design(cx);
     crate::scroll_bars::live_design(cx);
+    crate::scroll_view::live_design(cx);
     crate::check_box::live_design(cx);
     crate::radio_button::live_design(cx);
     crate::popup_menu::live_design(cx);
+    crate::modal::live_design(cx);
+    crate::tooltip::live_design(cx);
+    crate::context_menu::live_design(cx);
     crate::drop_down::live_design(cx);
     crate::multi_window::live_design(cx);
     crate::designer::live_design(cx);
     crate::portal_list::live_design(cx);
+    crate::virtual_list::live_design(cx);
+    crate::tree_view::live_design(cx);
     crate::flat_list::live_design(cx);
     crate::slide_panel::live_design(cx);
     crate::tab::live_design(cx);
     crate::tab_bar::live_design(cx);
+    crate::tab_view::live_design(cx);
     crate::dock::live_design(cx);
     crate::color_picker::live_design(cx);
+    crate::progress_bar::live_design(cx);
+    crate::spinner::live_design(cx);
+    crate::svg_icon::live_design(cx);
+    crate::transform_2d::live_design(cx);
+    crate::nine_slice_image::live_design(cx);
+    crate::flex::live_design(cx);
+    crate::grid_layout::live_design(cx);
+    crate::overlay_layer::live_design(cx);
+    crate::rich_text::live_design(cx);
     crate::file_tree::live_design(cx);
     crate::slides_view::live_design(cx);
     crate::tab_close_button::live_design(cx);
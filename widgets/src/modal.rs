@@ -0,0 +1,185 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    DrawModalBackdrop = {{DrawModalBackdrop}} {}
+    ModalBase = {{Modal}} {}
+}
+
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawModalBackdrop {
+    #[deref] draw_super: DrawQuad,
+    #[live] color: Vec4,
+    #[live] opacity: f32,
+    #[live] open: f32,
+}
+
+/// Tracks the open modal, only one modal can be open at a time.
+#[derive(Default)]
+struct ModalGlobal {
+    open: Option<WidgetUid>,
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ModalAction {
+    Dismissed,
+    None
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct Modal {
+    #[find] #[live] content: WidgetRef,
+    #[live] draw_bg: DrawModalBackdrop,
+    #[live] draw_shadow: DrawShadow,
+    #[redraw] #[live] draw_list: DrawList2d,
+    #[layout] layout: Layout,
+    #[walk] walk: Walk,
+    #[animator] animator: Animator,
+    /// 0.0 when closed, 1.0 when open, driven by the animator
+    #[live] open: f64,
+    /// How far the content slides in from below whilst opening
+    #[live(40.0)] slide_distance: f64,
+    /// Shadows drawn under the content, they fade in with it
+    #[live] shadows: Vec<Shadow>,
+    #[live] shadow_radius: f64,
+    #[rust] is_open: bool,
+    #[rust] draw_state: DrawStateWrap<()>,
+}
+
+impl Modal {
+    pub fn open(&mut self, cx: &mut Cx) -> bool {
+        let uid = self.widget_uid();
+        let global = cx.global::<ModalGlobal>();
+        if global.open.map_or(false, | open | open != uid) {
+            return false
+        }
+        global.open = Some(uid);
+        self.is_open = true;
+        self.animator_play(cx, id!(mode.open));
+        // the sweep lock is taken once the backdrop has been drawn
+        self.draw_list.redraw(cx);
+        true
+    }
+
+    pub fn close(&mut self, cx: &mut Cx) {
+        if !self.is_open {
+            return
+        }
+        let uid = self.widget_uid();
+        let global = cx.global::<ModalGlobal>();
+        if global.open == Some(uid) {
+            global.open = None;
+        }
+        self.is_open = false;
+        self.animator_play(cx, id!(mode.close));
+        cx.sweep_unlock(self.draw_bg.area());
+        self.draw_list.redraw(cx);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    fn content_rect(&self, cx: &Cx) -> Rect {
+        self.content.area().rect(cx)
+    }
+}
+
+impl Widget for Modal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        if self.animator_handle_event(cx, event).must_redraw() {
+            self.draw_list.redraw(cx);
+        }
+        if !self.is_open || self.draw_bg.area().is_empty() {
+            return
+        }
+
+        // the sweep lock keeps everything outside the modal from receiving finger events,
+        // lift it whilst the content handles the event so the modal itself stays usable
+        cx.sweep_unlock(self.draw_bg.area());
+        self.content.handle_event(cx, event, scope);
+        cx.sweep_lock(self.draw_bg.area());
+
+        if let Event::KeyDown(ke) = event {
+            if ke.key_code == KeyCode::Escape {
+                self.close(cx);
+                cx.widget_action(uid, &scope.path, ModalAction::Dismissed);
+                return
+            }
+        }
+
+        match event.hits_with_capture_overload(cx, self.draw_bg.area(), true) {
+            Hit::FingerDown(_) => {
+                cx.set_key_focus(self.draw_bg.area());
+            }
+            Hit::FingerUp(fe) => {
+                if fe.is_over && !self.content_rect(cx).contains(fe.abs) {
+                    self.close(cx);
+                    cx.widget_action(uid, &scope.path, ModalAction::Dismissed);
+                }
+            }
+            _ => ()
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, _walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            if !self.is_open && self.open <= 0.001 {
+                self.draw_state.end();
+                return DrawStep::done()
+            }
+            self.draw_list.begin_overlay_reuse(cx);
+            cx.begin_pass_sized_turtle(self.layout);
+            let pass_rect = Rect {pos: dvec2(0.0, 0.0), size: cx.current_pass_size()};
+            self.draw_bg.draw_abs(cx, pass_rect);
+            if self.is_open {
+                cx.sweep_lock(self.draw_bg.area());
+            }
+            let shadows: Vec<Shadow> = self.shadows.iter().map( | shadow | shadow.with_opacity(self.open as f32)).collect();
+            self.draw_shadow.begin(cx, self.shadow_radius, &shadows);
+            // the content is wrapped in a turtle so its rect is known for the shadows
+            let mut walk = self.content.walk(cx);
+            // the content is centered, so twice the margin moves it by the full distance
+            walk.margin.top += 2.0 * self.slide_distance * (1.0 - self.open);
+            cx.begin_turtle(walk, Layout::flow_down());
+        }
+        if self.draw_state.get().is_some() {
+            let walk = self.content.walk(cx);
+            self.content.draw_walk(cx, scope, Walk {margin: Margin::default(), abs_pos: None, ..walk}) ?;
+            let rect = cx.end_turtle();
+            self.draw_shadow.end(cx, rect);
+            cx.end_pass_sized_turtle();
+            self.draw_list.end(cx);
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+}
+
+impl ModalRef {
+    pub fn open(&self, cx: &mut Cx) -> bool {
+        self.borrow_mut().map_or(false, | mut inner | inner.open(cx))
+    }
+
+    pub fn close(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.close(cx);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.borrow().map_or(false, | inner | inner.is_open())
+    }
+
+    pub fn dismissed(&self, actions: &Actions) -> bool {
+        if let ModalAction::Dismissed = actions.find_widget_action_cast(self.widget_uid()) {
+            return true
+        }
+        false
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    makepad_derive_widget::*,
+    image_cache::*,
+    makepad_draw::*,
+    widget::*
+};
+
+live_design!{
+    NineSliceImageBase = {{NineSliceImage}} {}
+}
+
+/// An image stretched to its walk without distorting the corners, for backgrounds like button
+/// faces, dialog chrome or speech bubbles.
+#[derive(Live, Widget)]
+pub struct NineSliceImage {
+    #[walk] walk: Walk,
+    #[live] draw_bg: DrawNineSlice,
+    #[redraw] #[rust] area: Area,
+    #[live] source: LiveDependency,
+    /// Sizes of the fixed border regions, in pixels of the texture
+    #[live] slice_margins: Margin,
+    #[rust] texture: Option<Texture>,
+}
+
+impl ImageCacheImpl for NineSliceImage {
+    fn get_texture(&self) -> &Option<Texture> {
+        &self.texture
+    }
+
+    fn set_texture(&mut self, texture: Option<Texture>) {
+        self.texture = texture;
+    }
+}
+
+impl LiveHook for NineSliceImage {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        self.lazy_create_image_cache(cx);
+        let source = self.source.clone();
+        if source.as_str().len() > 0 {
+            let _ = self.load_image_dep_by_path(cx, source.as_str());
+        }
+    }
+}
+
+impl Widget for NineSliceImage {
+    fn handle_event(&mut self, _cx: &mut Cx, _event: &Event, _scope: &mut Scope) {
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        let rect = cx.walk_turtle_with_area(&mut self.area, walk);
+        if let Some(texture) = &self.texture {
+            self.draw_bg.draw_nine_slice_abs(cx, rect, texture, self.slice_margins);
+        }
+        DrawStep::done()
+    }
+}
+
+impl NineSliceImage {
+    pub fn set_slice_margins(&mut self, cx: &mut Cx, slice_margins: Margin) {
+        self.slice_margins = slice_margins;
+        self.area.redraw(cx);
+    }
+}
+
+impl NineSliceImageRef {
+    pub fn set_slice_margins(&self, cx: &mut Cx, slice_margins: Margin) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_slice_margins(cx, slice_margins);
+        }
+    }
+
+    /// Loads the image at the given `image_path` into this `NineSliceImageRef`.
+    pub fn load_image_dep_by_path(&self, cx: &mut Cx, image_path: &str) -> Result<(), ImageError> {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.load_image_dep_by_path(cx, image_path)
+        } else {
+            Ok(())
+        }
+    }
+}
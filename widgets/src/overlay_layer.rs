@@ -0,0 +1,200 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    OverlayLayerBase = {{OverlayLayer}} {}
+    AbsolutePositionBase = {{AbsolutePosition}} {}
+}
+
+/// Draws its children above everything else in the window, outside of the normal flow: the
+/// layer takes no space where it sits and its parents don't clip it. Children wrapped in an
+/// `AbsolutePosition` are placed relative to the window, later children draw over earlier
+/// ones.
+#[derive(Live, LiveRegisterWidget, WidgetRef, WidgetSet)]
+pub struct OverlayLayer {
+    #[live] draw_list: DrawList2d,
+    #[rust] children: ComponentMap<LiveId, WidgetRef>,
+    #[rust] draw_order: Vec<LiveId>,
+    #[rust] draw_state: DrawStateWrap<usize>,
+}
+
+impl WidgetNode for OverlayLayer {
+    fn walk(&mut self, _cx: &mut Cx) -> Walk {
+        Walk::size(Size::Fixed(0.0), Size::Fixed(0.0))
+    }
+
+    fn redraw(&mut self, cx: &mut Cx) {
+        self.draw_list.redraw(cx)
+    }
+
+    fn find_widgets(&mut self, path: &[LiveId], cached: WidgetCache, results: &mut WidgetSet) {
+        for child in self.children.values_mut() {
+            child.find_widgets(path, cached, results);
+        }
+    }
+}
+
+impl LiveHook for OverlayLayer {
+    fn before_apply(&mut self, _cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if let ApplyFrom::UpdateFromDoc {..} = apply.from {
+            self.draw_order.clear();
+        }
+    }
+
+    fn apply_value_instance(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) -> usize {
+        let id = nodes[index].id;
+        match apply.from {
+            ApplyFrom::Animate | ApplyFrom::Over => {
+                if let Some(component) = self.children.get_mut(&nodes[index].id) {
+                    component.apply(cx, apply, index, nodes)
+                }
+                else {
+                    nodes.skip_node(index)
+                }
+            }
+            ApplyFrom::NewFromDoc {..} | ApplyFrom::UpdateFromDoc {..} => {
+                if nodes[index].origin.has_prop_type(LivePropType::Instance) {
+                    self.draw_order.push(id);
+                    return self.children.get_or_insert(cx, id, | cx | {
+                        WidgetRef::new(cx)
+                    }).apply(cx, apply, index, nodes);
+                }
+                else {
+                    cx.apply_error_no_matching_field(live_error_origin!(), index, nodes);
+                    nodes.skip_node(index)
+                }
+            }
+            _ => {
+                nodes.skip_node(index)
+            }
+        }
+    }
+}
+
+impl Widget for OverlayLayer {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        // the topmost child gets first go at the event
+        for id in self.draw_order.iter().rev() {
+            if let Some(child) = self.children.get(id) {
+                scope.with_id(*id, | scope | child.handle_event(cx, event, scope));
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, _walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, 0) {
+            self.draw_list.begin_overlay_last(cx);
+            cx.begin_pass_sized_turtle(Layout::flow_overlay());
+        }
+        while let Some(index) = self.draw_state.get() {
+            let Some(id) = self.draw_order.get(index).copied() else {
+                break
+            };
+            if let Some(child) = self.children.get(&id) {
+                let walk = child.walk(cx);
+                scope.with_id(id, | scope | child.draw_walk(cx, scope, walk)) ?;
+            }
+            self.draw_state.set(index + 1);
+        }
+        cx.end_pass_sized_turtle();
+        self.draw_list.end(cx);
+        self.draw_state.end();
+        DrawStep::done()
+    }
+}
+
+impl OverlayLayer {
+    /// The ids of the children, bottom to top.
+    pub fn draw_order(&self) -> &[LiveId] {
+        &self.draw_order
+    }
+
+    /// Moves a child above the other children.
+    pub fn bring_to_front(&mut self, cx: &mut Cx, id: LiveId) {
+        if let Some(index) = self.draw_order.iter().position( | child | *child == id) {
+            let id = self.draw_order.remove(index);
+            self.draw_order.push(id);
+            self.draw_list.redraw(cx);
+        }
+    }
+}
+
+impl OverlayLayerRef {
+    pub fn bring_to_front(&self, cx: &mut Cx, id: LiveId) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.bring_to_front(cx, id);
+        }
+    }
+}
+
+/// Places its content so that the point `anchor` of the content, (0, 0) being its top left
+/// and (1, 1) its bottom right corner, sits at `x`, `y`. Within an `OverlayLayer` that is
+/// relative to the window.
+///
+/// The size of the content is the one it had when last drawn, so a content that changes size
+/// is placed right a frame later.
+#[derive(Live, LiveHook, LiveRegisterWidget, WidgetRef, WidgetSet)]
+pub struct AbsolutePosition {
+    #[live] content: WidgetRef,
+    #[live] x: f64,
+    #[live] y: f64,
+    #[live] anchor: Vec2,
+    #[rust] size: DVec2,
+    #[rust] draw_state: DrawStateWrap<()>,
+}
+
+impl WidgetNode for AbsolutePosition {
+    fn walk(&mut self, cx: &mut Cx) -> Walk {
+        self.content.walk(cx)
+    }
+
+    fn redraw(&mut self, cx: &mut Cx) {
+        self.content.redraw(cx)
+    }
+
+    fn find_widgets(&mut self, path: &[LiveId], cached: WidgetCache, results: &mut WidgetSet) {
+        self.content.find_widgets(path, cached, results);
+    }
+}
+
+impl Widget for AbsolutePosition {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.content.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            let pos = dvec2(self.x, self.y) - self.size * self.anchor.into_dvec2();
+            cx.begin_turtle(Walk::fit().with_abs_pos(pos), Layout::flow_down());
+        }
+        if self.draw_state.get().is_some() {
+            self.content.draw_walk(cx, scope, Walk {abs_pos: None, ..walk}) ?;
+            let rect = cx.end_turtle();
+            if rect.size != self.size {
+                self.size = rect.size;
+                self.content.redraw(cx);
+            }
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+}
+
+impl AbsolutePosition {
+    pub fn set_position(&mut self, cx: &mut Cx, x: f64, y: f64) {
+        self.x = x;
+        self.y = y;
+        self.content.redraw(cx);
+    }
+}
+
+impl AbsolutePositionRef {
+    pub fn set_position(&self, cx: &mut Cx, x: f64, y: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_position(cx, x, y);
+        }
+    }
+}
@@ -192,6 +192,11 @@ impl PopupMenu {
         self.first_tap = true;
     }
     
+    pub fn highlight_item(&mut self, cx: &mut Cx, which_id: PopupMenuItemId) {
+        self.select_item_state(cx, which_id);
+        self.draw_list.redraw(cx);
+    }
+    
     fn select_item_state(&mut self, cx: &mut Cx, which_id: PopupMenuItemId) {
         for (id, item) in &mut *self.menu_items {
             if *id == which_id {
@@ -0,0 +1,146 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    DrawProgressBar = {{DrawProgressBar}} {}
+    ProgressBarBase = {{ProgressBar}} {}
+}
+
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawProgressBar {
+    #[deref] draw_super: DrawQuad,
+    #[live] bar_color: Vec4,
+    #[live] track_color: Vec4,
+    #[live] value: f32,
+    /// 0.0 shows the value, 1.0 the sliding highlight, driven by the animator
+    #[live] indeterminate: f32,
+    /// Position of the sliding highlight, wraps around at 1.0
+    #[live] phase: f32,
+}
+
+#[derive(Live, Widget)]
+pub struct ProgressBar {
+    #[redraw] #[live] draw_bar: DrawProgressBar,
+    #[walk] walk: Walk,
+    #[animator] animator: Animator,
+
+    #[live] value: f64,
+    #[live] indeterminate: bool,
+    #[live] bar_color: Vec4,
+    #[live] track_color: Vec4,
+    /// How quickly `set_value` moves the bar towards the new value, per second
+    #[live(8.0)] value_speed: f64,
+    /// Seconds for the indeterminate highlight to cross the bar once
+    #[live(1.2)] sweep_duration: f64,
+
+    #[rust] shown_value: f64,
+    #[rust] phase: f64,
+    #[rust] next_frame: NextFrame,
+    #[rust] last_frame_time: Option<f64>,
+}
+
+impl LiveHook for ProgressBar {
+    fn after_new_from_doc(&mut self, _cx: &mut Cx) {
+        self.shown_value = self.value;
+    }
+
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        self.value = self.value.max(0.0).min(1.0);
+        self.sync_mode(cx);
+        self.start_frames(cx);
+    }
+}
+
+impl ProgressBar {
+    /// Animates the bar towards `value`, clamped to 0..1.
+    pub fn set_value(&mut self, cx: &mut Cx, value: f64) {
+        self.value = value.max(0.0).min(1.0);
+        self.start_frames(cx);
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn set_indeterminate(&mut self, cx: &mut Cx, indeterminate: bool) {
+        if self.indeterminate != indeterminate {
+            self.indeterminate = indeterminate;
+            self.sync_mode(cx);
+            self.start_frames(cx);
+        }
+    }
+
+    fn sync_mode(&mut self, cx: &mut Cx) {
+        let target = if self.indeterminate {id!(mode.indeterminate)} else {id!(mode.determinate)};
+        if !self.animator_in_state(cx, target) {
+            self.animator_play(cx, target);
+        }
+    }
+
+    fn is_animating(&self) -> bool {
+        self.indeterminate || (self.shown_value - self.value).abs() > 0.0005
+    }
+
+    fn start_frames(&mut self, cx: &mut Cx) {
+        if self.is_animating() {
+            self.last_frame_time = None;
+            self.next_frame = cx.new_next_frame();
+        }
+    }
+}
+
+impl Widget for ProgressBar {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, _scope: &mut Scope) {
+        if self.animator_handle_event(cx, event).must_redraw() {
+            self.draw_bar.redraw(cx);
+        }
+        if let Some(ne) = self.next_frame.is_event(event) {
+            let dt = self.last_frame_time.map( | t | ne.time - t).unwrap_or(1.0 / 60.0);
+            self.last_frame_time = Some(ne.time);
+            // exponential ease towards the target, snaps once close enough
+            let delta = self.value - self.shown_value;
+            self.shown_value += delta * (1.0 - (-self.value_speed * dt).exp());
+            if (self.value - self.shown_value).abs() <= 0.0005 {
+                self.shown_value = self.value;
+            }
+            if self.indeterminate {
+                self.phase = (self.phase + dt / self.sweep_duration.max(0.01)).fract();
+            }
+            if self.is_animating() {
+                self.next_frame = cx.new_next_frame();
+            }
+            self.draw_bar.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.draw_bar.bar_color = self.bar_color;
+        self.draw_bar.track_color = self.track_color;
+        self.draw_bar.value = self.shown_value as f32;
+        self.draw_bar.phase = self.phase as f32;
+        self.draw_bar.draw_walk(cx, walk);
+        DrawStep::done()
+    }
+}
+
+impl ProgressBarRef {
+    pub fn set_value(&self, cx: &mut Cx, value: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_value(cx, value);
+        }
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.borrow().map( | inner | inner.value())
+    }
+
+    pub fn set_indeterminate(&self, cx: &mut Cx, indeterminate: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_indeterminate(cx, indeterminate);
+        }
+    }
+}
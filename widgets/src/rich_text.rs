@@ -0,0 +1,159 @@
+use {
+    crate::{
+        makepad_derive_widget::*,
+        makepad_draw::*,
+        widget::*,
+        text_flow::TextFlow,
+    },
+};
+
+live_design!{
+    RichTextBase = {{RichText}} {}
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub color: Option<Vec4>,
+    pub link: Option<String>,
+    pub monospace: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichSpan {
+    pub text: String,
+    pub style: RichStyle,
+}
+
+impl RichSpan {
+    pub fn plain(text: &str) -> Self {
+        Self {text: text.to_string(), style: RichStyle::default()}
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum RichTextAction {
+    LinkClicked {url: String},
+    None
+}
+
+/// Styled text on top of `TextFlow`, wrapping at the widget boundary.
+#[derive(Live, LiveHook, Widget)]
+pub struct RichText {
+    #[deref] text_flow: TextFlow,
+    #[live] link_color: Vec4,
+    #[live] draw_link_underline: DrawColor,
+    #[rust] spans: Vec<RichSpan>,
+    /// Hit rects of the links drawn last, with the index of their span
+    #[rust] link_rects: Vec<(usize, Rect)>,
+    #[rust] hovered_link: Option<usize>,
+    #[rust] pressed_link: Option<usize>,
+}
+
+impl RichText {
+    pub fn set_spans(&mut self, cx: &mut Cx, spans: Vec<RichSpan>) {
+        self.spans = spans;
+        self.hovered_link = None;
+        self.pressed_link = None;
+        self.text_flow.redraw(cx);
+    }
+
+    pub fn spans(&self) -> &[RichSpan] {
+        &self.spans
+    }
+
+    fn link_at(&self, abs: DVec2) -> Option<usize> {
+        self.link_rects.iter().find( | (_, rect) | rect.contains(abs)).map( | (index, _) | *index)
+    }
+}
+
+impl Widget for RichText {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        self.text_flow.handle_event(cx, event, scope);
+
+        match event.hits(cx, self.text_flow.area()) {
+            Hit::FingerHoverIn(fe) | Hit::FingerHoverOver(fe) => {
+                self.hovered_link = self.link_at(fe.abs);
+                if self.hovered_link.is_some() {
+                    cx.set_cursor(MouseCursor::Hand);
+                }
+            }
+            Hit::FingerHoverOut(_) => {
+                self.hovered_link = None;
+            }
+            Hit::FingerDown(fe) => {
+                self.pressed_link = self.link_at(fe.abs);
+            }
+            Hit::FingerUp(fe) => {
+                if let Some(index) = self.pressed_link.take() {
+                    if fe.is_over && self.link_at(fe.abs) == Some(index) {
+                        if let Some(url) = &self.spans[index].style.link {
+                            cx.widget_action(uid, &scope.path, RichTextAction::LinkClicked {url: url.clone()});
+                        }
+                    }
+                }
+            }
+            _ => ()
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.link_rects.clear();
+        let tf = &mut self.text_flow;
+        tf.begin(cx, walk);
+        for (index, span) in self.spans.iter().enumerate() {
+            let style = &span.style;
+            if style.bold {tf.push_bold()}
+            if style.italic {tf.push_italic()}
+            if style.monospace {tf.push_fixed()}
+            let color = style.color.or_else( || style.link.as_ref().map( | _ | self.link_color));
+            if let Some(color) = color {tf.push_color(color)}
+
+            if style.link.is_some() {
+                for rect in tf.draw_text_rects(cx, &span.text) {
+                    self.draw_link_underline.color = color.unwrap_or(self.link_color);
+                    self.draw_link_underline.draw_abs(cx, Rect {
+                        pos: dvec2(rect.pos.x, rect.pos.y + rect.size.y - 2.0),
+                        size: dvec2(rect.size.x, 1.0)
+                    });
+                    self.link_rects.push((index, rect));
+                }
+            }
+            else {
+                tf.draw_text(cx, &span.text);
+            }
+
+            if color.is_some() {tf.pop_color()}
+            if style.monospace {tf.pop_fixed()}
+            if style.italic {tf.pop_italic()}
+            if style.bold {tf.pop_bold()}
+        }
+        tf.end(cx);
+        DrawStep::done()
+    }
+
+    fn text(&self) -> String {
+        self.spans.iter().map( | span | span.text.as_str()).collect()
+    }
+
+    fn set_text(&mut self, v: &str) {
+        self.spans = vec![RichSpan::plain(v)];
+    }
+}
+
+impl RichTextRef {
+    pub fn set_spans(&self, cx: &mut Cx, spans: Vec<RichSpan>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_spans(cx, spans);
+        }
+    }
+
+    pub fn link_clicked(&self, actions: &Actions) -> Option<String> {
+        if let RichTextAction::LinkClicked {url} = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(url)
+        }
+        None
+    }
+}
@@ -278,6 +278,11 @@ impl ScrollBars {
         }
     }
     
+    pub fn is_area_captured(&self, cx: &Cx) -> bool {
+        (self.show_scroll_x && self.scroll_bar_x.is_area_captured(cx)) ||
+        (self.show_scroll_y && self.scroll_bar_y.is_area_captured(cx))
+    }
+    
     pub fn set_area(&mut self, area: Area) {
         self.area = area;
     }
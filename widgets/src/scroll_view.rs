@@ -0,0 +1,339 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    scroll_bars::{ScrollBars, ScrollBarsAction},
+    widget::*,
+};
+
+live_design!{
+    ScrollViewBase = {{ScrollView}} {}
+}
+
+#[derive(Clone, Copy)]
+struct ScrollSample {
+    abs: DVec2,
+    time: f64,
+}
+
+enum ScrollState {
+    Stopped,
+    Drag {samples: Vec<ScrollSample>},
+    Animating,
+}
+
+#[derive(Live, Widget)]
+pub struct ScrollView {
+    #[rust] draw_state: DrawStateWrap<()>,
+    #[redraw] #[rust] area: Area,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[find] #[live] body: WidgetRef,
+    #[live] scroll_bars: ScrollBars,
+    #[animator] animator: Animator,
+
+    /// The scroll position of the content, can be set from the DSL or through `set_scroll_pos`
+    #[live] scroll: DVec2,
+    #[live] capture_overload: bool,
+    #[live(true)] drag_scrolling: bool,
+    #[live] mouse_drag_scrolling: bool,
+    /// How far the content can be dragged past its bounds, as a fraction of the visible size
+    #[live(0.2)] overscroll: f64,
+    /// Exponential decay rate of the flick velocity, per second
+    #[live(3.0)] flick_decay: f64,
+    /// Below this velocity (pixels per second) a flick comes to rest
+    #[live(20.0)] flick_velocity_minimum: f64,
+    #[live(4000.0)] flick_velocity_maximum: f64,
+    /// Rate at which overscrolled content snaps back to the boundary, per second
+    #[live(12.0)] bounce_speed: f64,
+    #[live(true)] auto_hide: bool,
+    #[live(1.5)] auto_hide_delay: f64,
+
+    #[rust] scroll_velocity: DVec2,
+    #[rust(ScrollState::Stopped)] scroll_state: ScrollState,
+    #[rust] next_frame: NextFrame,
+    #[rust] last_frame_time: Option<f64>,
+    #[rust] hide_timer: Timer,
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ScrollViewAction {
+    Scroll(DVec2),
+    None
+}
+
+impl LiveHook for ScrollView {
+    fn after_apply(&mut self, cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if apply.from.is_from_doc() {
+            self.scroll_velocity = DVec2::default();
+            self.scroll_bars.set_scroll_pos(cx, self.scroll);
+            if self.auto_hide {
+                self.animator_cut(cx, id!(bars.hide));
+            }
+            else {
+                self.animator_cut(cx, id!(bars.show));
+            }
+        }
+    }
+}
+
+impl ScrollView {
+    fn scroll_max(&mut self) -> DVec2 {
+        let total = self.scroll_bars.get_scroll_view_total();
+        let visible = self.scroll_bars.get_scroll_view_visible();
+        dvec2((total.x - visible.x).max(0.0), (total.y - visible.y).max(0.0))
+    }
+
+    fn overscroll_limit(&mut self) -> DVec2 {
+        self.scroll_bars.get_scroll_view_visible() * self.overscroll
+    }
+
+    fn clamped_scroll(&mut self) -> DVec2 {
+        let max = self.scroll_max();
+        dvec2(self.scroll.x.min(max.x).max(0.0), self.scroll.y.min(max.y).max(0.0))
+    }
+
+    fn show_scroll_bars(&mut self, cx: &mut Cx) {
+        if !self.auto_hide {
+            return
+        }
+        if !self.animator_in_state(cx, id!(bars.show)) {
+            self.animator_play(cx, id!(bars.show));
+        }
+        cx.stop_timer(self.hide_timer);
+        self.hide_timer = cx.start_timeout(self.auto_hide_delay);
+    }
+
+    fn start_animation(&mut self, cx: &mut Cx) {
+        self.scroll_state = ScrollState::Animating;
+        self.last_frame_time = None;
+        self.next_frame = cx.new_next_frame();
+    }
+
+    /// Integrates the scroll velocity over `dt` seconds, decaying it exponentially and
+    /// pulling overscrolled content back to the boundary. Returns true whilst still moving.
+    pub fn update_scroll_animation(&mut self, dt: f64) -> bool {
+        let max = self.scroll_max();
+        let limit = self.overscroll_limit();
+        let decay = (-self.flick_decay * dt).exp();
+        let bounce = (-self.bounce_speed * dt).exp();
+        let moving_x = Self::animate_axis(
+            &mut self.scroll.x, &mut self.scroll_velocity.x, max.x, limit.x, dt, decay, bounce, self.flick_velocity_minimum
+        );
+        let moving_y = Self::animate_axis(
+            &mut self.scroll.y, &mut self.scroll_velocity.y, max.y, limit.y, dt, decay, bounce, self.flick_velocity_minimum
+        );
+        moving_x || moving_y
+    }
+
+    fn animate_axis(pos: &mut f64, vel: &mut f64, max: f64, limit: f64, dt: f64, decay: f64, bounce: f64, min_vel: f64) -> bool {
+        *pos += *vel * dt;
+        *vel *= decay;
+        let bound = pos.min(max).max(0.0);
+        let over = (*pos - bound).min(limit).max(-limit);
+        if over != 0.0 {
+            // past the edge the flick dies out fast and the content springs back
+            *vel *= bounce * bounce;
+            *pos = bound + over * bounce;
+            if (*pos - bound).abs() < 0.5 {
+                *pos = bound;
+            }
+        }
+        if vel.abs() < min_vel {
+            *vel = 0.0;
+        }
+        *vel != 0.0 || *pos != bound
+    }
+
+    fn drag_axis(pos: &mut f64, delta: f64, max: f64, limit: f64) {
+        // rubber band: dragging past the edge only moves the content half as far
+        let delta = if *pos < 0.0 || *pos > max {delta * 0.5} else {delta};
+        *pos = (*pos - delta).min(max + limit).max(-limit);
+    }
+
+    pub fn scroll_pos(&self) -> DVec2 {
+        self.scroll
+    }
+
+    pub fn set_scroll_pos(&mut self, cx: &mut Cx, pos: DVec2) {
+        self.scroll = pos;
+        self.scroll_velocity = DVec2::default();
+        self.scroll_state = ScrollState::Stopped;
+        self.scroll = self.clamped_scroll();
+        self.scroll_bars.set_scroll_pos(cx, self.scroll);
+        self.area.redraw(cx);
+    }
+
+    pub fn scroll_velocity(&self) -> DVec2 {
+        self.scroll_velocity
+    }
+}
+
+impl Widget for ScrollView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        if self.animator_handle_event(cx, event).must_redraw() {
+            self.area.redraw(cx);
+        }
+
+        if self.hide_timer.is_event(event).is_some() {
+            self.hide_timer = Timer::empty();
+            if let ScrollState::Stopped = self.scroll_state {
+                self.animator_play(cx, id!(bars.hide));
+            }
+            else {
+                self.hide_timer = cx.start_timeout(self.auto_hide_delay);
+            }
+        }
+
+        let mut bar_actions = Vec::new();
+        self.scroll_bars.handle_main_event(cx, event, &mut bar_actions);
+        if !bar_actions.is_empty() {
+            // the bars report the clamped position, ignore that echo whilst we are overscrolled
+            let clamped = self.clamped_scroll();
+            let mut changed = false;
+            for action in bar_actions {
+                match action {
+                    ScrollBarsAction::ScrollX(x) if (x - clamped.x).abs() > 0.5 => {
+                        self.scroll.x = x;
+                        self.scroll_velocity.x = 0.0;
+                        changed = true;
+                    }
+                    ScrollBarsAction::ScrollY(y) if (y - clamped.y).abs() > 0.5 => {
+                        self.scroll.y = y;
+                        self.scroll_velocity.y = 0.0;
+                        changed = true;
+                    }
+                    _ => ()
+                }
+            }
+            if changed {
+                self.show_scroll_bars(cx);
+                cx.widget_action(uid, &scope.path, ScrollViewAction::Scroll(self.scroll));
+                self.area.redraw(cx);
+            }
+        }
+
+        if let Some(ne) = self.next_frame.is_event(event) {
+            if let ScrollState::Animating = self.scroll_state {
+                let dt = self.last_frame_time.map(|t| ne.time - t).unwrap_or(1.0 / 60.0);
+                self.last_frame_time = Some(ne.time);
+                if self.update_scroll_animation(dt) {
+                    self.next_frame = cx.new_next_frame();
+                }
+                else {
+                    self.scroll_state = ScrollState::Stopped;
+                }
+                self.show_scroll_bars(cx);
+                cx.widget_action(uid, &scope.path, ScrollViewAction::Scroll(self.scroll));
+                self.area.redraw(cx);
+            }
+        }
+
+        self.body.handle_event(cx, event, scope);
+
+        if self.scroll_bars.is_area_captured(cx) {
+            self.scroll_state = ScrollState::Stopped;
+            return
+        }
+
+        match event.hits_with_capture_overload(cx, self.area, self.capture_overload) {
+            Hit::FingerScroll(e) => {
+                self.scroll_velocity = DVec2::default();
+                self.scroll_state = ScrollState::Stopped;
+                self.scroll += e.scroll;
+                self.scroll = self.clamped_scroll();
+                self.scroll_bars.set_scroll_pos(cx, self.scroll);
+                self.show_scroll_bars(cx);
+                cx.widget_action(uid, &scope.path, ScrollViewAction::Scroll(self.scroll));
+                self.area.redraw(cx);
+            }
+            Hit::FingerDown(e) => {
+                if self.drag_scrolling && (e.device.is_touch() || self.mouse_drag_scrolling) {
+                    self.scroll_velocity = DVec2::default();
+                    self.scroll_state = ScrollState::Drag {
+                        samples: vec![ScrollSample {abs: e.abs, time: e.time}]
+                    };
+                }
+            }
+            Hit::FingerMove(e) => {
+                let max = self.scroll_max();
+                let limit = self.overscroll_limit();
+                if let ScrollState::Drag {samples} = &mut self.scroll_state {
+                    let old_sample = *samples.last().unwrap();
+                    samples.push(ScrollSample {abs: e.abs, time: e.time});
+                    if samples.len() > 4 {
+                        samples.remove(0);
+                    }
+                    let delta = e.abs - old_sample.abs;
+                    Self::drag_axis(&mut self.scroll.x, delta.x, max.x, limit.x);
+                    Self::drag_axis(&mut self.scroll.y, delta.y, max.y, limit.y);
+                    self.show_scroll_bars(cx);
+                    cx.widget_action(uid, &scope.path, ScrollViewAction::Scroll(self.scroll));
+                    self.area.redraw(cx);
+                }
+            }
+            Hit::FingerUp(e) => {
+                if let ScrollState::Drag {samples} = &self.scroll_state {
+                    let first = samples.first().unwrap();
+                    let last = samples.last().unwrap();
+                    let dt = last.time - first.time;
+                    // a finger that rested before lifting shouldn't flick
+                    self.scroll_velocity = if dt > 0.0 && e.time - last.time < 0.1 {
+                        let max = self.flick_velocity_maximum;
+                        let v = (first.abs - last.abs) / dt;
+                        dvec2(v.x.min(max).max(-max), v.y.min(max).max(-max))
+                    }
+                    else {
+                        DVec2::default()
+                    };
+                    self.start_animation(cx);
+                }
+            }
+            _ => ()
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            cx.begin_turtle(walk, self.layout.with_scroll(self.scroll));
+            self.scroll_bars.begin_nav_area(cx);
+        }
+        if self.draw_state.get().is_some() {
+            let walk = self.body.walk(cx);
+            self.body.draw_walk(cx, scope, walk) ?;
+            // the bars only ever show the in-bounds part of an overscroll
+            let clamped = self.clamped_scroll();
+            self.scroll_bars.set_scroll_pos(cx, clamped);
+            self.scroll_bars.draw_scroll_bars(cx);
+            cx.end_turtle_with_area(&mut self.area);
+            self.scroll_bars.set_area(self.area);
+            self.scroll_bars.end_nav_area(cx);
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+}
+
+impl ScrollViewRef {
+    pub fn scrolled(&self, actions: &Actions) -> Option<DVec2> {
+        if let ScrollViewAction::Scroll(pos) = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(pos)
+        }
+        None
+    }
+
+    pub fn scroll_pos(&self) -> DVec2 {
+        if let Some(inner) = self.borrow() {
+            inner.scroll_pos()
+        }
+        else {
+            DVec2::default()
+        }
+    }
+
+    pub fn set_scroll_pos(&self, cx: &mut Cx, pos: DVec2) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_scroll_pos(cx, pos);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    DrawSpinner = {{DrawSpinner}} {}
+    SpinnerBase = {{Spinner}} {}
+}
+
+#[derive(Live, LiveHook, LiveRegister)]
+#[repr(C)]
+pub struct DrawSpinner {
+    #[deref] draw_super: DrawQuad,
+    #[live] color: Vec4,
+    /// Rotation in turns, 0..1
+    #[live] phase: f32,
+    /// Fades the spinner in and out, driven by the animator
+    #[live] opacity: f32,
+}
+
+#[derive(Live, Widget)]
+pub struct Spinner {
+    #[redraw] #[live] draw_spinner: DrawSpinner,
+    #[walk] walk: Walk,
+    #[animator] animator: Animator,
+
+    #[live] color: Vec4,
+    /// Diameter in pixels, used when the walk doesn't size the spinner itself
+    #[live(24.0)] size: f64,
+    #[live(1.0)] speed_rps: f64,
+    #[live(true)] spinning: bool,
+
+    #[rust] phase: f64,
+    #[rust] next_frame: NextFrame,
+    #[rust] last_frame_time: Option<f64>,
+}
+
+impl LiveHook for Spinner {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if self.spinning {
+            self.last_frame_time = None;
+            self.next_frame = cx.new_next_frame();
+        }
+    }
+}
+
+impl Spinner {
+    pub fn start(&mut self, cx: &mut Cx) {
+        if !self.spinning {
+            self.spinning = true;
+            self.last_frame_time = None;
+            self.next_frame = cx.new_next_frame();
+        }
+        self.animator_play(cx, id!(visible.on));
+    }
+
+    /// Fades the spinner out, it keeps turning until it is invisible.
+    pub fn stop(&mut self, cx: &mut Cx) {
+        self.animator_play(cx, id!(visible.off));
+    }
+
+    pub fn is_spinning(&self) -> bool {
+        self.spinning
+    }
+}
+
+impl Widget for Spinner {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, _scope: &mut Scope) {
+        if self.animator_handle_event(cx, event).must_redraw() {
+            self.draw_spinner.redraw(cx);
+        }
+        if let Some(ne) = self.next_frame.is_event(event) {
+            if !self.spinning {
+                return
+            }
+            let dt = self.last_frame_time.map( | t | ne.time - t).unwrap_or(1.0 / 60.0);
+            self.last_frame_time = Some(ne.time);
+            self.phase = (self.phase + dt * self.speed_rps).fract();
+            if self.draw_spinner.opacity <= 0.001 && self.animator_in_state(cx, id!(visible.off)) {
+                self.spinning = false;
+            }
+            else {
+                self.next_frame = cx.new_next_frame();
+            }
+            self.draw_spinner.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        let mut walk = walk;
+        if let Size::Fit = walk.width {
+            walk.width = Size::Fixed(self.size);
+        }
+        if let Size::Fit = walk.height {
+            walk.height = Size::Fixed(self.size);
+        }
+        self.draw_spinner.color = self.color;
+        self.draw_spinner.phase = self.phase as f32;
+        self.draw_spinner.draw_walk(cx, walk);
+        DrawStep::done()
+    }
+}
+
+impl SpinnerRef {
+    pub fn start(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.start(cx);
+        }
+    }
+
+    pub fn stop(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.stop(cx);
+        }
+    }
+}
@@ -72,6 +72,9 @@ pub struct Splitter {
     #[live] max_vertical: f64,
     #[live] min_horizontal: f64,
     #[live] max_horizontal: f64,
+    /// Bounds for the fraction of the splitter given to `a`
+    #[live(0.0)] min_ratio: f64,
+    #[live(1.0)] max_ratio: f64,
     
     #[redraw] #[live] draw_splitter: DrawSplitter,
     #[live] split_bar_size: f64,
@@ -106,6 +109,13 @@ impl Widget for Splitter {
             Hit::FingerHoverOut(_) => {
                 self.animator_play(cx, id!(hover.off));
             },
+            Hit::FingerDown(f) if f.tap_count == 2 => {
+                self.drag_start_align = None;
+                self.set_ratio(0.5);
+                self.redraw_panels(cx);
+                cx.widget_action(uid, &scope.path, SplitterAction::Changed {axis: self.axis, align: self.align});
+                cx.widget_action(uid, &scope.path, SplitterAction::RatioChanged {ratio: self.ratio()});
+            }
             Hit::FingerDown(_) => {
                 match self.axis {
                     SplitterAxis::Horizontal => cx.set_cursor(MouseCursor::ColResize),
@@ -129,8 +139,9 @@ impl Widget for Splitter {
                         SplitterAxis::Horizontal => f.abs.x - f.abs_start.x,
                         SplitterAxis::Vertical => f.abs.y - f.abs_start.y,
                     };
-                    let new_position =
-                    drag_start_align.to_position(self.axis, self.rect) + delta;
+                    let new_position = self.clamp_position(
+                        drag_start_align.to_position(self.axis, self.rect) + delta
+                    );
                     self.align = match self.axis {
                         SplitterAxis::Horizontal => {
                             let center = self.rect.size.x / 2.0;
@@ -153,11 +164,10 @@ impl Widget for Splitter {
                             }
                         }
                     };
-                    self.draw_splitter.redraw(cx);
+                    self.position = new_position;
+                    self.redraw_panels(cx);
                     cx.widget_action(uid, &scope.path, SplitterAction::Changed {axis: self.axis, align: self.align});
-                    
-                    self.a.redraw(cx);
-                    self.b.redraw(cx);
+                    cx.widget_action(uid, &scope.path, SplitterAction::RatioChanged {ratio: self.ratio()});
                 }
             }
             _ => {}
@@ -200,7 +210,7 @@ impl Splitter {
         }
         
         self.rect = cx.turtle().padded_rect();
-        self.position = self.align.to_position(self.axis, self.rect);
+        self.position = self.clamp_position(self.align.to_position(self.axis, self.rect));
         
         let walk = match self.axis {
             SplitterAxis::Horizontal => Walk::size(Size::Fixed(self.position), Size::Fill),
@@ -253,6 +263,47 @@ impl Splitter {
         self.align = align;
     }
     
+    /// The fraction of the splitter currently given to `a`.
+    pub fn ratio(&self) -> f64 {
+        let total = self.total_size();
+        if total <= 0.0 {
+            return match self.align {
+                SplitterAlign::Weighted(weight) => weight,
+                _ => 0.5
+            }
+        }
+        self.position / total
+    }
+    
+    pub fn set_ratio(&mut self, ratio: f64) {
+        let ratio = ratio.max(self.min_ratio).min(self.max_ratio.max(self.min_ratio));
+        self.align = SplitterAlign::Weighted(ratio);
+        self.position = self.clamp_position(ratio * self.total_size());
+    }
+    
+    fn total_size(&self) -> f64 {
+        match self.axis {
+            SplitterAxis::Horizontal => self.rect.size.x,
+            SplitterAxis::Vertical => self.rect.size.y,
+        }
+    }
+    
+    /// Keeps the split inside the ratio bounds. When the splitter is too small to honour
+    /// them the bounds give way so neither panel ends up with a negative size.
+    fn clamp_position(&self, position: f64) -> f64 {
+        let total = self.total_size();
+        let available = (total - self.split_bar_size).max(0.0);
+        let min = (self.min_ratio * total).min(available);
+        let max = (self.max_ratio * total).min(available).max(min);
+        position.max(min).min(max)
+    }
+    
+    fn redraw_panels(&mut self, cx: &mut Cx) {
+        self.draw_splitter.redraw(cx);
+        self.a.redraw(cx);
+        self.b.redraw(cx);
+    }
+    
     fn margin(&self) -> Margin {
         match self.axis {
             SplitterAxis::Horizontal => Margin {
@@ -275,4 +326,25 @@ impl Splitter {
 pub enum SplitterAction {
     None,
     Changed {axis: SplitterAxis, align: SplitterAlign},
-}
\ No newline at end of file
+    RatioChanged {ratio: f64},
+}
+
+impl SplitterRef {
+    pub fn ratio(&self) -> Option<f64> {
+        self.borrow().map( | inner | inner.ratio())
+    }
+    
+    pub fn set_ratio(&self, cx: &mut Cx, ratio: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_ratio(ratio);
+            inner.redraw_panels(cx);
+        }
+    }
+    
+    pub fn ratio_changed(&self, actions: &Actions) -> Option<f64> {
+        if let SplitterAction::RatioChanged {ratio} = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(ratio)
+        }
+        None
+    }
+}
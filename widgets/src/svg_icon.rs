@@ -0,0 +1,105 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    makepad_draw::makepad_vector::svg::{FillRule, Mesh, SvgPath},
+    widget::*,
+};
+
+live_design!{
+    SvgIconBase = {{SvgIcon}} {}
+}
+
+/// Fills an SVG path, scaled to fit the widget while keeping its aspect ratio.
+#[derive(Live, Widget)]
+pub struct SvgIcon {
+    #[walk] walk: Walk,
+    #[live] draw_svg: DrawSvgPath,
+    #[redraw] #[rust] area: Area,
+
+    /// SVG path data, as in the `d` attribute of a `<path>` element
+    #[live] path: String,
+    #[live] color: Vec4,
+    #[live] even_odd: bool,
+    /// The region of path coordinates to show, as x, y, width and height. When it has no size
+    /// the bounds of the path are used.
+    #[live] view_box: Vec4,
+
+    #[rust] mesh: Mesh,
+}
+
+impl LiveHook for SvgIcon {
+    fn after_apply(&mut self, _cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        self.update_mesh();
+    }
+}
+
+impl SvgIcon {
+    pub fn set_path(&mut self, cx: &mut Cx, path: &str) {
+        self.path = path.to_string();
+        self.update_mesh();
+        self.area.redraw(cx);
+    }
+
+    pub fn set_color(&mut self, cx: &mut Cx, color: Vec4) {
+        self.color = color;
+        self.area.redraw(cx);
+    }
+
+    fn update_mesh(&mut self) {
+        let fill_rule = if self.even_odd {FillRule::EvenOdd} else {FillRule::NonZero};
+        self.mesh = match SvgPath::from_svg_d(&self.path) {
+            Ok(path) => path.tessellate(fill_rule),
+            Err(err) => {
+                error!("SvgIcon: invalid path data: {}", err);
+                Mesh::new()
+            }
+        };
+    }
+
+    /// Maps the view box into `rect`, centered and uniformly scaled.
+    fn fit_transform(&self, rect: Rect) -> Option<Mat4> {
+        let (view_pos, view_size) = if self.view_box.z > 0.0 && self.view_box.w > 0.0 {
+            (self.view_box.xy().into_dvec2(), self.view_box.zw().into_dvec2())
+        }
+        else {
+            let bounds = self.mesh.bounds()?;
+            (
+                dvec2(bounds.p_min.x, bounds.p_min.y),
+                dvec2(bounds.p_max.x - bounds.p_min.x, bounds.p_max.y - bounds.p_min.y)
+            )
+        };
+        if view_size.x <= 0.0 || view_size.y <= 0.0 {
+            return None
+        }
+        let scale = (rect.size.x / view_size.x).min(rect.size.y / view_size.y);
+        let offset = rect.pos + (rect.size - view_size * scale) * 0.5 - view_pos * scale;
+        Some(Mat4::scaled_translation(scale as f32, offset.x as f32, offset.y as f32, 0.0))
+    }
+}
+
+impl Widget for SvgIcon {
+    fn handle_event(&mut self, _cx: &mut Cx, _event: &Event, _scope: &mut Scope) {
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, _scope: &mut Scope, walk: Walk) -> DrawStep {
+        let rect = cx.walk_turtle_with_area(&mut self.area, walk);
+        if let Some(transform) = self.fit_transform(rect) {
+            self.draw_svg.draw_mesh(cx, &self.mesh, transform, self.color);
+        }
+        DrawStep::done()
+    }
+}
+
+impl SvgIconRef {
+    pub fn set_path(&self, cx: &mut Cx, path: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_path(cx, path);
+        }
+    }
+
+    pub fn set_color(&self, cx: &mut Cx, color: Vec4) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_color(cx, color);
+        }
+    }
+}
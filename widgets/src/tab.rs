@@ -38,7 +38,8 @@ pub enum TabAction {
     WasPressed,
     CloseWasPressed,
     ShouldTabStartDrag,
-    ShouldTabStopDrag
+    ShouldTabStopDrag,
+    Dragging(DVec2),
     //DragHit(DragHit)
 }
 
@@ -116,6 +117,9 @@ impl Tab {
                     self.is_dragging = true;
                     dispatch_action(cx, TabAction::ShouldTabStartDrag);
                 }
+                if self.is_dragging {
+                    dispatch_action(cx, TabAction::Dragging(e.abs));
+                }
             }
             Hit::FingerUp(_) => {
                 if self.is_dragging {
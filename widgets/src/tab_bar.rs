@@ -76,6 +76,9 @@ impl Widget for TabBar{
                     cx.widget_action(uid, &scope.path, TabBarAction::ShouldTabStartDrag(*tab_id));
                 }
                 TabAction::ShouldTabStopDrag=>{
+                }
+                TabAction::Dragging(abs)=>{
+                    cx.widget_action(uid, &scope.path, TabBarAction::TabDragging(*tab_id, abs));
                 }/*
                 TabAction::DragHit(hit)=>{
                     dispatch_action(cx, TabBarAction::DragHitTab(hit, *tab_id));
@@ -238,6 +241,28 @@ impl TabBar {
         None
     }
     
+    pub fn area(&self) -> Area {
+        self.scroll_bars.area()
+    }
+    
+    pub fn is_overflowing(&mut self) -> bool {
+        self.scroll_bars.get_scroll_view_total().x > self.scroll_bars.get_scroll_view_visible().x + 0.5
+    }
+    
+    pub fn scroll_tabs_by(&mut self, cx: &mut Cx, delta: f64) {
+        let pos = self.scroll_bars.get_scroll_pos();
+        if self.scroll_bars.set_scroll_pos(cx, pos + dvec2(delta, 0.0)) {
+            self.view_area.redraw(cx);
+        }
+    }
+    
+    pub fn scroll_tab_into_view(&mut self, cx: &mut Cx, tab_id: LiveId) {
+        if let Some(tab) = self.tabs.get(&tab_id) {
+            let rect = tab.area().rect(cx);
+            self.scroll_bars.scroll_into_view_abs(cx, rect);
+        }
+    }
+    
     pub fn is_over_tab_bar(&self, cx:&Cx, abs:DVec2)->Option<Rect>{
         let rect = self.scroll_bars.area().rect(cx);
         if rect.contains(abs){
@@ -254,6 +279,7 @@ pub enum TabBarAction {
     TabWasPressed(LiveId),
     ShouldTabStartDrag(LiveId),
     TabCloseWasPressed(LiveId),
+    TabDragging(LiveId, DVec2),
     None
     //DragHitTab(DragHit, LiveId),
     //DragHitTabBar(DragHit)
@@ -0,0 +1,256 @@
+use {
+    crate::{
+        makepad_derive_widget::*,
+        makepad_draw::*,
+        widget::*,
+        tab::TabClosable,
+        tab_bar::{TabBar, TabBarAction},
+    },
+};
+
+live_design!{
+    TabViewBase = {{TabView}} {}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TabInfo {
+    pub label: String,
+    pub closeable: bool,
+    pub id: u64,
+}
+
+struct TabEntry {
+    info: TabInfo,
+    widget: WidgetRef,
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum TabViewAction {
+    TabSelected {id: u64},
+    TabClosed {id: u64},
+    TabsReordered,
+    None
+}
+
+#[derive(Live, Widget)]
+pub struct TabView {
+    #[rust] draw_state: DrawStateWrap<()>,
+    #[redraw] #[rust] area: Area,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[live] tab_bar: TabBar,
+    #[live] draw_arrow_left: DrawQuad,
+    #[live] draw_arrow_right: DrawQuad,
+    #[live(20.0)] arrow_width: f64,
+    #[live(80.0)] arrow_scroll_step: f64,
+    #[live] reorderable: bool,
+
+    #[rust] templates: ComponentMap<LiveId, LivePtr>,
+    #[rust] tabs: Vec<TabEntry>,
+    #[rust] selected: Option<u64>,
+    #[rust] overflowing: bool,
+}
+
+impl LiveHook for TabView {
+    fn before_apply(&mut self, _cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        if let ApplyFrom::UpdateFromDoc {..} = apply.from {
+            self.templates.clear();
+        }
+    }
+
+    // collect the tab content templates
+    fn apply_value_instance(&mut self, cx: &mut Cx, apply: &mut Apply, index: usize, nodes: &[LiveNode]) -> usize {
+        let id = nodes[index].id;
+        match apply.from {
+            ApplyFrom::NewFromDoc {file_id} | ApplyFrom::UpdateFromDoc {file_id} => {
+                if nodes[index].origin.has_prop_type(LivePropType::Instance) {
+                    let live_ptr = cx.live_registry.borrow().file_id_index_to_live_ptr(file_id, index);
+                    self.templates.insert(id, live_ptr);
+                }
+                else {
+                    cx.apply_error_no_matching_field(live_error_origin!(), index, nodes);
+                }
+            }
+            _ => ()
+        }
+        nodes.skip_node(index)
+    }
+}
+
+impl TabView {
+    /// Adds a tab whose content is instanced from one of the templates declared on the
+    /// TabView in the DSL. The first tab added is selected.
+    pub fn add_tab(&mut self, cx: &mut Cx, info: TabInfo, template: LiveId) -> Option<WidgetRef> {
+        if self.tabs.iter().any( | tab | tab.info.id == info.id) {
+            return None
+        }
+        let ptr = *self.templates.get(&template)?;
+        let widget = WidgetRef::new_from_ptr(cx, Some(ptr));
+        if self.selected.is_none() {
+            self.selected = Some(info.id);
+        }
+        self.tabs.push(TabEntry {info, widget: widget.clone()});
+        self.area.redraw(cx);
+        Some(widget)
+    }
+
+    pub fn remove_tab(&mut self, cx: &mut Cx, id: u64) -> Option<TabInfo> {
+        let index = self.tab_index(id)?;
+        let entry = self.tabs.remove(index);
+        if self.selected == Some(id) {
+            self.selected = self.tabs.get(index.min(self.tabs.len().saturating_sub(1))).map( | tab | tab.info.id);
+        }
+        self.area.redraw(cx);
+        Some(entry.info)
+    }
+
+    pub fn select_tab(&mut self, cx: &mut Cx, id: u64) {
+        if self.tab_index(id).is_some() && self.selected != Some(id) {
+            self.selected = Some(id);
+            self.tab_bar.scroll_tab_into_view(cx, LiveId(id));
+            self.area.redraw(cx);
+        }
+    }
+
+    pub fn selected_tab(&self) -> Option<u64> {
+        self.selected
+    }
+
+    pub fn tab_widget(&self, id: u64) -> Option<WidgetRef> {
+        self.tabs.iter().find( | tab | tab.info.id == id).map( | tab | tab.widget.clone())
+    }
+
+    pub fn tab_infos(&self) -> Vec<TabInfo> {
+        self.tabs.iter().map( | tab | tab.info.clone()).collect()
+    }
+
+    fn tab_index(&self, id: u64) -> Option<usize> {
+        self.tabs.iter().position( | tab | tab.info.id == id)
+    }
+
+    fn draw_tab_strip(&mut self, cx: &mut Cx2d) {
+        let selected = self.selected.and_then( | id | self.tab_index(id));
+        let walk = self.tab_bar.walk(cx);
+        self.tab_bar.begin(cx, selected, walk);
+        for tab in &self.tabs {
+            let closable = if tab.info.closeable {TabClosable::Yes} else {TabClosable::No};
+            self.tab_bar.draw_tab(cx, LiveId(tab.info.id), &tab.info.label, closable);
+        }
+        self.tab_bar.end(cx);
+
+        // scroll arrows overlay the ends of the strip when the tabs don't fit
+        self.overflowing = self.tab_bar.is_overflowing();
+        if self.overflowing {
+            let rect = self.tab_bar.area().rect(cx);
+            let size = dvec2(self.arrow_width, rect.size.y);
+            self.draw_arrow_left.draw_abs(cx, Rect {pos: rect.pos, size});
+            self.draw_arrow_right.draw_abs(cx, Rect {pos: rect.pos + dvec2(rect.size.x - self.arrow_width, 0.0), size});
+        }
+    }
+}
+
+impl Widget for TabView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+
+        for action in cx.capture_actions( | cx | self.tab_bar.handle_event(cx, event, scope)) {
+            match action.as_widget_action().cast() {
+                TabBarAction::TabWasPressed(tab_id) => {
+                    if self.selected != Some(tab_id.0) {
+                        self.select_tab(cx, tab_id.0);
+                        cx.widget_action(uid, &scope.path, TabViewAction::TabSelected {id: tab_id.0});
+                    }
+                }
+                TabBarAction::TabCloseWasPressed(tab_id) => {
+                    let was_selected = self.selected == Some(tab_id.0);
+                    if self.remove_tab(cx, tab_id.0).is_some() {
+                        cx.widget_action(uid, &scope.path, TabViewAction::TabClosed {id: tab_id.0});
+                        if let (true, Some(id)) = (was_selected, self.selected) {
+                            cx.widget_action(uid, &scope.path, TabViewAction::TabSelected {id});
+                        }
+                    }
+                }
+                TabBarAction::TabDragging(tab_id, abs) => if self.reorderable {
+                    if let Some((over_id, _)) = self.tab_bar.is_over_tab(cx, abs) {
+                        if over_id != tab_id {
+                            if let (Some(from), Some(to)) = (self.tab_index(tab_id.0), self.tab_index(over_id.0)) {
+                                let tab = self.tabs.remove(from);
+                                self.tabs.insert(to, tab);
+                                self.area.redraw(cx);
+                                cx.widget_action(uid, &scope.path, TabViewAction::TabsReordered);
+                            }
+                        }
+                    }
+                }
+                TabBarAction::ShouldTabStartDrag(_) | TabBarAction::None => ()
+            }
+        }
+
+        if self.overflowing {
+            if let Hit::FingerDown(_) = event.hits(cx, self.draw_arrow_left.area()) {
+                self.tab_bar.scroll_tabs_by(cx, -self.arrow_scroll_step);
+            }
+            if let Hit::FingerDown(_) = event.hits(cx, self.draw_arrow_right.area()) {
+                self.tab_bar.scroll_tabs_by(cx, self.arrow_scroll_step);
+            }
+        }
+
+        // only the visible tab receives events
+        if let Some(tab) = self.selected.and_then( | id | self.tab_index(id)).map( | index | &self.tabs[index]) {
+            scope.with_id(LiveId(tab.info.id), | scope | {
+                tab.widget.handle_event(cx, event, scope);
+            });
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            cx.begin_turtle(walk, self.layout);
+            self.draw_tab_strip(cx);
+        }
+        if self.draw_state.get().is_some() {
+            if let Some(index) = self.selected.and_then( | id | self.tab_index(id)) {
+                let widget = self.tabs[index].widget.clone();
+                let walk = widget.walk(cx);
+                widget.draw_walk(cx, scope, walk) ?;
+            }
+            cx.end_turtle_with_area(&mut self.area);
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+}
+
+impl TabViewRef {
+    pub fn add_tab(&self, cx: &mut Cx, info: TabInfo, template: LiveId) -> Option<WidgetRef> {
+        self.borrow_mut().and_then( | mut inner | inner.add_tab(cx, info, template))
+    }
+
+    pub fn remove_tab(&self, cx: &mut Cx, id: u64) -> Option<TabInfo> {
+        self.borrow_mut().and_then( | mut inner | inner.remove_tab(cx, id))
+    }
+
+    pub fn select_tab(&self, cx: &mut Cx, id: u64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.select_tab(cx, id);
+        }
+    }
+
+    pub fn selected_tab(&self) -> Option<u64> {
+        self.borrow().and_then( | inner | inner.selected_tab())
+    }
+
+    pub fn tab_selected(&self, actions: &Actions) -> Option<u64> {
+        if let TabViewAction::TabSelected {id} = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(id)
+        }
+        None
+    }
+
+    pub fn tab_closed(&self, actions: &Actions) -> Option<u64> {
+        if let TabViewAction::TabClosed {id} = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(id)
+        }
+        None
+    }
+}
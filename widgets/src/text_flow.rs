@@ -22,10 +22,13 @@ pub struct TextFlow {
     #[live] draw_italic: DrawText,
     #[live] draw_bold: DrawText,
     #[live] draw_bold_italic: DrawText,
+    #[live] draw_fixed: DrawText,
     #[live] font_size: f64,
     #[walk] walk: Walk,
     #[rust] bold_counter: usize,
     #[rust] italic_counter: usize,
+    #[rust] fixed_counter: usize,
+    #[rust] color_stack: Vec<Vec4>,
     #[rust] font_size_stack: FontSizeStack,
     #[layout] layout: Layout,
     #[redraw] #[rust] area:Area,
@@ -153,6 +156,10 @@ impl TextFlow{
         self.items.retain_visible();
     }
     
+    pub fn area(&self) -> Area{
+        self.area
+    }
+    
     pub fn push_bold(&mut self){
         self.bold_counter += 1;
     }
@@ -173,6 +180,24 @@ impl TextFlow{
         }
     }
     
+    pub fn push_fixed(&mut self){
+        self.fixed_counter += 1;
+    }
+    
+    pub fn pop_fixed(&mut self){
+        if self.fixed_counter>0{
+            self.fixed_counter -= 1;
+        }
+    }
+    
+    pub fn push_color(&mut self, color: Vec4){
+        self.color_stack.push(color);
+    }
+    
+    pub fn pop_color(&mut self){
+        self.color_stack.pop();
+    }
+    
     pub fn push_size(&mut self, size: f64){
         self.font_size_stack.push(size);
     }
@@ -198,29 +223,66 @@ impl TextFlow{
         None
     }
     
-    pub fn draw_text(&mut self, cx:&mut Cx2d, text:&str){
-        if let Some(DrawState::Drawing) = self.draw_state.get(){
-            let dt = if self.bold_counter > 0{
-                if self.italic_counter > 0{
-                    &mut self.draw_bold_italic
-                }
-                else{
-                    &mut self.draw_bold
-                }
+    fn current_draw_text(&mut self) -> &mut DrawText {
+        if self.fixed_counter > 0 {
+            &mut self.draw_fixed
+        }
+        else if self.bold_counter > 0{
+            if self.italic_counter > 0{
+                &mut self.draw_bold_italic
             }
             else{
-                if self.italic_counter>0{
-                    &mut self.draw_italic
-                }
-                else{
-                    &mut self.draw_normal
-                }
-            };
+                &mut self.draw_bold
+            }
+        }
+        else{
+            if self.italic_counter>0{
+                &mut self.draw_italic
+            }
+            else{
+                &mut self.draw_normal
+            }
+        }
+    }
+    
+    pub fn draw_text(&mut self, cx:&mut Cx2d, text:&str){
+        if let Some(DrawState::Drawing) = self.draw_state.get(){
             let fs = self.font_size_stack.value(self.font_size);
+            let color = self.color_stack.last().copied();
+            let dt = self.current_draw_text();
             dt.text_style.font_size = fs;
+            let old_color = dt.color;
+            if let Some(color) = color {
+                dt.color = color;
+            }
             // the turtle is at pos X so we walk it.
             dt.draw_walk_word(cx, text);
+            dt.color = old_color;
+        }
+    }
+    
+    /// Draws like `draw_text` and returns the rects the text covers, one per line it
+    /// spans, so callers can hit test parts of the flow.
+    pub fn draw_text_rects(&mut self, cx:&mut Cx2d, text:&str) -> Vec<Rect> {
+        let start = cx.turtle().pos();
+        self.draw_text(cx, text);
+        let end = cx.turtle().pos();
+        let padded = cx.turtle().padded_rect();
+        let dt = self.current_draw_text();
+        let line_height = dt.text_style.font_size * dt.text_style.height_factor * dt.font_scale;
+        if end.y <= start.y {
+            return vec![Rect {pos: start, size: dvec2(end.x - start.x, line_height)}]
+        }
+        let right = padded.pos.x + padded.size.x;
+        let mut rects = vec![Rect {pos: start, size: dvec2(right - start.x, line_height)}];
+        if end.y - start.y > line_height {
+            rects.push(Rect {
+                pos: dvec2(padded.pos.x, start.y + line_height),
+                size: dvec2(padded.size.x, end.y - start.y - line_height)
+            });
         }
+        rects.push(Rect {pos: dvec2(padded.pos.x, end.y), size: dvec2(end.x - padded.pos.x, line_height)});
+        rects
     }
 }
 
@@ -81,7 +81,16 @@ impl Widget for TextInput {
         if self.animator_handle_event(cx, event).must_redraw() {
             self.draw_bg.redraw(cx);
         }
-        match event.hits(cx, self.draw_bg.area()) {
+        // compositions aren't shown, their committed text is treated as regular input
+        let hit = match event.hits(cx, self.draw_bg.area()) {
+            Hit::ImeCompositionEnd(e) => Hit::TextInput(TextInputEvent {
+                input: e.text,
+                replace_last: false,
+                was_paste: false
+            }),
+            hit => hit
+        };
+        match hit {
             Hit::KeyFocusLost(_) => {
                 self.animator_play(cx, id!(focus.off));
                 cx.hide_text_ime();
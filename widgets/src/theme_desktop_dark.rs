@@ -167,6 +167,20 @@ live_design! {
         draw_italic: {text_style:<THEME_FONT_ITALIC>{}}
         draw_bold: {text_style:<THEME_FONT_BOLD>{}}
         draw_bold_italic: {text_style:<THEME_FONT_BOLD_ITALIC>{}}
+        draw_fixed: {text_style:<THEME_FONT_CODE>{}}
+    }
+
+    RichText = <RichTextBase>{
+        width: Fill
+        height: Fit
+        flow: RightWrap
+        font_size: 9.0
+        link_color: #x8ab4f8
+        draw_normal: {text_style:<THEME_FONT_LABEL>{}}
+        draw_italic: {text_style:<THEME_FONT_ITALIC>{}}
+        draw_bold: {text_style:<THEME_FONT_BOLD>{}}
+        draw_bold_italic: {text_style:<THEME_FONT_BOLD_ITALIC>{}}
+        draw_fixed: {text_style:<THEME_FONT_CODE>{}}
     }
 
     ScrollBar = <ScrollBarBase> {
@@ -179,6 +193,7 @@ live_design! {
             instance bar_width: 6.0
             instance pressed: 0.0
             instance hover: 0.0
+            instance fade: 0.0
 
             fn pixel(self) -> vec4 {
                 let sdf = Sdf2d::viewport(self.pos * self.rect_size);
@@ -208,7 +223,7 @@ live_design! {
                         self.pressed
                     ),
                     self.hover
-                ));
+                )) * (1.0 - self.fade);
             }
         }
         animator: {
@@ -438,6 +453,9 @@ live_design! {
                         sdf.line_to(c.x, c.y + szs);
                         sdf.line_to(c.x + szs, c.y - szs);
                         sdf.stroke(mix(#fff0, #f, self.selected), 1.25);
+                        sdf.move_to(left + 4.0, c.y);
+                        sdf.line_to(c.x + szs, c.y);
+                        sdf.stroke(mix(#fff0, #f, self.indeterminate), 1.25);
                     }
                     CheckType::Radio => {
                         let sz = self.size;
@@ -1053,6 +1071,163 @@ live_design! {
     }
 
 
+    Modal = <ModalBase> {
+        align: {x: 0.5, y: 0.5}
+        draw_list: {}
+        draw_bg: {
+            color: #000
+            opacity: 0.6
+            fn pixel(self) -> vec4 {
+                return Pal::premul(vec4(self.color.xyz, self.color.w * self.opacity * self.open));
+            }
+        }
+        content: <View> {width: Fit, height: Fit}
+        shadows: [{offset: vec2(0.0, 8.0), blur: 24.0, color: #0008}]
+        shadow_radius: 4.0
+        animator: {
+            mode = {
+                default: close
+                close = {
+                    redraw: true
+                    ease: InQuad
+                    from: {all: Forward {duration: 0.15}}
+                    apply: {open: 0.0, draw_bg: {open: 0.0}}
+                }
+                open = {
+                    redraw: true
+                    ease: OutQuad
+                    from: {all: Forward {duration: 0.2}}
+                    apply: {open: 1.0, draw_bg: {open: 1.0}}
+                }
+            }
+        }
+    }
+
+    Tooltip = <TooltipBase> {
+        width: Fit
+        height: Fit
+        delay_secs: 0.5
+        placement: Above
+        content: <View> {width: Fit, height: Fit}
+        tooltip_walk: {width: Fit, height: Fit}
+        tooltip_layout: {padding: {left: 6.0, top: 4.0, right: 6.0, bottom: 4.0}}
+        shadows: [{offset: vec2(0.0, 2.0), blur: 6.0, color: #0006}]
+        shadow_radius: 3.0
+        draw_list: {}
+        draw_bg: {
+            instance opacity: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1., 1., self.rect_size.x - 2., self.rect_size.y - 2., 3.);
+                sdf.fill_keep(THEME_COLOR_BG_HEADER);
+                sdf.stroke(THEME_COLOR_TEXT_DEFAULT, 0.5);
+                return sdf.result * self.opacity;
+            }
+        }
+        draw_text: {
+            instance opacity: 0.0
+            text_style: <THEME_FONT_LABEL> {}
+            fn get_color(self) -> vec4 {
+                return THEME_COLOR_TEXT_DEFAULT * self.opacity;
+            }
+        }
+        animator: {
+            tip = {
+                default: hide
+                hide = {
+                    redraw: true
+                    from: {all: Forward {duration: 0.1}}
+                    apply: {opacity: 0.0, draw_bg: {opacity: 0.0}, draw_text: {opacity: 0.0}}
+                }
+                show = {
+                    redraw: true
+                    from: {all: Forward {duration: 0.15}}
+                    apply: {opacity: 1.0, draw_bg: {opacity: 1.0}, draw_text: {opacity: 1.0}}
+                }
+            }
+        }
+    }
+
+    ContextMenu = <ContextMenuBase> {
+        width: Fit
+        height: Fit
+        content: <View> {width: Fit, height: Fit}
+        submenu_delay: 0.2
+        draw_list: {}
+        menu_walk: {width: 160.0, height: Fit}
+        menu_layout: {flow: Down, padding: 4.0}
+        item_walk: {width: Fill, height: Fit}
+        item_layout: {padding: {left: 10.0, top: 5.0, right: 10.0, bottom: 5.0}}
+        shadows: [
+            {offset: vec2(0.0, 1.0), blur: 2.0, color: #0006},
+            {offset: vec2(0.0, 4.0), blur: 12.0, color: #0005}
+        ]
+        shadow_radius: 4.0
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1., 1., self.rect_size.x - 2., self.rect_size.y - 2., 4.);
+                sdf.fill_keep(THEME_COLOR_BG_EDITOR);
+                sdf.stroke(THEME_COLOR_BG_HEADER, 1.0);
+                return sdf.result;
+            }
+        }
+        draw_item: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0., 0., self.rect_size.x, self.rect_size.y, 2.);
+                sdf.fill(mix(#0000, THEME_COLOR_BG_SELECTED, self.highlighted * self.enabled));
+                // submenu arrow
+                let c = vec2(self.rect_size.x - 8.0, self.rect_size.y * 0.5);
+                sdf.move_to(c.x - 2.0, c.y - 3.5);
+                sdf.line_to(c.x + 1.5, c.y);
+                sdf.line_to(c.x - 2.0, c.y + 3.5);
+                sdf.stroke(mix(#0000, THEME_COLOR_TEXT_DEFAULT, self.has_submenu), 1.2);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            text_style: <THEME_FONT_LABEL> {}
+            color: (THEME_COLOR_TEXT_DEFAULT)
+        }
+        draw_text_disabled: {
+            text_style: <THEME_FONT_LABEL> {}
+            color: #6
+        }
+    }
+
+    TabView = <TabViewBase> {
+        width: Fill
+        height: Fill
+        flow: Down
+        tab_bar: <TabBar> {}
+        arrow_width: 20.0
+        draw_arrow_left: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.clear(THEME_COLOR_BG_HEADER);
+                let c = self.rect_size * 0.5;
+                sdf.move_to(c.x + 3.0, c.y - 5.0);
+                sdf.line_to(c.x - 3.0, c.y);
+                sdf.line_to(c.x + 3.0, c.y + 5.0);
+                sdf.stroke(THEME_COLOR_TEXT_DEFAULT, 1.5);
+                return sdf.result;
+            }
+        }
+        draw_arrow_right: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.clear(THEME_COLOR_BG_HEADER);
+                let c = self.rect_size * 0.5;
+                sdf.move_to(c.x - 3.0, c.y - 5.0);
+                sdf.line_to(c.x + 3.0, c.y);
+                sdf.line_to(c.x - 3.0, c.y + 5.0);
+                sdf.stroke(THEME_COLOR_TEXT_DEFAULT, 1.5);
+                return sdf.result;
+            }
+        }
+    }
+
     const BORDER_SIZE: 6.0
     Dock = <DockBase> {
         round_corner: {
@@ -1304,7 +1479,12 @@ live_design! {
 
         popup_shift: vec2(-6.0, 4.0)
 
-        selected_item: 0
+        draw_search: {
+            text_style: <THEME_FONT_DATA> {}
+            color: #c
+        }
+        searchable: false
+
         animator: {
             hover = {
                 default: off,
@@ -1935,6 +2115,60 @@ live_design! {
         flow: Down
     }
 
+    VirtualList = <VirtualListBase> {
+        width: Fill
+        height: Fill
+        capture_overload: true
+        scroll_bar: <ScrollBar> {}
+    }
+
+    TreeView = <TreeViewBase> {
+        width: Fill
+        height: Fill
+        capture_overload: true
+        grab_key_focus: true
+        indent_width: 16.0
+        row_height: 24.0
+        scroll_bar: <ScrollBar> {}
+        row: <View> {
+            width: Fill
+            height: Fill
+            flow: Right
+            align: {y: 0.5}
+            spacing: 4.0
+            show_bg: true
+            draw_bg: {
+                instance selected: 0.0
+                fn pixel(self) -> vec4 {
+                    return mix(THEME_COLOR_BG_EDITOR, THEME_COLOR_BG_SELECTED, self.selected);
+                }
+            }
+            toggle = <View> {
+                width: 12
+                height: 12
+                show_bg: true
+                draw_bg: {
+                    instance open: 0.0
+                    instance expandable: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sz = 3.;
+                        let c = vec2(0.5 * self.rect_size.x, 0.5 * self.rect_size.y);
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.clear(vec4(0.));
+                        sdf.rotate(self.open * 0.5 * PI + 0.5 * PI, c.x, c.y);
+                        sdf.move_to(c.x - sz, c.y + sz);
+                        sdf.line_to(c.x, c.y - sz);
+                        sdf.line_to(c.x + sz, c.y + sz);
+                        sdf.close_path();
+                        sdf.fill(#a);
+                        return sdf.result * self.expandable;
+                    }
+                }
+            }
+            label = <Label> {}
+        }
+    }
+
     FlatList = <FlatListBase> {
         width: Fill
         height: Fill
@@ -1955,6 +2189,37 @@ live_design! {
         scroll_bars: <ScrollBars> {show_scroll_x: false, show_scroll_y: true}
     }
 
+    ScrollView = <ScrollViewBase> {
+        width: Fill
+        height: Fill
+        capture_overload: true
+        scroll_bars: <ScrollBars> {show_scroll_x: false, show_scroll_y: true}
+        body: <View> {width: Fill, height: Fit, flow: Down}
+        animator: {
+            bars = {
+                default: hide
+                show = {
+                    from: {all: Forward {duration: 0.15}}
+                    apply: {
+                        scroll_bars: {
+                            scroll_bar_x: {draw_bar: {fade: 0.0}}
+                            scroll_bar_y: {draw_bar: {fade: 0.0}}
+                        }
+                    }
+                }
+                hide = {
+                    from: {all: Forward {duration: 0.4}}
+                    apply: {
+                        scroll_bars: {
+                            scroll_bar_x: {draw_bar: {fade: 1.0}}
+                            scroll_bar_y: {draw_bar: {fade: 1.0}}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     ScrollXYView = <ViewBase> {scroll_bars: <ScrollBars> {show_scroll_x: true, show_scroll_y: true}}
     ScrollXView = <ViewBase> {scroll_bars: <ScrollBars> {show_scroll_x: true, show_scroll_y: false}}
     ScrollYView = <ViewBase> {scroll_bars: <ScrollBars> {show_scroll_x: false, show_scroll_y: true}}
@@ -2242,6 +2507,186 @@ live_design! {
             }
         }
     }
+    ProgressBar = <ProgressBarBase> {
+        width: Fill
+        height: 6.0
+        bar_color: (THEME_COLOR_CONTROL_PRESSED)
+        track_color: #3
+        value_speed: 8.0
+        sweep_duration: 1.2
+
+        draw_bar: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let r = self.rect_size.y * 0.5;
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, r);
+                sdf.fill_keep(self.track_color);
+
+                // determinate: the bar grows from the left
+                let fill = step(self.pos.x, self.value);
+                // indeterminate: a highlight a third of the width sweeps across
+                let center = self.phase * 1.6 - 0.3;
+                let sweep = 1.0 - smoothstep(0.1, 0.2, abs(self.pos.x - center));
+                let amount = mix(fill, sweep, self.indeterminate);
+                sdf.fill(mix(self.track_color, self.bar_color, amount));
+                return sdf.result;
+            }
+        }
+
+        animator: {
+            mode = {
+                default: determinate
+                determinate = {
+                    from: {all: Forward {duration: 0.25}}
+                    apply: {draw_bar: {indeterminate: 0.0}}
+                }
+                indeterminate = {
+                    from: {all: Forward {duration: 0.25}}
+                    apply: {draw_bar: {indeterminate: 1.0}}
+                }
+            }
+        }
+    }
+
+    SvgIcon = <SvgIconBase> {
+        width: 24.0
+        height: 24.0
+        color: (THEME_COLOR_TEXT_DEFAULT)
+    }
+
+    Transform2D = <Transform2DBase> {
+        width: Fit
+        height: Fit
+        draw_list: {}
+        content: <View> {width: Fit, height: Fit}
+    }
+
+    FlexContainer = <FlexContainerBase> {
+        width: Fill
+        height: Fit
+    }
+
+    FlexItem = <FlexItemBase> {
+        content: <View> {width: Fit, height: Fit}
+    }
+
+    GridLayout = <GridLayoutBase> {
+        width: Fill
+        height: Fit
+    }
+
+    GridItem = <GridItemBase> {
+        content: <View> {width: Fill, height: Fill}
+    }
+
+    OverlayLayer = <OverlayLayerBase> {
+        draw_list: {}
+    }
+
+    AbsolutePosition = <AbsolutePositionBase> {
+        content: <View> {width: Fit, height: Fit}
+    }
+
+    Spinner = <SpinnerBase> {
+        width: Fit
+        height: Fit
+        size: 24.0
+        speed_rps: 1.0
+        color: (THEME_COLOR_TEXT_DEFAULT)
+
+        draw_spinner: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let c = self.rect_size * 0.5;
+                let thickness = max(2.0, min(c.x, c.y) * 0.2);
+                let outer = min(c.x, c.y) - 1.0;
+                sdf.circle(c.x, c.y, outer);
+                sdf.circle(c.x, c.y, outer - thickness);
+                sdf.subtract();
+                // the arc fades out behind its rotating head
+                let p = self.pos * self.rect_size - c;
+                let angle = fract((atan(p.y, p.x) / (2.0 * PI)) + 1.0 - self.phase);
+                let tail = smoothstep(0.0, 0.75, angle);
+                return sdf.fill(vec4(self.color.xyz, self.color.w * tail * self.opacity));
+            }
+        }
+
+        animator: {
+            visible = {
+                default: on
+                off = {
+                    from: {all: Forward {duration: 0.3}}
+                    apply: {draw_spinner: {opacity: 0.0}}
+                }
+                on = {
+                    from: {all: Forward {duration: 0.2}}
+                    apply: {draw_spinner: {opacity: 1.0}}
+                }
+            }
+        }
+    }
+
+    ColorPicker = <ColorPickerBase> {
+        width: Fit
+        height: Fit
+        flow: Down
+        spacing: 6.0
+        color: #f00
+        swatch_walk: {width: 40, height: 20}
+        palette_layout: {flow: Right, spacing: 6.0}
+        square_size: 160.0
+        hue_bar_width: 20.0
+
+        draw_swatch: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                // checkerboard so transparency is visible
+                let check = mod(floor(self.pos.x * self.rect_size.x / 5.0) + floor(self.pos.y * self.rect_size.y / 5.0), 2.0);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 2.0);
+                sdf.fill_keep(mix(mix(#8, #c, check), vec4(self.color.xyz, 1.0), self.color.w));
+                sdf.stroke(#5, 1.0);
+                return sdf.result;
+            }
+        }
+
+        draw_square: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0.0, 0.0, self.rect_size.x, self.rect_size.y);
+                sdf.fill(Pal::hsv2rgb(vec4(self.hue, self.pos.x, 1.0 - self.pos.y, 1.0)));
+                let puck = vec2(self.sat, 1.0 - self.val) * self.rect_size;
+                sdf.circle(puck.x, puck.y, 5.0);
+                sdf.stroke(#f, 1.5);
+                sdf.circle(puck.x, puck.y, 6.5);
+                sdf.stroke(#0, 1.0);
+                return sdf.result;
+            }
+        }
+
+        draw_hue: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0.0, 0.0, self.rect_size.x, self.rect_size.y);
+                sdf.fill(Pal::hsv2rgb(vec4(self.pos.y, 1.0, 1.0, 1.0)));
+                let y = self.hue * self.rect_size.y;
+                sdf.rect(0.0, y - 1.5, self.rect_size.x, 3.0);
+                sdf.fill(#f);
+                return sdf.result;
+            }
+        }
+
+        controls: <View> {
+            width: 186, height: Fit
+            flow: Down
+            spacing: 4.0
+            h = <Slider> {text: "H", min: 0.0, max: 360.0}
+            s = <Slider> {text: "S"}
+            v = <Slider> {text: "V"}
+            a = <Slider> {text: "A"}
+            hex = <TextInput> {width: Fill, empty_message: "#rrggbbaa"}
+        }
+    }
+
 
 
     SlideBody = <Label> {
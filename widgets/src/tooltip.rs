@@ -0,0 +1,207 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    TooltipBase = {{Tooltip}} {}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Live, LiveHook)]
+#[live_ignore]
+pub enum TooltipPlacement {
+    #[pick] Above,
+    Below,
+    Left,
+    Right,
+}
+
+impl TooltipPlacement {
+    fn opposite(&self) -> Self {
+        match self {
+            Self::Above => Self::Below,
+            Self::Below => Self::Above,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct Tooltip {
+    #[find] #[live] content: WidgetRef,
+    #[live] draw_bg: DrawQuad,
+    #[live] draw_shadow: DrawShadow,
+    #[live] draw_text: DrawText,
+    #[redraw] #[live] draw_list: DrawList2d,
+    #[live] tooltip_walk: Walk,
+    #[live] tooltip_layout: Layout,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[animator] animator: Animator,
+
+    #[live] tooltip_text: String,
+    #[live(0.5)] delay_secs: f64,
+    #[live] placement: TooltipPlacement,
+    /// Distance between the wrapped widget and the tooltip box
+    #[live(4.0)] gap: f64,
+    #[live] shadows: Vec<Shadow>,
+    /// Corner radius the shadows follow, matching the rounding of `draw_bg`
+    #[live] shadow_radius: f64,
+    /// Driven by the animator, the tooltip is drawn until it has faded out
+    #[live] opacity: f32,
+
+    #[rust] area: Area,
+    #[rust] show_timer: Timer,
+    #[rust] is_shown: bool,
+    #[rust] draw_state: DrawStateWrap<()>,
+}
+
+impl Tooltip {
+    pub fn show(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.show_timer);
+        self.is_shown = true;
+        self.animator_play(cx, id!(tip.show));
+        self.draw_list.redraw(cx);
+    }
+
+    pub fn hide(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.show_timer);
+        if self.is_shown {
+            self.is_shown = false;
+            self.animator_play(cx, id!(tip.hide));
+        }
+    }
+
+    pub fn set_tooltip_text(&mut self, cx: &mut Cx, text: &str) {
+        self.tooltip_text = text.to_string();
+        if self.is_shown {
+            self.draw_list.redraw(cx);
+        }
+    }
+
+    fn fits(&self, placement: TooltipPlacement, anchor: Rect, size: DVec2, pass: DVec2) -> bool {
+        match placement {
+            TooltipPlacement::Above => anchor.pos.y - self.gap - size.y >= 0.0,
+            TooltipPlacement::Below => anchor.pos.y + anchor.size.y + self.gap + size.y <= pass.y,
+            TooltipPlacement::Left => anchor.pos.x - self.gap - size.x >= 0.0,
+            TooltipPlacement::Right => anchor.pos.x + anchor.size.x + self.gap + size.x <= pass.x,
+        }
+    }
+
+    /// Where to put the tooltip relative to the wrapped widget; falls back to the other
+    /// sides when the preferred one has no room, and keeps the box inside the window.
+    fn tooltip_shift(&self, anchor: Rect, size: DVec2, pass: DVec2) -> DVec2 {
+        let candidates = [
+            self.placement,
+            self.placement.opposite(),
+            TooltipPlacement::Above,
+            TooltipPlacement::Below,
+            TooltipPlacement::Right,
+            TooltipPlacement::Left,
+        ];
+        let placement = candidates.into_iter()
+            .find( | placement | self.fits(*placement, anchor, size, pass))
+            .unwrap_or(self.placement);
+        let shift = match placement {
+            TooltipPlacement::Above => dvec2((anchor.size.x - size.x) * 0.5, -size.y - self.gap),
+            TooltipPlacement::Below => dvec2((anchor.size.x - size.x) * 0.5, anchor.size.y + self.gap),
+            TooltipPlacement::Left => dvec2(-size.x - self.gap, (anchor.size.y - size.y) * 0.5),
+            TooltipPlacement::Right => dvec2(anchor.size.x + self.gap, (anchor.size.y - size.y) * 0.5),
+        };
+        let pos = anchor.pos + shift;
+        let clamped = dvec2(
+            pos.x.min(pass.x - size.x).max(0.0),
+            pos.y.min(pass.y - size.y).max(0.0)
+        );
+        shift + (clamped - pos)
+    }
+
+    fn draw_tooltip(&mut self, cx: &mut Cx2d) {
+        // the tooltip lives in an overlay so its parent can't clip it
+        self.draw_list.begin_overlay_reuse(cx);
+        cx.begin_pass_sized_turtle(Layout::flow_down());
+        let shadows: Vec<Shadow> = self.shadows.iter().map( | shadow | shadow.with_opacity(self.opacity)).collect();
+        self.draw_shadow.begin(cx, self.shadow_radius, &shadows);
+        self.draw_bg.begin(cx, self.tooltip_walk, self.tooltip_layout);
+        self.draw_text.draw_walk(cx, Walk::fit(), Align::default(), &self.tooltip_text);
+        self.draw_bg.end(cx);
+        let rect = self.draw_bg.area().rect(cx);
+        self.draw_shadow.end(cx, rect);
+        let size = rect.size;
+        let anchor = self.area.rect(cx);
+        let shift = self.tooltip_shift(anchor, size, cx.current_pass_size());
+        cx.end_pass_sized_turtle_with_shift(self.area, shift);
+        self.draw_list.end(cx);
+    }
+}
+
+impl Widget for Tooltip {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.animator_handle_event(cx, event).must_redraw() {
+            self.draw_list.redraw(cx);
+        }
+        if self.show_timer.is_event(event).is_some() {
+            self.show_timer = Timer::empty();
+            self.show(cx);
+        }
+
+        self.content.handle_event(cx, event, scope);
+
+        match event.hits(cx, self.area) {
+            Hit::FingerHoverIn(_) => {
+                cx.stop_timer(self.show_timer);
+                self.show_timer = cx.start_timeout(self.delay_secs);
+            }
+            Hit::FingerHoverOut(_) | Hit::FingerDown(_) => {
+                self.hide(cx);
+            }
+            _ => ()
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            cx.begin_turtle(walk, self.layout);
+        }
+        if self.draw_state.get().is_some() {
+            let walk = self.content.walk(cx);
+            self.content.draw_walk(cx, scope, walk) ?;
+            cx.end_turtle_with_area(&mut self.area);
+            if self.is_shown || self.opacity > 0.001 {
+                self.draw_tooltip(cx);
+            }
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+
+    fn text(&self) -> String {
+        self.tooltip_text.clone()
+    }
+
+    fn set_text(&mut self, v: &str) {
+        self.tooltip_text = v.to_string();
+    }
+}
+
+impl TooltipRef {
+    pub fn set_tooltip_text(&self, cx: &mut Cx, text: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_tooltip_text(cx, text);
+        }
+    }
+
+    pub fn show(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.show(cx);
+        }
+    }
+
+    pub fn hide(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.hide(cx);
+        }
+    }
+}
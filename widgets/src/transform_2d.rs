@@ -0,0 +1,128 @@
+use crate::{
+    makepad_derive_widget::*,
+    makepad_draw::*,
+    widget::*,
+};
+
+live_design!{
+    Transform2DBase = {{Transform2D}} {}
+}
+
+/// Draws its content rotated, scaled and translated. The content is drawn into its own draw
+/// list whose view transform is set once the content rect is known, so layout and hit testing
+/// use the untransformed bounds. Nested transforms don't compose, the outermost one wins.
+#[derive(Live, Widget)]
+pub struct Transform2D {
+    #[find] #[live] content: WidgetRef,
+    #[redraw] #[live] draw_list: DrawList2d,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[animator] animator: Animator,
+
+    /// Rotation around `origin` in radians, clockwise
+    #[live] rotation: f64,
+    #[live(vec2(1.0, 1.0))] scale: Vec2,
+    /// Pivot of the rotation and scale, relative to the bounds: (0.5, 0.5) is the center
+    #[live(vec2(0.5, 0.5))] origin: Vec2,
+    #[live] translation: Vec2,
+
+    #[rust] area: Area,
+    #[rust] draw_state: DrawStateWrap<()>,
+}
+
+impl LiveHook for Transform2D {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        self.update_transform(cx);
+    }
+}
+
+impl Transform2D {
+    pub fn set_rotation(&mut self, cx: &mut Cx, rotation: f64) {
+        self.rotation = rotation;
+        self.update_transform(cx);
+    }
+
+    pub fn set_scale(&mut self, cx: &mut Cx, scale: Vec2) {
+        self.scale = scale;
+        self.update_transform(cx);
+    }
+
+    pub fn set_translation(&mut self, cx: &mut Cx, translation: Vec2) {
+        self.translation = translation;
+        self.update_transform(cx);
+    }
+
+    /// The transform mapping untransformed coordinates within `rect` to where they are drawn
+    pub fn transform_for_rect(&self, rect: Rect) -> Mat4 {
+        let pivot = rect.pos + rect.size * self.origin.into_dvec2();
+        let (sin, cos) = self.rotation.sin_cos();
+        let (sx, sy) = (self.scale.x as f64, self.scale.y as f64);
+        let (a, b, c, d) = (sx * cos, sx * sin, -sy * sin, sy * cos);
+        let e = pivot.x + self.translation.x as f64 - (a * pivot.x + c * pivot.y);
+        let f = pivot.y + self.translation.y as f64 - (b * pivot.x + d * pivot.y);
+        Mat4 {v: [
+            a as f32, b as f32, 0.0, 0.0,
+            c as f32, d as f32, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            e as f32, f as f32, 0.0, 1.0,
+        ]}
+    }
+
+    fn update_transform(&mut self, cx: &mut Cx) {
+        let rect = self.area.rect(cx);
+        if rect.size.x <= 0.0 && rect.size.y <= 0.0 {
+            return
+        }
+        let transform = self.transform_for_rect(rect);
+        self.draw_list.set_view_transform(cx, &transform);
+        // the new uniforms are picked up on repaint
+        self.draw_list.redraw(cx);
+    }
+}
+
+impl Widget for Transform2D {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.animator_handle_event(cx, event).must_redraw() {
+            self.update_transform(cx);
+        }
+        self.content.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.draw_state.begin(cx, ()) {
+            self.draw_list.begin_always(cx);
+            cx.begin_turtle(walk, self.layout);
+        }
+        if self.draw_state.get().is_some() {
+            let walk = self.content.walk(cx);
+            self.content.draw_walk(cx, scope, walk) ?;
+            let rect = cx.end_turtle();
+            cx.add_aligned_rect_area(&mut self.area, rect);
+            self.draw_list.end(cx);
+            let transform = self.transform_for_rect(rect);
+            self.draw_list.set_view_transform(cx, &transform);
+            self.draw_state.end();
+        }
+        DrawStep::done()
+    }
+}
+
+impl Transform2DRef {
+    pub fn set_rotation(&self, cx: &mut Cx, rotation: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_rotation(cx, rotation);
+        }
+    }
+
+    pub fn set_scale(&self, cx: &mut Cx, scale: Vec2) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_scale(cx, scale);
+        }
+    }
+
+    pub fn set_translation(&self, cx: &mut Cx, translation: Vec2) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_translation(cx, translation);
+        }
+    }
+}
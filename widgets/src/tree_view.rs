@@ -0,0 +1,312 @@
+use {
+    std::{
+        rc::Rc,
+        cell::RefCell,
+        collections::HashSet,
+    },
+    crate::{
+        widget::*,
+        makepad_derive_widget::*,
+        makepad_draw::*,
+        label::*,
+        virtual_list::{VirtualList, VirtualListAction, VirtualListDataSource},
+    }
+};
+
+live_design!{
+    TreeViewBase = {{TreeView}} {}
+}
+
+/// Supplies the nodes of a `TreeView`. Nodes are addressed by their path of child
+/// indices from the root; children are only asked for once their parent is opened.
+pub trait TreeDataSource {
+    fn node_count(&self) -> usize;
+    fn node_label(&self, path: &[usize]) -> &str;
+    fn is_expandable(&self, path: &[usize]) -> bool;
+    fn child_count(&self, path: &[usize]) -> usize;
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum TreeViewAction {
+    Selected(Vec<usize>),
+    Expanded(Vec<usize>),
+    Collapsed(Vec<usize>),
+    None
+}
+
+struct TreeRow {
+    path: Vec<usize>,
+    expandable: bool,
+}
+
+#[derive(Default)]
+struct TreeState {
+    source: Option<Box<dyn TreeDataSource>>,
+    open: HashSet<Vec<usize>>,
+    rows: Vec<TreeRow>,
+    selected: Option<Vec<usize>>,
+    indent_width: f64,
+    row_height: f64,
+}
+
+impl TreeState {
+    fn rebuild_rows(&mut self) {
+        self.rows.clear();
+        if let Some(source) = &self.source {
+            let mut path = Vec::new();
+            Self::push_rows(source.as_ref(), &self.open, &mut path, source.node_count(), &mut self.rows);
+        }
+    }
+
+    fn push_rows(source: &dyn TreeDataSource, open: &HashSet<Vec<usize>>, path: &mut Vec<usize>, count: usize, rows: &mut Vec<TreeRow>) {
+        for i in 0..count {
+            path.push(i);
+            let expandable = source.is_expandable(path);
+            rows.push(TreeRow {path: path.clone(), expandable});
+            if expandable && open.contains(path) {
+                let child_count = source.child_count(path);
+                Self::push_rows(source, open, path, child_count, rows);
+            }
+            path.pop();
+        }
+    }
+
+    fn selected_row(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.rows.iter().position( | row | &row.path == selected)
+    }
+}
+
+struct TreeRowSource(Rc<RefCell<TreeState>>);
+
+impl VirtualListDataSource for TreeRowSource {
+    fn row_count(&self) -> usize {
+        self.0.borrow().rows.len()
+    }
+
+    fn row_height(&self, _index: usize) -> f64 {
+        self.0.borrow().row_height
+    }
+
+    fn draw_row(&mut self, cx: &mut Cx2d, scope: &mut Scope, index: usize, rect: Rect, row: &WidgetRef) {
+        let state = self.0.borrow();
+        let Some(source) = &state.source else {return};
+        let tree_row = &state.rows[index];
+        let indent = (tree_row.path.len() - 1) as f64 * state.indent_width;
+        let selected = if state.selected.as_ref() == Some(&tree_row.path) {1.0} else {0.0};
+        let open = if state.open.contains(&tree_row.path) {1.0} else {0.0};
+        let expandable = if tree_row.expandable {1.0} else {0.0};
+        row.apply_over(cx, live!{
+            padding: {left: (indent)}
+            draw_bg: {selected: (selected)}
+            toggle = {draw_bg: {open: (open), expandable: (expandable)}}
+        });
+        row.label(id!(label)).set_text(source.node_label(&tree_row.path));
+        let _ = row.draw_walk(cx, scope, Walk::abs_rect(rect));
+    }
+}
+
+#[derive(Live, Widget)]
+pub struct TreeView {
+    #[deref] list: VirtualList,
+    #[live(16.0)] indent_width: f64,
+    #[live(24.0)] row_height: f64,
+    #[rust] state: Rc<RefCell<TreeState>>,
+}
+
+impl LiveHook for TreeView {
+    fn after_apply(&mut self, cx: &mut Cx, _apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        let mut state = self.state.borrow_mut();
+        state.indent_width = self.indent_width;
+        state.row_height = self.row_height;
+        drop(state);
+        self.list.invalidate_heights(cx);
+    }
+}
+
+impl TreeView {
+    pub fn set_data_source(&mut self, cx: &mut Cx, source: Box<dyn TreeDataSource>) {
+        let mut state = self.state.borrow_mut();
+        state.source = Some(source);
+        state.open.clear();
+        state.selected = None;
+        state.rebuild_rows();
+        drop(state);
+        self.list.set_data_source(cx, Box::new(TreeRowSource(self.state.clone())));
+    }
+
+    /// Call when the data source changed underneath the tree, open nodes stay open.
+    pub fn reload(&mut self, cx: &mut Cx) {
+        self.state.borrow_mut().rebuild_rows();
+        self.list.invalidate_heights(cx);
+    }
+
+    pub fn is_open(&self, path: &[usize]) -> bool {
+        self.state.borrow().open.contains(path)
+    }
+
+    pub fn set_open(&mut self, cx: &mut Cx, path: &[usize], open: bool) -> bool {
+        let mut state = self.state.borrow_mut();
+        let changed = if open {
+            let expandable = state.source.as_ref().map_or(false, | source | source.is_expandable(path));
+            expandable && state.open.insert(path.to_vec())
+        }
+        else {
+            state.open.remove(path)
+        };
+        if changed {
+            state.rebuild_rows();
+            drop(state);
+            self.list.invalidate_heights(cx);
+        }
+        changed
+    }
+
+    pub fn selected(&self) -> Option<Vec<usize>> {
+        self.state.borrow().selected.clone()
+    }
+
+    pub fn set_selected(&mut self, cx: &mut Cx, path: Option<Vec<usize>>) {
+        self.state.borrow_mut().selected = path;
+        let index = self.state.borrow().selected_row();
+        if let Some(index) = index {
+            self.list.scroll_to_row(cx, index);
+        }
+        self.list.redraw(cx);
+    }
+
+    fn select_row(&mut self, cx: &mut Cx, index: usize, dispatch_action: &mut dyn FnMut(&mut Cx, TreeViewAction)) {
+        let path = self.state.borrow().rows[index].path.clone();
+        self.set_selected(cx, Some(path.clone()));
+        dispatch_action(cx, TreeViewAction::Selected(path));
+    }
+
+    fn toggle_row(&mut self, cx: &mut Cx, index: usize, open: bool, dispatch_action: &mut dyn FnMut(&mut Cx, TreeViewAction)) {
+        let path = self.state.borrow().rows[index].path.clone();
+        if self.set_open(cx, &path, open) {
+            if open {
+                dispatch_action(cx, TreeViewAction::Expanded(path));
+            }
+            else {
+                dispatch_action(cx, TreeViewAction::Collapsed(path));
+            }
+        }
+    }
+
+    pub fn handle_event_with(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope, dispatch_action: &mut dyn FnMut(&mut Cx, TreeViewAction)) {
+        let mut list_actions = Vec::new();
+        self.list.handle_event_with(cx, event, scope, &mut | _, action | list_actions.push(action));
+
+        for action in list_actions {
+            match action {
+                VirtualListAction::RowPressed(index) => {
+                    self.select_row(cx, index, dispatch_action);
+                    if self.state.borrow().rows[index].expandable {
+                        let open = !self.is_open(&self.state.borrow().rows[index].path);
+                        self.toggle_row(cx, index, open, dispatch_action);
+                    }
+                }
+                VirtualListAction::KeyDown(ke) => {
+                    let (selected, row_count) = {
+                        let state = self.state.borrow();
+                        (state.selected_row(), state.rows.len())
+                    };
+                    if row_count == 0 {
+                        continue
+                    }
+                    let Some(index) = selected else {
+                        if let KeyCode::ArrowDown | KeyCode::ArrowUp = ke.key_code {
+                            self.select_row(cx, 0, dispatch_action);
+                        }
+                        continue
+                    };
+                    match ke.key_code {
+                        KeyCode::ArrowDown if index + 1 < row_count => {
+                            self.select_row(cx, index + 1, dispatch_action);
+                        }
+                        KeyCode::ArrowUp if index > 0 => {
+                            self.select_row(cx, index - 1, dispatch_action);
+                        }
+                        KeyCode::ArrowRight => {
+                            let (expandable, open) = {
+                                let state = self.state.borrow();
+                                let row = &state.rows[index];
+                                (row.expandable, state.open.contains(&row.path))
+                            };
+                            if expandable && !open {
+                                self.toggle_row(cx, index, true, dispatch_action);
+                            }
+                            else if expandable && index + 1 < row_count {
+                                // already open, step into the first child
+                                self.select_row(cx, index + 1, dispatch_action);
+                            }
+                        }
+                        KeyCode::ArrowLeft => {
+                            let path = self.state.borrow().rows[index].path.clone();
+                            if self.is_open(&path) {
+                                self.toggle_row(cx, index, false, dispatch_action);
+                            }
+                            else if path.len() > 1 {
+                                self.set_selected(cx, Some(path[..path.len() - 1].to_vec()));
+                                dispatch_action(cx, TreeViewAction::Selected(path[..path.len() - 1].to_vec()));
+                            }
+                        }
+                        _ => ()
+                    }
+                }
+                _ => ()
+            }
+        }
+    }
+}
+
+impl Widget for TreeView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        let path = scope.path.clone();
+        self.handle_event_with(cx, event, scope, &mut | cx, action | {
+            cx.widget_action(uid, &path, action);
+        });
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.list.draw_walk(cx, scope, walk)
+    }
+}
+
+impl TreeViewRef {
+    pub fn set_data_source(&self, cx: &mut Cx, source: Box<dyn TreeDataSource>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_data_source(cx, source);
+        }
+    }
+
+    pub fn reload(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reload(cx);
+        }
+    }
+
+    pub fn set_open(&self, cx: &mut Cx, path: &[usize], open: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_open(cx, path, open);
+        }
+    }
+
+    pub fn selected(&self) -> Option<Vec<usize>> {
+        self.borrow().and_then( | inner | inner.selected())
+    }
+
+    pub fn set_selected(&self, cx: &mut Cx, path: Option<Vec<usize>>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_selected(cx, path);
+        }
+    }
+
+    pub fn selection_changed(&self, actions: &Actions) -> Option<Vec<usize>> {
+        if let TreeViewAction::Selected(path) = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(path)
+        }
+        None
+    }
+}
@@ -140,6 +140,10 @@ pub struct View {
 
     #[live(true)]
     visible: bool,
+    /// Views with a higher z order paint over their siblings without moving in the layout,
+    /// see `Cx2d::begin_layer`. Zero paints in tree order.
+    #[live]
+    z_order: i32,
 
     #[live(true)]
     grab_key_focus: bool,
@@ -671,6 +675,7 @@ impl Widget for View {
             }
 
             self.defer_walks.clear();
+            cx.begin_layer(self.z_order);
 
             match self.optimize {
                 ViewOptimize::Texture => {
@@ -705,6 +710,7 @@ impl Widget for View {
                                 );
                             }
                         }
+                        cx.end_layer();
                         return DrawStep::done();
                     }
                     // lets start a pass
@@ -743,6 +749,7 @@ impl Widget for View {
                         .is_not_redrawing()
                     {
                         cx.walk_turtle_with_area(&mut self.area, walk);
+                        cx.end_layer();
                         return DrawStep::done();
                     }
                 }
@@ -856,6 +863,7 @@ impl Widget for View {
                         }
                     }
                 }
+                cx.end_layer();
                 self.draw_state.end();
             }
         }
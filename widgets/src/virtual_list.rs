@@ -0,0 +1,335 @@
+use {
+    std::ops::Range,
+    crate::{
+        widget::*,
+        makepad_derive_widget::*,
+        makepad_draw::*,
+        fenwick_tree::FenwickTree,
+        scroll_bar::{ScrollBar, ScrollAxis, ScrollBarAction},
+    }
+};
+
+live_design!{
+    VirtualListBase = {{VirtualList}} {}
+}
+
+/// Supplies the rows of a `VirtualList`. Only rows that are in view are ever drawn,
+/// `row` is a pooled instance of the list's row template that may be used to draw them.
+pub trait VirtualListDataSource {
+    fn row_count(&self) -> usize;
+    fn row_height(&self, index: usize) -> f64;
+    fn draw_row(&mut self, cx: &mut Cx2d, scope: &mut Scope, index: usize, rect: Rect, row: &WidgetRef);
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum VirtualListAction {
+    Scroll,
+    RowPressed(usize),
+    /// Keys the list itself doesn't use, so wrapping widgets can add their own navigation
+    KeyDown(KeyEvent),
+    None
+}
+
+#[derive(Live, Widget)]
+pub struct VirtualList {
+    #[redraw] #[rust] area: Area,
+    #[walk] walk: Walk,
+    #[layout] layout: Layout,
+    #[live] row: Option<LivePtr>,
+    #[live] scroll_bar: ScrollBar,
+    #[live(false)] grab_key_focus: bool,
+    #[live] capture_overload: bool,
+
+    #[rust] scroll: f64,
+    #[rust] view_height: f64,
+    #[rust] heights: FenwickTree,
+    #[rust(true)] heights_dirty: bool,
+    #[rust] visible: Range<usize>,
+    #[rust] rows: Vec<(usize, WidgetRef)>,
+    #[rust] pool: Vec<WidgetRef>,
+    #[rust] data_source: Option<Box<dyn VirtualListDataSource>>,
+}
+
+impl LiveHook for VirtualList {
+    fn after_apply(&mut self, _cx: &mut Cx, apply: &mut Apply, _index: usize, _nodes: &[LiveNode]) {
+        // row instances hold the old template, let them be recreated
+        if let ApplyFrom::UpdateFromDoc {..} = apply.from {
+            self.rows.clear();
+            self.pool.clear();
+        }
+    }
+}
+
+impl VirtualList {
+    pub fn set_data_source(&mut self, cx: &mut Cx, data_source: Box<dyn VirtualListDataSource>) {
+        self.data_source = Some(data_source);
+        self.invalidate_heights(cx);
+    }
+
+    /// Re-reads the row count and all row heights from the data source on the next draw.
+    pub fn invalidate_heights(&mut self, cx: &mut Cx) {
+        self.heights_dirty = true;
+        self.area.redraw(cx);
+    }
+
+    /// Re-reads the height of a single row, without rebuilding the whole height table.
+    pub fn invalidate_row_height(&mut self, cx: &mut Cx, index: usize) {
+        if let Some(data_source) = &self.data_source {
+            if !self.heights_dirty && index < self.heights.len() {
+                self.heights.set(index, data_source.row_height(index));
+            }
+        }
+        self.area.redraw(cx);
+    }
+
+    fn sync_heights(&mut self) {
+        if let Some(data_source) = &self.data_source {
+            if self.heights_dirty || data_source.row_count() != self.heights.len() {
+                self.heights = FenwickTree::from_values(
+                    (0..data_source.row_count()).map( | i | data_source.row_height(i))
+                );
+                self.heights_dirty = false;
+                // row instances may now be bound to different data
+                self.pool.extend(self.rows.drain(..).map( | (_, row) | row));
+            }
+        }
+    }
+
+    fn max_scroll(&self) -> f64 {
+        (self.heights.total() - self.view_height).max(0.0)
+    }
+
+    fn visible_range(&self) -> Range<usize> {
+        let len = self.heights.len();
+        let start = self.heights.find(self.scroll).min(len);
+        let end = (self.heights.find(self.scroll + self.view_height) + 1).min(len);
+        start..end
+    }
+
+    /// Returns rows that went out of view to the pool, so only rows that scrolled in
+    /// need a fresh instance bound to them.
+    fn update_visible(&mut self, range: Range<usize>) {
+        if range == self.visible {
+            return
+        }
+        let mut i = 0;
+        while i < self.rows.len() {
+            if range.contains(&self.rows[i].0) {
+                i += 1;
+            }
+            else {
+                let (_, row) = self.rows.swap_remove(i);
+                self.pool.push(row);
+            }
+        }
+        self.visible = range;
+    }
+
+    fn row_for(&mut self, cx: &mut Cx, index: usize) -> WidgetRef {
+        if let Some((_, row)) = self.rows.iter().find( | (i, _) | *i == index) {
+            return row.clone()
+        }
+        let row = self.pool.pop().unwrap_or_else( || WidgetRef::new_from_ptr(cx, self.row));
+        self.rows.push((index, row.clone()));
+        row
+    }
+
+    pub fn set_scroll_pos(&mut self, cx: &mut Cx, scroll: f64) -> bool {
+        let scroll = scroll.min(self.max_scroll()).max(0.0);
+        if scroll == self.scroll {
+            return false
+        }
+        self.scroll = scroll;
+        self.scroll_bar.set_scroll_pos_no_action(cx, scroll);
+        let range = self.visible_range();
+        self.update_visible(range);
+        self.area.redraw(cx);
+        true
+    }
+
+    pub fn scroll_pos(&self) -> f64 {
+        self.scroll
+    }
+
+    pub fn scroll_to_row(&mut self, cx: &mut Cx, index: usize) {
+        self.sync_heights();
+        if index >= self.heights.len() {
+            return
+        }
+        let top = self.heights.prefix_sum(index);
+        let bottom = top + self.heights.get(index);
+        if top < self.scroll {
+            self.set_scroll_pos(cx, top);
+        }
+        else if bottom > self.scroll + self.view_height {
+            self.set_scroll_pos(cx, bottom - self.view_height);
+        }
+    }
+
+    pub fn visible_rows(&self) -> Range<usize> {
+        self.visible.clone()
+    }
+
+    pub fn row_at(&self, cx: &Cx, abs: DVec2) -> Option<usize> {
+        let rect = self.area.rect(cx);
+        if !rect.contains(abs) {
+            return None
+        }
+        let index = self.heights.find(abs.y - rect.pos.y + self.scroll);
+        if index < self.heights.len() {Some(index)} else {None}
+    }
+
+    pub fn area(&self) -> Area {
+        self.area
+    }
+
+    pub fn handle_event_with(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope, dispatch_action: &mut dyn FnMut(&mut Cx, VirtualListAction)) {
+        let uid = self.widget_uid();
+
+        let mut scroll_to = None;
+        self.scroll_bar.handle_event_with(cx, event, &mut | _cx, action | {
+            if let ScrollBarAction::Scroll {scroll_pos, ..} = action {
+                scroll_to = Some(scroll_pos);
+            }
+        });
+        if let Some(scroll_to) = scroll_to {
+            if self.set_scroll_pos(cx, scroll_to) {
+                dispatch_action(cx, VirtualListAction::Scroll);
+            }
+        }
+
+        for (_, row) in &self.rows {
+            let row_uid = row.widget_uid();
+            cx.group_widget_actions(uid, row_uid, |cx|{
+                row.handle_event(cx, event, scope)
+            });
+        }
+
+        let is_scroll = if let Event::Scroll(_) = event {true} else {false};
+        if self.scroll_bar.is_area_captured(cx) && !is_scroll {
+            return
+        }
+        match event.hits_with_capture_overload(cx, self.area, self.capture_overload) {
+            Hit::FingerScroll(e) => {
+                if self.set_scroll_pos(cx, self.scroll + e.scroll.y) {
+                    dispatch_action(cx, VirtualListAction::Scroll);
+                }
+            }
+            Hit::FingerDown(fe) => {
+                if self.grab_key_focus {
+                    cx.set_key_focus(self.area);
+                }
+                if let Some(index) = self.row_at(cx, fe.abs) {
+                    dispatch_action(cx, VirtualListAction::RowPressed(index));
+                }
+            }
+            Hit::KeyDown(ke) => {
+                let scroll = match ke.key_code {
+                    KeyCode::Home => Some(0.0),
+                    KeyCode::End => Some(self.max_scroll()),
+                    KeyCode::PageUp => Some(self.scroll - self.view_height),
+                    KeyCode::PageDown => Some(self.scroll + self.view_height),
+                    _ => None
+                };
+                if let Some(scroll) = scroll {
+                    if self.set_scroll_pos(cx, scroll) {
+                        dispatch_action(cx, VirtualListAction::Scroll);
+                    }
+                }
+                else {
+                    dispatch_action(cx, VirtualListAction::KeyDown(ke));
+                }
+            }
+            _ => ()
+        }
+    }
+}
+
+impl Widget for VirtualList {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        let path = scope.path.clone();
+        self.handle_event_with(cx, event, scope, &mut | cx, action | {
+            cx.widget_action(uid, &path, action);
+        });
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let Some(mut data_source) = self.data_source.take() else {
+            cx.walk_turtle_with_area(&mut self.area, walk);
+            return DrawStep::done()
+        };
+        self.sync_heights();
+
+        cx.begin_turtle(walk, self.layout);
+        let rect = cx.turtle().rect();
+        self.view_height = rect.size.y;
+        self.scroll = self.scroll.min(self.max_scroll()).max(0.0);
+        let range = self.visible_range();
+        self.update_visible(range.clone());
+
+        for index in range {
+            let row = self.row_for(cx, index);
+            let row_rect = Rect {
+                pos: rect.pos + dvec2(0.0, self.heights.prefix_sum(index) - self.scroll),
+                size: dvec2(rect.size.x, self.heights.get(index))
+            };
+            data_source.draw_row(cx, scope, index, row_rect, &row);
+        }
+
+        self.scroll_bar.set_scroll_pos_no_action(cx, self.scroll);
+        self.scroll_bar.draw_scroll_bar(cx, ScrollAxis::Vertical, rect, dvec2(rect.size.x, self.heights.total()));
+        cx.end_turtle_with_area(&mut self.area);
+        self.data_source = Some(data_source);
+        DrawStep::done()
+    }
+}
+
+impl VirtualListRef {
+    pub fn set_data_source(&self, cx: &mut Cx, data_source: Box<dyn VirtualListDataSource>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_data_source(cx, data_source);
+        }
+    }
+
+    pub fn invalidate_heights(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.invalidate_heights(cx);
+        }
+    }
+
+    pub fn invalidate_row_height(&self, cx: &mut Cx, index: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.invalidate_row_height(cx, index);
+        }
+    }
+
+    pub fn scroll_to_row(&self, cx: &mut Cx, index: usize) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.scroll_to_row(cx, index);
+        }
+    }
+
+    pub fn visible_rows(&self) -> Range<usize> {
+        if let Some(inner) = self.borrow() {
+            inner.visible_rows()
+        }
+        else {
+            0..0
+        }
+    }
+
+    pub fn scrolled(&self, actions: &Actions) -> bool {
+        if let VirtualListAction::Scroll = actions.find_widget_action_cast(self.widget_uid()) {
+            return true
+        }
+        false
+    }
+
+    pub fn row_pressed(&self, actions: &Actions) -> Option<usize> {
+        if let VirtualListAction::RowPressed(index) = actions.find_widget_action_cast(self.widget_uid()) {
+            return Some(index)
+        }
+        None
+    }
+}